@@ -0,0 +1,215 @@
+use chrono::{DateTime, Duration, TimeZone, Utc};
+
+use solar_tracker::angles::solar_position;
+use solar_tracker::controller::{fallback_rotation, Controller};
+use solar_tracker::event_log::EventLog;
+use solar_tracker::lookup_table::{generate_single_axis_table, lookup_single_axis};
+use solar_tracker::types::{
+    ControllerInputs, Event, EventKind, LinkStatus, LookupTableConfig, MinuteOfDay, SingleAxisTable,
+    StowReason,
+};
+
+// A tracker daemon's main loop: every tick it looks up the rotation, steps
+// the controller with link and weather inputs, logs mode changes and
+// publishes the mode and rotation over MQTT. The crate has no network
+// dependency, so `Publisher` stands in for an MQTT client (rumqttc, paho):
+// this example prints `topic payload` lines and replays one simulated day
+// with a wind stow and a dropped link instead of sleeping in real time. Run
+// with `cargo run --example mqtt_daemon`.
+
+const SITE: &str = "springfield/row-1";
+const TICK_MINUTES: i64 = 5;
+const STOW_ROTATION: f64 = 0.0;
+const DEADBAND: f64 = 0.5;
+
+trait Publisher {
+    fn publish(&mut self, topic: &str, payload: String);
+}
+
+struct StdoutPublisher;
+
+impl Publisher for StdoutPublisher {
+    fn publish(&mut self, topic: &str, payload: String) {
+        println!("{topic} {payload}");
+    }
+}
+
+// Scripted field conditions, in minutes from the start of the run: wind
+// for an hour late in the morning, then the SCADA link lost mid-afternoon
+// for the rest of the run.
+fn conditions(elapsed: i64) -> (Option<StowReason>, LinkStatus) {
+    let wind = (600..660).contains(&elapsed).then_some(StowReason::Wind);
+    let link = LinkStatus {
+        minutes_since_contact: (elapsed - 840).max(0),
+        minutes_since_lightning: None,
+    };
+    (wind, link)
+}
+
+fn site_config() -> LookupTableConfig {
+    LookupTableConfig {
+        min_rotation: Some(-60.0),
+        max_rotation: Some(60.0),
+        ..Default::default()
+    }
+}
+
+// 06:00 UTC is 1 am in Springfield, so the run covers one local day.
+fn run_start(config: &LookupTableConfig) -> DateTime<Utc> {
+    Utc.with_ymd_and_hms(config.year, 6, 21, 6, 0, 0).unwrap()
+}
+
+struct Daemon {
+    table: SingleAxisTable,
+    controller: Controller,
+    log: EventLog<256>,
+    rotation: Option<f64>,
+}
+
+impl Daemon {
+    fn new(config: &LookupTableConfig) -> Self {
+        Self {
+            table: generate_single_axis_table(config),
+            controller: Controller::default(),
+            log: EventLog::new(),
+            rotation: None,
+        }
+    }
+
+    fn tick(&mut self, at: DateTime<Utc>, elapsed: i64, publisher: &mut impl Publisher) {
+        let config = self.table.config;
+        let pos = solar_position(config.latitude, config.longitude, &at);
+        let (stow_request, link) = conditions(elapsed);
+        let before = self.controller.mode();
+        let action = self.controller.step(&ControllerInputs {
+            altitude: pos.altitude,
+            backtracking: false,
+            link,
+            stow_request,
+            fault: false,
+        });
+        let timestamp = at.timestamp() as u32;
+        let mode = self.controller.mode();
+        if mode != before {
+            self.log.push(Event {
+                timestamp,
+                kind: EventKind::ModeChange {
+                    from: before,
+                    to: mode,
+                },
+            });
+            publisher.publish(&format!("solar/{SITE}/mode"), format!("{mode:?}"));
+        }
+
+        let minutes = MinuteOfDay::from_time(at.time());
+        let tracked = lookup_single_axis(&self.table, pos.day_of_year, minutes)
+            .and_then(|e| e.rotation)
+            .unwrap_or(STOW_ROTATION);
+        let target = fallback_rotation(action, tracked, STOW_ROTATION);
+        if self
+            .rotation
+            .is_some_and(|r| (target - r).abs() <= DEADBAND)
+        {
+            return;
+        }
+        self.rotation = Some(target);
+        self.log.push(Event {
+            timestamp,
+            kind: EventKind::Move {
+                target: target as f32,
+            },
+        });
+        publisher.publish(&format!("solar/{SITE}/rotation"), format!("{target:.1}"));
+    }
+
+    fn run_day(&mut self, start: DateTime<Utc>, publisher: &mut impl Publisher) {
+        let ticks = 24 * 60 / TICK_MINUTES;
+        for elapsed in (0..ticks).map(|i| i * TICK_MINUTES) {
+            self.tick(start + Duration::minutes(elapsed), elapsed, publisher);
+        }
+    }
+}
+
+fn main() {
+    let config = site_config();
+    let mut daemon = Daemon::new(&config);
+    daemon.run_day(run_start(&config), &mut StdoutPublisher);
+    println!(
+        "# {} events logged, {} dropped",
+        daemon.log.len(),
+        daemon.log.dropped()
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use solar_tracker::types::ControllerMode;
+
+    #[derive(Default)]
+    struct Recorder(Vec<(String, String)>);
+
+    impl Publisher for Recorder {
+        fn publish(&mut self, topic: &str, payload: String) {
+            self.0.push((topic.to_string(), payload));
+        }
+    }
+
+    fn simulated_day() -> (Daemon, Recorder) {
+        let config = site_config();
+        let mut daemon = Daemon::new(&config);
+        let mut recorder = Recorder::default();
+        daemon.run_day(run_start(&config), &mut recorder);
+        (daemon, recorder)
+    }
+
+    #[test]
+    fn test_mode_sequence_published() {
+        let (_, recorder) = simulated_day();
+        let modes: Vec<&str> = recorder
+            .0
+            .iter()
+            .filter(|(topic, _)| topic.ends_with("/mode"))
+            .map(|(_, payload)| payload.as_str())
+            .collect();
+        assert_eq!(
+            modes,
+            [
+                "Tracking",
+                "Stowed(Wind)",
+                "Tracking",
+                "Stowed(CommunicationLoss)"
+            ],
+            "{modes:?}"
+        );
+    }
+
+    #[test]
+    fn test_wind_stow_and_link_loss_limit_rotation() {
+        let (daemon, _) = simulated_day();
+        let moves: Vec<(u32, f32)> = daemon
+            .log
+            .iter()
+            .filter_map(|e| match e.kind {
+                EventKind::Move { target } => Some((e.timestamp, target)),
+                _ => None,
+            })
+            .collect();
+        let start = run_start(&daemon.table.config).timestamp() as u32;
+        let elapsed = |ts: u32| (ts - start) as i64 / 60;
+        assert!(moves
+            .iter()
+            .any(|&(ts, target)| (600..660).contains(&elapsed(ts)) && target == 0.0));
+        let policy = daemon.controller.policy();
+        let max_without_link = policy.conservative_max_rotation as f32;
+        assert!(moves
+            .iter()
+            .filter(|&&(ts, _)| elapsed(ts) >= 840 + policy.comm_timeout_minutes)
+            .all(|&(_, target)| target.abs() <= max_without_link));
+        assert_eq!(daemon.log.dropped(), 0);
+        assert_eq!(
+            daemon.controller.mode(),
+            ControllerMode::Stowed(StowReason::CommunicationLoss)
+        );
+    }
+}
@@ -0,0 +1,101 @@
+use solar_tracker::daily_fit::{evaluate_fit, fit_daily_curves, fit_storage_bytes};
+use solar_tracker::event_log::EventLog;
+use solar_tracker::types::{DailyFitTable, Event, EventKind, LookupTableConfig, MinuteOfDay};
+
+// The control loop of a small tracker controller: the year lives in flash as
+// five i16 coefficients a day (the daily-fit micro mode), the loop wakes once
+// a minute, evaluates the day's curve and only moves the motor when the
+// target drifts past a deadband. Moves go to a heap-free event log. Run with
+// `cargo run --example quantized_lookup`.
+
+const DEADBAND: f64 = 0.5;
+const LOG_CAPACITY: usize = 512;
+
+// One day of the loop; returns the log and the number of moves.
+fn run_day(fit: &DailyFitTable, day_of_year: i32) -> (EventLog<LOG_CAPACITY>, usize) {
+    let mut log = EventLog::<LOG_CAPACITY>::new();
+    let mut position: Option<f64> = None;
+    let mut moves = 0;
+    for minutes in (0..1440).filter_map(MinuteOfDay::new) {
+        // Night: hold wherever the night stow left the motor
+        let Some(target) = evaluate_fit(fit, day_of_year, minutes) else {
+            continue;
+        };
+        if position.is_some_and(|p| (target - p).abs() <= DEADBAND) {
+            continue;
+        }
+        position = Some(target);
+        moves += 1;
+        log.push(Event {
+            timestamp: (day_of_year * 1440 + minutes.get()) as u32 * 60,
+            kind: EventKind::Move {
+                target: target as f32,
+            },
+        });
+    }
+    (log, moves)
+}
+
+fn controller_config() -> LookupTableConfig {
+    LookupTableConfig {
+        min_rotation: Some(-60.0),
+        max_rotation: Some(60.0),
+        ..Default::default()
+    }
+}
+
+fn main() {
+    let config = controller_config();
+    let fit = fit_daily_curves(&config);
+    println!("=== Quantized Lookup Loop Example ===");
+    println!(
+        "Year in flash: {} bytes ({} days × 5 × i16)",
+        fit_storage_bytes(&fit),
+        fit.days.len()
+    );
+    for doy in [80, 172, 355] {
+        let (log, moves) = run_day(&fit, doy);
+        let last = log.latest().map(|e| e.kind);
+        println!("Day {doy}: {moves} moves with a {DEADBAND}° deadband, last {last:?}");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use solar_tracker::lookup_table::{generate_single_axis_table, lookup_single_axis};
+
+    #[test]
+    fn test_moves_track_table_within_deadband() {
+        let config = controller_config();
+        let fit = fit_daily_curves(&config);
+        let table = generate_single_axis_table(&config);
+        let (log, moves) = run_day(&fit, 172);
+        assert!(moves > 10 && moves < LOG_CAPACITY, "{moves}");
+        assert_eq!(log.len(), moves);
+        assert_eq!(log.dropped(), 0);
+        let targets: Vec<(i32, f64)> = log
+            .iter()
+            .map(|e| match e.kind {
+                EventKind::Move { target } => ((e.timestamp / 60 % 1440) as i32, target as f64),
+                other => panic!("unexpected event {other:?}"),
+            })
+            .collect();
+        for window in targets.windows(2) {
+            let (start, held) = window[0];
+            for minutes in (start..window[1].0).step_by(5).filter_map(MinuteOfDay::new) {
+                let Some(exact) = lookup_single_axis(&table, 172, minutes).and_then(|e| e.rotation)
+                else {
+                    continue;
+                };
+                assert!((exact - held).abs() <= DEADBAND + 0.05, "{minutes}");
+            }
+        }
+    }
+
+    #[test]
+    fn test_year_fits_small_flash() {
+        let fit = fit_daily_curves(&controller_config());
+        assert!(fit_storage_bytes(&fit) < 4096);
+    }
+}
@@ -0,0 +1,97 @@
+use std::env;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use solar_tracker::chebyshev::{
+    compress_chebyshev, decode_chebyshev, encode_chebyshev, lookup_chebyshev,
+};
+use solar_tracker::lookup_table::{generate_single_axis_table, lookup_single_axis};
+use solar_tracker::types::{ChebyshevTable, LookupTableConfig, MinuteOfDay};
+
+// A year of single-axis rotations, compressed to within 0.1° and written in
+// the STCH binary format a controller flashes. Run with
+// `cargo run --example table_to_file [path]`.
+
+const ERROR_BOUND: f64 = 0.1;
+
+fn write_table(config: &LookupTableConfig, path: &Path) -> io::Result<usize> {
+    let table = generate_single_axis_table(config);
+    let compressed = compress_chebyshev(&table, ERROR_BOUND)
+        .and_then(|compressed| encode_chebyshev(&compressed));
+    let bytes = compressed.map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))?;
+    fs::write(path, &bytes)?;
+    Ok(bytes.len())
+}
+
+// What the firmware does at boot: read the file back and reject it if the
+// header or length is off.
+fn read_table(path: &Path) -> io::Result<ChebyshevTable> {
+    let bytes = fs::read(path)?;
+    decode_chebyshev(&bytes).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+}
+
+fn main() {
+    let path = env::args_os()
+        .nth(1)
+        .map(PathBuf::from)
+        .unwrap_or_else(|| env::temp_dir().join("single_axis.stch"));
+    let config = LookupTableConfig::default();
+
+    let bytes = write_table(&config, &path).expect("table file should be writable");
+    println!("=== Table To File Example ===");
+    println!("Wrote {} bytes to {}", bytes, path.display());
+
+    let loaded = read_table(&path).expect("table file should read back");
+    let table = generate_single_axis_table(&config);
+    println!(
+        "Days: {}, error bound: {:.2}°",
+        loaded.days.len(),
+        loaded.error_bound
+    );
+    let evening = MinuteOfDay::from_hm(18, 0).expect("18:00 is a time of day");
+    for doy in [80, 172, 355] {
+        let stored = lookup_chebyshev(&loaded, doy, evening).and_then(|e| e.rotation);
+        let exact = lookup_single_axis(&table, doy, evening).and_then(|e| e.rotation);
+        println!("Day {doy} {evening} UTC: file {stored:?}, table {exact:?}");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_file_round_trip_within_bound() {
+        let config = LookupTableConfig::default();
+        let path = env::temp_dir().join(format!("solar_tracker_{}.stch", std::process::id()));
+        let bytes = write_table(&config, &path).unwrap();
+        assert_eq!(fs::metadata(&path).unwrap().len() as usize, bytes);
+        let loaded = read_table(&path).unwrap();
+        fs::remove_file(&path).unwrap();
+
+        assert_eq!(loaded.days.len(), 365);
+        let table = generate_single_axis_table(&config);
+        for day in table.days.iter().step_by(7) {
+            for entry in &day.entries {
+                let Some(expected) = entry.rotation else {
+                    continue;
+                };
+                let minutes = MinuteOfDay::new(entry.minutes).unwrap();
+                let got = lookup_chebyshev(&loaded, day.day_of_year, minutes)
+                    .and_then(|e| e.rotation)
+                    .unwrap();
+                assert!((got - expected).abs() <= ERROR_BOUND + 1e-6);
+            }
+        }
+    }
+
+    #[test]
+    fn test_corrupt_file_rejected() {
+        let path = env::temp_dir().join(format!("solar_tracker_bad_{}.stch", std::process::id()));
+        fs::write(&path, b"NOPE").unwrap();
+        let err = read_table(&path).unwrap_err();
+        fs::remove_file(&path).unwrap();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+    }
+}
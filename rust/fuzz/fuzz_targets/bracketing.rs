@@ -0,0 +1,31 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use solar_tracker::interp::{find_bracketing_entries, HasMinutes};
+
+struct Entry(i32);
+
+impl HasMinutes for Entry {
+    fn minutes(&self) -> i32 {
+        self.0
+    }
+    fn set_minutes(&mut self, minutes: i32) {
+        self.0 = minutes;
+    }
+}
+
+// Entries straight from the input, so unsorted, duplicated and unevenly
+// spaced minutes all get through, as they would from a tampered table.
+fuzz_target!(|input: (Vec<i32>, i32, i32)| {
+    let (minutes, interval_minutes, query) = input;
+    let entries: Vec<Entry> = minutes.into_iter().map(Entry).collect();
+    if let Some((before, after, fraction)) =
+        find_bracketing_entries(&entries, interval_minutes, query)
+    {
+        assert!(before.minutes() <= query);
+        assert!((0.0..=1.0).contains(&fraction));
+        if let Some(after) = after {
+            assert!(after.minutes() >= query);
+        }
+    }
+});
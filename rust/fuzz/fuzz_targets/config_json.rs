@@ -0,0 +1,13 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use solar_tracker::*;
+
+// A config that survives validation must also generate and look up cleanly.
+fuzz_target!(|input: &str| {
+    let Ok(config) = config_from_json(input) else {
+        return;
+    };
+    let table = generate_single_axis_table(&config);
+    let _ = lookup_single_axis(&table, 172, 720);
+});
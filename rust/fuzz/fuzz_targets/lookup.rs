@@ -0,0 +1,19 @@
+#![no_main]
+
+use std::sync::LazyLock;
+
+use libfuzzer_sys::fuzz_target;
+use solar_tracker::*;
+
+static SINGLE: LazyLock<SingleAxisTable> =
+    LazyLock::new(|| generate_single_axis_table(&LookupTableConfig::default()));
+static DUAL: LazyLock<DualAxisTable> =
+    LazyLock::new(|| generate_dual_axis_table(&LookupTableConfig::default()));
+
+// Any day-of-year and minute must give Some or None, never a panic.
+fuzz_target!(|input: (i32, i32)| {
+    let (day_of_year, minutes) = input;
+    let _ = lookup_single_axis(&SINGLE, day_of_year, minutes);
+    let _ = lookup_dual_axis(&DUAL, day_of_year, minutes);
+    let _ = lookup_nearest(&SINGLE, day_of_year, minutes);
+});
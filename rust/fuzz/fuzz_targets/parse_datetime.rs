@@ -0,0 +1,8 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use solar_tracker::parse_datetime;
+
+fuzz_target!(|input: &str| {
+    let _ = parse_datetime(input);
+});
@@ -1,27 +1,120 @@
-use chrono::{DateTime, Datelike, TimeZone, Timelike, Utc};
+use std::fmt;
+use std::ops::RangeInclusive;
 
-use crate::types::{DualAxisAngles, Season, SolarPosition};
+use chrono::{DateTime, Datelike, Duration, NaiveDate, NaiveTime, TimeZone, Timelike, Utc};
+
+use crate::ephemeris;
+use crate::math;
+use crate::types::{
+    DeclinationModel, DualAxisAngles, DualAxisMount, EquationOfTimeModel, MountAngles,
+    PositionModel, Season, SolarPosition, SolarPositionDebug, SurfaceOrientation, TerrainSlope,
+    VectorFrame,
+};
 
 pub const EARTH_AXIAL_TILT: f64 = 23.45;
 pub const DEGREES_PER_HOUR: f64 = 15.0;
 
-pub fn deg_to_rad(deg: f64) -> f64 {
+// UTC years the day-of-year Fourier series for declination and equation of
+// time are trusted over. They are fitted to the current epoch and ignore
+// perihelion drift, so accuracy decays slowly away from it; this is the
+// range NOAA quotes for the same family of formulas.
+pub const VALID_YEARS: RangeInclusive<i32> = 1901..=2099;
+
+pub const JULIAN_DAY_J2000: f64 = 2_451_545.0;
+pub const JULIAN_DAY_UNIX_EPOCH: f64 = 2_440_587.5;
+pub const DAYS_PER_JULIAN_CENTURY: f64 = 36_525.0;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum PositionError {
+    YearOutOfRange { year: i32 },
+    LatitudeOutOfRange { latitude: f64 },
+    LongitudeOutOfRange { longitude: f64 },
+    InvalidDate { year: i32, month: u32, day: u32 },
+}
+
+impl fmt::Display for PositionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PositionError::YearOutOfRange { year } => write!(
+                f,
+                "year {year} outside the model's valid range {}-{}",
+                VALID_YEARS.start(),
+                VALID_YEARS.end()
+            ),
+            PositionError::LatitudeOutOfRange { latitude } => {
+                write!(f, "latitude {latitude} outside -90..=90")
+            }
+            PositionError::LongitudeOutOfRange { longitude } => {
+                write!(f, "longitude {longitude} outside -180..=180")
+            }
+            PositionError::InvalidDate { year, month, day } => {
+                write!(f, "{year}-{month:02}-{day:02} is not a calendar date")
+            }
+        }
+    }
+}
+
+impl std::error::Error for PositionError {}
+
+pub const fn deg_to_rad(deg: f64) -> f64 {
     deg * (std::f64::consts::PI / 180.0)
 }
 
-pub fn rad_to_deg(rad: f64) -> f64 {
+pub const fn rad_to_deg(rad: f64) -> f64 {
     rad * (180.0 / std::f64::consts::PI)
 }
 
-pub fn normalize_angle(angle: f64) -> f64 {
-    angle.rem_euclid(360.0)
+// `f64::rem_euclid` is not const; this is its definition spelled out, so
+// results are bit-identical to it.
+const fn rem_euclid_360(angle: f64) -> f64 {
+    let r = angle % 360.0;
+    if r < 0.0 {
+        r + 360.0
+    } else {
+        r
+    }
+}
+
+pub const fn normalize_angle(angle: f64) -> f64 {
+    rem_euclid_360(angle)
 }
 
-pub fn leap_year(year: i32) -> bool {
+// Signed shortest turn from `from` to `to`, in (-180, 180].
+pub const fn angle_difference(from: f64, to: f64) -> f64 {
+    let diff = rem_euclid_360(to - from);
+    if diff > 180.0 {
+        diff - 360.0
+    } else {
+        diff
+    }
+}
+
+// Mean direction in [0, 360); None when empty or when the weighted unit
+// vectors cancel out (e.g. 0° and 180°), where no mean is defined.
+pub fn circular_mean_weighted(samples: &[(f64, f64)]) -> Option<f64> {
+    weighted_mean_direction(samples.iter().copied())
+}
+
+pub fn circular_mean(angles: &[f64]) -> Option<f64> {
+    weighted_mean_direction(angles.iter().map(|&a| (a, 1.0)))
+}
+
+fn weighted_mean_direction(samples: impl Iterator<Item = (f64, f64)>) -> Option<f64> {
+    let (sin_sum, cos_sum) = samples.fold((0.0, 0.0), |(s, c), (angle, weight)| {
+        let (sin_a, cos_a) = math::sin_cos(deg_to_rad(angle));
+        (s + weight * sin_a, c + weight * cos_a)
+    });
+    if math::hypot(sin_sum, cos_sum) < 1e-9 {
+        return None;
+    }
+    Some(normalize_angle(rad_to_deg(math::atan2(sin_sum, cos_sum))))
+}
+
+pub const fn leap_year(year: i32) -> bool {
     (year % 400 == 0) || (year % 4 == 0 && year % 100 != 0)
 }
 
-pub fn days_in_months(year: i32) -> [u32; 12] {
+pub const fn days_in_months(year: i32) -> [u32; 12] {
     [
         31,
         if leap_year(year) { 29 } else { 28 },
@@ -29,48 +122,192 @@ pub fn days_in_months(year: i32) -> [u32; 12] {
     ]
 }
 
-pub fn day_of_year(year: i32, month: u32, day: u32) -> i32 {
+pub const fn day_of_year(year: i32, month: u32, day: u32) -> i32 {
     let dim = days_in_months(year);
-    let sum: u32 = dim[..(month - 1) as usize].iter().sum();
-    (sum + day) as i32
+    let mut sum = day;
+    let mut m = 1;
+    while m < month {
+        sum += dim[(m - 1) as usize];
+        m += 1;
+    }
+    sum as i32
+}
+
+// `day_of_year` for untrusted input: refuses month 13, 30 February and the
+// like instead of counting past the end of the month.
+pub fn day_of_year_checked(year: i32, month: u32, day: u32) -> Result<i32, PositionError> {
+    match NaiveDate::from_ymd_opt(year, month, day) {
+        Some(date) => Ok(day_of_year_from_date(date)),
+        None => Err(PositionError::InvalidDate { year, month, day }),
+    }
+}
+
+// Proleptic Gregorian (year, month, day) of a count of days since
+// 1970-01-01, negative before it. Integer-only (Hinnant's
+// `civil_from_days`), so it needs no calendar library.
+pub const fn days_to_ymd(days: i64) -> (i64, u32, u32) {
+    let z = days + 719_468;
+    let era = (if z >= 0 { z } else { z - 146_096 }) / 146_097;
+    let doe = z - era * 146_097;
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = (doy - (153 * mp + 2) / 5 + 1) as u32;
+    let month = (if mp < 10 { mp + 3 } else { mp - 9 }) as u32;
+    let year = yoe + era * 400 + (month <= 2) as i64;
+    (year, month, day)
+}
+
+// Prefer this in new APIs over (year, month, day) triples, which leave
+// month/day validation to the caller.
+pub fn day_of_year_from_date(date: NaiveDate) -> i32 {
+    date.ordinal() as i32
+}
+
+// Days (and fraction) since noon UT on 1 January 4713 BC, Julian calendar,
+// from the UTC instant with its sub-second part. Time is UT as chrono gives
+// it; no ΔT is applied. An f64 near 2.46 million days resolves about 40 µs.
+pub fn julian_day<Tz: TimeZone>(dt: &DateTime<Tz>) -> f64 {
+    let utc = dt.with_timezone(&Utc);
+    let seconds = utc.timestamp() as f64 + utc.timestamp_subsec_nanos() as f64 / 1e9;
+    JULIAN_DAY_UNIX_EPOCH + seconds / 86_400.0
+}
+
+// Midnight UT starting `date`, which always ends in .5.
+pub fn julian_day_from_date(date: NaiveDate) -> f64 {
+    julian_day(&date.and_time(NaiveTime::MIN).and_utc())
+}
+
+// Inverse of `julian_day`, rounded to the millisecond since the f64 cannot
+// hold more; None outside chrono's range.
+pub fn datetime_from_julian_day(jd: f64) -> Option<DateTime<Utc>> {
+    let millis = ((jd - JULIAN_DAY_UNIX_EPOCH) * 86_400e3).round();
+    if !millis.is_finite() || millis.abs() >= i64::MAX as f64 {
+        return None;
+    }
+    DateTime::from_timestamp_millis(millis as i64)
+}
+
+// Julian centuries since J2000.0, the time argument of the SPA and Meeus
+// series.
+pub fn julian_century(jd: f64) -> f64 {
+    (jd - JULIAN_DAY_J2000) / DAYS_PER_JULIAN_CENTURY
+}
+
+pub fn julian_day_from_century(t: f64) -> f64 {
+    JULIAN_DAY_J2000 + t * DAYS_PER_JULIAN_CENTURY
 }
 
 pub fn intermediate_angle_b(n: i32) -> f64 {
     deg_to_rad((n - 1) as f64 * (360.0 / 365.0))
 }
 
+// `intermediate_angle_b` over the year's actual length, so day 366 of a leap
+// year stays short of a full turn instead of wrapping past 1 January. Equal
+// to it in common years.
+pub fn fractional_year(n: i32, year: i32) -> f64 {
+    let days = if leap_year(year) { 366.0 } else { 365.0 };
+    deg_to_rad((n - 1) as f64 * (360.0 / days))
+}
+
 pub fn equation_of_time(n: i32) -> f64 {
-    let b = intermediate_angle_b(n);
+    equation_of_time_at(intermediate_angle_b(n))
+}
+
+pub fn equation_of_time_in_year(n: i32, year: i32) -> f64 {
+    equation_of_time_at(fractional_year(n, year))
+}
+
+pub fn equation_of_time_with_model(n: i32, year: i32, model: EquationOfTimeModel) -> f64 {
+    match model {
+        EquationOfTimeModel::Fourier => equation_of_time_in_year(n, year),
+        EquationOfTimeModel::Ecliptic => ephemeris::equation_of_time_for_day(n, year),
+    }
+}
+
+fn equation_of_time_at(b: f64) -> f64 {
     229.18
         * (0.000075
-            + 0.001868 * b.cos()
-            - 0.032077 * b.sin()
-            - 0.014615 * (2.0 * b).cos()
-            - 0.040849 * (2.0 * b).sin())
+            + 0.001868 * math::cos(b)
+            - 0.032077 * math::sin(b)
+            - 0.014615 * math::cos(2.0 * b)
+            - 0.040849 * math::sin(2.0 * b))
+}
+
+// Spencer (1971) series for (r0/r)², same Fourier angle as the equation of
+// time; multiplies the solar constant to give extraterrestrial irradiance.
+pub fn eccentricity_correction(n: i32) -> f64 {
+    let b = intermediate_angle_b(n);
+    1.000110
+        + 0.034221 * math::cos(b)
+        + 0.001280 * math::sin(b)
+        + 0.000719 * math::cos(2.0 * b)
+        + 0.000077 * math::sin(2.0 * b)
 }
 
-pub fn utc_lst_correction(longitude: f64, eot: f64) -> f64 {
+// In astronomical units.
+pub fn earth_sun_distance(n: i32) -> f64 {
+    1.0 / eccentricity_correction(n).sqrt()
+}
+
+pub const fn utc_lst_correction(longitude: f64, eot: f64) -> f64 {
     (4.0 * longitude + eot) / 60.0
 }
 
-pub fn hour_angle(local_solar_time: f64) -> f64 {
+pub const fn hour_angle(local_solar_time: f64) -> f64 {
     DEGREES_PER_HOUR * (local_solar_time - 12.0)
 }
 
 pub fn solar_declination(n: i32) -> f64 {
-    EARTH_AXIAL_TILT * deg_to_rad(360.0 * ((284 + n) as f64 / 365.0)).sin()
+    cooper_declination(n, 365.0)
+}
+
+fn cooper_declination(n: i32, days: f64) -> f64 {
+    EARTH_AXIAL_TILT * math::sin(deg_to_rad(360.0 * ((284 + n) as f64 / days)))
+}
+
+// Spencer (1971), on the same Fourier angle as the equation of time.
+pub fn spencer_declination(n: i32) -> f64 {
+    spencer_declination_at(intermediate_angle_b(n))
+}
+
+fn spencer_declination_at(b: f64) -> f64 {
+    rad_to_deg(
+        0.006918 - 0.399912 * math::cos(b) + 0.070257 * math::sin(b)
+            - 0.006758 * math::cos(2.0 * b)
+            + 0.000907 * math::sin(2.0 * b)
+            - 0.002697 * math::cos(3.0 * b)
+            + 0.00148 * math::sin(3.0 * b),
+    )
+}
+
+pub fn declination(n: i32, model: DeclinationModel) -> f64 {
+    match model {
+        DeclinationModel::Cooper => solar_declination(n),
+        DeclinationModel::Spencer => spencer_declination(n),
+    }
+}
+
+// `declination` with the year's length in both models' day angle.
+pub fn declination_in_year(n: i32, year: i32, model: DeclinationModel) -> f64 {
+    match model {
+        DeclinationModel::Cooper => {
+            cooper_declination(n, if leap_year(year) { 366.0 } else { 365.0 })
+        }
+        DeclinationModel::Spencer => spencer_declination_at(fractional_year(n, year)),
+    }
 }
 
 pub fn solar_zenith_angle(latitude: f64, declination: f64, hour_angle: f64) -> f64 {
     let lat_rad = deg_to_rad(latitude);
     let dec_rad = deg_to_rad(declination);
     let ha_rad = deg_to_rad(hour_angle);
-    let cos_zenith =
-        lat_rad.sin() * dec_rad.sin() + lat_rad.cos() * dec_rad.cos() * ha_rad.cos();
-    rad_to_deg(cos_zenith.clamp(-1.0, 1.0).acos())
+    let cos_zenith = math::sin(lat_rad) * math::sin(dec_rad)
+        + math::cos(lat_rad) * math::cos(dec_rad) * math::cos(ha_rad);
+    rad_to_deg(math::acos(cos_zenith.clamp(-1.0, 1.0)))
 }
 
-pub fn solar_altitude(zenith_angle: f64) -> f64 {
+pub const fn solar_altitude(zenith_angle: f64) -> f64 {
     90.0 - zenith_angle
 }
 
@@ -78,9 +315,10 @@ pub fn solar_azimuth(latitude: f64, declination: f64, hour_angle: f64) -> f64 {
     let lat_rad = deg_to_rad(latitude);
     let dec_rad = deg_to_rad(declination);
     let ha_rad = deg_to_rad(hour_angle);
-    let sin_az = -dec_rad.cos() * ha_rad.sin();
-    let cos_az = dec_rad.sin() * lat_rad.cos() - dec_rad.cos() * lat_rad.sin() * ha_rad.cos();
-    let az_rad = sin_az.atan2(cos_az);
+    let sin_az = -math::cos(dec_rad) * math::sin(ha_rad);
+    let cos_az = math::sin(dec_rad) * math::cos(lat_rad)
+        - math::cos(dec_rad) * math::sin(lat_rad) * math::cos(ha_rad);
+    let az_rad = math::atan2(sin_az, cos_az);
     normalize_angle(rad_to_deg(az_rad))
 }
 
@@ -90,31 +328,92 @@ pub fn solar_angles_at(
     correction: f64,
     utc_hours: f64,
 ) -> (f64, f64, f64, f64, f64) {
-    let lst = (utc_hours + correction).rem_euclid(24.0);
-    let ha = hour_angle(lst);
     let lat_rad = deg_to_rad(latitude);
     let dec_rad = deg_to_rad(decl);
-    let ha_rad = deg_to_rad(ha);
-    let cos_zenith =
-        lat_rad.sin() * dec_rad.sin() + lat_rad.cos() * dec_rad.cos() * ha_rad.cos();
-    let z = rad_to_deg(cos_zenith.clamp(-1.0, 1.0).acos());
+    let trig = [
+        math::sin(lat_rad),
+        math::cos(lat_rad),
+        math::sin(dec_rad),
+        math::cos(dec_rad),
+    ];
+    solar_angles_with_trig(trig, correction, utc_hours)
+}
+
+// `solar_angles_at` with the sines and cosines of latitude and declination
+// already taken, for callers that hold them across many instants.
+fn solar_angles_with_trig(
+    [sin_lat, cos_lat, sin_dec, cos_dec]: [f64; 4],
+    correction: f64,
+    utc_hours: f64,
+) -> (f64, f64, f64, f64, f64) {
+    let lst = (utc_hours + correction).rem_euclid(24.0);
+    let ha = hour_angle(lst);
+    let (sin_ha, cos_ha) = (math::sin(deg_to_rad(ha)), math::cos(deg_to_rad(ha)));
+    let cos_zenith = sin_lat * sin_dec + cos_lat * cos_dec * cos_ha;
+    let z = rad_to_deg(math::acos(cos_zenith.clamp(-1.0, 1.0)));
     let alt = solar_altitude(z);
-    let sin_az = -dec_rad.cos() * ha_rad.sin();
-    let cos_az = dec_rad.sin() * lat_rad.cos() - dec_rad.cos() * lat_rad.sin() * ha_rad.cos();
-    let azim = normalize_angle(rad_to_deg(sin_az.atan2(cos_az)));
+    let sin_az = -cos_dec * sin_ha;
+    let cos_az = sin_dec * cos_lat - cos_dec * sin_lat * cos_ha;
+    let azim = normalize_angle(rad_to_deg(math::atan2(sin_az, cos_az)));
     (lst, ha, z, alt, azim)
 }
 
+// Day of year and time of day come from the UTC instant, so a local New
+// Year's Eve east of Greenwich is day 1 of the next year. Only whole seconds
+// count: a leap second (chrono's 23:59:60, stored as second 59 with an
+// overlong fraction) gives the position at 23:59:59. Neither coordinates nor
+// years are checked; see `solar_position_checked`.
 pub fn solar_position<Tz: TimeZone>(
     latitude: f64,
     longitude: f64,
     dt: &DateTime<Tz>,
+) -> SolarPosition {
+    solar_position_with_model(latitude, longitude, dt, DeclinationModel::Cooper)
+}
+
+pub fn solar_position_with_model<Tz: TimeZone>(
+    latitude: f64,
+    longitude: f64,
+    dt: &DateTime<Tz>,
+    model: DeclinationModel,
+) -> SolarPosition {
+    solar_position_with_models(latitude, longitude, dt, model, EquationOfTimeModel::Fourier)
+}
+
+pub fn solar_position_with_models<Tz: TimeZone>(
+    latitude: f64,
+    longitude: f64,
+    dt: &DateTime<Tz>,
+    declination_model: DeclinationModel,
+    equation_of_time_model: EquationOfTimeModel,
 ) -> SolarPosition {
     let utc = dt.with_timezone(&Utc);
-    let utc_hours = utc.hour() as f64 + utc.minute() as f64 / 60.0 + utc.second() as f64 / 3600.0;
-    let n = utc.ordinal() as i32;
-    let eot = equation_of_time(n);
-    let decl = solar_declination(n);
+    position_at(
+        latitude,
+        longitude,
+        utc.year(),
+        day_of_year_from_date(utc.date_naive()),
+        utc_hours(utc.hour(), utc.minute(), utc.second()),
+        declination_model,
+        equation_of_time_model,
+    )
+}
+
+fn utc_hours(hour: u32, minute: u32, second: u32) -> f64 {
+    hour as f64 + minute as f64 / 60.0 + second as f64 / 3600.0
+}
+
+fn position_at(
+    latitude: f64,
+    longitude: f64,
+    year: i32,
+    n: i32,
+    utc_hours: f64,
+    declination_model: DeclinationModel,
+    equation_of_time_model: EquationOfTimeModel,
+) -> SolarPosition {
+    let eot = equation_of_time_with_model(n, year, equation_of_time_model);
+    let decl = declination_in_year(n, year, declination_model);
     let correction = utc_lst_correction(longitude, eot);
     let (lst, ha, zenith, alt, azim) = solar_angles_at(latitude, decl, correction, utc_hours);
     SolarPosition {
@@ -129,10 +428,484 @@ pub fn solar_position<Tz: TimeZone>(
     }
 }
 
+// `solar_position` at many instants, identical to calling it on each. The
+// declination, equation of time and their trig are worked out once per UTC
+// date and the latitude's once in all, so a schedule sampled every minute
+// pays for little more than the hour angle.
+pub fn solar_positions<Tz: TimeZone>(
+    latitude: f64,
+    longitude: f64,
+    times: &[DateTime<Tz>],
+) -> Vec<SolarPosition> {
+    let mut tracker = PositionTracker::new(latitude, longitude);
+    times.iter().map(|dt| tracker.position(dt)).collect()
+}
+
+type DayTerms = (f64, f64, f64, [f64; 4]);
+
+// A site's sun for a controller that polls every few seconds. A full
+// recompute (declination, equation of time and their trig) happens only
+// when the UTC date changes; every other call costs the hour angle's sine
+// and cosine plus an acos and an atan2. The model itself holds those terms
+// fixed across a UTC date, so the cache adds no error: every position is
+// bit-for-bit `solar_position` at the same instant, and the error against
+// the real sun is the model's own (see `solar_position_precise` for that).
+#[derive(Debug, Clone, PartialEq)]
+pub struct PositionTracker {
+    latitude: f64,
+    longitude: f64,
+    sin_lat: f64,
+    cos_lat: f64,
+    day: Option<((i32, i32), DayTerms)>,
+    recomputes: u64,
+}
+
+impl PositionTracker {
+    pub fn new(latitude: f64, longitude: f64) -> Self {
+        let lat_rad = deg_to_rad(latitude);
+        Self {
+            latitude,
+            longitude,
+            sin_lat: math::sin(lat_rad),
+            cos_lat: math::cos(lat_rad),
+            day: None,
+            recomputes: 0,
+        }
+    }
+
+    pub fn latitude(&self) -> f64 {
+        self.latitude
+    }
+
+    pub fn longitude(&self) -> f64 {
+        self.longitude
+    }
+
+    // Full recomputes so far, one per distinct UTC date met in a row
+    pub fn recomputes(&self) -> u64 {
+        self.recomputes
+    }
+
+    // Instants may arrive in any order; going back to an earlier date just
+    // costs another recompute.
+    pub fn position<Tz: TimeZone>(&mut self, dt: &DateTime<Tz>) -> SolarPosition {
+        let utc = dt.with_timezone(&Utc);
+        let key = (utc.year(), day_of_year_from_date(utc.date_naive()));
+        let (eot, decl, correction, trig) = match self.day {
+            Some((cached, terms)) if cached == key => terms,
+            _ => {
+                self.recomputes += 1;
+                let terms = self.day_terms(key);
+                self.day.insert((key, terms)).1
+            }
+        };
+        let hours = utc_hours(utc.hour(), utc.minute(), utc.second());
+        let (lst, ha, zenith, alt, azim) = solar_angles_with_trig(trig, correction, hours);
+        SolarPosition {
+            day_of_year: key.1,
+            declination: decl,
+            equation_of_time: eot,
+            local_solar_time: lst,
+            hour_angle: ha,
+            zenith,
+            altitude: alt,
+            azimuth: azim,
+        }
+    }
+
+    fn day_terms(&self, (year, n): (i32, i32)) -> DayTerms {
+        let eot = equation_of_time_in_year(n, year);
+        let decl = declination_in_year(n, year, DeclinationModel::Cooper);
+        let dec_rad = deg_to_rad(decl);
+        let trig = [self.sin_lat, self.cos_lat, math::sin(dec_rad), math::cos(dec_rad)];
+        (eot, decl, utc_lst_correction(self.longitude, eot), trig)
+    }
+}
+
+// `count` positions from `start`, `step` apart, each with its UTC instant,
+// so simulations and plots are iterator chains rather than loops doing
+// their own time arithmetic. A zero or negative step is allowed here; the
+// series ends early only if the time would leave chrono's range.
+pub fn solar_position_series<Tz: TimeZone>(
+    latitude: f64,
+    longitude: f64,
+    start: &DateTime<Tz>,
+    step: Duration,
+    count: usize,
+) -> impl Iterator<Item = (DateTime<Utc>, SolarPosition)> {
+    let start = start.with_timezone(&Utc);
+    std::iter::successors(Some(start), move |t| t.checked_add_signed(step))
+        .take(count)
+        .map(move |t| (t, solar_position(latitude, longitude, &t)))
+}
+
+// The same, from `start` up to and including `end`. Empty for a step that
+// is not positive, which would never reach `end`.
+pub fn solar_position_series_until<Tz: TimeZone>(
+    latitude: f64,
+    longitude: f64,
+    start: &DateTime<Tz>,
+    step: Duration,
+    end: &DateTime<Tz>,
+) -> impl Iterator<Item = (DateTime<Utc>, SolarPosition)> {
+    let end = end.with_timezone(&Utc);
+    let count = if step > Duration::zero() { usize::MAX } else { 0 };
+    solar_position_series(latitude, longitude, start, step, count)
+        .take_while(move |(t, _)| *t <= end)
+}
+
+// `solar_position` from whole seconds since the Unix epoch, for targets
+// whose only clock is an RTC counter: the date comes from `days_to_ymd`, so
+// nothing here touches chrono. Identical to `solar_position` at the same
+// instant. Like the RTC, the count has no leap seconds.
+pub fn solar_position_unix(latitude: f64, longitude: f64, epoch_seconds: u64) -> SolarPosition {
+    let (year, month, day) = days_to_ymd((epoch_seconds / 86_400) as i64);
+    let year = year.clamp(i32::MIN as i64, i32::MAX as i64) as i32;
+    let seconds = (epoch_seconds % 86_400) as u32;
+    position_at(
+        latitude,
+        longitude,
+        year,
+        day_of_year(year, month, day),
+        utc_hours(seconds / 3600, seconds % 3600 / 60, seconds % 60),
+        DeclinationModel::Cooper,
+        EquationOfTimeModel::Fourier,
+    )
+}
+
+// `Precise` goes through the ephemeris with its own ΔT estimate.
+pub fn solar_position_with_position_model<Tz: TimeZone>(
+    latitude: f64,
+    longitude: f64,
+    dt: &DateTime<Tz>,
+    model: PositionModel,
+) -> SolarPosition {
+    match model {
+        PositionModel::Precise => ephemeris::solar_position_precise(latitude, longitude, dt, None),
+        PositionModel::Fast | PositionModel::Standard => solar_position_with_models(
+            latitude,
+            longitude,
+            dt,
+            model.declination_model(),
+            model.equation_of_time_model(),
+        ),
+    }
+}
+
+// `solar_position` for untrusted input: refuses coordinates off the globe
+// (or NaN), and UTC years outside `VALID_YEARS`, instead of returning
+// numbers the model cannot vouch for. Trusted callers keep the unchecked
+// `solar_position`.
+pub fn solar_position_checked<Tz: TimeZone>(
+    latitude: f64,
+    longitude: f64,
+    dt: &DateTime<Tz>,
+) -> Result<SolarPosition, PositionError> {
+    if !(-90.0..=90.0).contains(&latitude) {
+        return Err(PositionError::LatitudeOutOfRange { latitude });
+    }
+    if !(-180.0..=180.0).contains(&longitude) {
+        return Err(PositionError::LongitudeOutOfRange { longitude });
+    }
+    let year = dt.with_timezone(&Utc).year();
+    if !VALID_YEARS.contains(&year) {
+        return Err(PositionError::YearOutOfRange { year });
+    }
+    Ok(solar_position(latitude, longitude, dt))
+}
+
+// Same pipeline as `solar_position` with every intermediate kept, for
+// checking against the archnotes formulas step by step.
+pub fn solar_position_debug<Tz: TimeZone>(
+    latitude: f64,
+    longitude: f64,
+    dt: &DateTime<Tz>,
+) -> SolarPositionDebug {
+    let utc = dt.with_timezone(&Utc);
+    let utc_hours = utc.hour() as f64 + utc.minute() as f64 / 60.0 + utc.second() as f64 / 3600.0;
+    let n = day_of_year_from_date(utc.date_naive());
+    let b = fractional_year(n, utc.year());
+    let eot_terms = [
+        229.18 * 0.000075,
+        229.18 * 0.001868 * math::cos(b),
+        229.18 * -0.032077 * math::sin(b),
+        229.18 * -0.014615 * math::cos(2.0 * b),
+        229.18 * -0.040849 * math::sin(2.0 * b),
+    ];
+    let eot = equation_of_time_in_year(n, utc.year());
+    let decl = declination_in_year(n, utc.year(), DeclinationModel::Cooper);
+    let correction = utc_lst_correction(longitude, eot);
+    let lst = (utc_hours + correction).rem_euclid(24.0);
+    let ha = hour_angle(lst);
+    let lat_rad = deg_to_rad(latitude);
+    let dec_rad = deg_to_rad(decl);
+    let ha_rad = deg_to_rad(ha);
+    let cos_zenith = math::sin(lat_rad) * math::sin(dec_rad)
+        + math::cos(lat_rad) * math::cos(dec_rad) * math::cos(ha_rad);
+    let zenith = rad_to_deg(math::acos(cos_zenith.clamp(-1.0, 1.0)));
+    let sin_azimuth = -math::cos(dec_rad) * math::sin(ha_rad);
+    let cos_azimuth = math::sin(dec_rad) * math::cos(lat_rad)
+        - math::cos(dec_rad) * math::sin(lat_rad) * math::cos(ha_rad);
+    SolarPositionDebug {
+        day_of_year: n,
+        utc_hours,
+        intermediate_angle_b: b,
+        eot_terms,
+        equation_of_time: eot,
+        declination: decl,
+        correction,
+        local_solar_time: lst,
+        hour_angle: ha,
+        cos_zenith,
+        zenith,
+        altitude: solar_altitude(zenith),
+        sin_azimuth,
+        cos_azimuth,
+        azimuth: normalize_angle(rad_to_deg(math::atan2(sin_azimuth, cos_azimuth))),
+    }
+}
+
+// `clock_hour` is UTC; one sample per day of the year.
+pub fn analemma(latitude: f64, longitude: f64, clock_hour: f64, year: i32) -> Vec<(f64, f64)> {
+    let n_days = if leap_year(year) { 366 } else { 365 };
+    (1..=n_days)
+        .map(|n| {
+            let correction = utc_lst_correction(longitude, equation_of_time_in_year(n, year));
+            let decl = declination_in_year(n, year, DeclinationModel::Cooper);
+            let (_, _, _, alt, azim) = solar_angles_at(latitude, decl, correction, clock_hour);
+            (alt, azim)
+        })
+        .collect()
+}
+
+pub fn sun_vector(pos: &SolarPosition) -> [f64; 3] {
+    sun_vector_in(pos, VectorFrame::Enu)
+}
+
+pub fn sun_vector_in(pos: &SolarPosition, frame: VectorFrame) -> [f64; 3] {
+    let (sin_alt, cos_alt) = math::sin_cos(deg_to_rad(pos.altitude));
+    let (sin_az, cos_az) = math::sin_cos(deg_to_rad(pos.azimuth));
+    let (east, north, up) = (cos_alt * sin_az, cos_alt * cos_az, sin_alt);
+    match frame {
+        VectorFrame::Enu => [east, north, up],
+        VectorFrame::Ned => [north, east, -up],
+        VectorFrame::Nwu => [north, -east, up],
+    }
+}
+
+// `panel_azimuth` follows `dual_axis_angles` and sits opposite the sun, so
+// the normal leans away from it.
+pub fn panel_normal(tilt: f64, panel_azimuth: f64) -> [f64; 3] {
+    let (sin_t, cos_t) = math::sin_cos(deg_to_rad(tilt));
+    let (sin_az, cos_az) = math::sin_cos(deg_to_rad(panel_azimuth));
+    [-sin_t * sin_az, -sin_t * cos_az, cos_t]
+}
+
+pub fn surface_normal(surface: &SurfaceOrientation) -> [f64; 3] {
+    panel_normal(surface.surface_tilt, surface.surface_azimuth + 180.0)
+}
+
+pub fn vector_dot(a: [f64; 3], b: [f64; 3]) -> f64 {
+    a[0] * b[0] + a[1] * b[1] + a[2] * b[2]
+}
+
+pub fn angle_between(a: [f64; 3], b: [f64; 3]) -> f64 {
+    let norm = vector_dot(a, a).sqrt() * vector_dot(b, b).sqrt();
+    rad_to_deg(math::acos((vector_dot(a, b) / norm).clamp(-1.0, 1.0)))
+}
+
+pub fn incidence_angle(pos: &SolarPosition, tilt: f64, panel_azimuth: f64) -> f64 {
+    angle_between(panel_normal(tilt, panel_azimuth), sun_vector(pos))
+}
+
+// Shadow cast on level ground by a vertical pole; the bearing points away
+// from the sun. None while the sun is at or below the horizon.
+pub fn shadow_of_pole(height: f64, pos: &SolarPosition) -> Option<(f64, f64)> {
+    if pos.altitude <= 0.0 {
+        return None;
+    }
+    let length = height / math::tan(deg_to_rad(pos.altitude));
+    Some((length, normalize_angle(pos.azimuth + 180.0)))
+}
+
+// Ground extent (near, far) of the shadow of a panel of chord `width`
+// rotated about a horizontal axis at `hub_height`, measured across the axis
+// toward `axis_azimuth + 90` (east for a N-S axis) from the point under it.
+// Compare against row pitch to check for row-to-row shading.
+pub fn panel_edge_shadow(
+    width: f64,
+    hub_height: f64,
+    rotation: f64,
+    axis_azimuth: f64,
+    pos: &SolarPosition,
+) -> Option<(f64, f64)> {
+    let [east, north, up] = sun_vector(pos);
+    if up <= 0.0 {
+        return None;
+    }
+    let (sin_a, cos_a) = math::sin_cos(deg_to_rad(axis_azimuth));
+    let cross = east * cos_a - north * sin_a;
+    let (sin_r, cos_r) = math::sin_cos(deg_to_rad(rotation));
+    // Negative rotation faces the +cross side, lowering that edge
+    let edge = |offset: f64| {
+        let height = hub_height + offset * sin_r;
+        offset * cos_r - height * cross / up
+    };
+    let (a, b) = (edge(-width / 2.0), edge(width / 2.0));
+    Some((a.min(b), a.max(b)))
+}
+
 pub fn single_axis_tilt(pos: &SolarPosition, latitude: f64) -> f64 {
     let ha_rad = deg_to_rad(pos.hour_angle);
     let lat_rad = deg_to_rad(latitude);
-    rad_to_deg(ha_rad.tan().atan2(lat_rad.cos()))
+    rad_to_deg(math::atan2(math::tan(ha_rad), math::cos(lat_rad)))
+}
+
+pub fn ew_axis_tilt(pos: &SolarPosition) -> f64 {
+    ew_axis_tilt_at(pos.zenith, pos.azimuth)
+}
+
+pub(crate) fn ew_axis_tilt_at(zenith: f64, azimuth: f64) -> f64 {
+    let z_rad = deg_to_rad(zenith);
+    let az_rad = deg_to_rad(azimuth);
+    rad_to_deg(math::atan2(-math::sin(z_rad) * math::cos(az_rad), math::cos(z_rad)))
+}
+
+pub fn surface_orientation(rotation: f64, axis_tilt: f64, axis_azimuth: f64) -> SurfaceOrientation {
+    let (sin_r, cos_r) = math::sin_cos(deg_to_rad(rotation));
+    let (sin_bt, cos_bt) = math::sin_cos(deg_to_rad(axis_tilt));
+    let (sin_ga, cos_ga) = math::sin_cos(deg_to_rad(axis_azimuth));
+    // Panel normal at zero rotation leans toward the axis azimuth by the axis
+    // tilt; positive rotation turns it west about the axis.
+    let n0 = [sin_bt * sin_ga, sin_bt * cos_ga, cos_bt];
+    let axis = [cos_bt * sin_ga, cos_bt * cos_ga, -sin_bt];
+    let cross = [
+        n0[1] * axis[2] - n0[2] * axis[1],
+        n0[2] * axis[0] - n0[0] * axis[2],
+        n0[0] * axis[1] - n0[1] * axis[0],
+    ];
+    let east = n0[0] * cos_r - cross[0] * sin_r;
+    let north = n0[1] * cos_r - cross[1] * sin_r;
+    let up = n0[2] * cos_r - cross[2] * sin_r;
+    SurfaceOrientation {
+        surface_tilt: rad_to_deg(math::acos(up.clamp(-1.0, 1.0))),
+        surface_azimuth: normalize_angle(rad_to_deg(math::atan2(east, north))),
+    }
+}
+
+pub const fn polar_axis_rotation(pos: &SolarPosition) -> f64 {
+    pos.hour_angle
+}
+
+pub fn polar_axis_incidence(pos: &SolarPosition) -> f64 {
+    pos.declination.abs()
+}
+
+pub fn clamp_rotation(
+    angle: f64,
+    min_rotation: Option<f64>,
+    max_rotation: Option<f64>,
+) -> (f64, bool) {
+    // Limits come straight from configs: a NaN limit counts as none and
+    // limits given the wrong way round are put in order, where `f64::clamp`
+    // would panic on either
+    let lo = min_rotation.filter(|v| !v.is_nan()).unwrap_or(f64::NEG_INFINITY);
+    let hi = max_rotation.filter(|v| !v.is_nan()).unwrap_or(f64::INFINITY);
+    let (lo, hi) = if lo <= hi { (lo, hi) } else { (hi, lo) };
+    let clamped = if angle < lo {
+        lo
+    } else if angle > hi {
+        hi
+    } else {
+        angle
+    };
+    (clamped, clamped != angle)
+}
+
+pub fn single_axis_tilt_clamped(
+    pos: &SolarPosition,
+    latitude: f64,
+    min_rotation: Option<f64>,
+    max_rotation: Option<f64>,
+) -> (f64, bool) {
+    clamp_rotation(single_axis_tilt(pos, latitude), min_rotation, max_rotation)
+}
+
+pub fn backtrack(rotation: f64, gcr: f64) -> f64 {
+    let cos_ratio = math::cos(deg_to_rad(rotation)) / gcr;
+    if cos_ratio >= 1.0 {
+        return rotation;
+    }
+    let correction = rad_to_deg(math::acos(cos_ratio.clamp(-1.0, 1.0)));
+    rotation - rotation.signum() * correction
+}
+
+pub fn backtracking_rotation(pos: &SolarPosition, latitude: f64, gcr: f64, max_angle: f64) -> f64 {
+    let ideal = single_axis_tilt(pos, latitude);
+    let rotation = if pos.altitude > 0.0 {
+        backtrack(ideal, gcr)
+    } else {
+        0.0
+    };
+    rotation.clamp(-max_angle, max_angle)
+}
+
+// Exact true-tracking rotation about an axis descending toward
+// `axis_azimuth` by `axis_tilt`, using the same frame as
+// `surface_orientation`.
+fn axis_rotation_toward(direction: [f64; 3], axis_tilt: f64, axis_azimuth: f64) -> f64 {
+    let (sin_bt, cos_bt) = math::sin_cos(deg_to_rad(axis_tilt));
+    let (sin_ga, cos_ga) = math::sin_cos(deg_to_rad(axis_azimuth));
+    let n0 = [sin_bt * sin_ga, sin_bt * cos_ga, cos_bt];
+    // n0 × axis, the direction a positive rotation turns away from
+    let side = [-cos_ga, sin_ga, 0.0];
+    rad_to_deg(math::atan2(-vector_dot(direction, side), vector_dot(direction, n0)))
+}
+
+pub(crate) fn tilted_axis_rotation_at(
+    zenith: f64,
+    azimuth: f64,
+    axis_tilt: f64,
+    axis_azimuth: f64,
+) -> f64 {
+    let (sin_z, cos_z) = math::sin_cos(deg_to_rad(zenith));
+    let (sin_az, cos_az) = math::sin_cos(deg_to_rad(azimuth));
+    let sun = [sin_z * sin_az, sin_z * cos_az, cos_z];
+    axis_rotation_toward(sun, axis_tilt, axis_azimuth)
+}
+
+pub fn tilted_axis_rotation(pos: &SolarPosition, axis_tilt: f64, axis_azimuth: f64) -> f64 {
+    tilted_axis_rotation_at(pos.zenith, pos.azimuth, axis_tilt, axis_azimuth)
+}
+
+// Aspect is the downslope azimuth. An axis laid on the ground picks up the
+// slope component along its own direction.
+pub fn terrain_axis_tilt(terrain: &TerrainSlope, axis_azimuth: f64) -> f64 {
+    let along = math::cos(deg_to_rad(terrain.aspect - axis_azimuth));
+    rad_to_deg(math::atan(math::tan(deg_to_rad(terrain.slope)) * along))
+}
+
+// Rotation that would lay the panel parallel to the ground; positive when
+// the ground falls away to the west of the axis.
+pub fn cross_axis_slope(terrain: &TerrainSlope, axis_azimuth: f64) -> f64 {
+    let (sin_s, cos_s) = math::sin_cos(deg_to_rad(terrain.slope));
+    let (sin_asp, cos_asp) = math::sin_cos(deg_to_rad(terrain.aspect));
+    let ground_normal = [sin_s * sin_asp, sin_s * cos_asp, cos_s];
+    let axis_tilt = terrain_axis_tilt(terrain, axis_azimuth);
+    axis_rotation_toward(ground_normal, axis_tilt, axis_azimuth)
+}
+
+// Backtracking relative to a ground line inclined by `cross_axis` in the
+// rotation plane; `gcr` uses horizontal row pitch, so it reduces to
+// `backtrack` on flat ground.
+pub fn slope_aware_backtrack(rotation: f64, gcr: f64, cross_axis: f64) -> f64 {
+    let cos_cross = math::cos(deg_to_rad(cross_axis));
+    let cos_ratio = math::cos(deg_to_rad(rotation - cross_axis)) / (gcr * cos_cross);
+    if cos_ratio >= 1.0 {
+        return rotation;
+    }
+    let correction = rad_to_deg(math::acos(cos_ratio.clamp(-1.0, 1.0)));
+    rotation - (rotation - cross_axis).signum() * correction
 }
 
 pub fn dual_axis_angles(pos: &SolarPosition) -> DualAxisAngles {
@@ -142,10 +915,70 @@ pub fn dual_axis_angles(pos: &SolarPosition) -> DualAxisAngles {
     }
 }
 
+pub fn dual_axis_angles_clamped(
+    pos: &SolarPosition,
+    min_rotation: Option<f64>,
+    max_rotation: Option<f64>,
+) -> (DualAxisAngles, bool) {
+    let da = dual_axis_angles(pos);
+    let (tilt, clamped) = clamp_rotation(da.tilt, min_rotation, max_rotation);
+    (DualAxisAngles { tilt, ..da }, clamped)
+}
+
+// Primary is the outer (fixed-base) axis. Azimuth-elevation reports
+// (panel_azimuth, tilt) as in `dual_axis_angles`; tilt-roll rolls about a
+// horizontal N-S axis (negative faces east) then tilts toward the equator
+// (positive faces south); polar-declination turns by hour angle about the
+// polar axis then by declination.
+pub(crate) fn mount_angles_from(
+    zenith: f64,
+    azimuth: f64,
+    hour_angle: f64,
+    latitude: f64,
+    mount: DualAxisMount,
+) -> MountAngles {
+    let (sin_z, cos_z) = math::sin_cos(deg_to_rad(zenith));
+    let (sin_az, cos_az) = math::sin_cos(deg_to_rad(azimuth));
+    let (east, north, up) = (sin_z * sin_az, sin_z * cos_az, cos_z);
+    match mount {
+        DualAxisMount::AzimuthElevation => MountAngles {
+            primary: normalize_angle(azimuth + 180.0),
+            secondary: zenith,
+        },
+        DualAxisMount::TiltRoll => MountAngles {
+            primary: rad_to_deg(math::atan2(-east, up)),
+            secondary: rad_to_deg(math::atan2(-north, math::hypot(east, up))),
+        },
+        DualAxisMount::PolarDeclination => {
+            let (sin_lat, cos_lat) = math::sin_cos(deg_to_rad(latitude));
+            MountAngles {
+                primary: hour_angle,
+                secondary: rad_to_deg(math::asin(
+                    (north * cos_lat + up * sin_lat).clamp(-1.0, 1.0),
+                )),
+            }
+        }
+    }
+}
+
+pub fn mount_angles(pos: &SolarPosition, latitude: f64, mount: DualAxisMount) -> MountAngles {
+    mount_angles_from(pos.zenith, pos.azimuth, pos.hour_angle, latitude, mount)
+}
+
 pub fn optimal_fixed_tilt(latitude: f64) -> f64 {
     0.76 * latitude.abs() + 3.1
 }
 
+// Racking angle to set relative to sloped ground so the panel still sits
+// at `optimal_fixed_tilt` facing the equator.
+pub fn fixed_tilt_on_slope(latitude: f64, terrain: &TerrainSlope) -> f64 {
+    let facing = if latitude >= 0.0 { 180.0 } else { 0.0 };
+    let target = panel_normal(optimal_fixed_tilt(latitude), facing + 180.0);
+    let (sin_s, cos_s) = math::sin_cos(deg_to_rad(terrain.slope));
+    let (sin_asp, cos_asp) = math::sin_cos(deg_to_rad(terrain.aspect));
+    angle_between(target, [sin_s * sin_asp, sin_s * cos_asp, cos_s])
+}
+
 pub fn seasonal_tilt_adjustment(latitude: f64, season: Season) -> f64 {
     let abs_lat = latitude.abs();
     match season {
@@ -155,3 +988,73 @@ pub fn seasonal_tilt_adjustment(latitude: f64, season: Season) -> f64 {
     }
 }
 
+// Calendar seasons for `seasonal_tilt_adjustment`, flipped south of the
+// equator (the equator counts as northern). Month and day are not
+// validated; anything past December reads as winter in the north.
+impl Season {
+    // Astronomical seasons, starting on fixed equinox and solstice dates
+    // (20 Mar, 21 Jun, 22 Sep, 21 Dec), which are within a day of the true
+    // ones every year this century.
+    pub fn from_date(latitude: f64, month: u32, day: u32) -> Season {
+        let northern = match (month, day) {
+            d if d < (3, 20) => Season::Winter,
+            d if d < (6, 21) => Season::Spring,
+            d if d < (9, 22) => Season::Summer,
+            d if d < (12, 21) => Season::Fall,
+            _ => Season::Winter,
+        };
+        northern.for_hemisphere(latitude)
+    }
+
+    // Meteorological seasons: whole months, with December-February as
+    // northern winter, so only the month matters.
+    pub fn from_month_meteorological(latitude: f64, month: u32) -> Season {
+        let northern = match month {
+            3..=5 => Season::Spring,
+            6..=8 => Season::Summer,
+            9..=11 => Season::Fall,
+            _ => Season::Winter,
+        };
+        northern.for_hemisphere(latitude)
+    }
+
+    fn for_hemisphere(self, latitude: f64) -> Season {
+        if latitude >= 0.0 {
+            return self;
+        }
+        match self {
+            Season::Summer => Season::Winter,
+            Season::Winter => Season::Summer,
+            Season::Spring => Season::Fall,
+            Season::Fall => Season::Spring,
+        }
+    }
+}
+
+// Season by how high the sun rides at the site, not by calendar name: summer
+// is the quarter of the year centred on the local summer solstice, when the
+// declination is past sin 45° of the axial tilt toward the site's pole. The
+// southern hemisphere mirrors the northern one, and the equator counts as
+// northern.
+fn solar_season(latitude: f64, day_of_year: i32) -> Season {
+    let sign = if latitude >= 0.0 { 1.0 } else { -1.0 };
+    let decl = sign * solar_declination(day_of_year);
+    let threshold = EARTH_AXIAL_TILT * std::f64::consts::FRAC_1_SQRT_2;
+    if decl >= threshold {
+        Season::Summer
+    } else if decl <= -threshold {
+        Season::Winter
+    } else if sign * solar_declination(day_of_year + 1) > decl {
+        Season::Spring
+    } else {
+        Season::Fall
+    }
+}
+
+// `seasonal_tilt_adjustment` for the local season on `day_of_year`, so
+// Australian sites get their winter tilt in July. Clamped to 0..=90: within
+// 15° of the equator the summer setting lies flat rather than going negative.
+pub fn seasonal_tilt_for_day(latitude: f64, day_of_year: i32) -> f64 {
+    seasonal_tilt_adjustment(latitude, solar_season(latitude, day_of_year)).clamp(0.0, 90.0)
+}
+
@@ -0,0 +1,167 @@
+// Zenith and azimuth for many hour angles at once, for year-long 1-minute
+// runs and table generation across many sites. Work proceeds `LANES` hour
+// angles at a time through branch-free kernels on fixed-size arrays, which
+// the compiler turns into SIMD on targets that have it (SSE2/AVX, NEON)
+// without nightly `std::simd` or per-target intrinsics. The kernels are the
+// fdlibm polynomials of `portable_math` with selects in place of branches,
+// so results agree with the scalar `solar_zenith_angle` and `solar_azimuth`
+// to about 1e-12° and, being built only from +, -, *, / and sqrt, are
+// bit-identical across targets.
+
+use std::f64::consts::{FRAC_2_PI, FRAC_PI_2, FRAC_PI_4, PI};
+
+use crate::angles::{self, deg_to_rad, rad_to_deg};
+use crate::lookup_table::{intervals_per_day, key_correction};
+use crate::portable_math::{AT, C, FRAC_PI_4_LO, PIO2_1, PIO2_2, PIO2_3, S};
+use crate::types::{LookupTableConfig, MinuteOfDay};
+
+pub const LANES: usize = 4;
+
+type Lanes = [f64; LANES];
+
+const TAN_PI_8: f64 = 0.41421356237309503;
+
+fn map(f: impl Fn(usize) -> f64) -> Lanes {
+    std::array::from_fn(f)
+}
+
+fn sin_cos(x: &Lanes) -> (Lanes, Lanes) {
+    let n = map(|i| (x[i] * FRAC_2_PI).round());
+    let r = map(|i| ((x[i] - n[i] * PIO2_1) - n[i] * PIO2_2) - n[i] * PIO2_3);
+    let s = map(|i| {
+        let z = r[i] * r[i];
+        r[i] + r[i] * z * (S[0] + z * (S[1] + z * (S[2] + z * (S[3] + z * (S[4] + z * S[5])))))
+    });
+    let c = map(|i| {
+        let z = r[i] * r[i];
+        let p = z * (C[0] + z * (C[1] + z * (C[2] + z * (C[3] + z * (C[4] + z * C[5])))));
+        let hz = 0.5 * z;
+        let w = 1.0 - hz;
+        w + (((1.0 - w) - hz) + z * p)
+    });
+    // Quadrant 0..4 as a float, so the selects stay in vector registers
+    let q = map(|i| n[i] - 4.0 * (n[i] * 0.25).floor());
+    let sin = map(|i| {
+        let v = if q[i] == 1.0 || q[i] == 3.0 {
+            c[i]
+        } else {
+            s[i]
+        };
+        if q[i] >= 2.0 {
+            -v
+        } else {
+            v
+        }
+    });
+    let cos = map(|i| {
+        let v = if q[i] == 1.0 || q[i] == 3.0 {
+            s[i]
+        } else {
+            c[i]
+        };
+        if q[i] == 1.0 || q[i] == 2.0 {
+            -v
+        } else {
+            v
+        }
+    });
+    (sin, cos)
+}
+
+// Finite inputs only; both zero gives 0.
+fn atan2(y: &Lanes, x: &Lanes) -> Lanes {
+    map(|i| {
+        let (ax, ay) = (x[i].abs(), y[i].abs());
+        let (hi, lo) = (ax.max(ay), ax.min(ay));
+        let t = if hi > 0.0 { lo / hi } else { 0.0 };
+        // Reduced to |u| <= tan(π/8), where the series needs no further split
+        let big = t > TAN_PI_8;
+        let u = if big { (t - 1.0) / (t + 1.0) } else { t };
+        let z = u * u;
+        let w = z * z;
+        let s1 = z * (AT[0] + w * (AT[2] + w * (AT[4] + w * (AT[6] + w * (AT[8] + w * AT[10])))));
+        let s2 = w * (AT[1] + w * (AT[3] + w * (AT[5] + w * (AT[7] + w * AT[9]))));
+        let a = if big {
+            FRAC_PI_4 - ((u * (s1 + s2) - FRAC_PI_4_LO) - u)
+        } else {
+            u - u * (s1 + s2)
+        };
+        let a = if ay > ax { FRAC_PI_2 - a } else { a };
+        let a = if x[i] < 0.0 { PI - a } else { a };
+        a.copysign(y[i])
+    })
+}
+
+fn zenith_azimuth_lanes(
+    sin_lat: f64,
+    cos_lat: f64,
+    sin_dec: f64,
+    cos_dec: f64,
+    ha: &Lanes,
+) -> (Lanes, Lanes) {
+    let (sin_h, cos_h) = sin_cos(&map(|i| deg_to_rad(ha[i])));
+    let cos_z = map(|i| (sin_lat * sin_dec + cos_lat * cos_dec * cos_h[i]).clamp(-1.0, 1.0));
+    let sin_z = map(|i| ((1.0 - cos_z[i]) * (1.0 + cos_z[i])).sqrt());
+    let zenith = atan2(&sin_z, &cos_z);
+    let azimuth = atan2(
+        &map(|i| -cos_dec * sin_h[i]),
+        &map(|i| sin_dec * cos_lat - cos_dec * sin_lat * cos_h[i]),
+    );
+    (
+        map(|i| rad_to_deg(zenith[i])),
+        map(|i| angles::normalize_angle(rad_to_deg(azimuth[i]))),
+    )
+}
+
+// (zenith, azimuth) in degrees for each hour angle, at one latitude and
+// declination: `solar_zenith_angle` and `solar_azimuth` in bulk.
+pub fn bulk_zenith_azimuth(
+    latitude: f64,
+    declination: f64,
+    hour_angles: &[f64],
+) -> Vec<(f64, f64)> {
+    let (sin_lat, cos_lat) = crate::portable_math::sin_cos(deg_to_rad(latitude));
+    let (sin_dec, cos_dec) = crate::portable_math::sin_cos(deg_to_rad(declination));
+    let mut out = Vec::with_capacity(hour_angles.len());
+    for chunk in hour_angles.chunks(LANES) {
+        // The last chunk is padded; its spare lanes are dropped
+        let ha = map(|i| chunk.get(i).copied().unwrap_or(0.0));
+        let (zenith, azimuth) = zenith_azimuth_lanes(sin_lat, cos_lat, sin_dec, cos_dec, &ha);
+        out.extend((0..chunk.len()).map(|i| (zenith[i], azimuth[i])));
+    }
+    out
+}
+
+// (minutes, zenith, azimuth) at every interval of one day of a table
+// generated from `config`, keyed the same way. For custom generators that
+// sweep many sites at 1-minute resolution; daylight and limits are the
+// caller's.
+pub fn bulk_day_angles(
+    config: &LookupTableConfig,
+    day_of_year: i32,
+) -> Vec<(MinuteOfDay, f64, f64)> {
+    let eot = angles::equation_of_time_with_model(
+        day_of_year,
+        config.year,
+        config.equation_of_time_model,
+    );
+    let declination =
+        angles::declination_in_year(day_of_year, config.year, config.declination_model);
+    let correction = key_correction(config, eot);
+    let minutes: Vec<i32> = (0..intervals_per_day(config.interval_minutes))
+        .map(|i| i * config.interval_minutes)
+        .collect();
+    let hour_angles: Vec<f64> = minutes
+        .iter()
+        .map(|&m| angles::hour_angle((m as f64 / 60.0 + correction).rem_euclid(24.0)))
+        .collect();
+    minutes
+        .into_iter()
+        .zip(bulk_zenith_azimuth(
+            config.latitude,
+            declination,
+            &hour_angles,
+        ))
+        .map(|(m, (zenith, azimuth))| (MinuteOfDay::wrapping(m), zenith, azimuth))
+        .collect()
+}
@@ -0,0 +1,93 @@
+use crate::angles;
+use crate::lookup_table::{angles_at_key, lookup_single_axis};
+use crate::types::{
+    CalibrationOffsets, LookupTableConfig, MinuteOfDay, SingleAxisEntry, SingleAxisTable,
+};
+
+// Field calibration applied at query time: tables keep true rotations and
+// every lookup result passes through here on its way to the actuator, so a
+// new set of offsets never means regenerating or re-flashing a table.
+
+// True rotation to the actuator's frame, as `apply_calibration` does to a
+// whole table.
+pub fn calibrate_rotation(rotation: f64, calibration: &CalibrationOffsets) -> f64 {
+    rotation * calibration.scale + calibration.zero_offset
+}
+
+// The actuator reading back to a true rotation, for feedback; None for a
+// zero scale.
+pub fn uncalibrate_rotation(actuator: f64, calibration: &CalibrationOffsets) -> Option<f64> {
+    (calibration.scale != 0.0).then(|| (actuator - calibration.zero_offset) / calibration.scale)
+}
+
+// Change in the ideal rotation at a table key when the as-built axis points
+// `error` degrees clockwise of the one the table was generated for.
+fn axis_azimuth_correction(
+    config: &LookupTableConfig,
+    day_of_year: i32,
+    minutes: i32,
+    error: f64,
+) -> f64 {
+    let axis_azimuth = config.axis_azimuth.unwrap_or(180.0);
+    let axis_tilt = config
+        .axis_tilt
+        .or(config
+            .terrain
+            .map(|terrain| angles::terrain_axis_tilt(&terrain, axis_azimuth)))
+        .unwrap_or(0.0);
+    let sun = angles_at_key(config, day_of_year, minutes);
+    let rotation =
+        |azimuth| angles::tilted_axis_rotation_at(sun.zenith, sun.azimuth, axis_tilt, azimuth);
+    angles::angle_difference(rotation(axis_azimuth), rotation(axis_azimuth + error))
+}
+
+// Any single-axis lookup result (`lookup_single_axis`, `lookup_nearest`,
+// `lookup_chebyshev`, the clock lookups) for the table generated from
+// `config`, in the calibrated actuator frame. An axis azimuth error moves the
+// rotation by the change in the ideal angle at that key; a backtracked entry
+// takes the same shift, which holds to first order in the error. The shifted
+// rotation is clamped to the config's limits, still in true angles, before
+// the zero offset and scale. Night entries pass through.
+pub fn calibrate_single_axis_entry(
+    config: &LookupTableConfig,
+    day_of_year: i32,
+    entry: &SingleAxisEntry,
+    calibration: &CalibrationOffsets,
+) -> SingleAxisEntry {
+    let Some(rotation) = entry.rotation else {
+        return *entry;
+    };
+    let (rotation, clamped) = if calibration.axis_azimuth_error != 0.0 {
+        let shift = axis_azimuth_correction(
+            config,
+            day_of_year,
+            entry.minutes,
+            calibration.axis_azimuth_error,
+        );
+        let (rotation, clamped) =
+            angles::clamp_rotation(rotation + shift, config.min_rotation, config.max_rotation);
+        (rotation, entry.clamped || clamped)
+    } else {
+        (rotation, entry.clamped)
+    };
+    SingleAxisEntry {
+        rotation: Some(calibrate_rotation(rotation, calibration)),
+        clamped,
+        ..*entry
+    }
+}
+
+pub fn lookup_single_axis_calibrated(
+    table: &SingleAxisTable,
+    day_of_year: i32,
+    minutes: MinuteOfDay,
+    calibration: &CalibrationOffsets,
+) -> Option<SingleAxisEntry> {
+    let entry = lookup_single_axis(table, day_of_year, minutes)?;
+    Some(calibrate_single_axis_entry(
+        &table.config,
+        day_of_year,
+        &entry,
+        calibration,
+    ))
+}
@@ -0,0 +1,455 @@
+use std::f64::consts::PI;
+use std::fmt;
+
+use crate::angles;
+use crate::types::{
+    ChebyshevDay, ChebyshevSegment, ChebyshevTable, MinuteOfDay, SingleAxisEntry, SingleAxisTable,
+};
+
+// Series length cap; a run that misses the bound at this length is split at
+// its sharpest kink (a rotation limit, or where backtracking starts) instead.
+pub const MAX_CHEBYSHEV_COEFFICIENTS: usize = 32;
+
+const MAGIC: [u8; 4] = *b"STCH";
+const VERSION: u8 = 1;
+// Magic, version, error bound, rotation limits, day count
+const HEADER_BYTES: usize = 4 + 1 + 4 + 4 + 4 + 2;
+// Day of year, max error, segment count
+const DAY_HEADER_BYTES: usize = 2 + 4 + 1;
+// Start, end, coefficient count
+const SEGMENT_HEADER_BYTES: usize = 2 + 2 + 1;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChebyshevDecodeError {
+    BadMagic,
+    UnsupportedVersion(u8),
+    Truncated,
+    // Infinite limits, or a minimum above the maximum
+    InvalidRotationLimits,
+}
+
+impl fmt::Display for ChebyshevDecodeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ChebyshevDecodeError::BadMagic => write!(f, "not a Chebyshev table"),
+            ChebyshevDecodeError::UnsupportedVersion(version) => {
+                write!(f, "unsupported Chebyshev table version {version}")
+            }
+            ChebyshevDecodeError::Truncated => write!(f, "truncated Chebyshev table"),
+            ChebyshevDecodeError::InvalidRotationLimits => {
+                write!(f, "invalid rotation limits in Chebyshev table")
+            }
+        }
+    }
+}
+
+impl std::error::Error for ChebyshevDecodeError {}
+
+// Counts go out as single bytes (segments per day, coefficients per segment)
+// and the day count as a u16; a table past those is refused rather than
+// written with a wrapped count the decoder would misread.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ChebyshevError {
+    // Not finite, or not above zero
+    InvalidErrorBound(f64),
+    TooManyDays(usize),
+    TooManySegments { day_of_year: i32, segments: usize },
+    TooManyCoefficients { day_of_year: i32, coefficients: usize },
+}
+
+impl fmt::Display for ChebyshevError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ChebyshevError::InvalidErrorBound(bound) => {
+                write!(f, "error bound {bound} must be finite and positive")
+            }
+            ChebyshevError::TooManyDays(days) => {
+                write!(f, "{days} days exceed the {} a Chebyshev table holds", u16::MAX)
+            }
+            ChebyshevError::TooManySegments { day_of_year, segments } => write!(
+                f,
+                "day {day_of_year} needs {segments} segments, more than {}",
+                u8::MAX
+            ),
+            ChebyshevError::TooManyCoefficients { day_of_year, coefficients } => write!(
+                f,
+                "day {day_of_year} has a segment of {coefficients} coefficients, more than {}",
+                u8::MAX
+            ),
+        }
+    }
+}
+
+impl std::error::Error for ChebyshevError {}
+
+// Clenshaw recurrence at x in [-1, 1]; allocation-free.
+fn clenshaw(coefficients: &[f32], x: f64) -> f64 {
+    let (mut b1, mut b2) = (0.0, 0.0);
+    for &c in coefficients.iter().skip(1).rev() {
+        (b1, b2) = (2.0 * x * b1 - b2 + c as f64, b1);
+    }
+    let c0 = coefficients.first().map_or(0.0, |&c| c as f64);
+    x * b1 - b2 + c0
+}
+
+fn unit_x(start: i32, end: i32, minutes: f64) -> f64 {
+    if end > start {
+        (2.0 * (minutes - start as f64) / (end - start) as f64 - 1.0).clamp(-1.0, 1.0)
+    } else {
+        0.0
+    }
+}
+
+// Linear between the source entries, so the series follows what
+// `lookup_single_axis` would have returned.
+fn sample(points: &[(i32, f64)], minutes: f64) -> f64 {
+    let i = points.partition_point(|&(m, _)| (m as f64) < minutes);
+    match (i.checked_sub(1).map(|i| points[i]), points.get(i)) {
+        (Some((m0, r0)), Some((m1, r1))) => {
+            r0 + (r1 - r0) * (minutes - m0 as f64) / (m1 - m0) as f64
+        }
+        (Some((_, r)), None) | (None, Some(&(_, r))) => r,
+        (None, None) => 0.0,
+    }
+}
+
+// Interpolation at n Chebyshev nodes, so the coefficients are exact for
+// polynomials below degree n and near-minimax otherwise.
+fn chebyshev_coefficients(points: &[(i32, f64)], start: i32, end: i32, n: usize) -> Vec<f32> {
+    let mid = (start + end) as f64 / 2.0;
+    let half = (end - start) as f64 / 2.0;
+    let values: Vec<f64> = (0..n)
+        .map(|k| {
+            sample(
+                points,
+                mid + half * (PI * (k as f64 + 0.5) / n as f64).cos(),
+            )
+        })
+        .collect();
+    (0..n)
+        .map(|j| {
+            let sum: f64 = values
+                .iter()
+                .enumerate()
+                .map(|(k, v)| v * (PI * j as f64 * (k as f64 + 0.5) / n as f64).cos())
+                .sum();
+            let scale = if j == 0 { 1.0 } else { 2.0 };
+            (scale * sum / n as f64) as f32
+        })
+        .collect()
+}
+
+fn segment_error(points: &[(i32, f64)], segment: &ChebyshevSegment, table: &ChebyshevTable) -> f64 {
+    points
+        .iter()
+        .map(|&(minutes, rotation)| {
+            let x = unit_x(segment.start_minutes, segment.end_minutes, minutes as f64);
+            let (value, _) = angles::clamp_rotation(
+                clenshaw(&segment.coefficients, x),
+                table.min_rotation,
+                table.max_rotation,
+            );
+            (value - rotation).abs()
+        })
+        .fold(0.0, f64::max)
+}
+
+// Shortest series within the bound at every point: doubling, then bisection
+// between the last miss and the first fit.
+fn fit_segment(
+    points: &[(i32, f64)],
+    table: &ChebyshevTable,
+    error_bound: f64,
+) -> (ChebyshevSegment, f64) {
+    let (start, end) = (points[0].0, points[points.len() - 1].0);
+    let fits = |n: usize| {
+        let segment = ChebyshevSegment {
+            start_minutes: start,
+            end_minutes: end,
+            coefficients: chebyshev_coefficients(points, start, end, n),
+        };
+        let error = segment_error(points, &segment, table);
+        (segment, error)
+    };
+    let max_n = MAX_CHEBYSHEV_COEFFICIENTS.min(points.len());
+    let (mut miss, mut n) = (0, 1);
+    let mut best = fits(n);
+    while best.1 > error_bound && n < max_n {
+        miss = n;
+        n = (n * 2).min(max_n);
+        best = fits(n);
+    }
+    if best.1 <= error_bound {
+        let (mut lo, mut hi) = (miss, n);
+        while hi - lo > 1 {
+            let mid = (lo + hi) / 2;
+            let trial = fits(mid);
+            if trial.1 <= error_bound {
+                (hi, best) = (mid, trial);
+            } else {
+                lo = mid;
+            }
+        }
+    }
+    best
+}
+
+// Runs of daylight entries, broken at parked (None) entries and at jumps of
+// more than 90° that no polynomial could follow.
+fn daylight_runs(entries: &[SingleAxisEntry]) -> Vec<Vec<(i32, f64)>> {
+    let mut runs: Vec<Vec<(i32, f64)>> = Vec::new();
+    let mut current: Vec<(i32, f64)> = Vec::new();
+    for entry in entries {
+        match entry.rotation {
+            Some(rotation) => {
+                if current
+                    .last()
+                    .is_some_and(|&(_, prev)| (rotation - prev).abs() > 90.0)
+                {
+                    runs.push(std::mem::take(&mut current));
+                }
+                current.push((entry.minutes, rotation));
+            }
+            None if !current.is_empty() => runs.push(std::mem::take(&mut current)),
+            None => {}
+        }
+    }
+    if !current.is_empty() {
+        runs.push(current);
+    }
+    runs
+}
+
+// Index of the largest second difference, the sharpest bend in the run.
+fn sharpest_kink(points: &[(i32, f64)]) -> usize {
+    (1..points.len() - 1)
+        .max_by(|&a, &b| {
+            let bend = |i: usize| (points[i - 1].1 - 2.0 * points[i].1 + points[i + 1].1).abs();
+            bend(a).total_cmp(&bend(b))
+        })
+        .unwrap_or(points.len() / 2)
+}
+
+fn fit_run(
+    points: &[(i32, f64)],
+    table: &ChebyshevTable,
+    error_bound: f64,
+    segments: &mut Vec<ChebyshevSegment>,
+) -> f64 {
+    let (segment, error) = fit_segment(points, table, error_bound);
+    if error <= error_bound || points.len() < 3 {
+        segments.push(segment);
+        return error;
+    }
+    // The kink point ends one half and starts the other
+    let kink = sharpest_kink(points);
+    let before = fit_run(&points[..=kink], table, error_bound, segments);
+    let after = fit_run(&points[kink..], table, error_bound, segments);
+    before.max(after)
+}
+
+// Each segment keeps the shortest series whose error at every source entry
+// is within `error_bound` degrees. A zero bound would split every run down
+// to single intervals, so the bound must be positive.
+pub fn compress_chebyshev(
+    table: &SingleAxisTable,
+    error_bound: f64,
+) -> Result<ChebyshevTable, ChebyshevError> {
+    if !error_bound.is_finite() || error_bound <= 0.0 {
+        return Err(ChebyshevError::InvalidErrorBound(error_bound));
+    }
+    let mut compressed = ChebyshevTable {
+        error_bound,
+        min_rotation: table.config.min_rotation,
+        max_rotation: table.config.max_rotation,
+        days: Vec::with_capacity(table.days.len()),
+    };
+    for source in &table.days {
+        let mut day = ChebyshevDay {
+            day_of_year: source.day_of_year,
+            segments: Vec::new(),
+            max_error: 0.0,
+        };
+        for run in daylight_runs(&source.entries) {
+            let error = fit_run(&run, &compressed, error_bound, &mut day.segments);
+            day.max_error = day.max_error.max(error);
+        }
+        compressed.days.push(day);
+    }
+    Ok(compressed)
+}
+
+// Same contract as `lookup_single_axis`: None for a day the table does not
+// hold, and a None rotation outside daylight, including the minutes between
+// segments. Allocation-free.
+pub fn lookup_chebyshev(
+    table: &ChebyshevTable,
+    day_of_year: i32,
+    minutes: MinuteOfDay,
+) -> Option<SingleAxisEntry> {
+    let minutes = minutes.get();
+    let index = usize::try_from(day_of_year.checked_sub(1)?).ok()?;
+    let day = table.days.get(index)?;
+    let segment = day
+        .segments
+        .iter()
+        .find(|s| minutes >= s.start_minutes && minutes <= s.end_minutes);
+    let Some(segment) = segment else {
+        return Some(SingleAxisEntry {
+            minutes,
+            rotation: None,
+            clamped: false,
+            surface: None,
+        });
+    };
+    let x = unit_x(segment.start_minutes, segment.end_minutes, minutes as f64);
+    let (rotation, clamped) = angles::clamp_rotation(
+        clenshaw(&segment.coefficients, x),
+        table.min_rotation,
+        table.max_rotation,
+    );
+    Some(SingleAxisEntry {
+        minutes,
+        rotation: Some(rotation),
+        clamped,
+        surface: None,
+    })
+}
+
+pub fn chebyshev_storage_bytes(table: &ChebyshevTable) -> usize {
+    let segments: usize = table
+        .days
+        .iter()
+        .flat_map(|d| &d.segments)
+        .map(|s| SEGMENT_HEADER_BYTES + 4 * s.coefficients.len())
+        .sum();
+    HEADER_BYTES + table.days.len() * DAY_HEADER_BYTES + segments
+}
+
+fn limit_bits(limit: Option<f64>) -> [u8; 4] {
+    limit.map_or(f32::NAN, |l| l as f32).to_le_bytes()
+}
+
+// Little-endian, like `Event::encode`: an `STCH` header, then per day its
+// number and achieved error followed by its segments, each a span and f32
+// coefficients. Absent rotation limits are NaN.
+pub fn encode_chebyshev(table: &ChebyshevTable) -> Result<Vec<u8>, ChebyshevError> {
+    let n_days = u16::try_from(table.days.len())
+        .map_err(|_| ChebyshevError::TooManyDays(table.days.len()))?;
+    let mut out = Vec::with_capacity(chebyshev_storage_bytes(table));
+    out.extend_from_slice(&MAGIC);
+    out.push(VERSION);
+    out.extend_from_slice(&(table.error_bound as f32).to_le_bytes());
+    out.extend_from_slice(&limit_bits(table.min_rotation));
+    out.extend_from_slice(&limit_bits(table.max_rotation));
+    out.extend_from_slice(&n_days.to_le_bytes());
+    for day in &table.days {
+        let day_of_year = day.day_of_year;
+        let n_segments = u8::try_from(day.segments.len()).map_err(|_| {
+            ChebyshevError::TooManySegments {
+                day_of_year,
+                segments: day.segments.len(),
+            }
+        })?;
+        out.extend_from_slice(&(day_of_year as u16).to_le_bytes());
+        out.extend_from_slice(&(day.max_error as f32).to_le_bytes());
+        out.push(n_segments);
+        for segment in &day.segments {
+            let count = u8::try_from(segment.coefficients.len()).map_err(|_| {
+                ChebyshevError::TooManyCoefficients {
+                    day_of_year,
+                    coefficients: segment.coefficients.len(),
+                }
+            })?;
+            out.extend_from_slice(&(segment.start_minutes as u16).to_le_bytes());
+            out.extend_from_slice(&(segment.end_minutes as u16).to_le_bytes());
+            out.push(count);
+            for c in &segment.coefficients {
+                out.extend_from_slice(&c.to_le_bytes());
+            }
+        }
+    }
+    Ok(out)
+}
+
+struct Reader<'a> {
+    bytes: &'a [u8],
+}
+
+impl<'a> Reader<'a> {
+    fn take<const N: usize>(&mut self) -> Result<[u8; N], ChebyshevDecodeError> {
+        let (head, rest) = self
+            .bytes
+            .split_first_chunk::<N>()
+            .ok_or(ChebyshevDecodeError::Truncated)?;
+        self.bytes = rest;
+        Ok(*head)
+    }
+
+    fn u16(&mut self) -> Result<u16, ChebyshevDecodeError> {
+        self.take().map(u16::from_le_bytes)
+    }
+
+    fn f32(&mut self) -> Result<f32, ChebyshevDecodeError> {
+        self.take().map(f32::from_le_bytes)
+    }
+}
+
+pub fn decode_chebyshev(bytes: &[u8]) -> Result<ChebyshevTable, ChebyshevDecodeError> {
+    let mut reader = Reader { bytes };
+    if reader
+        .take::<4>()
+        .map_err(|_| ChebyshevDecodeError::BadMagic)?
+        != MAGIC
+    {
+        return Err(ChebyshevDecodeError::BadMagic);
+    }
+    let [version] = reader.take()?;
+    if version != VERSION {
+        return Err(ChebyshevDecodeError::UnsupportedVersion(version));
+    }
+    let error_bound = reader.f32()? as f64;
+    let limit = |l: f32| (!l.is_nan()).then_some(l as f64);
+    let min_rotation = limit(reader.f32()?);
+    let max_rotation = limit(reader.f32()?);
+    // NaN stands for no limit; anything else must be a usable bound
+    let finite = [min_rotation, max_rotation]
+        .iter()
+        .flatten()
+        .all(|l| l.is_finite());
+    if !finite || matches!((min_rotation, max_rotation), (Some(lo), Some(hi)) if lo > hi) {
+        return Err(ChebyshevDecodeError::InvalidRotationLimits);
+    }
+    let n_days = reader.u16()? as usize;
+    let mut days = Vec::with_capacity(n_days);
+    for _ in 0..n_days {
+        let day_of_year = reader.u16()? as i32;
+        let max_error = reader.f32()? as f64;
+        let [n_segments] = reader.take()?;
+        let mut segments = Vec::with_capacity(n_segments as usize);
+        for _ in 0..n_segments {
+            let start_minutes = reader.u16()? as i32;
+            let end_minutes = reader.u16()? as i32;
+            let [count] = reader.take()?;
+            let coefficients = (0..count)
+                .map(|_| reader.f32())
+                .collect::<Result<Vec<_>, _>>()?;
+            segments.push(ChebyshevSegment {
+                start_minutes,
+                end_minutes,
+                coefficients,
+            });
+        }
+        days.push(ChebyshevDay {
+            day_of_year,
+            segments,
+            max_error,
+        });
+    }
+    Ok(ChebyshevTable {
+        error_bound,
+        min_rotation,
+        max_rotation,
+        days,
+    })
+}
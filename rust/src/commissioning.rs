@@ -0,0 +1,94 @@
+use crate::types::{
+    CommissioningIssue, CommissioningReport, CommissioningStep, CommissioningTarget,
+};
+
+// Rotation sign convention: negative faces east (morning), positive faces
+// west (afternoon), matching `single_axis_tilt`.
+pub fn commissioning_sequence(min_rotation: f64, max_rotation: f64) -> Vec<CommissioningStep> {
+    vec![
+        CommissioningStep {
+            target: CommissioningTarget::EastLimit,
+            rotation: min_rotation,
+            facing_azimuth: Some(90.0),
+        },
+        CommissioningStep {
+            target: CommissioningTarget::Flat,
+            rotation: 0.0,
+            facing_azimuth: None,
+        },
+        CommissioningStep {
+            target: CommissioningTarget::WestLimit,
+            rotation: max_rotation,
+            facing_azimuth: Some(270.0),
+        },
+    ]
+}
+
+pub fn verify_commissioning(
+    steps: &[CommissioningStep],
+    readings: &[(CommissioningTarget, f64)],
+    tolerance: f64,
+) -> CommissioningReport {
+    let mut issues = Vec::new();
+    let mut pairs = Vec::with_capacity(steps.len());
+    for step in steps {
+        match readings.iter().find(|(t, _)| *t == step.target) {
+            Some(&(_, measured)) => pairs.push((step, measured)),
+            None => issues.push(CommissioningIssue::MissingReading {
+                target: step.target,
+            }),
+        }
+    }
+
+    let measured = |target| {
+        pairs
+            .iter()
+            .find(|(s, _)| s.target == target)
+            .map(|&(_, m)| m)
+    };
+    let east = measured(CommissioningTarget::EastLimit);
+    let west = measured(CommissioningTarget::WestLimit);
+
+    // Structural problems are reported instead of per-step tolerance
+    // failures, since every step would fail for the same root cause.
+    if let (Some(east), Some(west)) = (east, west) {
+        let span = west - east;
+        if span.abs() < tolerance {
+            issues.push(CommissioningIssue::AxisNotMoving { span });
+            return report(issues);
+        }
+        if span < 0.0 {
+            issues.push(CommissioningIssue::SignInverted);
+            return report(issues);
+        }
+    }
+    if let Some(flat) = measured(CommissioningTarget::Flat) {
+        if flat.abs() > tolerance {
+            let offset = flat;
+            let consistent = pairs
+                .iter()
+                .all(|(s, m)| (m - offset - s.rotation).abs() <= tolerance);
+            if consistent {
+                issues.push(CommissioningIssue::ZeroOffset { offset });
+                return report(issues);
+            }
+        }
+    }
+    for (step, m) in &pairs {
+        if (m - step.rotation).abs() > tolerance {
+            issues.push(CommissioningIssue::OutOfTolerance {
+                target: step.target,
+                expected: step.rotation,
+                measured: *m,
+            });
+        }
+    }
+    report(issues)
+}
+
+fn report(issues: Vec<CommissioningIssue>) -> CommissioningReport {
+    CommissioningReport {
+        passed: issues.is_empty(),
+        issues,
+    }
+}
@@ -0,0 +1,57 @@
+use crate::irradiance::clear_sky_components;
+use crate::orientation::optimize_fixed_orientation_with;
+use crate::simulation::simulate_yield_with;
+use crate::types::{
+    IrradianceComponents, MinuteOfDay, OrientationSearch, SimulationConfig, SolarPosition,
+    TrackerGainReport, TrackingStrategy,
+};
+
+// Tracker gains over the best fixed array under clear skies.
+pub fn tracker_gain_report(config: &SimulationConfig) -> TrackerGainReport {
+    tracker_gain_report_with(config, |_, _, pos| clear_sky_components(pos))
+}
+
+// The baseline is the optimal fixed (tilt, azimuth) for the site, not a
+// rule-of-thumb tilt, so the gains are what a tracker adds over a well-built
+// fixed array. The single-axis case is `config.strategy` when that is a
+// single-axis tracker and the default N-S tracker otherwise. The irradiance
+// source is swept once per configuration.
+pub fn tracker_gain_report_with<F>(
+    config: &SimulationConfig,
+    mut irradiance: F,
+) -> TrackerGainReport
+where
+    F: FnMut(i32, MinuteOfDay, &SolarPosition) -> Option<IrradianceComponents>,
+{
+    let fixed =
+        optimize_fixed_orientation_with(config, &OrientationSearch::default(), &mut irradiance);
+    let single_axis = match config.strategy {
+        strategy @ TrackingStrategy::SingleAxis { .. } => strategy,
+        _ => SimulationConfig::default().strategy,
+    };
+    let mut annual = |strategy| {
+        let config = SimulationConfig {
+            strategy,
+            ..*config
+        };
+        simulate_yield_with(&config, &mut irradiance).annual_kwh_per_m2
+    };
+    let single_axis_kwh_per_m2 = annual(single_axis);
+    let dual_axis_kwh_per_m2 = annual(TrackingStrategy::DualAxis);
+    let fixed_kwh_per_m2 = fixed.weighted_kwh_per_m2;
+    let gain = |kwh: f64| {
+        if fixed_kwh_per_m2 > 0.0 {
+            (kwh / fixed_kwh_per_m2 - 1.0) * 100.0
+        } else {
+            0.0
+        }
+    };
+    TrackerGainReport {
+        fixed,
+        fixed_kwh_per_m2,
+        single_axis_kwh_per_m2,
+        dual_axis_kwh_per_m2,
+        single_axis_gain_percent: gain(single_axis_kwh_per_m2),
+        dual_axis_gain_percent: gain(dual_axis_kwh_per_m2),
+    }
+}
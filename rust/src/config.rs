@@ -0,0 +1,146 @@
+use std::fmt;
+
+use crate::types::{LookupTableConfig, TimeBase};
+
+// Copy, with no owned data, so `validate_config` can run in const contexts.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ConfigError {
+    OutOfRange { field: &'static str, value: f64 },
+    InvertedRotationLimits { min: f64, max: f64 },
+}
+
+impl fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ConfigError::OutOfRange { field, value } => {
+                write!(f, "{field} out of range: {value}")
+            }
+            ConfigError::InvertedRotationLimits { min, max } => {
+                write!(f, "min_rotation {min} exceeds max_rotation {max}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for ConfigError {}
+
+// Macros rather than helpers so `validate_config` stays a const fn: `?`
+// and range methods are not available in const contexts. NaN fails both
+// comparisons, so non-finite values are rejected too.
+macro_rules! check {
+    ($field:literal, $value:expr, $min:expr, $max:expr) => {
+        let value = $value;
+        if !(value >= $min && value <= $max) {
+            return Err(ConfigError::OutOfRange {
+                field: $field,
+                value,
+            });
+        }
+    };
+}
+
+macro_rules! check_opt {
+    ($field:literal, $value:expr, $min:expr, $max:expr) => {
+        if let Some(value) = $value {
+            check!($field, value, $min, $max);
+        }
+    };
+}
+
+const DAY_MINUTES: f64 = 1440.0;
+// ±18 h, the ISO 8601 bound; civil offsets run from −12:00 to +14:00
+const MAX_OFFSET_MINUTES: f64 = 1080.0;
+
+// The generators assume a config that passes this check; anything that
+// arrives over the wire should go through it (or `config_from_json`) first.
+// Buffers are capped at a full day so sunrise/sunset arithmetic cannot
+// overflow, and years stay inside the range chrono can represent as dates.
+// Being const, it can also reject a config baked into firmware at compile
+// time: `const _: () = assert!(validate_config(&CONFIG).is_ok());`.
+pub const fn validate_config(config: &LookupTableConfig) -> Result<(), ConfigError> {
+    check!(
+        "interval_minutes",
+        config.interval_minutes as f64,
+        1.0,
+        DAY_MINUTES
+    );
+    check!("latitude", config.latitude, -90.0, 90.0);
+    check!("longitude", config.longitude, -180.0, 180.0);
+    check!("year", config.year as f64, 1.0, 9999.0);
+    check!(
+        "sunrise_buffer_minutes",
+        config.sunrise_buffer_minutes as f64,
+        0.0,
+        DAY_MINUTES
+    );
+    check!(
+        "sunset_buffer_minutes",
+        config.sunset_buffer_minutes as f64,
+        0.0,
+        DAY_MINUTES
+    );
+    check_opt!("gcr", config.gcr, f64::MIN_POSITIVE, 1.0);
+    check_opt!("min_rotation", config.min_rotation, -180.0, 180.0);
+    check_opt!("max_rotation", config.max_rotation, -180.0, 180.0);
+    if let (Some(min), Some(max)) = (config.min_rotation, config.max_rotation) {
+        if min > max {
+            return Err(ConfigError::InvertedRotationLimits { min, max });
+        }
+    }
+    check_opt!(
+        "magnetic_declination",
+        config.magnetic_declination,
+        -180.0,
+        180.0
+    );
+    if let Some(terrain) = config.terrain {
+        check!("terrain.slope", terrain.slope, 0.0, 90.0);
+        check!("terrain.aspect", terrain.aspect, -360.0, 360.0);
+    }
+    check_opt!("axis_azimuth", config.axis_azimuth, -360.0, 360.0);
+    check_opt!("axis_tilt", config.axis_tilt, -90.0, 90.0);
+    if let TimeBase::FixedOffset { offset_minutes } = config.time_base {
+        check!(
+            "time_base.offset_minutes",
+            offset_minutes as f64,
+            -MAX_OFFSET_MINUTES,
+            MAX_OFFSET_MINUTES
+        );
+    }
+    Ok(())
+}
+
+#[cfg(feature = "json")]
+#[derive(Debug, Clone, PartialEq)]
+pub enum ConfigLoadError {
+    Json(String),
+    Invalid(ConfigError),
+}
+
+#[cfg(feature = "json")]
+impl fmt::Display for ConfigLoadError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ConfigLoadError::Json(message) => write!(f, "invalid config JSON: {message}"),
+            ConfigLoadError::Invalid(err) => err.fmt(f),
+        }
+    }
+}
+
+#[cfg(feature = "json")]
+impl std::error::Error for ConfigLoadError {}
+
+#[cfg(feature = "json")]
+impl From<ConfigError> for ConfigLoadError {
+    fn from(err: ConfigError) -> Self {
+        ConfigLoadError::Invalid(err)
+    }
+}
+
+#[cfg(feature = "json")]
+pub fn config_from_json(text: &str) -> Result<LookupTableConfig, ConfigLoadError> {
+    let config: LookupTableConfig =
+        serde_json::from_str(text).map_err(|e| ConfigLoadError::Json(e.to_string()))?;
+    validate_config(&config)?;
+    Ok(config)
+}
@@ -0,0 +1,198 @@
+use std::fmt;
+
+use crate::types::{
+    ControllerInputs, ControllerMode, FallbackAction, FallbackPolicy, LinkStatus, StowReason,
+    TrackingMode,
+};
+
+pub fn fallback_action(
+    policy: &FallbackPolicy,
+    link: &LinkStatus,
+    altitude: f64,
+) -> FallbackAction {
+    if let Some(since) = link.minutes_since_lightning {
+        if since < policy.lightning_hold_minutes {
+            return FallbackAction::Stow(StowReason::Lightning);
+        }
+    }
+    if link.minutes_since_contact < policy.comm_timeout_minutes {
+        return FallbackAction::Normal;
+    }
+    if altitude <= 0.0 && policy.stow_at_night {
+        FallbackAction::Stow(StowReason::CommunicationLoss)
+    } else {
+        FallbackAction::Conservative {
+            max_rotation: policy.conservative_max_rotation,
+        }
+    }
+}
+
+pub fn fallback_rotation(action: FallbackAction, rotation: f64, stow_rotation: f64) -> f64 {
+    match action {
+        FallbackAction::Normal => rotation,
+        FallbackAction::Conservative { max_rotation } => {
+            rotation.clamp(-max_rotation, max_rotation)
+        }
+        FallbackAction::Stow(_) => stow_rotation,
+    }
+}
+
+// Hook for cloudiness: pick the rotation for the mode a `ClearnessMonitor`
+// reports, before `fallback_rotation` applies link and stow overrides.
+pub fn mode_rotation(mode: TrackingMode, sun_pointing: f64, diffuse_optimal: f64) -> f64 {
+    match mode {
+        TrackingMode::SunPointing => sun_pointing,
+        TrackingMode::DiffuseOptimal => diffuse_optimal,
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TransitionError {
+    pub from: ControllerMode,
+    pub to: ControllerMode,
+}
+
+impl fmt::Display for TransitionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "transition from {:?} to {:?} is not allowed",
+            self.from, self.to
+        )
+    }
+}
+
+impl std::error::Error for TransitionError {}
+
+pub fn can_transition(from: ControllerMode, to: ControllerMode) -> bool {
+    use ControllerMode::*;
+    if from == to {
+        return true;
+    }
+    match (from, to) {
+        (_, Fault | Maintenance) => true,
+        // A fault is only cleared by an operator putting the unit into maintenance
+        (Fault, _) => false,
+        // Automatic tracking resumes through Night so the next step picks the mode
+        (Maintenance, Night | Stowed(_)) => true,
+        (Maintenance, _) => false,
+        _ => true,
+    }
+}
+
+impl StowReason {
+    pub fn code(self) -> u8 {
+        match self {
+            StowReason::Wind => 0,
+            StowReason::Snow => 1,
+            StowReason::Hail => 2,
+            StowReason::Lightning => 3,
+            StowReason::CommunicationLoss => 4,
+            StowReason::Manual => 5,
+        }
+    }
+
+    pub fn from_code(code: u8) -> Option<Self> {
+        match code {
+            0 => Some(StowReason::Wind),
+            1 => Some(StowReason::Snow),
+            2 => Some(StowReason::Hail),
+            3 => Some(StowReason::Lightning),
+            4 => Some(StowReason::CommunicationLoss),
+            5 => Some(StowReason::Manual),
+            _ => None,
+        }
+    }
+}
+
+impl ControllerMode {
+    // Single-byte encoding for persisting state across reboots: the high
+    // nibble is the mode, the low nibble the stow reason.
+    pub fn code(self) -> u8 {
+        match self {
+            ControllerMode::Tracking => 0x00,
+            ControllerMode::Backtracking => 0x10,
+            ControllerMode::Stowed(reason) => 0x20 | reason.code(),
+            ControllerMode::Maintenance => 0x30,
+            ControllerMode::Fault => 0x40,
+            ControllerMode::Night => 0x50,
+        }
+    }
+
+    pub fn from_code(code: u8) -> Option<Self> {
+        match code {
+            0x00 => Some(ControllerMode::Tracking),
+            0x10 => Some(ControllerMode::Backtracking),
+            0x20..=0x2f => StowReason::from_code(code & 0x0f).map(ControllerMode::Stowed),
+            0x30 => Some(ControllerMode::Maintenance),
+            0x40 => Some(ControllerMode::Fault),
+            0x50 => Some(ControllerMode::Night),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Controller {
+    mode: ControllerMode,
+    policy: FallbackPolicy,
+}
+
+impl Controller {
+    pub fn new(policy: FallbackPolicy) -> Self {
+        Self {
+            mode: ControllerMode::Night,
+            policy,
+        }
+    }
+
+    pub fn with_mode(policy: FallbackPolicy, mode: ControllerMode) -> Self {
+        Self { mode, policy }
+    }
+
+    pub fn mode(&self) -> ControllerMode {
+        self.mode
+    }
+
+    pub fn policy(&self) -> &FallbackPolicy {
+        &self.policy
+    }
+
+    pub fn transition(&mut self, to: ControllerMode) -> Result<ControllerMode, TransitionError> {
+        if !can_transition(self.mode, to) {
+            return Err(TransitionError {
+                from: self.mode,
+                to,
+            });
+        }
+        Ok(std::mem::replace(&mut self.mode, to))
+    }
+
+    pub fn step(&mut self, inputs: &ControllerInputs) -> FallbackAction {
+        let action = fallback_action(&self.policy, &inputs.link, inputs.altitude);
+        let next = match self.mode {
+            // Both modes are left only through an explicit `transition`
+            ControllerMode::Fault | ControllerMode::Maintenance => self.mode,
+            _ if inputs.fault => ControllerMode::Fault,
+            _ => match (inputs.stow_request, action) {
+                (Some(reason), _) | (None, FallbackAction::Stow(reason)) => {
+                    ControllerMode::Stowed(reason)
+                }
+                _ if inputs.altitude <= 0.0 => ControllerMode::Night,
+                _ if inputs.backtracking => ControllerMode::Backtracking,
+                _ => ControllerMode::Tracking,
+            },
+        };
+        self.mode = next;
+        match self.mode {
+            ControllerMode::Stowed(reason) => FallbackAction::Stow(reason),
+            _ => action,
+        }
+    }
+}
+
+impl Default for Controller {
+    fn default() -> Self {
+        Self::new(FallbackPolicy::default())
+    }
+}
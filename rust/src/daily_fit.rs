@@ -0,0 +1,273 @@
+use crate::angles;
+use crate::difference::{difference_table, summarize_difference};
+use crate::lookup_table::{generate_single_axis_table, key_correction, table_sunrise_sunset};
+use crate::math;
+use crate::types::{
+    DailyFit, DailyFitTable, DayData, DifferenceSummary, LookupTable, LookupTableConfig,
+    MinuteOfDay, SingleAxisEntry, SingleAxisTable, TableMetadata,
+};
+
+// Fixed-point scale of the stored coefficients: ±2 at 16 bits.
+pub const DAILY_FIT_SCALE: f64 = 16384.0;
+
+// Five i16 coefficients; sunrise, sunset and solar noon are recomputed on
+// the device from the config.
+pub const DAILY_FIT_BYTES_PER_DAY: usize = 10;
+
+// Per-day quantities the device works out from the config rather than
+// storing.
+struct DayTerms {
+    sunrise: i32,
+    sunset: i32,
+    correction: f64,
+    sin_lat_sin_dec: f64,
+    cos_lat_cos_dec: f64,
+}
+
+fn day_terms(config: &LookupTableConfig, day_of_year: i32) -> DayTerms {
+    let ss = table_sunrise_sunset(config, day_of_year);
+    let eot = angles::equation_of_time_with_model(
+        day_of_year,
+        config.year,
+        config.equation_of_time_model,
+    );
+    let (sin_lat, cos_lat) = math::sin_cos(angles::deg_to_rad(config.latitude));
+    let (sin_dec, cos_dec) = math::sin_cos(angles::deg_to_rad(angles::declination_in_year(
+        day_of_year,
+        config.year,
+        config.declination_model,
+    )));
+    DayTerms {
+        sunrise: ss.sunrise,
+        sunset: ss.sunset,
+        correction: key_correction(config, eot),
+        sin_lat_sin_dec: sin_lat * sin_dec,
+        cos_lat_cos_dec: cos_lat * cos_dec,
+    }
+}
+
+// Local hour angle in degrees, or None outside the table's daylight window.
+fn hour_angle(terms: &DayTerms, minutes: i32) -> Option<f64> {
+    let local_minutes = (minutes as f64 + terms.correction * 60.0) as i32;
+    if local_minutes < terms.sunrise || local_minutes > terms.sunset {
+        return None;
+    }
+    let lst = (minutes as f64 / 60.0 + terms.correction).rem_euclid(24.0);
+    Some(angles::DEGREES_PER_HOUR * (lst - 12.0))
+}
+
+// The default north-south table uses atan(tan h / cos φ), which folds into
+// ±90° once the hour angle passes 6 am/6 pm; the exact tilted-axis solution
+// keeps the full atan2 range.
+fn curve(coefficients: &[f64; 5], hour_angle: f64, folded: bool) -> f64 {
+    let [c0, c1, c2, c3, c4] = *coefficients;
+    let (s, c) = math::sin_cos(angles::deg_to_rad(hour_angle));
+    let num = s + c0 * c + c1;
+    let den = c2 * s + c3 * c + c4;
+    let rotation = angles::rad_to_deg(math::atan2(num, den));
+    if !folded {
+        rotation
+    } else if rotation > 90.0 {
+        rotation - 180.0
+    } else if rotation < -90.0 {
+        rotation + 180.0
+    } else {
+        rotation
+    }
+}
+
+fn dequantize(coefficients: &[i16; 5]) -> [f64; 5] {
+    coefficients.map(|c| c as f64 / DAILY_FIT_SCALE)
+}
+
+fn quantize(coefficients: &[f64; 5]) -> [i16; 5] {
+    coefficients.map(|c| {
+        (c * DAILY_FIT_SCALE)
+            .round()
+            .clamp(i16::MIN as f64, i16::MAX as f64) as i16
+    })
+}
+
+// Least squares on the cross-multiplied form
+// num·cos θ − den·sin θ = 0, which is linear in the coefficients and stays
+// bounded where the rotation nears ±90°.
+fn fit_samples(samples: &[(f64, f64)]) -> Option<[f64; 5]> {
+    let mut normal = [[0.0; 6]; 5];
+    for &(hour_angle, rotation) in samples {
+        let (s, c) = math::sin_cos(angles::deg_to_rad(hour_angle));
+        let (sin_r, cos_r) = math::sin_cos(angles::deg_to_rad(rotation));
+        let features = [c * cos_r, cos_r, -s * sin_r, -c * sin_r, -sin_r];
+        let target = -s * cos_r;
+        for (i, row) in normal.iter_mut().enumerate() {
+            for (j, cell) in row.iter_mut().take(5).enumerate() {
+                *cell += features[i] * features[j];
+            }
+            row[5] += features[i] * target;
+        }
+    }
+    // Gaussian elimination with partial pivoting on the augmented matrix
+    for col in 0..5 {
+        let pivot =
+            (col..5).max_by(|&a, &b| normal[a][col].abs().total_cmp(&normal[b][col].abs()))?;
+        if normal[pivot][col].abs() < 1e-12 {
+            return None;
+        }
+        normal.swap(col, pivot);
+        let pivot_row = normal[col];
+        for row in normal.iter_mut().skip(col + 1) {
+            let factor = row[col] / pivot_row[col];
+            for (cell, pivot_cell) in row.iter_mut().zip(pivot_row).skip(col) {
+                *cell -= factor * pivot_cell;
+            }
+        }
+    }
+    let mut solution = [0.0; 5];
+    for row in (0..5).rev() {
+        let tail: f64 = (row + 1..5).map(|k| normal[row][k] * solution[k]).sum();
+        solution[row] = (normal[row][5] - tail) / normal[row][row];
+    }
+    Some(solution)
+}
+
+fn evaluate(
+    fit: &DailyFitTable,
+    coefficients: &[f64; 5],
+    terms: &DayTerms,
+    minutes: i32,
+) -> Option<(f64, bool)> {
+    let config = &fit.config;
+    let hour_angle = hour_angle(terms, minutes)?;
+    let exact =
+        config.terrain.is_some() || config.axis_azimuth.is_some() || config.axis_tilt.is_some();
+    let ideal = curve(coefficients, hour_angle, !exact);
+    let rotation = match config.gcr {
+        Some(gcr) => {
+            let cos_ha = math::cos(angles::deg_to_rad(hour_angle));
+            if terms.sin_lat_sin_dec + terms.cos_lat_cos_dec * cos_ha > 0.0 {
+                let cross_axis = config.terrain.map_or(0.0, |terrain| {
+                    angles::cross_axis_slope(&terrain, config.axis_azimuth.unwrap_or(180.0))
+                });
+                angles::slope_aware_backtrack(ideal, gcr, cross_axis)
+            } else {
+                0.0
+            }
+        }
+        None => ideal,
+    };
+    Some(angles::clamp_rotation(
+        rotation,
+        config.min_rotation,
+        config.max_rotation,
+    ))
+}
+
+// Fits each day's ideal (true-tracking, unclamped) rotation curve, so
+// backtracking and rotation limits are reapplied exactly at evaluation.
+// The form is exact for any fixed axis, leaving coefficient rounding and
+// out-of-range coefficients (axes far from north-south) as the error;
+// `max_error` records it per day against `generate_single_axis_table`.
+pub fn fit_daily_curves(config: &LookupTableConfig) -> DailyFitTable {
+    let ideal_config = LookupTableConfig {
+        gcr: None,
+        min_rotation: None,
+        max_rotation: None,
+        include_surface_orientation: false,
+        ..*config
+    };
+    let ideal = generate_single_axis_table(&ideal_config);
+    let reference = generate_single_axis_table(config);
+    let mut fit = DailyFitTable {
+        config: *config,
+        days: Vec::with_capacity(ideal.days.len()),
+    };
+    for (ideal_day, reference_day) in ideal.days.iter().zip(&reference.days) {
+        let doy = ideal_day.day_of_year;
+        let terms = day_terms(config, doy);
+        let samples: Vec<(f64, f64)> = ideal_day
+            .entries
+            .iter()
+            .filter_map(|e| Some((hour_angle(&terms, e.minutes)?, e.rotation?)))
+            .collect();
+        // Without enough daylight to fit, fall back to the hour angle itself
+        let coefficients = fit_samples(&samples).unwrap_or([0.0, 0.0, 0.0, 1.0, 0.0]);
+        let coefficients = quantize(&coefficients);
+        let stored = dequantize(&coefficients);
+        let max_error = reference_day
+            .entries
+            .iter()
+            .filter_map(|e| {
+                let (rotation, _) = evaluate(&fit, &stored, &terms, e.minutes)?;
+                Some((rotation - e.rotation?).abs())
+            })
+            .fold(0.0, f64::max);
+        fit.days.push(DailyFit {
+            day_of_year: doy,
+            coefficients,
+            max_error,
+        });
+    }
+    fit
+}
+
+// The on-device evaluation: None at night, like a table lookup, and for
+// days the fit does not cover.
+pub fn evaluate_fit(fit: &DailyFitTable, day_of_year: i32, minutes: MinuteOfDay) -> Option<f64> {
+    let minutes = minutes.get();
+    let day = fit.days.iter().find(|d| d.day_of_year == day_of_year)?;
+    let terms = day_terms(&fit.config, day_of_year);
+    evaluate(fit, &dequantize(&day.coefficients), &terms, minutes).map(|(rotation, _)| rotation)
+}
+
+pub fn fit_storage_bytes(fit: &DailyFitTable) -> usize {
+    fit.days.len() * DAILY_FIT_BYTES_PER_DAY
+}
+
+// Expands the fit back onto the config's table grid, e.g. for
+// `difference_table` or the export formats.
+pub fn fit_rotation_table(fit: &DailyFitTable) -> SingleAxisTable {
+    let grid = generate_single_axis_table(&LookupTableConfig {
+        include_surface_orientation: false,
+        ..fit.config
+    });
+    let days: Vec<DayData<SingleAxisEntry>> = grid
+        .days
+        .into_iter()
+        .zip(&fit.days)
+        .map(|(day, daily)| {
+            let terms = day_terms(&fit.config, day.day_of_year);
+            let stored = dequantize(&daily.coefficients);
+            let entries = day
+                .entries
+                .iter()
+                .map(|e| {
+                    let evaluated = evaluate(fit, &stored, &terms, e.minutes);
+                    SingleAxisEntry {
+                        minutes: e.minutes,
+                        rotation: evaluated.map(|(rotation, _)| rotation),
+                        clamped: evaluated.is_some_and(|(_, clamped)| clamped),
+                        surface: None,
+                    }
+                })
+                .collect();
+            DayData { entries, ..day }
+        })
+        .collect();
+    LookupTable {
+        config: grid.config,
+        days,
+        metadata: TableMetadata {
+            storage_estimate_kb: fit_storage_bytes(fit) as f64 / 1024.0,
+            ..grid.metadata
+        },
+    }
+}
+
+// Fit against the full table over the year, for checking an accuracy
+// target; `threshold` counts the intervals off by more than it.
+pub fn fit_error(fit: &DailyFitTable, threshold: f64) -> DifferenceSummary {
+    let reference = generate_single_axis_table(&fit.config);
+    summarize_difference(
+        &difference_table(&reference, &fit_rotation_table(fit)),
+        threshold,
+    )
+}
@@ -0,0 +1,149 @@
+use std::fmt;
+
+use chrono::{DateTime, FixedOffset, NaiveDate, NaiveDateTime, TimeZone, Utc};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DateTimeInputError {
+    Empty,
+    InvalidFormat(String),
+    UnknownZone(String),
+    NonexistentLocalTime(String),
+}
+
+impl fmt::Display for DateTimeInputError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DateTimeInputError::Empty => write!(f, "empty date-time"),
+            DateTimeInputError::InvalidFormat(input) => {
+                write!(f, "unrecognized date-time {input:?}")
+            }
+            DateTimeInputError::UnknownZone(zone) => write!(f, "unknown time zone {zone}"),
+            DateTimeInputError::NonexistentLocalTime(input) => {
+                write!(f, "{input} does not exist in the given zone (DST gap)")
+            }
+        }
+    }
+}
+
+impl std::error::Error for DateTimeInputError {}
+
+// Seconds and fractional seconds are optional; 'T' or a space separates date
+// and time.
+const NAIVE_FORMATS: [&str; 6] = [
+    "%Y-%m-%dT%H:%M:%S%.f",
+    "%Y-%m-%d %H:%M:%S%.f",
+    "%Y-%m-%dT%H:%M",
+    "%Y-%m-%d %H:%M",
+    "%Y-%m-%dT%H:%M%:z",
+    "%Y-%m-%d %H:%M%:z",
+];
+
+// Shared by every entry point so the same string always means the same
+// instant. Accepts RFC 3339, "2026-03-21 14:30[:ss]" or a bare date, with an
+// optional trailing IANA zone either space-separated or bracketed
+// ("... America/Chicago", "...[America/Chicago]"). Times without an offset
+// or zone are UTC. A leap second (":60") is accepted. With both an offset
+// and a zone the offset fixes the instant and the zone only changes how it
+// is expressed. Ambiguous local times (DST fall-back) resolve to the
+// earlier instant. Zone names other than UTC need the `tz` feature.
+pub fn parse_datetime(input: &str) -> Result<DateTime<FixedOffset>, DateTimeInputError> {
+    let input = input.trim();
+    if input.is_empty() {
+        return Err(DateTimeInputError::Empty);
+    }
+    let (text, zone) = split_zone(input);
+    let invalid = || DateTimeInputError::InvalidFormat(input.to_string());
+
+    if let Some(dt) = parse_with_offset(text) {
+        return match zone {
+            Some(zone) => in_zone(zone, &dt.naive_utc(), input, true),
+            None => Ok(dt),
+        };
+    }
+    let naive = parse_naive(text).ok_or_else(invalid)?;
+    match zone {
+        Some(zone) => in_zone(zone, &naive, input, false),
+        None => Ok(Utc.from_utc_datetime(&naive).fixed_offset()),
+    }
+}
+
+fn split_zone(input: &str) -> (&str, Option<&str>) {
+    if let Some(body) = input.strip_suffix(']') {
+        if let Some((text, zone)) = body.rsplit_once('[') {
+            return (text.trim_end(), Some(zone.trim()));
+        }
+    }
+    match input.rsplit_once(' ') {
+        Some((text, zone)) if zone.contains('/') || zone.eq_ignore_ascii_case("UTC") => {
+            (text.trim_end(), Some(zone))
+        }
+        _ => (input, None),
+    }
+}
+
+fn parse_with_offset(text: &str) -> Option<DateTime<FixedOffset>> {
+    if let Ok(dt) = DateTime::parse_from_rfc3339(text) {
+        return Some(dt);
+    }
+    let text = match text.strip_suffix(['Z', 'z']) {
+        Some(stripped) => return parse_naive(stripped).map(|n| n.and_utc().fixed_offset()),
+        None => text,
+    };
+    NAIVE_FORMATS[4..]
+        .iter()
+        .find_map(|fmt| DateTime::parse_from_str(text, fmt).ok())
+}
+
+fn parse_naive(text: &str) -> Option<NaiveDateTime> {
+    NAIVE_FORMATS[..4]
+        .iter()
+        .find_map(|fmt| NaiveDateTime::parse_from_str(text, fmt).ok())
+        .or_else(|| {
+            NaiveDate::parse_from_str(text, "%Y-%m-%d")
+                .ok()
+                .map(|d| d.and_time(chrono::NaiveTime::MIN))
+        })
+}
+
+// `is_utc` says whether `naive` is already a UTC instant or a wall-clock
+// time in `zone`.
+fn in_zone(
+    zone: &str,
+    naive: &NaiveDateTime,
+    input: &str,
+    is_utc: bool,
+) -> Result<DateTime<FixedOffset>, DateTimeInputError> {
+    if zone.eq_ignore_ascii_case("UTC") {
+        return Ok(naive.and_utc().fixed_offset());
+    }
+    resolve_named_zone(zone, naive, input, is_utc)
+}
+
+#[cfg(feature = "tz")]
+fn resolve_named_zone(
+    zone: &str,
+    naive: &NaiveDateTime,
+    input: &str,
+    is_utc: bool,
+) -> Result<DateTime<FixedOffset>, DateTimeInputError> {
+    let tz: chrono_tz::Tz = zone
+        .parse()
+        .map_err(|_| DateTimeInputError::UnknownZone(zone.to_string()))?;
+    if is_utc {
+        return Ok(tz.from_utc_datetime(naive).fixed_offset());
+    }
+    tz.from_local_datetime(naive)
+        .earliest()
+        .map(|dt| dt.fixed_offset())
+        .ok_or_else(|| DateTimeInputError::NonexistentLocalTime(input.to_string()))
+}
+
+#[cfg(not(feature = "tz"))]
+fn resolve_named_zone(
+    zone: &str,
+    _naive: &NaiveDateTime,
+    _input: &str,
+    _is_utc: bool,
+) -> Result<DateTime<FixedOffset>, DateTimeInputError> {
+    Err(DateTimeInputError::UnknownZone(zone.to_string()))
+}
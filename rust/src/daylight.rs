@@ -0,0 +1,157 @@
+use std::ops::RangeInclusive;
+
+use chrono::{DateTime, Duration, FixedOffset, Months, NaiveDate, NaiveTime, Utc};
+
+use crate::angles;
+use crate::irradiance::clear_sky_ghi;
+use crate::lookup_table::{cos_sunrise_hour_angle, sunrise_sunset, SUNRISE_ALTITUDE};
+use crate::types::DaylightMetrics;
+
+// Searches at most a year ahead; the sunrise pattern repeats after that.
+const SEARCH_DAYS: usize = 366;
+
+// Days with both a sunrise and a sunset (polar day and night never match).
+pub fn first_date_after<F>(
+    latitude: f64,
+    longitude: f64,
+    after: NaiveDate,
+    predicate: F,
+) -> Option<NaiveDate>
+where
+    F: Fn(DateTime<Utc>, DateTime<Utc>) -> bool,
+{
+    after.iter_days().skip(1).take(SEARCH_DAYS).find(|&date| {
+        sunrise_sunset(latitude, longitude, date).is_some_and(|(r, s)| predicate(r, s))
+    })
+}
+
+pub fn dates_between<F>(
+    latitude: f64,
+    longitude: f64,
+    from: NaiveDate,
+    to: NaiveDate,
+    predicate: F,
+) -> Vec<NaiveDate>
+where
+    F: Fn(DateTime<Utc>, DateTime<Utc>) -> bool,
+{
+    from.iter_days()
+        .take_while(|&date| date <= to)
+        .filter(|&date| {
+            sunrise_sunset(latitude, longitude, date).is_some_and(|(r, s)| predicate(r, s))
+        })
+        .collect()
+}
+
+pub fn first_sunrise_before(
+    latitude: f64,
+    longitude: f64,
+    after: NaiveDate,
+    local_time: NaiveTime,
+    offset: FixedOffset,
+) -> Option<NaiveDate> {
+    first_date_after(latitude, longitude, after, |sunrise, _| {
+        sunrise.with_timezone(&offset).time() < local_time
+    })
+}
+
+pub fn dates_with_day_length_over(
+    latitude: f64,
+    longitude: f64,
+    year: i32,
+    min_length: Duration,
+) -> Vec<NaiveDate> {
+    let (Some(from), Some(to)) = (
+        NaiveDate::from_ymd_opt(year, 1, 1),
+        NaiveDate::from_ymd_opt(year, 12, 31),
+    ) else {
+        return Vec::new();
+    };
+    dates_between(latitude, longitude, from, to, |sunrise, sunset| {
+        sunset - sunrise > min_length
+    })
+}
+
+// Sunrise to sunset with refraction and the solar disk: a full day under
+// the midnight sun, zero through polar night.
+pub fn day_length(latitude: f64, day_of_year: i32) -> Duration {
+    let cos_h = cos_sunrise_hour_angle(latitude, day_of_year).clamp(-1.0, 1.0);
+    let minutes = angles::rad_to_deg(cos_h.acos()) * 8.0;
+    Duration::milliseconds((minutes * 60_000.0).round() as i64)
+}
+
+pub fn day_lengths(latitude: f64, year: i32) -> impl Iterator<Item = (i32, Duration)> {
+    let n_days = if angles::leap_year(year) { 366 } else { 365 };
+    (1..=n_days).map(move |doy| (doy, day_length(latitude, doy)))
+}
+
+// Azimuths of the sun's centre at the same -0.833° altitude as
+// `sunrise_sunset`; sunset mirrors sunrise about the meridian. None during
+// polar day or night.
+pub fn sunrise_sunset_azimuth(latitude: f64, day_of_year: i32) -> Option<(f64, f64)> {
+    if !(-1.0..=1.0).contains(&cos_sunrise_hour_angle(latitude, day_of_year)) {
+        return None;
+    }
+    let (sin_lat, cos_lat) = angles::deg_to_rad(latitude).sin_cos();
+    let (sin_h0, cos_h0) = angles::deg_to_rad(SUNRISE_ALTITUDE).sin_cos();
+    let sin_dec = angles::deg_to_rad(angles::solar_declination(day_of_year)).sin();
+    let cos_az = (sin_dec - sin_h0 * sin_lat) / (cos_h0 * cos_lat);
+    let sunrise = angles::rad_to_deg(cos_az.clamp(-1.0, 1.0).acos());
+    Some((sunrise, 360.0 - sunrise))
+}
+
+const METRIC_STEP_MINUTES: i32 = 5;
+
+// Daily sums only depend on local solar time, so longitude drops out.
+// Threshold hours and peak sun hours (clear-sky kWh/m²) are integrated at
+// five-minute steps.
+pub fn daylight_metrics(
+    latitude: f64,
+    days: RangeInclusive<i32>,
+    altitude_threshold: f64,
+) -> DaylightMetrics {
+    let step_hours = METRIC_STEP_MINUTES as f64 / 60.0;
+    let mut metrics = DaylightMetrics {
+        sun_up_hours: 0.0,
+        hours_above_threshold: 0.0,
+        peak_sun_hours: 0.0,
+    };
+    for doy in days {
+        metrics.sun_up_hours += day_length(latitude, doy).num_milliseconds() as f64 / 3_600_000.0;
+        let decl = angles::solar_declination(doy);
+        for minute in (0..1440).step_by(METRIC_STEP_MINUTES as usize) {
+            let local_hours = (minute as f64 + METRIC_STEP_MINUTES as f64 / 2.0) / 60.0;
+            let zenith =
+                angles::solar_zenith_angle(latitude, decl, angles::hour_angle(local_hours));
+            if 90.0 - zenith > altitude_threshold {
+                metrics.hours_above_threshold += step_hours;
+            }
+            metrics.peak_sun_hours += clear_sky_ghi(zenith) / 1000.0 * step_hours;
+        }
+    }
+    metrics
+}
+
+pub fn monthly_daylight_metrics(
+    latitude: f64,
+    year: i32,
+    altitude_threshold: f64,
+) -> Vec<DaylightMetrics> {
+    (1..=12)
+        .filter_map(|month| {
+            let first = NaiveDate::from_ymd_opt(year, month, 1)?;
+            let last = first.checked_add_months(Months::new(1))?.pred_opt()?;
+            let days = angles::day_of_year_from_date(first)..=angles::day_of_year_from_date(last);
+            Some(daylight_metrics(latitude, days, altitude_threshold))
+        })
+        .collect()
+}
+
+pub fn annual_daylight_metrics(
+    latitude: f64,
+    year: i32,
+    altitude_threshold: f64,
+) -> DaylightMetrics {
+    let n_days = if angles::leap_year(year) { 366 } else { 365 };
+    daylight_metrics(latitude, 1..=n_days, altitude_threshold)
+}
@@ -0,0 +1,30 @@
+use crate::event_log::EventLog;
+use crate::lookup_table::format_utc_now;
+use crate::types::{
+    AngleReading, CalibrationOffsets, ControllerMode, DiagnosticBundle, LookupTable,
+};
+
+pub fn diagnostic_bundle<E, const N: usize>(
+    table: &LookupTable<E>,
+    log: &EventLog<N>,
+    mode: ControllerMode,
+    calibration: CalibrationOffsets,
+    reading: Option<AngleReading>,
+) -> DiagnosticBundle {
+    DiagnosticBundle {
+        generated_at: format_utc_now(),
+        config: table.config,
+        metadata: table.metadata.clone(),
+        mode,
+        events: log.iter().copied().collect(),
+        dropped_events: log.dropped(),
+        calibration,
+        reading,
+        angle_error: reading.map(|r| r.reported - r.predicted),
+    }
+}
+
+#[cfg(feature = "json")]
+pub fn diagnostic_bundle_json(bundle: &DiagnosticBundle) -> String {
+    serde_json::to_string_pretty(bundle).expect("diagnostic bundle is always serializable")
+}
@@ -0,0 +1,137 @@
+use crate::interp::HasMinutes;
+use crate::lookup_table::{day_entries, generate_single_axis_table, month_statistics};
+use crate::types::{
+    DayData, DifferenceEntry, DifferenceSummary, DifferenceTable, LookupTable, LookupTableConfig,
+    SingleAxisTable, TableMetadata,
+};
+
+// One f32 difference per entry.
+const DIFFERENCE_BYTES: usize = 4;
+
+impl HasMinutes for DifferenceEntry {
+    fn minutes(&self) -> i32 {
+        self.minutes
+    }
+
+    fn set_minutes(&mut self, minutes: i32) {
+        self.minutes = minutes;
+    }
+}
+
+// Entries are matched by UTC minute, so the two tables may use different
+// sunrise/sunset buffers; minutes only one table covers keep the other side
+// as None. Both tables must share interval and year.
+pub fn difference_table(
+    reference: &SingleAxisTable,
+    candidate: &SingleAxisTable,
+) -> DifferenceTable {
+    let days: Vec<DayData<DifferenceEntry>> = reference
+        .days
+        .iter()
+        .zip(&candidate.days)
+        .map(|(ref_day, cand_day)| {
+            let entries = ref_day
+                .entries
+                .iter()
+                .map(|r| {
+                    let c = cand_day
+                        .entries
+                        .binary_search_by_key(&r.minutes, |c| c.minutes)
+                        .ok()
+                        .and_then(|i| cand_day.entries[i].rotation);
+                    DifferenceEntry {
+                        minutes: r.minutes,
+                        reference: r.rotation,
+                        candidate: c,
+                        difference: r.rotation.zip(c).map(|(r, c)| c - r),
+                    }
+                })
+                .collect();
+            DayData {
+                day_of_year: ref_day.day_of_year,
+                sunrise_minutes: ref_day.sunrise_minutes,
+                sunset_minutes: ref_day.sunset_minutes,
+                solar_noon_minutes: ref_day.solar_noon_minutes,
+                entries,
+            }
+        })
+        .collect();
+
+    let total_entries: usize = days.iter().map(|d| d.entries.len()).sum();
+    let months = month_statistics(reference.config.year, &days);
+    LookupTable {
+        config: reference.config,
+        days,
+        metadata: TableMetadata {
+            generated_at: reference.metadata.generated_at.clone(),
+            total_entries,
+            storage_estimate_kb: (total_entries * DIFFERENCE_BYTES) as f64 / 1024.0,
+            project: reference.metadata.project.clone(),
+            months,
+        },
+    }
+}
+
+// True tracking (no GCR) as reference against the configured backtracking.
+pub fn backtracking_difference_table(config: &LookupTableConfig) -> DifferenceTable {
+    let true_tracking = LookupTableConfig {
+        gcr: None,
+        ..*config
+    };
+    difference_table(
+        &generate_single_axis_table(&true_tracking),
+        &generate_single_axis_table(config),
+    )
+}
+
+pub fn summarize_difference(table: &DifferenceTable, threshold: f64) -> DifferenceSummary {
+    let mut summary = DifferenceSummary::default();
+    let mut compared = 0usize;
+    let mut abs_sum = 0.0;
+    for day in &table.days {
+        for entry in &day.entries {
+            let Some(diff) = entry.difference else {
+                continue;
+            };
+            compared += 1;
+            abs_sum += diff.abs();
+            if diff.abs() > threshold {
+                summary.intervals_over_threshold += 1;
+            }
+            if diff.abs() > summary.max_abs_difference {
+                summary.max_abs_difference = diff.abs();
+                summary.max_day_of_year = day.day_of_year;
+                summary.max_minutes = entry.minutes;
+            }
+        }
+    }
+    if compared > 0 {
+        summary.mean_abs_difference = abs_sum / compared as f64;
+    }
+    summary
+}
+
+// Contiguous (start, end) UTC minute ranges where |difference| exceeds the
+// threshold, e.g. the morning and evening backtracking windows.
+pub fn deviation_windows(
+    table: &DifferenceTable,
+    day_of_year: i32,
+    threshold: f64,
+) -> Vec<(i32, i32)> {
+    let mut windows = Vec::new();
+    let mut open: Option<(i32, i32)> = None;
+    for entry in day_entries(table, day_of_year).unwrap_or_default() {
+        let deviating = entry.difference.is_some_and(|d| d.abs() > threshold);
+        open = match (open, deviating) {
+            (Some((start, _)), true) => Some((start, entry.minutes)),
+            (None, true) => Some((entry.minutes, entry.minutes)),
+            (Some(window), false) => {
+                windows.push(window);
+                None
+            }
+            (None, false) => None,
+        };
+    }
+    windows.extend(open);
+    windows
+}
@@ -0,0 +1,191 @@
+use crate::angles::{angle_difference, declination_in_year, leap_year};
+use crate::magnetic::magnetic_bearing;
+use crate::types::{
+    AzimuthFlipConfig, DualAxisAngles, DualAxisTable, LookupTableConfig, OverheadSunDay,
+};
+
+// Near the equator the sun can pass almost overhead, where azimuth swings
+// through ~180° within minutes while tilt passes through zero. Each step is
+// conditioned against the previous command: a near-flat panel keeps its
+// azimuth, a mount that can tilt past vertical takes the equivalent
+// (-tilt, azimuth + 180°) pose when that is closer, and what remains is
+// rate limited.
+pub fn condition_dual_axis_step(
+    prev: DualAxisAngles,
+    next: DualAxisAngles,
+    elapsed_minutes: f64,
+    config: &AzimuthFlipConfig,
+) -> (DualAxisAngles, bool) {
+    condition_step(prev, next, elapsed_minutes, config, None)
+}
+
+// The same, for the days the sun passes within a few degrees of the zenith.
+// Coming out of the flat hold the panel can face ~180° away from its target,
+// and the shorter way round then flips between sides from one poll or day to
+// the next. The panel azimuth at solar noon settles it: a turn that would
+// cross the meridian on the far side from the sun goes the other way, as the
+// sun did. `condition_dual_axis_table` supplies it for each day.
+pub fn condition_dual_axis_step_with_noon(
+    prev: DualAxisAngles,
+    next: DualAxisAngles,
+    elapsed_minutes: f64,
+    config: &AzimuthFlipConfig,
+    noon_panel_azimuth: f64,
+) -> (DualAxisAngles, bool) {
+    condition_step(
+        prev,
+        next,
+        elapsed_minutes,
+        config,
+        Some(noon_panel_azimuth),
+    )
+}
+
+// True panel azimuth at solar noon, the sun's azimuth plus 180°: 180° when
+// the sun culminates north of the zenith, 0° otherwise.
+pub fn noon_panel_azimuth(latitude: f64, declination: f64) -> f64 {
+    if declination > latitude {
+        180.0
+    } else {
+        0.0
+    }
+}
+
+fn condition_step(
+    prev: DualAxisAngles,
+    next: DualAxisAngles,
+    elapsed_minutes: f64,
+    config: &AzimuthFlipConfig,
+    noon_panel_azimuth: Option<f64>,
+) -> (DualAxisAngles, bool) {
+    let mut target = next;
+    if config.allow_negative_tilt {
+        let flipped_azimuth = (next.panel_azimuth + 180.0).rem_euclid(360.0);
+        if angle_difference(prev.panel_azimuth, flipped_azimuth).abs()
+            < angle_difference(prev.panel_azimuth, next.panel_azimuth).abs()
+        {
+            target = DualAxisAngles {
+                tilt: -next.tilt,
+                panel_azimuth: flipped_azimuth,
+            };
+        }
+    }
+    if target.tilt.abs() < config.flat_tilt_threshold {
+        let held = DualAxisAngles {
+            tilt: target.tilt,
+            panel_azimuth: prev.panel_azimuth,
+        };
+        return (held, held != next);
+    }
+    let mut delta = angle_difference(prev.panel_azimuth, target.panel_azimuth);
+    // Only a swing this large is ambiguous; a midnight sun crosses the far
+    // meridian for real, but a step at a time.
+    if let Some(noon) = noon_panel_azimuth.filter(|_| delta.abs() >= 90.0) {
+        let wrong_side = angle_difference(prev.panel_azimuth, noon + 180.0);
+        if wrong_side.signum() == delta.signum() && wrong_side.abs() < delta.abs() {
+            delta -= 360.0_f64.copysign(delta);
+        }
+    }
+    let max_step = config.max_azimuth_rate * elapsed_minutes;
+    if delta.abs() <= max_step {
+        // A flip alone still changes both commanded axes
+        return (target, target != next);
+    }
+    let limited = DualAxisAngles {
+        tilt: target.tilt,
+        panel_azimuth: (prev.panel_azimuth + max_step.copysign(delta)).rem_euclid(360.0),
+    };
+    (limited, true)
+}
+
+pub fn condition_dual_axis_table(table: &mut DualAxisTable, config: &AzimuthFlipConfig) -> usize {
+    let table_config = table.config;
+    let encoding = table_config.azimuth_encoding;
+    let magnetic_declination = table_config.magnetic_declination.unwrap_or(0.0);
+    let mut adjusted = 0;
+    for day in &mut table.days {
+        let declination = declination_in_year(
+            day.day_of_year,
+            table_config.year,
+            table_config.declination_model,
+        );
+        let noon = magnetic_bearing(
+            noon_panel_azimuth(table_config.latitude, declination),
+            magnetic_declination,
+        );
+        let mut prev: Option<(i32, DualAxisAngles)> = None;
+        for entry in &mut day.entries {
+            let (Some(tilt), Some(panel_azimuth)) = (entry.tilt, entry.panel_azimuth) else {
+                prev = None;
+                continue;
+            };
+            let next = DualAxisAngles {
+                tilt,
+                panel_azimuth: encoding.decode(panel_azimuth),
+            };
+            let commanded = match prev {
+                Some((prev_minutes, prev_angles)) => {
+                    let elapsed = (entry.minutes - prev_minutes) as f64;
+                    let (angles, changed) =
+                        condition_step(prev_angles, next, elapsed, config, Some(noon));
+                    if changed {
+                        entry.adjusted = true;
+                        adjusted += 1;
+                    }
+                    angles
+                }
+                None => next,
+            };
+            entry.tilt = Some(commanded.tilt);
+            entry.panel_azimuth = Some(encoding.encode(commanded.panel_azimuth));
+            prev = Some((entry.minutes, commanded));
+        }
+    }
+    adjusted
+}
+
+pub fn max_azimuth_rate(table: &DualAxisTable) -> f64 {
+    table
+        .days
+        .iter()
+        .flat_map(|day| day.entries.windows(2))
+        .filter_map(|pair| {
+            let (a0, a1) = (pair[0].panel_azimuth?, pair[1].panel_azimuth?);
+            let dt = (pair[1].minutes - pair[0].minutes) as f64;
+            Some(angle_difference(a0, a1).abs() / dt)
+        })
+        .fold(0.0, f64::max)
+}
+
+// The "Lahaina noon" days of the config's year: where the declination
+// crosses the latitude, the day of each crossing closer to it. Within the
+// tropics that is twice a year, merging towards the solstice at the tropic
+// itself; outside them, never. Crossings are found between consecutive
+// per-day samples, so one falling over New Year is not reported.
+pub fn overhead_sun_days(config: &LookupTableConfig) -> Vec<OverheadSunDay> {
+    let n_days = if leap_year(config.year) { 366 } else { 365 };
+    let offset =
+        |doy| declination_in_year(doy, config.year, config.declination_model) - config.latitude;
+    let mut days: Vec<OverheadSunDay> = Vec::new();
+    for doy in 1..n_days {
+        let (d0, d1) = (offset(doy), offset(doy + 1));
+        if (d0 < 0.0) == (d1 < 0.0) {
+            continue;
+        }
+        let day = if d0.abs() <= d1.abs() {
+            OverheadSunDay {
+                day_of_year: doy,
+                noon_zenith: d0.abs(),
+            }
+        } else {
+            OverheadSunDay {
+                day_of_year: doy + 1,
+                noon_zenith: d1.abs(),
+            }
+        };
+        if days.last().map(|last| last.day_of_year) != Some(day.day_of_year) {
+            days.push(day);
+        }
+    }
+    days
+}
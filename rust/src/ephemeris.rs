@@ -0,0 +1,183 @@
+use chrono::{DateTime, Datelike, TimeZone, Utc};
+
+use crate::angles::{
+    day_of_year_from_date, deg_to_rad, julian_century, julian_day, normalize_angle, rad_to_deg,
+    solar_altitude, solar_azimuth, solar_zenith_angle, DEGREES_PER_HOUR, JULIAN_DAY_J2000,
+};
+use crate::math;
+use crate::types::SolarPosition;
+
+// ΔT = TT − UT in seconds, from the Espenak & Meeus (2006) polynomials the
+// NASA eclipse canon uses. `year` is decimal, e.g. 2026.5 for mid-2026.
+// Past 2005 the polynomial is an extrapolation that already runs several
+// seconds above measured values; pass a measured ΔT where it matters.
+pub fn delta_t_estimate(year: f64) -> f64 {
+    let y = year;
+    let long_term = |y: f64| {
+        let u = (y - 1820.0) / 100.0;
+        -20.0 + 32.0 * u * u
+    };
+    if y < -500.0 {
+        long_term(y)
+    } else if y < 500.0 {
+        let u = y / 100.0;
+        10583.6 - 1014.41 * u + 33.78311 * u.powi(2) - 5.952053 * u.powi(3) - 0.1798452 * u.powi(4)
+            + 0.022174192 * u.powi(5)
+            + 0.0090316521 * u.powi(6)
+    } else if y < 1600.0 {
+        let u = (y - 1000.0) / 100.0;
+        1574.2 - 556.01 * u + 71.23472 * u.powi(2) + 0.319781 * u.powi(3)
+            - 0.8503463 * u.powi(4)
+            - 0.005050998 * u.powi(5)
+            + 0.0083572073 * u.powi(6)
+    } else if y < 1700.0 {
+        let t = y - 1600.0;
+        120.0 - 0.9808 * t - 0.01532 * t.powi(2) + t.powi(3) / 7129.0
+    } else if y < 1800.0 {
+        let t = y - 1700.0;
+        8.83 + 0.1603 * t - 0.0059285 * t.powi(2) + 0.00013336 * t.powi(3) - t.powi(4) / 1_174_000.0
+    } else if y < 1860.0 {
+        let t = y - 1800.0;
+        13.72 - 0.332447 * t + 0.0068612 * t.powi(2) + 0.0041116 * t.powi(3)
+            - 0.00037436 * t.powi(4)
+            + 0.0000121272 * t.powi(5)
+            - 0.0000001699 * t.powi(6)
+            + 0.000000000875 * t.powi(7)
+    } else if y < 1900.0 {
+        let t = y - 1860.0;
+        7.62 + 0.5737 * t - 0.251754 * t.powi(2) + 0.01680668 * t.powi(3) - 0.0004473624 * t.powi(4)
+            + t.powi(5) / 233_174.0
+    } else if y < 1920.0 {
+        let t = y - 1900.0;
+        -2.79 + 1.494119 * t - 0.0598939 * t.powi(2) + 0.0061966 * t.powi(3) - 0.000197 * t.powi(4)
+    } else if y < 1941.0 {
+        let t = y - 1920.0;
+        21.20 + 0.84493 * t - 0.076100 * t.powi(2) + 0.0020936 * t.powi(3)
+    } else if y < 1961.0 {
+        let t = y - 1950.0;
+        29.07 + 0.407 * t - t.powi(2) / 233.0 + t.powi(3) / 2547.0
+    } else if y < 1986.0 {
+        let t = y - 1975.0;
+        45.45 + 1.067 * t - t.powi(2) / 260.0 - t.powi(3) / 718.0
+    } else if y < 2005.0 {
+        let t = y - 2000.0;
+        63.86 + 0.3345 * t - 0.060374 * t.powi(2)
+            + 0.0017275 * t.powi(3)
+            + 0.000651814 * t.powi(4)
+            + 0.00002373599 * t.powi(5)
+    } else if y < 2050.0 {
+        let t = y - 2000.0;
+        62.92 + 0.32217 * t + 0.005589 * t.powi(2)
+    } else if y < 2150.0 {
+        long_term(y) - 0.5628 * (2150.0 - y)
+    } else {
+        long_term(y)
+    }
+}
+
+// The decimal year Espenak & Meeus evaluate ΔT at: mid-month resolution.
+pub fn delta_t_for<Tz: TimeZone>(dt: &DateTime<Tz>) -> f64 {
+    let utc = dt.with_timezone(&Utc);
+    delta_t_estimate(utc.year() as f64 + (utc.month() as f64 - 0.5) / 12.0)
+}
+
+pub fn julian_ephemeris_day(jd: f64, delta_t: f64) -> f64 {
+    jd + delta_t / 86_400.0
+}
+
+// Minutes, for `t` Julian centuries of TT since J2000.0: Smart's series in
+// the Sun's mean longitude, the mean anomaly, the orbit's eccentricity and
+// the mean obliquity (Meeus 28.3).
+pub fn equation_of_time_ecliptic(t: f64) -> f64 {
+    let mean_longitude = deg_to_rad(280.46646 + t * (36000.76983 + t * 0.0003032));
+    let mean_anomaly = deg_to_rad(357.52911 + t * (35999.05029 - t * 0.0001537));
+    let e = 0.016708634 - t * (0.000042037 + t * 0.0000001267);
+    let obliquity = deg_to_rad(
+        23.0 + (26.0 + (21.448 - t * (46.815 + t * (0.00059 - t * 0.001813))) / 60.0) / 60.0,
+    );
+    let y = math::tan(obliquity / 2.0).powi(2);
+    let eot = y * math::sin(2.0 * mean_longitude) - 2.0 * e * math::sin(mean_anomaly)
+        + 4.0 * e * y * math::sin(mean_anomaly) * math::cos(2.0 * mean_longitude)
+        - 0.5 * y * y * math::sin(4.0 * mean_longitude)
+        - 1.25 * e * e * math::sin(2.0 * mean_anomaly);
+    rad_to_deg(eot) * 4.0
+}
+
+// Julian day of noon UT on day `n` of `year`, proleptic Gregorian, in plain
+// arithmetic so any year works.
+fn julian_day_at_noon(n: i32, year: i32) -> f64 {
+    let y = year as f64 - 1.0;
+    let jan_0 =
+        1_721_424.5 + 365.0 * y + (y / 4.0).floor() - (y / 100.0).floor() + (y / 400.0).floor();
+    jan_0 + n as f64 + 0.5
+}
+
+// `equation_of_time_ecliptic` at noon UT of day `n`, for the per-day paths
+// that take a day of year.
+pub fn equation_of_time_for_day(n: i32, year: i32) -> f64 {
+    let jd = julian_day_at_noon(n, year);
+    let delta_t = delta_t_estimate(year as f64 + 0.5);
+    equation_of_time_ecliptic(julian_century(julian_ephemeris_day(jd, delta_t)))
+}
+
+// Geocentric position from Meeus's solar coordinates (Astronomical
+// Algorithms, ch. 25 and 12): the Sun's apparent longitude and the true
+// obliquity on the TT time scale, and Greenwich sidereal time on UT. The
+// split is what ΔT is for; `None` uses `delta_t_for`. Good to about 0.01°
+// against NREL SPA's geocentric angles over 1950-2050. No refraction or
+// parallax is applied, matching `solar_position`.
+pub fn solar_position_precise<Tz: TimeZone>(
+    latitude: f64,
+    longitude: f64,
+    dt: &DateTime<Tz>,
+    delta_t: Option<f64>,
+) -> SolarPosition {
+    let jd = julian_day(dt);
+    let delta_t = delta_t.unwrap_or_else(|| delta_t_for(dt));
+    let t = julian_century(julian_ephemeris_day(jd, delta_t));
+
+    let mean_longitude = normalize_angle(280.46646 + t * (36000.76983 + t * 0.0003032));
+    let mean_anomaly = deg_to_rad(357.52911 + t * (35999.05029 - t * 0.0001537));
+    let center = (1.914602 - t * (0.004817 + t * 0.000014)) * math::sin(mean_anomaly)
+        + (0.019993 - t * 0.000101) * math::sin(2.0 * mean_anomaly)
+        + 0.000289 * math::sin(3.0 * mean_anomaly);
+    let omega = deg_to_rad(125.04 - 1934.136 * t);
+    let nutation_longitude = -0.00478 * math::sin(omega);
+    let apparent_longitude = deg_to_rad(mean_longitude + center - 0.00569 + nutation_longitude);
+    let mean_obliquity =
+        23.0 + (26.0 + (21.448 - t * (46.815 + t * (0.00059 - t * 0.001813))) / 60.0) / 60.0;
+    let obliquity = deg_to_rad(mean_obliquity + 0.00256 * math::cos(omega));
+
+    let (sin_lambda, cos_lambda) = math::sin_cos(apparent_longitude);
+    let declination = rad_to_deg(math::asin(math::sin(obliquity) * sin_lambda));
+    let right_ascension = normalize_angle(rad_to_deg(math::atan2(
+        math::cos(obliquity) * sin_lambda,
+        cos_lambda,
+    )));
+
+    // Apparent sidereal time: mean plus the equation of the equinoxes
+    let tu = (jd - JULIAN_DAY_J2000) / 36_525.0;
+    let mean_sidereal = 280.46061837
+        + 360.98564736629 * (jd - JULIAN_DAY_J2000)
+        + tu * tu * (0.000387933 - tu / 38_710_000.0);
+    let sidereal = mean_sidereal + nutation_longitude * math::cos(obliquity);
+    let hour_angle = normalize_angle(sidereal + longitude - right_ascension + 180.0) - 180.0;
+
+    let eot_degrees = normalize_angle(
+        mean_longitude - 0.0057183 - right_ascension
+            + nutation_longitude * math::cos(obliquity)
+            + 180.0,
+    ) - 180.0;
+    let zenith = solar_zenith_angle(latitude, declination, hour_angle);
+    let utc = dt.with_timezone(&Utc);
+    SolarPosition {
+        day_of_year: day_of_year_from_date(utc.date_naive()),
+        declination,
+        equation_of_time: eot_degrees * 4.0,
+        local_solar_time: 12.0 + hour_angle / DEGREES_PER_HOUR,
+        hour_angle,
+        zenith,
+        altitude: solar_altitude(zenith),
+        azimuth: solar_azimuth(latitude, declination, hour_angle),
+    }
+}
@@ -0,0 +1,136 @@
+use crate::types::{ControllerMode, Event, EventKind, StowReason};
+
+pub const EVENT_BYTES: usize = 8;
+
+const TAG_MODE_CHANGE: u8 = 1;
+const TAG_MOVE: u8 = 2;
+const TAG_FAULT: u8 = 3;
+const TAG_STOW: u8 = 4;
+
+impl Event {
+    // Layout: u32 LE timestamp, tag byte, 3 payload bytes. Move targets are
+    // stored as i16 hundredths of a degree.
+    pub fn encode(&self) -> [u8; EVENT_BYTES] {
+        let mut out = [0u8; EVENT_BYTES];
+        out[..4].copy_from_slice(&self.timestamp.to_le_bytes());
+        match self.kind {
+            EventKind::ModeChange { from, to } => {
+                out[4] = TAG_MODE_CHANGE;
+                out[5] = from.code();
+                out[6] = to.code();
+            }
+            EventKind::Move { target } => {
+                out[4] = TAG_MOVE;
+                let centi = (target * 100.0)
+                    .round()
+                    .clamp(i16::MIN as f32, i16::MAX as f32) as i16;
+                out[5..7].copy_from_slice(&centi.to_le_bytes());
+            }
+            EventKind::Fault { code } => {
+                out[4] = TAG_FAULT;
+                out[5..7].copy_from_slice(&code.to_le_bytes());
+            }
+            EventKind::Stow { reason } => {
+                out[4] = TAG_STOW;
+                out[5] = reason.code();
+            }
+        }
+        out
+    }
+
+    pub fn decode(bytes: &[u8; EVENT_BYTES]) -> Option<Self> {
+        let timestamp = u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]);
+        let kind = match bytes[4] {
+            TAG_MODE_CHANGE => EventKind::ModeChange {
+                from: ControllerMode::from_code(bytes[5])?,
+                to: ControllerMode::from_code(bytes[6])?,
+            },
+            TAG_MOVE => EventKind::Move {
+                target: i16::from_le_bytes([bytes[5], bytes[6]]) as f32 / 100.0,
+            },
+            TAG_FAULT => EventKind::Fault {
+                code: u16::from_le_bytes([bytes[5], bytes[6]]),
+            },
+            TAG_STOW => EventKind::Stow {
+                reason: StowReason::from_code(bytes[5])?,
+            },
+            _ => return None,
+        };
+        Some(Event { timestamp, kind })
+    }
+}
+
+// Fixed-capacity ring buffer; never allocates, so it can live in a static on
+// devices without a heap. The oldest event is overwritten when full.
+#[derive(Debug, Clone)]
+pub struct EventLog<const N: usize> {
+    events: [Option<Event>; N],
+    head: usize,
+    len: usize,
+    dropped: u32,
+}
+
+impl<const N: usize> EventLog<N> {
+    pub const fn new() -> Self {
+        Self {
+            events: [None; N],
+            head: 0,
+            len: 0,
+            dropped: 0,
+        }
+    }
+
+    pub fn push(&mut self, event: Event) {
+        if N == 0 {
+            self.dropped = self.dropped.saturating_add(1);
+            return;
+        }
+        let idx = (self.head + self.len) % N;
+        self.events[idx] = Some(event);
+        if self.len == N {
+            self.head = (self.head + 1) % N;
+            self.dropped = self.dropped.saturating_add(1);
+        } else {
+            self.len += 1;
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub const fn capacity(&self) -> usize {
+        N
+    }
+
+    pub fn dropped(&self) -> u32 {
+        self.dropped
+    }
+
+    pub fn latest(&self) -> Option<&Event> {
+        if self.len == 0 {
+            return None;
+        }
+        self.events[(self.head + self.len - 1) % N].as_ref()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &Event> + '_ {
+        (0..self.len).filter_map(move |i| self.events[(self.head + i) % N].as_ref())
+    }
+
+    pub fn clear(&mut self) {
+        self.events = [None; N];
+        self.head = 0;
+        self.len = 0;
+    }
+}
+
+impl<const N: usize> Default for EventLog<N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
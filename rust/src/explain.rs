@@ -0,0 +1,192 @@
+use std::fmt::Write;
+
+use chrono::{DateTime, Datelike, TimeZone, Utc};
+
+use crate::angles::{self, backtrack, single_axis_tilt, solar_position_debug};
+use crate::types::{ExplainStep, SolarPosition};
+
+// Explain mode: the same pipeline as `solar_position`, returned one formula
+// at a time for teaching tools. Every output is copied from the real
+// computation rather than recomputed, so an explanation cannot drift from
+// the numbers the library returns.
+
+fn step(name: &str, formula: &str, inputs: &[(&str, f64)], output: f64, unit: &str) -> ExplainStep {
+    ExplainStep {
+        name: name.to_string(),
+        formula: formula.to_string(),
+        inputs: inputs.iter().map(|&(k, v)| (k.to_string(), v)).collect(),
+        output,
+        unit: unit.to_string(),
+    }
+}
+
+pub fn explain_solar_position<Tz: TimeZone>(
+    latitude: f64,
+    longitude: f64,
+    dt: &DateTime<Tz>,
+) -> Vec<ExplainStep> {
+    let utc = dt.with_timezone(&Utc);
+    let d = solar_position_debug(latitude, longitude, dt);
+    let days = if angles::leap_year(utc.year()) {
+        366.0
+    } else {
+        365.0
+    };
+    let n = d.day_of_year as f64;
+    vec![
+        step(
+            "day_of_year",
+            "n = ordinal day of the UTC date",
+            &[
+                ("year", utc.year() as f64),
+                ("month", utc.month() as f64),
+                ("day", utc.day() as f64),
+            ],
+            n,
+            "",
+        ),
+        step(
+            "utc_hours",
+            "t = hour + minute / 60 + second / 3600 (UTC)",
+            &[],
+            d.utc_hours,
+            "h",
+        ),
+        step(
+            "fractional_year",
+            "B = (n - 1) · 360° / days_in_year",
+            &[("n", n), ("days_in_year", days)],
+            d.intermediate_angle_b,
+            "rad",
+        ),
+        step(
+            "equation_of_time",
+            "E = 229.18 · (0.000075 + 0.001868 cos B - 0.032077 sin B \
+             - 0.014615 cos 2B - 0.040849 sin 2B)",
+            &[("B", d.intermediate_angle_b)],
+            d.equation_of_time,
+            "min",
+        ),
+        step(
+            "declination",
+            "δ = 23.45° · sin(360° · (284 + n) / days_in_year)",
+            &[("n", n), ("days_in_year", days)],
+            d.declination,
+            "°",
+        ),
+        step(
+            "utc_lst_correction",
+            "C = (4 · longitude + E) / 60",
+            &[("longitude", longitude), ("E", d.equation_of_time)],
+            d.correction,
+            "h",
+        ),
+        step(
+            "local_solar_time",
+            "LST = (t + C) mod 24",
+            &[("t", d.utc_hours), ("C", d.correction)],
+            d.local_solar_time,
+            "h",
+        ),
+        step(
+            "hour_angle",
+            "ω = 15° · (LST - 12)",
+            &[("LST", d.local_solar_time)],
+            d.hour_angle,
+            "°",
+        ),
+        step(
+            "cos_zenith",
+            "cos θz = sin φ sin δ + cos φ cos δ cos ω",
+            &[("φ", latitude), ("δ", d.declination), ("ω", d.hour_angle)],
+            d.cos_zenith,
+            "",
+        ),
+        step(
+            "zenith",
+            "θz = acos(cos θz)",
+            &[("cos θz", d.cos_zenith)],
+            d.zenith,
+            "°",
+        ),
+        step(
+            "altitude",
+            "α = 90° - θz",
+            &[("θz", d.zenith)],
+            d.altitude,
+            "°",
+        ),
+        step(
+            "azimuth",
+            "γ = atan2(-cos δ sin ω, sin δ cos φ - cos δ sin φ cos ω), \
+             clockwise from north",
+            &[("φ", latitude), ("δ", d.declination), ("ω", d.hour_angle)],
+            d.azimuth,
+            "°",
+        ),
+    ]
+}
+
+// The position steps followed by the horizontal single-axis rotation and,
+// with a ground coverage ratio, the backtracking correction. The final
+// output is what `single_axis_tilt` (or `backtrack` on it) returns.
+pub fn explain_single_axis<Tz: TimeZone>(
+    latitude: f64,
+    longitude: f64,
+    dt: &DateTime<Tz>,
+    gcr: Option<f64>,
+) -> Vec<ExplainStep> {
+    let mut steps = explain_solar_position(latitude, longitude, dt);
+    let d = solar_position_debug(latitude, longitude, dt);
+    let pos = SolarPosition {
+        day_of_year: d.day_of_year,
+        declination: d.declination,
+        equation_of_time: d.equation_of_time,
+        local_solar_time: d.local_solar_time,
+        hour_angle: d.hour_angle,
+        zenith: d.zenith,
+        altitude: d.altitude,
+        azimuth: d.azimuth,
+    };
+    let rotation = single_axis_tilt(&pos, latitude);
+    steps.push(step(
+        "single_axis_rotation",
+        "R = atan2(tan ω, cos φ), positive facing west",
+        &[("ω", d.hour_angle), ("φ", latitude)],
+        rotation,
+        "°",
+    ));
+    if let Some(gcr) = gcr {
+        steps.push(step(
+            "backtracking",
+            "R' = R - sign(R) · acos(cos R / GCR) when cos R < GCR, else R",
+            &[("R", rotation), ("GCR", gcr)],
+            backtrack(rotation, gcr),
+            "°",
+        ));
+    }
+    steps
+}
+
+// One line per step: `name = output unit  [formula; input = value, ...]`.
+pub fn format_explanation(steps: &[ExplainStep]) -> String {
+    let mut out = String::new();
+    for s in steps {
+        let inputs: Vec<String> = s
+            .inputs
+            .iter()
+            .map(|(k, v)| format!("{k} = {v:.6}"))
+            .collect();
+        let unit = if s.unit.is_empty() {
+            String::new()
+        } else {
+            format!(" {}", s.unit)
+        };
+        let _ = write!(out, "{} = {:.6}{unit}  [{}", s.name, s.output, s.formula);
+        if !inputs.is_empty() {
+            let _ = write!(out, "; {}", inputs.join(", "));
+        }
+        out.push_str("]\n");
+    }
+    out
+}
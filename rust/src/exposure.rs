@@ -0,0 +1,66 @@
+use chrono::{DateTime, Duration, TimeZone};
+
+use crate::angles;
+use crate::shading::azimuth_in_range;
+use crate::types::{SolarPosition, SunExposure};
+
+// Answers are to the minute and look at most this far ahead.
+const SEARCH_MINUTES: i64 = 2 * 1440;
+
+// A vertical wall facing `facing` (compass degrees) sees the half of the sky
+// within 90° of its normal, down to a flat horizon.
+pub fn facade_exposure(latitude: f64, longitude: f64, facing: f64) -> SunExposure {
+    SunExposure {
+        latitude,
+        longitude,
+        azimuth_range: Some((
+            (facing - 90.0).rem_euclid(360.0),
+            (facing + 90.0).rem_euclid(360.0),
+        )),
+        min_altitude: 0.0,
+    }
+}
+
+// Geometric altitude, so `min_altitude: 0.0` ends at sunset to within the
+// few minutes refraction would add.
+pub fn sun_on_at(exposure: &SunExposure, pos: &SolarPosition) -> bool {
+    pos.altitude > exposure.min_altitude
+        && exposure
+            .azimuth_range
+            .is_none_or(|range| azimuth_in_range(pos.azimuth, range))
+}
+
+pub fn sun_on<Tz: TimeZone>(exposure: &SunExposure, now: &DateTime<Tz>) -> bool {
+    let pos = angles::solar_position(exposure.latitude, exposure.longitude, now);
+    sun_on_at(exposure, &pos)
+}
+
+// First whole minute from `now` at which `sun_on` equals `on`.
+fn minutes_until<Tz: TimeZone>(
+    exposure: &SunExposure,
+    now: &DateTime<Tz>,
+    on: bool,
+) -> Option<i64> {
+    (0..=SEARCH_MINUTES).find(|&m| sun_on(exposure, &(now.clone() + Duration::minutes(m))) == on)
+}
+
+// How much longer the sun stays on: until it passes the edge of the azimuth
+// window, drops behind the horizon or sets, whichever comes first. None when
+// the sun is not on now; under the midnight sun with no azimuth window this
+// is capped at two days.
+pub fn sun_on_for<Tz: TimeZone>(exposure: &SunExposure, now: &DateTime<Tz>) -> Option<Duration> {
+    if !sun_on(exposure, now) {
+        return None;
+    }
+    let minutes = minutes_until(exposure, now, false).unwrap_or(SEARCH_MINUTES);
+    Some(Duration::minutes(minutes))
+}
+
+// When the sun next reaches the exposure, `now` itself if it already does.
+// None if it stays off for the next two days, e.g. a north wall in winter.
+pub fn next_sun_on<Tz: TimeZone>(
+    exposure: &SunExposure,
+    now: &DateTime<Tz>,
+) -> Option<DateTime<Tz>> {
+    minutes_until(exposure, now, true).map(|m| now.clone() + Duration::minutes(m))
+}
@@ -0,0 +1,166 @@
+use crate::angles::{self, deg_to_rad, rad_to_deg};
+use crate::interp::{find_bracketing_entries, interpolate_linear, HasMinutes};
+use crate::irradiance::clear_sky_components;
+use crate::lookup_table::{day_entries, generate_table};
+use crate::math;
+use crate::transposition::poa_irradiance;
+use crate::types::{
+    FixedEntry, FixedMountConfig, FixedTable, InverterEvent, LookupTableConfig, MinuteOfDay,
+    SkyDiffuseModel, SolarPosition, SurfaceOrientation,
+};
+
+impl HasMinutes for FixedEntry {
+    fn minutes(&self) -> i32 {
+        self.minutes
+    }
+
+    fn set_minutes(&mut self, minutes: i32) {
+        self.minutes = minutes;
+    }
+}
+
+// Angle of incidence on a plane facing `azimuth` (compass bearing, as in
+// `SurfaceOrientation`), from the fast path's zenith and azimuth.
+fn aoi_at(zenith: f64, sun_azimuth: f64, mount: &FixedMountConfig) -> f64 {
+    let (sin_z, cos_z) = math::sin_cos(deg_to_rad(zenith));
+    let (sin_t, cos_t) = math::sin_cos(deg_to_rad(mount.tilt));
+    let cos_aoi =
+        cos_z * cos_t + sin_z * sin_t * math::cos(deg_to_rad(sun_azimuth - mount.azimuth));
+    rad_to_deg(math::acos(cos_aoi.clamp(-1.0, 1.0)))
+}
+
+// Same day grid and buffers as the tracker tables, but each entry only says
+// whether the sun clears `min_altitude` and, if so, the angle of incidence on
+// the fixed array. AOI is None below the threshold and when the sun is behind
+// the panel, so `cos(aoi) * dni` is always the beam on the array.
+pub fn generate_fixed_table(config: &LookupTableConfig, mount: &FixedMountConfig) -> FixedTable {
+    let mount = *mount;
+    generate_table(
+        config,
+        move |minutes, angles, is_daylight| {
+            let above = is_daylight && 90.0 - angles.zenith >= mount.min_altitude;
+            let aoi = above
+                .then(|| aoi_at(angles.zenith, angles.azimuth, &mount))
+                .filter(|aoi| *aoi < 90.0);
+            FixedEntry {
+                minutes,
+                above_threshold: above,
+                aoi,
+            }
+        },
+        3,
+    )
+}
+
+// AOI is interpolated between entries; the flag comes from the nearer one.
+pub fn lookup_fixed(
+    table: &FixedTable,
+    day_of_year: i32,
+    minutes: MinuteOfDay,
+) -> Option<FixedEntry> {
+    let minutes = minutes.get();
+    let entries = day_entries(table, day_of_year)?;
+    let interval_minutes = table.config.interval_minutes;
+    let (before, after, fraction) = find_bracketing_entries(entries, interval_minutes, minutes)?;
+    let Some(after) = after else {
+        return Some(FixedEntry { minutes, ..*before });
+    };
+    let nearest = if fraction > 0.5 { after } else { before };
+    Some(FixedEntry {
+        minutes,
+        above_threshold: nearest.above_threshold,
+        aoi: interpolate_linear(before.aoi, after.aoi, fraction),
+    })
+}
+
+// First and last UTC minute with the sun above the threshold, for waking and
+// sleeping on-device. None when it never clears it that day.
+pub fn fixed_window(table: &FixedTable, day_of_year: i32) -> Option<(i32, i32)> {
+    let entries = day_entries(table, day_of_year)?;
+    let mut above = entries.iter().filter(|e| e.above_threshold);
+    let first = above.next()?.minutes;
+    Some((first, above.next_back().map_or(first, |e| e.minutes)))
+}
+
+// Clear-sky irradiance on the array at a UTC minute of the table's site and
+// year. Isotropic sky and no ground reflection keep it cheap and on the low
+// side, which is the safe direction for waking an inverter. Zero with the
+// sun down.
+pub fn clear_sky_poa(
+    table: &FixedTable,
+    mount: &FixedMountConfig,
+    day_of_year: i32,
+    minutes: MinuteOfDay,
+) -> f64 {
+    let minutes = minutes.get();
+    let config = &table.config;
+    let eot = angles::equation_of_time(day_of_year);
+    let decl = angles::solar_declination(day_of_year);
+    let correction = angles::utc_lst_correction(config.longitude, eot);
+    let (lst, ha, zenith, altitude, azimuth) =
+        angles::solar_angles_at(config.latitude, decl, correction, minutes as f64 / 60.0);
+    let pos = SolarPosition {
+        day_of_year,
+        declination: decl,
+        equation_of_time: eot,
+        local_solar_time: lst,
+        hour_angle: ha,
+        zenith,
+        altitude,
+        azimuth,
+    };
+    let surface = SurfaceOrientation {
+        surface_tilt: mount.tilt,
+        surface_azimuth: mount.azimuth,
+    };
+    clear_sky_components(&pos).map_or(0.0, |components| {
+        poa_irradiance(&pos, &surface, &components, 0.0, SkyDiffuseModel::Isotropic).total
+    })
+}
+
+// First and last table entry whose clear-sky POA reaches `min_poa` W/m².
+// `mount` must be the one the table was generated with. Allocation-free.
+pub fn inverter_window(
+    table: &FixedTable,
+    mount: &FixedMountConfig,
+    day_of_year: i32,
+    min_poa: f64,
+) -> Option<(i32, i32)> {
+    let entries = day_entries(table, day_of_year)?;
+    let mut producing = entries
+        .iter()
+        .filter(|e| {
+            e.above_threshold
+                && MinuteOfDay::new(e.minutes)
+                    .is_some_and(|m| clear_sky_poa(table, mount, day_of_year, m) >= min_poa)
+        })
+        .map(|e| e.minutes);
+    let wake = producing.next()?;
+    Some((wake, producing.next_back().unwrap_or(wake)))
+}
+
+// The window as a wake event and a sleep event, or nothing on a day the
+// threshold is never reached. Sleep is one interval after the last producing
+// entry, so the inverter stays up through it, but no later than 23:59 UTC.
+pub fn inverter_events(
+    table: &FixedTable,
+    mount: &FixedMountConfig,
+    day_of_year: i32,
+    min_poa: f64,
+) -> impl Iterator<Item = InverterEvent> {
+    let interval = table.config.interval_minutes;
+    inverter_window(table, mount, day_of_year, min_poa)
+        .into_iter()
+        .flat_map(move |(wake, last)| {
+            [
+                InverterEvent {
+                    minutes: wake,
+                    awake: true,
+                },
+                InverterEvent {
+                    minutes: (last + interval).min(1439),
+                    awake: false,
+                },
+            ]
+        })
+}
@@ -0,0 +1,55 @@
+use crate::calibration::calibrate_rotation;
+use crate::types::{
+    CalibrationOffsets, FleetTable, LookupTableConfig, SingleAxisTable, TrackerOverride,
+};
+
+// Overrides replace the site value; a calibrated axis azimuth error is added
+// on top of whichever axis azimuth results.
+pub fn tracker_config(site: &LookupTableConfig, tracker: &TrackerOverride) -> LookupTableConfig {
+    let axis_azimuth = tracker.axis_azimuth.or(site.axis_azimuth);
+    let azimuth_error = tracker.calibration.map_or(0.0, |c| c.axis_azimuth_error);
+    let axis_azimuth = if azimuth_error != 0.0 {
+        Some(axis_azimuth.unwrap_or(180.0) + azimuth_error)
+    } else {
+        axis_azimuth
+    };
+    LookupTableConfig {
+        axis_azimuth,
+        min_rotation: tracker.min_rotation.or(site.min_rotation),
+        max_rotation: tracker.max_rotation.or(site.max_rotation),
+        ..*site
+    }
+}
+
+// Converts true rotations into the actuator's frame. Limits are applied
+// beforehand, in true angles, by the generator.
+pub fn apply_calibration(table: &mut SingleAxisTable, calibration: &CalibrationOffsets) {
+    for entry in table.days.iter_mut().flat_map(|day| day.entries.iter_mut()) {
+        if let Some(rotation) = entry.rotation {
+            entry.rotation = Some(calibrate_rotation(rotation, calibration));
+        }
+    }
+}
+
+pub fn generate_fleet_tables<F>(
+    site: &LookupTableConfig,
+    trackers: &[TrackerOverride],
+    generate: F,
+) -> Vec<FleetTable>
+where
+    F: Fn(&LookupTableConfig) -> SingleAxisTable,
+{
+    trackers
+        .iter()
+        .map(|tracker| {
+            let calibration = tracker.calibration.unwrap_or_default();
+            let mut table = generate(&tracker_config(site, tracker));
+            apply_calibration(&mut table, &calibration);
+            FleetTable {
+                id: tracker.id.clone(),
+                calibration,
+                table,
+            }
+        })
+        .collect()
+}
@@ -0,0 +1,112 @@
+use crate::angles::{angle_difference, deg_to_rad, rad_to_deg, vector_dot};
+use crate::types::{GimbalAvoidance, GimbalAxes, GimbalCommand, GimbalConfig, GimbalSolution};
+
+fn cross(a: [f64; 3], b: [f64; 3]) -> [f64; 3] {
+    [
+        a[1] * b[2] - a[2] * b[1],
+        a[2] * b[0] - a[0] * b[2],
+        a[0] * b[1] - a[1] * b[0],
+    ]
+}
+
+fn unit(v: [f64; 3]) -> Option<[f64; 3]> {
+    let norm = vector_dot(v, v).sqrt();
+    (norm > 1e-9).then(|| [v[0] / norm, v[1] / norm, v[2] / norm])
+}
+
+// Orthonormal frame (outer, inner, boresight) for the zero pose, where the
+// boresight is outer × inner. Angles follow the right-hand rule about each
+// axis; the inner axis is carried by the outer one.
+fn frame(axes: &GimbalAxes) -> Option<([f64; 3], [f64; 3], [f64; 3])> {
+    let a = unit(axes.outer)?;
+    let b = unit(axes.inner)?;
+    if vector_dot(a, b).abs() > 1e-6 {
+        return None;
+    }
+    Some((a, b, cross(a, b)))
+}
+
+pub fn gimbal_pointing(axes: &GimbalAxes, outer_angle: f64, inner_angle: f64) -> Option<[f64; 3]> {
+    let (a, b, c) = frame(axes)?;
+    let (sin_1, cos_1) = deg_to_rad(outer_angle).sin_cos();
+    let (sin_2, cos_2) = deg_to_rad(inner_angle).sin_cos();
+    let (ka, kb, kc) = (sin_2, -cos_2 * sin_1, cos_2 * cos_1);
+    Some([
+        ka * a[0] + kb * b[0] + kc * c[0],
+        ka * a[1] + kb * b[1] + kc * c[1],
+        ka * a[2] + kb * b[2] + kc * c[2],
+    ])
+}
+
+// The outer angle is undefined once the target lines up with the outer
+// axis; `singularity_margin` is the angular distance to that pose.
+pub fn decompose_gimbal(axes: &GimbalAxes, target: [f64; 3]) -> Option<GimbalSolution> {
+    let (a, b, c) = frame(axes)?;
+    let s = unit(target)?;
+    let (sa, sb, sc) = (vector_dot(s, a), vector_dot(s, b), vector_dot(s, c));
+    let inner_angle = rad_to_deg(sa.clamp(-1.0, 1.0).asin());
+    Some(GimbalSolution {
+        outer_angle: rad_to_deg((-sb).atan2(sc)),
+        inner_angle,
+        singularity_margin: 90.0 - inner_angle.abs(),
+    })
+}
+
+// Same pointing reached the other way round the outer axis.
+pub fn alternate_solution(solution: &GimbalSolution) -> GimbalSolution {
+    GimbalSolution {
+        outer_angle: angle_difference(0.0, solution.outer_angle + 180.0),
+        inner_angle: angle_difference(0.0, 180.0 - solution.inner_angle),
+        ..*solution
+    }
+}
+
+fn best_inner_for_outer(axes: &GimbalAxes, target: [f64; 3], outer_angle: f64) -> Option<f64> {
+    let (a, b, c) = frame(axes)?;
+    let s = unit(target)?;
+    let (sin_1, cos_1) = deg_to_rad(outer_angle).sin_cos();
+    let in_plane = -vector_dot(s, b) * sin_1 + vector_dot(s, c) * cos_1;
+    Some(rad_to_deg(vector_dot(s, a).atan2(in_plane)))
+}
+
+pub fn plan_gimbal_move(
+    axes: &GimbalAxes,
+    target: [f64; 3],
+    previous: Option<&GimbalSolution>,
+    config: &GimbalConfig,
+) -> Option<GimbalCommand> {
+    let solution = decompose_gimbal(axes, target)?;
+    let Some(previous) = previous else {
+        return Some(GimbalCommand {
+            solution,
+            avoidance: GimbalAvoidance::None,
+        });
+    };
+    if solution.singularity_margin < config.lock_margin {
+        // Near lock any outer angle points within twice the margin, so keep
+        // the outer axis still and let the inner axis do the work.
+        let inner_angle = best_inner_for_outer(axes, target, previous.outer_angle)?;
+        return Some(GimbalCommand {
+            solution: GimbalSolution {
+                outer_angle: previous.outer_angle,
+                inner_angle,
+                ..solution
+            },
+            avoidance: GimbalAvoidance::HoldOuter,
+        });
+    }
+    let alternate = alternate_solution(&solution);
+    let travel = |s: &GimbalSolution| angle_difference(previous.outer_angle, s.outer_angle).abs();
+    if alternate.inner_angle.abs() <= config.max_inner_angle
+        && travel(&alternate) < travel(&solution)
+    {
+        return Some(GimbalCommand {
+            solution: alternate,
+            avoidance: GimbalAvoidance::UseAlternate,
+        });
+    }
+    Some(GimbalCommand {
+        solution,
+        avoidance: GimbalAvoidance::None,
+    })
+}
@@ -0,0 +1,256 @@
+use std::collections::{BTreeSet, HashMap, VecDeque};
+
+use chrono::{Datelike, TimeZone, Utc};
+
+use crate::angles;
+use crate::lookup_table::{generate_dual_axis_table, generate_single_axis_table};
+use crate::types::{
+    DualAxisAngles, GoldenCase, GoldenChangeReport, GoldenConfig, GoldenDataset,
+    GoldenOutputChange, GoldenTableRow, LookupTableConfig, SolarPosition,
+};
+
+// Every float is rounded before export so ports compare exact values after
+// parsing instead of depending on each language's shortest-float printing.
+pub const GOLDEN_DECIMALS: i32 = 9;
+
+fn round(value: f64) -> f64 {
+    let scale = 10f64.powi(GOLDEN_DECIMALS);
+    (value * scale).round() / scale
+}
+
+fn round_position(pos: SolarPosition) -> SolarPosition {
+    SolarPosition {
+        declination: round(pos.declination),
+        equation_of_time: round(pos.equation_of_time),
+        local_solar_time: round(pos.local_solar_time),
+        hour_angle: round(pos.hour_angle),
+        zenith: round(pos.zenith),
+        altitude: round(pos.altitude),
+        azimuth: round(pos.azimuth),
+        ..pos
+    }
+}
+
+// Only covers what every port implements: `solar_position`,
+// `single_axis_tilt`, `dual_axis_angles` and the default single/dual-axis
+// tables. Cases and rows are ordered latitude, longitude, date, time.
+pub fn golden_dataset(config: &GoldenConfig) -> GoldenDataset {
+    let mut cases = Vec::new();
+    let mut table_rows = Vec::new();
+    let years: BTreeSet<i32> = config.dates.iter().map(|d| d.year()).collect();
+    for &latitude in &config.latitudes {
+        for &longitude in &config.longitudes {
+            for date in &config.dates {
+                for &hour in &config.utc_hours {
+                    let Some(naive) = date.and_hms_opt(hour, 0, 0) else {
+                        continue;
+                    };
+                    let dt = Utc.from_utc_datetime(&naive);
+                    let pos = angles::solar_position(latitude, longitude, &dt);
+                    let dual = angles::dual_axis_angles(&pos);
+                    cases.push(GoldenCase {
+                        latitude,
+                        longitude,
+                        timestamp: dt.format("%Y-%m-%dT%H:%M:%SZ").to_string(),
+                        position: round_position(pos),
+                        single_axis_tilt: round(angles::single_axis_tilt(&pos, latitude)),
+                        dual_axis: DualAxisAngles {
+                            tilt: round(dual.tilt),
+                            panel_azimuth: round(dual.panel_azimuth),
+                        },
+                    });
+                }
+            }
+            for &year in &years {
+                let table_config = LookupTableConfig {
+                    interval_minutes: config.table_interval_minutes,
+                    latitude,
+                    longitude,
+                    year,
+                    ..Default::default()
+                };
+                let single = generate_single_axis_table(&table_config);
+                let dual = generate_dual_axis_table(&table_config);
+                for date in config.dates.iter().filter(|d| d.year() == year) {
+                    let idx = (angles::day_of_year_from_date(*date) - 1) as usize;
+                    let (single_day, dual_day) = (&single.days[idx], &dual.days[idx]);
+                    for (s, d) in single_day.entries.iter().zip(&dual_day.entries) {
+                        table_rows.push(GoldenTableRow {
+                            latitude,
+                            longitude,
+                            day_of_year: single_day.day_of_year,
+                            minutes: s.minutes,
+                            rotation: s.rotation.map(round),
+                            tilt: d.tilt.map(round),
+                            panel_azimuth: d.panel_azimuth.map(round),
+                        });
+                    }
+                }
+            }
+        }
+    }
+    GoldenDataset { cases, table_rows }
+}
+
+// Compact, fields in declaration order, trailing newline.
+#[cfg(feature = "json")]
+pub fn golden_dataset_json(dataset: &GoldenDataset) -> String {
+    let mut json = serde_json::to_string(dataset).expect("golden dataset is always serializable");
+    json.push('\n');
+    json
+}
+
+#[cfg(feature = "json")]
+pub fn golden_dataset_from_json(text: &str) -> Result<GoldenDataset, serde_json::Error> {
+    serde_json::from_str(text)
+}
+
+// Compared outputs, in report order; the flag marks compass angles, whose
+// deltas go the short way round.
+const CASE_OUTPUTS: [(&str, bool); 9] = [
+    ("solar_position.declination", false),
+    ("solar_position.equation_of_time", false),
+    ("solar_position.hour_angle", false),
+    ("solar_position.zenith", false),
+    ("solar_position.altitude", false),
+    ("solar_position.azimuth", true),
+    ("single_axis_tilt", false),
+    ("dual_axis_angles.tilt", false),
+    ("dual_axis_angles.panel_azimuth", true),
+];
+const ROW_OUTPUTS: [(&str, bool); 3] = [
+    ("single_axis_table.rotation", false),
+    ("dual_axis_table.tilt", false),
+    ("dual_axis_table.panel_azimuth", true),
+];
+
+fn case_values(case: &GoldenCase) -> [Option<f64>; 9] {
+    let pos = &case.position;
+    [
+        Some(pos.declination),
+        Some(pos.equation_of_time),
+        Some(pos.hour_angle),
+        Some(pos.zenith),
+        Some(pos.altitude),
+        Some(pos.azimuth),
+        Some(case.single_axis_tilt),
+        Some(case.dual_axis.tilt),
+        Some(case.dual_axis.panel_azimuth),
+    ]
+}
+
+fn row_values(row: &GoldenTableRow) -> [Option<f64>; 3] {
+    [row.rotation, row.tilt, row.panel_azimuth]
+}
+
+fn case_key(case: &GoldenCase) -> String {
+    format!("{},{} {}", case.latitude, case.longitude, case.timestamp)
+}
+
+fn row_key(row: &GoldenTableRow) -> String {
+    format!(
+        "{},{} day {} minute {}",
+        row.latitude, row.longitude, row.day_of_year, row.minutes
+    )
+}
+
+fn record(
+    change: &mut GoldenOutputChange,
+    old: Option<f64>,
+    new: Option<f64>,
+    wraps: bool,
+    tolerance: f64,
+    key: &str,
+) {
+    let (old, new) = match (old, new) {
+        (Some(old), Some(new)) => (old, new),
+        (None, None) => return,
+        _ => {
+            change.compared += 1;
+            change.availability_changed += 1;
+            return;
+        }
+    };
+    change.compared += 1;
+    let delta = if wraps {
+        angles::angle_difference(old, new).abs()
+    } else {
+        (new - old).abs()
+    };
+    if delta > tolerance {
+        change.changed += 1;
+    }
+    if delta > change.max_delta {
+        change.max_delta = delta;
+        change.max_delta_at = Some(key.to_string());
+    }
+}
+
+// Pairs items by key, in order where a key repeats (table rows for the same
+// day in different years), returning the pairs and the unmatched counts.
+fn pair_by_key<'a, T>(
+    old: &'a [T],
+    new: &'a [T],
+    key: fn(&T) -> String,
+) -> (Vec<(&'a T, &'a T, String)>, usize, usize) {
+    let mut pending: HashMap<String, VecDeque<&T>> = HashMap::new();
+    for item in new {
+        pending.entry(key(item)).or_default().push_back(item);
+    }
+    let mut pairs = Vec::new();
+    let mut unmatched_old = 0;
+    for item in old {
+        let k = key(item);
+        match pending.get_mut(&k).and_then(VecDeque::pop_front) {
+            Some(other) => pairs.push((item, other, k)),
+            None => unmatched_old += 1,
+        }
+    }
+    let unmatched_new = pending.values().map(VecDeque::len).sum();
+    (pairs, unmatched_old, unmatched_new)
+}
+
+fn empty_change(output: &str) -> GoldenOutputChange {
+    GoldenOutputChange {
+        output: output.to_string(),
+        compared: 0,
+        changed: 0,
+        availability_changed: 0,
+        max_delta: 0.0,
+        max_delta_at: None,
+    }
+}
+
+// Output-by-output differences between two releases' golden datasets, for
+// reviewing an upgrade before it reaches deployed controllers. Deltas at or
+// below `tolerance` still count towards `max_delta` but not `changed`.
+pub fn compare_golden(
+    old: &GoldenDataset,
+    new: &GoldenDataset,
+    tolerance: f64,
+) -> GoldenChangeReport {
+    let (cases, old_cases, new_cases) = pair_by_key(&old.cases, &new.cases, case_key);
+    let (rows, old_rows, new_rows) = pair_by_key(&old.table_rows, &new.table_rows, row_key);
+
+    let mut case_changes = CASE_OUTPUTS.map(|(name, _)| empty_change(name));
+    for (a, b, key) in &cases {
+        let (va, vb) = (case_values(a), case_values(b));
+        for (i, change) in case_changes.iter_mut().enumerate() {
+            record(change, va[i], vb[i], CASE_OUTPUTS[i].1, tolerance, key);
+        }
+    }
+    let mut row_changes = ROW_OUTPUTS.map(|(name, _)| empty_change(name));
+    for (a, b, key) in &rows {
+        let (va, vb) = (row_values(a), row_values(b));
+        for (i, change) in row_changes.iter_mut().enumerate() {
+            record(change, va[i], vb[i], ROW_OUTPUTS[i].1, tolerance, key);
+        }
+    }
+
+    GoldenChangeReport {
+        tolerance,
+        outputs: case_changes.into_iter().chain(row_changes).collect(),
+        unmatched_old: old_cases + old_rows,
+        unmatched_new: new_cases + new_rows,
+    }
+}
@@ -0,0 +1,110 @@
+use crate::interp::HasMinutes;
+use crate::lookup_table::{generate_table, lookup_nearest};
+use crate::types::{
+    DualAxisTable, HorizonEntry, HorizonProfile, HorizonTable, LookupTable, LookupTableConfig,
+    MinuteOfDay, SingleAxisTable, SolarPosition,
+};
+
+impl HasMinutes for HorizonEntry {
+    fn minutes(&self) -> i32 {
+        self.minutes
+    }
+
+    fn set_minutes(&mut self, minutes: i32) {
+        self.minutes = minutes;
+    }
+}
+
+// Linear in azimuth between the neighbouring points either side, wrapping
+// through north.
+pub fn horizon_elevation(profile: &HorizonProfile, azimuth: f64) -> f64 {
+    let azimuth = azimuth.rem_euclid(360.0);
+    let mut before: Option<(f64, f64)> = None;
+    let mut after: Option<(f64, f64)> = None;
+    for &(az, elevation) in &profile.points {
+        // Offsets back to the previous point and on to the next, in (0, 360]
+        let back = (azimuth - az).rem_euclid(360.0);
+        let ahead = (az - azimuth).rem_euclid(360.0);
+        if back == 0.0 {
+            return elevation;
+        }
+        if before.is_none_or(|(d, _)| back < d) {
+            before = Some((back, elevation));
+        }
+        if after.is_none_or(|(d, _)| ahead < d) {
+            after = Some((ahead, elevation));
+        }
+    }
+    match (before, after) {
+        (Some((back, e0)), Some((ahead, e1))) => e0 + (e1 - e0) * back / (back + ahead),
+        _ => 0.0,
+    }
+}
+
+// True while the sun is below the skyline, night included. Uses geometric
+// altitude, like the rest of the position API.
+pub fn sun_behind_horizon(profile: &HorizonProfile, pos: &SolarPosition) -> bool {
+    pos.altitude <= horizon_elevation(profile, pos.azimuth)
+}
+
+// Daylight entries with the sun behind terrain; the grid matches every other
+// table generated from the same config. Only terrain above the flat horizon
+// counts, since the table's own sunrise and sunset already cover that.
+pub fn generate_horizon_table(
+    config: &LookupTableConfig,
+    profile: &HorizonProfile,
+) -> HorizonTable {
+    generate_table(
+        config,
+        |minutes, angles, is_daylight| {
+            let elevation = horizon_elevation(profile, angles.azimuth);
+            HorizonEntry {
+                minutes,
+                blocked: is_daylight && elevation > 0.0 && 90.0 - angles.zenith <= elevation,
+            }
+        },
+        1,
+    )
+}
+
+pub fn lookup_horizon(
+    table: &HorizonTable,
+    day_of_year: i32,
+    minutes: MinuteOfDay,
+) -> Option<HorizonEntry> {
+    lookup_nearest(table, day_of_year, minutes)
+}
+
+fn clear_blocked<E: HasMinutes>(
+    table: &mut LookupTable<E>,
+    profile: &HorizonProfile,
+    clear: impl Fn(&mut E),
+) {
+    let horizon = generate_horizon_table(&table.config, profile);
+    for (day, blocked_day) in table.days.iter_mut().zip(&horizon.days) {
+        for (entry, blocked) in day.entries.iter_mut().zip(&blocked_day.entries) {
+            if blocked.blocked && entry.minutes() == blocked.minutes {
+                clear(entry);
+            }
+        }
+    }
+}
+
+// Clears the rotation wherever the sun is behind terrain, so the tracker
+// parks as it does at night; `fill_single_axis_stow` then sets the parking
+// angle.
+pub fn park_single_axis_behind_horizon(table: &mut SingleAxisTable, profile: &HorizonProfile) {
+    clear_blocked(table, profile, |entry| {
+        entry.rotation = None;
+        entry.clamped = false;
+        entry.surface = None;
+    });
+}
+
+pub fn park_dual_axis_behind_horizon(table: &mut DualAxisTable, profile: &HorizonProfile) {
+    clear_blocked(table, profile, |entry| {
+        entry.tilt = None;
+        entry.panel_azimuth = None;
+        entry.adjusted = false;
+    });
+}
@@ -0,0 +1,60 @@
+//! Interpolation over time-indexed entries, shared by the table lookups and
+//! available for downstream data that follows the same layout.
+
+use crate::angles::{angle_difference, normalize_angle};
+
+/// Entries keyed by minute of day, evenly spaced within a day.
+pub trait HasMinutes {
+    fn minutes(&self) -> i32;
+    fn set_minutes(&mut self, minutes: i32);
+}
+
+/// Interpolates along the shorter arc, so 350° → 10° passes through 0°.
+/// The result is normalized to [0, 360); None if either side is None.
+pub fn interpolate_angle(a1: Option<f64>, a2: Option<f64>, fraction: f64) -> Option<f64> {
+    let (v1, v2) = (a1?, a2?);
+    Some(normalize_angle(v1 + angle_difference(v1, v2) * fraction))
+}
+
+/// Plain linear interpolation for values that do not wrap (rotation, tilt).
+pub fn interpolate_linear(v1: Option<f64>, v2: Option<f64>, fraction: f64) -> Option<f64> {
+    let a = v1?;
+    let b = v2?;
+    Some(a + fraction * (b - a))
+}
+
+/// Finds `(before, after, fraction)` around `minutes` by index arithmetic
+/// rather than search, so `entries` must be sorted and `interval_minutes`
+/// apart. `after` is None on an exact hit or at the last entry; the whole
+/// result is None outside the entry range, for a non-positive interval, or
+/// when the entries turn out not to follow that layout.
+pub fn find_bracketing_entries<E: HasMinutes>(
+    entries: &[E],
+    interval_minutes: i32,
+    minutes: i32,
+) -> Option<(&E, Option<&E>, f64)> {
+    let (first, last) = (entries.first()?, entries.last()?);
+    if interval_minutes <= 0 || minutes < first.minutes() || minutes > last.minutes() {
+        return None;
+    }
+
+    // Widened so a table spanning the whole i32 range cannot overflow
+    let offset = (minutes as i64 - first.minutes() as i64) / interval_minutes as i64;
+    let idx_before = offset.min(entries.len() as i64 - 1) as usize;
+    let entry_before = &entries[idx_before];
+    let entry_after = entries.get(idx_before + 1);
+    let t0 = entry_before.minutes();
+    if t0 > minutes {
+        return None;
+    }
+
+    let Some(after) = entry_after.filter(|_| minutes != t0) else {
+        return Some((entry_before, None, 0.0));
+    };
+    let t1 = after.minutes();
+    if t1 < minutes {
+        return None;
+    }
+    let fraction = (minutes as f64 - t0 as f64) / (t1 as f64 - t0 as f64);
+    Some((entry_before, Some(after), fraction))
+}
@@ -0,0 +1,171 @@
+use crate::types::{
+    ClearnessConfig, ClearnessSample, GhiDecomposition, IrradianceComponents, SolarPosition,
+    TrackingMode,
+};
+
+// W/m² at 1 AU (Kopp & Lean 2011).
+pub const SOLAR_CONSTANT: f64 = 1361.0;
+
+// Normal-incidence irradiance at the top of the atmosphere.
+pub fn extraterrestrial_irradiance(day_of_year: i32) -> f64 {
+    SOLAR_CONSTANT * crate::angles::eccentricity_correction(day_of_year)
+}
+
+// Instantaneous, on a horizontal plane; zero with the sun below the horizon.
+pub fn extraterrestrial_horizontal_irradiance(pos: &SolarPosition) -> f64 {
+    if pos.zenith >= 90.0 {
+        return 0.0;
+    }
+    extraterrestrial_irradiance(pos.day_of_year) * crate::angles::deg_to_rad(pos.zenith).cos()
+}
+
+// Daily horizontal total in Wh/m², integrating cos(zenith) from sunrise to
+// sunset (Duffie & Beckman 1.10.3). Polar day integrates the full circle and
+// polar night gives zero.
+pub fn daily_extraterrestrial_irradiation(latitude: f64, day_of_year: i32) -> f64 {
+    let (sin_lat, cos_lat) = crate::angles::deg_to_rad(latitude).sin_cos();
+    let decl = crate::angles::solar_declination(day_of_year);
+    let (sin_dec, cos_dec) = crate::angles::deg_to_rad(decl).sin_cos();
+    let cos_ws = (-(sin_lat * sin_dec) / (cos_lat * cos_dec)).clamp(-1.0, 1.0);
+    let ws = cos_ws.acos();
+    let daily = cos_lat * cos_dec * ws.sin() + ws * sin_lat * sin_dec;
+    24.0 / std::f64::consts::PI * extraterrestrial_irradiance(day_of_year) * daily
+}
+
+// Kasten & Young (1989); None with the sun below the horizon.
+pub fn relative_air_mass(zenith: f64) -> Option<f64> {
+    if zenith >= 90.0 {
+        return None;
+    }
+    let cos_z = crate::angles::deg_to_rad(zenith).cos();
+    Some(1.0 / (cos_z + 0.50572 * (96.07995 - zenith).powf(-1.6364)))
+}
+
+pub fn clear_sky_ghi(zenith: f64) -> f64 {
+    if zenith >= 90.0 {
+        return 0.0;
+    }
+    let cos_z = crate::angles::deg_to_rad(zenith).cos();
+    1098.0 * cos_z * (-0.057 / cos_z).exp()
+}
+
+// Clear-sky GHI split into beam and diffuse with Erbs; None with the sun at
+// or below the horizon.
+pub fn clear_sky_components(pos: &SolarPosition) -> Option<IrradianceComponents> {
+    let ghi = clear_sky_ghi(pos.zenith);
+    let split = decompose_ghi(ghi, pos)?;
+    Some(IrradianceComponents {
+        ghi,
+        dni: split.dni,
+        dhi: split.dhi,
+    })
+}
+
+pub fn clearness_index(measured_ghi: f64, clear_sky_ghi: f64) -> Option<f64> {
+    if clear_sky_ghi <= 0.0 {
+        return None;
+    }
+    Some((measured_ghi / clear_sky_ghi).clamp(0.0, 1.5))
+}
+
+// Erbs, Klein & Duffie (1982) diffuse fraction of GHI from the clearness
+// index kt = GHI / extraterrestrial horizontal irradiance.
+pub fn erbs_diffuse_fraction(kt: f64) -> f64 {
+    if kt <= 0.22 {
+        1.0 - 0.09 * kt
+    } else if kt <= 0.8 {
+        0.9511 - 0.1604 * kt + 4.388 * kt.powi(2) - 16.638 * kt.powi(3) + 12.336 * kt.powi(4)
+    } else {
+        0.165
+    }
+}
+
+// cos(zenith) floor when converting beam-horizontal to DNI; without it a
+// few W/m² of beam near sunrise turns into an absurd DNI. Same cutoff as
+// pvlib (about 86.3°).
+const MIN_COS_ZENITH: f64 = 0.065;
+
+// Split measured GHI into DNI and DHI. kt is clamped to [0, 1]; None with
+// the sun at or below the horizon.
+pub fn decompose_ghi(measured_ghi: f64, pos: &SolarPosition) -> Option<GhiDecomposition> {
+    let g0h = extraterrestrial_horizontal_irradiance(pos);
+    if g0h <= 0.0 {
+        return None;
+    }
+    let ghi = measured_ghi.max(0.0);
+    let kt = (ghi / g0h).clamp(0.0, 1.0);
+    let diffuse_fraction = erbs_diffuse_fraction(kt);
+    let dhi = diffuse_fraction * ghi;
+    let cos_z = crate::angles::deg_to_rad(pos.zenith)
+        .cos()
+        .max(MIN_COS_ZENITH);
+    Some(GhiDecomposition {
+        clearness_index: kt,
+        diffuse_fraction,
+        dni: (ghi - dhi) / cos_z,
+        dhi,
+    })
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ClearnessMonitor {
+    config: ClearnessConfig,
+    smoothed: Option<f64>,
+    mode: TrackingMode,
+}
+
+impl ClearnessMonitor {
+    pub fn new(config: ClearnessConfig) -> Self {
+        Self {
+            config,
+            smoothed: None,
+            mode: TrackingMode::SunPointing,
+        }
+    }
+
+    pub fn mode(&self) -> TrackingMode {
+        self.mode
+    }
+
+    pub fn update(&mut self, measured_ghi: f64, pos: &SolarPosition) -> ClearnessSample {
+        let cs = clear_sky_ghi(pos.zenith);
+        let kc = if cs < self.config.min_clear_sky_ghi {
+            None
+        } else {
+            clearness_index(measured_ghi, cs)
+        };
+
+        // Hold the previous mode and smoothed value while the sun is too low
+        // for the ratio to mean anything.
+        if let Some(kc) = kc {
+            let alpha = self.config.smoothing_alpha;
+            let smoothed = match self.smoothed {
+                Some(prev) => prev + alpha * (kc - prev),
+                None => kc,
+            };
+            self.smoothed = Some(smoothed);
+            self.mode = match self.mode {
+                TrackingMode::SunPointing if smoothed < self.config.diffuse_threshold => {
+                    TrackingMode::DiffuseOptimal
+                }
+                TrackingMode::DiffuseOptimal if smoothed > self.config.sun_pointing_threshold => {
+                    TrackingMode::SunPointing
+                }
+                mode => mode,
+            };
+        }
+
+        ClearnessSample {
+            clear_sky_ghi: cs,
+            clearness_index: kc,
+            smoothed_index: self.smoothed,
+            mode: self.mode,
+        }
+    }
+}
+
+impl Default for ClearnessMonitor {
+    fn default() -> Self {
+        Self::new(ClearnessConfig::default())
+    }
+}
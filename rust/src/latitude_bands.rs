@@ -0,0 +1,143 @@
+use std::fmt;
+
+use crate::lookup_table::{
+    blend_dual_axis, blend_single_axis, lookup_dual_axis, lookup_single_axis,
+};
+use crate::types::{
+    DualAxisEntry, LatitudeBandLibrary, LookupTable, LookupTableConfig, MinuteOfDay,
+    SingleAxisEntry,
+};
+
+// A band range the library cannot be built from: a step that is not finite
+// and above zero would never reach the last band, and the range must run
+// upward between finite latitudes.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum LatitudeBandError {
+    InvalidStep(f64),
+    InvalidRange { min_latitude: f64, max_latitude: f64 },
+}
+
+impl fmt::Display for LatitudeBandError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LatitudeBandError::InvalidStep(step) => {
+                write!(f, "band step {step} must be finite and positive")
+            }
+            LatitudeBandError::InvalidRange { min_latitude, max_latitude } => write!(
+                f,
+                "latitude range {min_latitude}..{max_latitude} must be finite and not inverted"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for LatitudeBandError {}
+
+// Band tables are generated at longitude 0, so a site's UTC minute maps onto
+// the band's key by the longitude time offset alone: the equation of time is
+// identical for every longitude on a given day and is already baked into each
+// band. Residual error comes from blending neighbouring bands linearly in
+// latitude (well under 0.5° of rotation at 1° spacing away from the poles)
+// and from declination drifting during the longitude shift (< 0.2°).
+pub fn generate_latitude_band_library<E, F>(
+    base: &LookupTableConfig,
+    min_latitude: f64,
+    max_latitude: f64,
+    band_step: f64,
+    generate: F,
+) -> Result<LatitudeBandLibrary<E>, LatitudeBandError>
+where
+    F: Fn(&LookupTableConfig) -> LookupTable<E>,
+{
+    if !(band_step.is_finite() && band_step > 0.0) {
+        return Err(LatitudeBandError::InvalidStep(band_step));
+    }
+    if !(min_latitude.is_finite() && max_latitude.is_finite() && min_latitude <= max_latitude) {
+        return Err(LatitudeBandError::InvalidRange { min_latitude, max_latitude });
+    }
+    let n_bands = ((max_latitude - min_latitude) / band_step).round() as usize + 1;
+    let tables = (0..n_bands)
+        .map(|i| {
+            let config = LookupTableConfig {
+                latitude: min_latitude + i as f64 * band_step,
+                longitude: 0.0,
+                ..*base
+            };
+            generate(&config)
+        })
+        .collect();
+    Ok(LatitudeBandLibrary {
+        min_latitude,
+        band_step,
+        tables,
+    })
+}
+
+fn band_position<E>(
+    library: &LatitudeBandLibrary<E>,
+    latitude: f64,
+) -> Option<(usize, usize, f64)> {
+    if library.tables.is_empty() {
+        return None;
+    }
+    let pos = (latitude - library.min_latitude) / library.band_step;
+    let last = library.tables.len() - 1;
+    if pos < -0.5 || pos > last as f64 + 0.5 {
+        return None;
+    }
+    let pos = pos.clamp(0.0, last as f64);
+    let lower = pos.floor() as usize;
+    let upper = (lower + 1).min(last);
+    Some((lower, upper, pos - lower as f64))
+}
+
+pub fn band_key(
+    longitude: f64,
+    day_of_year: i32,
+    minutes: MinuteOfDay,
+    n_days: i32,
+) -> (i32, MinuteOfDay) {
+    let shifted = minutes.get() + (4.0 * longitude).round() as i32;
+    let carry = shifted.div_euclid(1440);
+    let doy = (day_of_year + carry).clamp(1, n_days);
+    (doy, MinuteOfDay::wrapping(shifted))
+}
+
+pub fn lookup_single_axis_band(
+    library: &LatitudeBandLibrary<SingleAxisEntry>,
+    latitude: f64,
+    longitude: f64,
+    day_of_year: i32,
+    minutes: MinuteOfDay,
+) -> Option<SingleAxisEntry> {
+    let (lower, upper, fraction) = band_position(library, latitude)?;
+    let n_days = library.tables[lower].days.len() as i32;
+    let (doy, key) = band_key(longitude, day_of_year, minutes, n_days);
+    let minutes = minutes.get();
+    let a = lookup_single_axis(&library.tables[lower], doy, key)?;
+    if lower == upper || fraction == 0.0 {
+        return Some(SingleAxisEntry { minutes, ..a });
+    }
+    let b = lookup_single_axis(&library.tables[upper], doy, key)?;
+    Some(blend_single_axis(&a, &b, fraction, minutes))
+}
+
+pub fn lookup_dual_axis_band(
+    library: &LatitudeBandLibrary<DualAxisEntry>,
+    latitude: f64,
+    longitude: f64,
+    day_of_year: i32,
+    minutes: MinuteOfDay,
+) -> Option<DualAxisEntry> {
+    let (lower, upper, fraction) = band_position(library, latitude)?;
+    let n_days = library.tables[lower].days.len() as i32;
+    let (doy, key) = band_key(longitude, day_of_year, minutes, n_days);
+    let minutes = minutes.get();
+    let a = lookup_dual_axis(&library.tables[lower], doy, key)?;
+    if lower == upper || fraction == 0.0 {
+        return Some(DualAxisEntry { minutes, ..a });
+    }
+    let b = lookup_dual_axis(&library.tables[upper], doy, key)?;
+    let encoding = library.tables[lower].config.azimuth_encoding;
+    Some(blend_dual_axis(&a, &b, fraction, minutes, encoding))
+}
@@ -1,23 +1,271 @@
+// Library code never prints; output belongs to the examples and callers.
+#![deny(clippy::print_stdout, clippy::print_stderr)]
+
 pub mod angles;
+#[cfg(feature = "simd")]
+pub mod bulk;
+pub mod calibration;
+pub mod chebyshev;
+pub mod commissioning;
+pub mod comparison;
+pub mod config;
+pub mod controller;
+pub mod daily_fit;
+pub mod datetime_input;
+pub mod daylight;
+pub mod diagnostics;
+pub mod difference;
+pub mod dual_axis;
+pub mod ephemeris;
+pub mod event_log;
+pub mod explain;
+pub mod exposure;
+pub mod fixed_mount;
+pub mod fleet;
+pub mod gimbal;
+pub mod golden;
+pub mod horizon;
+pub mod interp;
+pub mod irradiance;
+pub mod latitude_bands;
 pub mod lookup_table;
+pub mod magnetic;
+mod math;
+pub mod noon_relative;
+pub mod orientation;
+pub mod planner;
+pub mod portable_math;
+#[cfg(feature = "heapless")]
+pub mod program;
+pub mod provenance;
+pub mod refraction;
+pub mod report;
+pub mod row_shading;
+pub mod scenario;
+pub mod shading;
+pub mod simplify;
+pub mod simulation;
+pub mod stow;
+#[cfg(feature = "proptest")]
+pub mod strategies;
+pub mod sun_path;
+pub mod survey;
+pub mod sweep;
+pub mod transposition;
 pub mod types;
+#[cfg(feature = "weather")]
+pub mod weather;
+pub mod worker;
 
 pub use angles::{
-    day_of_year, days_in_months, deg_to_rad, dual_axis_angles, equation_of_time, hour_angle,
-    intermediate_angle_b, leap_year, normalize_angle, optimal_fixed_tilt, rad_to_deg,
-    seasonal_tilt_adjustment, single_axis_tilt, solar_altitude, solar_angles_at, solar_azimuth,
-    solar_declination, solar_position, solar_zenith_angle, utc_lst_correction, DEGREES_PER_HOUR,
-    EARTH_AXIAL_TILT,
+    analemma, angle_between, angle_difference, backtrack, backtracking_rotation, circular_mean,
+    circular_mean_weighted, clamp_rotation, cross_axis_slope, datetime_from_julian_day,
+    day_of_year, day_of_year_checked, day_of_year_from_date, days_in_months, days_to_ymd,
+    declination, declination_in_year, deg_to_rad, dual_axis_angles, dual_axis_angles_clamped,
+    earth_sun_distance, eccentricity_correction, equation_of_time, equation_of_time_in_year,
+    equation_of_time_with_model, ew_axis_tilt, fixed_tilt_on_slope, fractional_year, hour_angle,
+    incidence_angle, intermediate_angle_b, julian_century, julian_day, julian_day_from_century,
+    julian_day_from_date, leap_year, mount_angles, normalize_angle, optimal_fixed_tilt,
+    panel_edge_shadow, panel_normal, polar_axis_incidence, polar_axis_rotation, rad_to_deg,
+    seasonal_tilt_adjustment, seasonal_tilt_for_day, shadow_of_pole, single_axis_tilt,
+    single_axis_tilt_clamped, slope_aware_backtrack, solar_altitude, solar_angles_at,
+    solar_azimuth, solar_declination, solar_position, solar_position_checked, solar_position_debug,
+    solar_position_series, solar_position_series_until, solar_position_unix,
+    solar_position_with_model, solar_position_with_models, solar_position_with_position_model,
+    solar_positions, solar_zenith_angle, spencer_declination, sun_vector, sun_vector_in,
+    surface_normal, surface_orientation, terrain_axis_tilt, tilted_axis_rotation,
+    utc_lst_correction, vector_dot, PositionError, PositionTracker, DAYS_PER_JULIAN_CENTURY,
+    DEGREES_PER_HOUR, EARTH_AXIAL_TILT, JULIAN_DAY_J2000, JULIAN_DAY_UNIX_EPOCH, VALID_YEARS,
+};
+
+#[cfg(feature = "simd")]
+pub use bulk::{bulk_day_angles, bulk_zenith_azimuth};
+
+pub use calibration::{
+    calibrate_rotation, calibrate_single_axis_entry, lookup_single_axis_calibrated,
+    uncalibrate_rotation,
+};
+
+pub use chebyshev::{
+    chebyshev_storage_bytes, compress_chebyshev, decode_chebyshev, encode_chebyshev,
+    lookup_chebyshev, ChebyshevDecodeError, ChebyshevError, MAX_CHEBYSHEV_COEFFICIENTS,
+};
+
+pub use commissioning::{commissioning_sequence, verify_commissioning};
+
+pub use comparison::{tracker_gain_report, tracker_gain_report_with};
+
+pub use config::{validate_config, ConfigError};
+#[cfg(feature = "json")]
+pub use config::{config_from_json, ConfigLoadError};
+
+pub use controller::{
+    can_transition, fallback_action, fallback_rotation, mode_rotation, Controller, TransitionError,
+};
+
+pub use daily_fit::{
+    evaluate_fit, fit_daily_curves, fit_error, fit_rotation_table, fit_storage_bytes,
+    DAILY_FIT_BYTES_PER_DAY, DAILY_FIT_SCALE,
+};
+
+pub use datetime_input::{parse_datetime, DateTimeInputError};
+
+pub use daylight::{
+    annual_daylight_metrics, dates_between, dates_with_day_length_over, day_length, day_lengths,
+    daylight_metrics, first_date_after, first_sunrise_before, monthly_daylight_metrics,
+    sunrise_sunset_azimuth,
+};
+
+pub use diagnostics::diagnostic_bundle;
+#[cfg(feature = "json")]
+pub use diagnostics::diagnostic_bundle_json;
+
+pub use difference::{
+    backtracking_difference_table, deviation_windows, difference_table, summarize_difference,
+};
+
+pub use dual_axis::{
+    condition_dual_axis_step, condition_dual_axis_step_with_noon, condition_dual_axis_table,
+    max_azimuth_rate, noon_panel_azimuth, overhead_sun_days,
+};
+
+pub use ephemeris::{
+    delta_t_estimate, delta_t_for, equation_of_time_ecliptic, equation_of_time_for_day,
+    julian_ephemeris_day, solar_position_precise,
+};
+
+pub use event_log::{EventLog, EVENT_BYTES};
+pub use explain::{explain_single_axis, explain_solar_position, format_explanation};
+
+pub use exposure::{facade_exposure, next_sun_on, sun_on, sun_on_at, sun_on_for};
+
+pub use fixed_mount::{
+    clear_sky_poa, fixed_window, generate_fixed_table, inverter_events, inverter_window,
+    lookup_fixed,
+};
+
+pub use fleet::{apply_calibration, generate_fleet_tables, tracker_config};
+
+pub use gimbal::{alternate_solution, decompose_gimbal, gimbal_pointing, plan_gimbal_move};
+
+pub use golden::{compare_golden, golden_dataset, GOLDEN_DECIMALS};
+#[cfg(feature = "json")]
+pub use golden::{golden_dataset_from_json, golden_dataset_json};
+
+pub use horizon::{
+    generate_horizon_table, horizon_elevation, lookup_horizon, park_dual_axis_behind_horizon,
+    park_single_axis_behind_horizon, sun_behind_horizon,
+};
+
+pub use interp::{find_bracketing_entries, interpolate_angle, interpolate_linear, HasMinutes};
+
+pub use irradiance::{
+    clear_sky_components, clear_sky_ghi, clearness_index, daily_extraterrestrial_irradiation,
+    decompose_ghi, erbs_diffuse_fraction, extraterrestrial_horizontal_irradiance,
+    extraterrestrial_irradiance, relative_air_mass, ClearnessMonitor, SOLAR_CONSTANT,
+};
+
+pub use latitude_bands::{
+    band_key, generate_latitude_band_library, lookup_dual_axis_band, lookup_single_axis_band,
+    LatitudeBandError,
 };
 
 pub use lookup_table::{
-    doy_to_month_day, dual_axis_table_to_compact, estimate_sunrise_sunset,
-    generate_dual_axis_table, generate_single_axis_table, interpolate_angle, intervals_per_day,
-    lookup_dual_axis, lookup_single_axis, minutes_to_time, single_axis_table_to_compact,
-    time_to_minutes,
+    clock_at_table_keys, date_from_day_of_year, doy_to_month_day, dual_axis_table_to_compact,
+    estimate_sunrise_sunset, generate_dual_axis_table, generate_ew_axis_table,
+    generate_mount_table, generate_polar_axis_table, generate_single_axis_table, hour_angle_keys,
+    intervals_per_day, lookup_dual_axis, lookup_dual_axis_at_clock, lookup_mount, lookup_nearest,
+    lookup_single_axis, lookup_single_axis_at_clock, minutes_to_time, precise_sunrise_sunset,
+    shift_dual_axis_table, shift_single_axis_table, single_axis_table_to_compact, solar_noon,
+    step_table, sunrise_sunset, sunrise_sunset_with_model, table_keys_at_clock, table_to_compact,
+    time_to_minutes, SUNRISE_ALTITUDE,
+};
+
+pub use magnetic::{magnetic_bearing, true_bearing};
+
+pub use noon_relative::{
+    generate_noon_relative_table, lookup_noon_relative, lookup_noon_relative_at_utc,
+};
+
+pub use orientation::{
+    optimize_fixed_orientation, optimize_fixed_orientation_with, seasonal_tilt_schedule,
+    seasonal_tilt_schedule_with,
+};
+
+pub use planner::{cosine_loss, monthly_storage_budget, recommend_table_layout, sweep_table_layouts};
+
+#[cfg(feature = "heapless")]
+pub use program::{
+    daily_program, inverter_program, shade_program, DailyProgram, FixedSchedule, InverterProgram,
+    ScheduleFull, ShadeProgram,
+};
+
+pub use provenance::{project_fields, stamp, Stamped};
+
+pub use refraction::{
+    apparent_altitude, atmospheric_refraction, solar_position_refracted, sunrise_altitude,
+};
+pub use report::{
+    commissioning_csv, format_number, format_time, monthly_budget_csv, schedule_csv, sweep_csv,
 };
 
+pub use row_shading::{row_shaded_fraction, shaded_fraction};
+
+pub use scenario::{generate_scenario, scenario_irradiance, NoisySensor};
+
+pub use shading::{generate_shade_table, lookup_shade, screen_closed, shade_events, shade_schedule};
+
+pub use simplify::{lookup_simplified, simplify_table, simplify_to_budget};
+
+pub use simulation::{
+    diffuse_optimal_rotation, diffuse_optimal_surface, remaining_clearsky_energy, simulate_yield,
+    simulate_yield_with, strategy_surface,
+};
+
+pub use stow::{
+    controller_stow_mode, dual_axis_stow_angles, fill_dual_axis_stow, fill_single_axis_stow,
+    plan_stow, plan_stow_from, single_axis_stow_rotation, stow_active, stow_angles,
+    stow_angles_with, WeatherProvider,
+};
+
+pub use sun_path::{key_sun_path_days, monthly_sun_path_days, sun_path, sun_path_diagram};
+
+pub use survey::{parse_survey_csv, survey_row_config, SurveyError};
+
+pub use sweep::{yield_sweep, yield_sweep_with};
+
+pub use transposition::poa_irradiance;
+
 pub use types::{
-    DayData, DualAxisAngles, DualAxisEntry, DualAxisTable, LookupTable, LookupTableConfig, Season,
-    SingleAxisEntry, SingleAxisTable, SolarPosition, SunriseSunset, TableMetadata,
+    AdjustmentFrequency, AngleReading, Atmosphere, AzimuthEncoding, AzimuthFlipConfig,
+    CalibrationOffsets, ChebyshevDay, ChebyshevTable, ClearnessConfig, ClearnessSample, ClockFormat,
+    CommissioningIssue, CommissioningReport, CommissioningStep, CommissioningTarget,
+    ControllerInputs, ControllerMode, DailyFit, DailyFitTable, DayData, DaylightMetrics,
+    DeclinationModel, DiagnosticBundle, DifferenceEntry, DifferenceSummary, DifferenceTable,
+    DualAxisAngles, DualAxisEntry, DualAxisMount, DualAxisTable, EquationOfTimeModel, Event,
+    EventKind, ExplainStep, FallbackAction, FallbackPolicy, FixedEntry, FixedMountConfig,
+    FixedOrientation, FixedTable, FleetTable, GhiDecomposition, GimbalAvoidance, GimbalAxes,
+    GimbalCommand, GimbalConfig, GimbalSolution, GoldenCase, GoldenChangeReport, GoldenConfig,
+    GoldenDataset, GoldenOutputChange, GoldenTableRow, HorizonEntry, HorizonProfile, HorizonTable,
+    InverterEvent, IrradianceComponents, LatitudeBandLibrary, LinkStatus, LookupTable,
+    LookupTableConfig, MinuteOfDay, MinuteOutOfRange, MonthBudget, MonthStatistics, MountAngles,
+    MountEntry, MountTable, NoonRelativeDay, NoonRelativeTable, OrientationSearch, PlannerConfig,
+    PoaIrradiance, PositionModel, ProgramStep, ProjectMetadata, ReportLocale, Scenario,
+    ScenarioConfig, Season, ShadeConfig, ShadeEntry, ShadeEvent, ShadeTable, SimplifiedTable,
+    SimplifyReport, SimulationConfig, SingleAxisEntry, SingleAxisTable, SkyDiffuseModel,
+    SolarPosition, SolarPositionDebug, StepEntry, StepTable, StorageFormat, StowAngles, StowConfig,
+    StowMode, StowPlanConfig, StowReason, StowWindow, SunExposure, SunPathCurve, SunPathPoint,
+    SunriseSunset, SurfaceOrientation, SurveyRow, SweepRow, SweepStrategy, TableKey, TableLayout,
+    TableMetadata, TerrainSlope, TiltAdjustment, TimeBase, TrackerGainReport, TrackerKind,
+    TrackerOverride, TrackingMode, TrackingStrategy, VectorFrame, WeatherFile, WeatherForecast,
+    WeatherRecord, YieldReport, YieldSweep,
 };
+
+pub use worker::{
+    generate_dual_axis_table_checked, generate_single_axis_table_checked,
+    lookup_dual_axis_at_epoch_millis, lookup_single_axis_at_epoch_millis, position_at_epoch_millis,
+};
+
+#[cfg(feature = "weather")]
+pub use weather::{parse_epw, parse_tmy3, weather_irradiance, weather_record, WeatherFileError};
+
@@ -1,34 +1,48 @@
-use chrono::{Datelike, Utc};
+#[cfg(feature = "tz")]
+use chrono::TimeZone;
+use chrono::{DateTime, Datelike, Duration, NaiveDate, NaiveDateTime, NaiveTime, Utc};
 
 use crate::angles;
+use crate::interp::{find_bracketing_entries, interpolate_angle, interpolate_linear, HasMinutes};
+use crate::magnetic;
+use crate::math;
 use crate::types::{
-    DayData, DualAxisEntry, DualAxisTable, LookupTable, LookupTableConfig, SingleAxisEntry,
-    SingleAxisTable, SunriseSunset, TableMetadata,
+    AzimuthEncoding, DayData, DeclinationModel, DualAxisEntry, DualAxisMount, DualAxisTable,
+    LookupTable, LookupTableConfig, MinuteOfDay, MonthStatistics, MountEntry, MountTable,
+    PositionModel, SingleAxisEntry, SingleAxisTable, StepEntry, StepTable, SunriseSunset,
+    SurfaceOrientation, TableKey, TableMetadata, TimeBase,
 };
 
-pub fn minutes_to_time(total_minutes: i32) -> (i32, i32) {
+pub const fn minutes_to_time(total_minutes: i32) -> (i32, i32) {
     (total_minutes / 60, total_minutes % 60)
 }
 
-pub fn time_to_minutes(time: (i32, i32)) -> i32 {
+pub const fn time_to_minutes(time: (i32, i32)) -> i32 {
     time.0 * 60 + time.1
 }
 
-pub fn intervals_per_day(interval_minutes: i32) -> i32 {
+pub const fn intervals_per_day(interval_minutes: i32) -> i32 {
     1440 / interval_minutes
 }
 
+// None outside 1..=365 (366 in leap years).
+pub fn date_from_day_of_year(year: i32, doy: i32) -> Option<NaiveDate> {
+    u32::try_from(doy).ok().and_then(|doy| NaiveDate::from_yo_opt(year, doy))
+}
+
 pub fn doy_to_month_day(year: i32, doy: i32) -> (u32, u32) {
-    let date = chrono::NaiveDate::from_yo_opt(year, doy as u32)
-        .expect("invalid year/day-of-year");
+    let date = date_from_day_of_year(year, doy).expect("invalid year/day-of-year");
     (date.month(), date.day())
 }
 
 pub fn estimate_sunrise_sunset(latitude: f64, day_of_year: i32) -> SunriseSunset {
+    estimate_sunrise_sunset_at(latitude, angles::solar_declination(day_of_year))
+}
+
+fn estimate_sunrise_sunset_at(latitude: f64, decl: f64) -> SunriseSunset {
     let lat_rad = angles::deg_to_rad(latitude);
-    let decl = angles::solar_declination(day_of_year);
     let decl_rad = angles::deg_to_rad(decl);
-    let cos_h = -lat_rad.tan() * decl_rad.tan();
+    let cos_h = -math::tan(lat_rad) * math::tan(decl_rad);
 
     if cos_h >= 1.0 {
         SunriseSunset {
@@ -41,7 +55,7 @@ pub fn estimate_sunrise_sunset(latitude: f64, day_of_year: i32) -> SunriseSunset
             sunset: 1440,
         }
     } else {
-        let h_deg = angles::rad_to_deg(cos_h.acos());
+        let h_deg = angles::rad_to_deg(math::acos(cos_h));
         let half_day_minutes = (h_deg / 15.0) * 60.0;
         let solar_noon_minutes = 720;
         SunriseSunset {
@@ -51,74 +65,170 @@ pub fn estimate_sunrise_sunset(latitude: f64, day_of_year: i32) -> SunriseSunset
     }
 }
 
-pub fn interpolate_angle(a1: Option<f64>, a2: Option<f64>, fraction: f64) -> Option<f64> {
-    let (v1, v2) = (a1?, a2?);
-    let diff = v2 - v1;
-    let adjusted_diff = if diff > 180.0 {
-        diff - 360.0
-    } else if diff < -180.0 {
-        diff + 360.0
+// Geometric centre at -0.833°: 34' of refraction at the horizon plus the
+// 16' solar radius, the convention almanacs use for upper-limb sunrise.
+pub const SUNRISE_ALTITUDE: f64 = -0.833;
+
+// Cosine of the sunrise hour angle; above 1 the sun stays down all day,
+// below -1 it stays up.
+pub(crate) fn cos_sunrise_hour_angle(latitude: f64, day_of_year: i32) -> f64 {
+    cos_sunrise_hour_angle_at(latitude, angles::solar_declination(day_of_year))
+}
+
+fn cos_sunrise_hour_angle_at(latitude: f64, decl: f64) -> f64 {
+    let (sin_lat, cos_lat) = math::sin_cos(angles::deg_to_rad(latitude));
+    let (sin_dec, cos_dec) = math::sin_cos(angles::deg_to_rad(decl));
+    let sin_h0 = math::sin(angles::deg_to_rad(SUNRISE_ALTITUDE));
+    (sin_h0 - sin_lat * sin_dec) / (cos_lat * cos_dec)
+}
+
+fn half_day_minutes_at(latitude: f64, decl: f64) -> Option<f64> {
+    let cos_h = cos_sunrise_hour_angle_at(latitude, decl);
+    (-1.0..=1.0)
+        .contains(&cos_h)
+        .then(|| angles::rad_to_deg(math::acos(cos_h)) * 4.0)
+}
+
+// To the geometric horizon, without refraction or the solar disk.
+fn geometric_half_day_minutes_at(latitude: f64, decl: f64) -> Option<f64> {
+    let cos_h = -math::tan(angles::deg_to_rad(latitude)) * math::tan(angles::deg_to_rad(decl));
+    (-1.0..=1.0)
+        .contains(&cos_h)
+        .then(|| angles::rad_to_deg(math::acos(cos_h)) * 4.0)
+}
+
+// Local solar time counterpart of `estimate_sunrise_sunset` that includes
+// refraction and the solar disk.
+pub fn precise_sunrise_sunset(latitude: f64, day_of_year: i32) -> SunriseSunset {
+    precise_sunrise_sunset_at(latitude, angles::solar_declination(day_of_year))
+}
+
+// Polar day and night follow the refracted horizon too: just inside the
+// polar circles the geometric horizon still has a crossing that refraction
+// removes.
+fn precise_sunrise_sunset_at(latitude: f64, decl: f64) -> SunriseSunset {
+    let cos_h = cos_sunrise_hour_angle_at(latitude, decl);
+    if cos_h > 1.0 {
+        SunriseSunset {
+            sunrise: 720,
+            sunset: 720,
+        }
+    } else if cos_h < -1.0 {
+        SunriseSunset {
+            sunrise: 0,
+            sunset: 1440,
+        }
+    } else {
+        let half_day = angles::rad_to_deg(math::acos(cos_h)) * 4.0;
+        SunriseSunset {
+            sunrise: (720.0 - half_day).round() as i32,
+            sunset: (720.0 + half_day).round() as i32,
+        }
+    }
+}
+
+// The sunrise and sunset a table generated from `config` uses, with the
+// config's declination model.
+pub(crate) fn table_sunrise_sunset(config: &LookupTableConfig, day_of_year: i32) -> SunriseSunset {
+    let decl = angles::declination_in_year(day_of_year, config.year, config.declination_model);
+    if config.precise_sunrise_sunset {
+        precise_sunrise_sunset_at(config.latitude, decl)
     } else {
-        diff
+        estimate_sunrise_sunset_at(config.latitude, decl)
+    }
+}
+
+// `sunrise_sunset` with the declination, equation of time and horizon of
+// `model`, matching what a table generated with the same model uses.
+pub fn sunrise_sunset_with_model(
+    latitude: f64,
+    longitude: f64,
+    date: NaiveDate,
+    model: PositionModel,
+) -> Option<(DateTime<Utc>, DateTime<Utc>)> {
+    let n = angles::day_of_year_from_date(date);
+    let decl = angles::declination_in_year(n, date.year(), model.declination_model());
+    let half_day = if model.refracted_horizon() {
+        half_day_minutes_at(latitude, decl)?
+    } else {
+        geometric_half_day_minutes_at(latitude, decl)?
     };
-    Some((v1 + adjusted_diff * fraction).rem_euclid(360.0))
+    let eot = angles::equation_of_time_with_model(n, date.year(), model.equation_of_time_model());
+    let noon = solar_noon_at(longitude, date, eot);
+    let half_day = Duration::milliseconds((half_day * 60_000.0).round() as i64);
+    Some((noon - half_day, noon + half_day))
 }
 
-fn interpolate_linear(v1: Option<f64>, v2: Option<f64>, fraction: f64) -> Option<f64> {
-    let a = v1?;
-    let b = v2?;
-    Some(a + fraction * (b - a))
+// None during polar day or night.
+pub fn sunrise_sunset(
+    latitude: f64,
+    longitude: f64,
+    date: NaiveDate,
+) -> Option<(DateTime<Utc>, DateTime<Utc>)> {
+    let n = angles::day_of_year_from_date(date);
+    let decl = angles::declination_in_year(n, date.year(), DeclinationModel::Cooper);
+    let half_day = half_day_minutes_at(latitude, decl)?;
+    let noon = solar_noon(longitude, date);
+    let half_day = Duration::milliseconds((half_day * 60_000.0).round() as i64);
+    Some((noon - half_day, noon + half_day))
 }
 
-trait HasMinutes {
-    fn minutes(&self) -> i32;
+pub fn solar_noon(longitude: f64, date: NaiveDate) -> DateTime<Utc> {
+    let eot = angles::equation_of_time_in_year(angles::day_of_year_from_date(date), date.year());
+    solar_noon_at(longitude, date, eot)
+}
+
+fn solar_noon_at(longitude: f64, date: NaiveDate, eot: f64) -> DateTime<Utc> {
+    let noon = 720.0 - 4.0 * longitude - eot;
+    let midnight = date.and_time(NaiveTime::MIN).and_utc();
+    midnight + Duration::milliseconds((noon * 60_000.0).round() as i64)
 }
 
 impl HasMinutes for SingleAxisEntry {
     fn minutes(&self) -> i32 {
         self.minutes
     }
+
+    fn set_minutes(&mut self, minutes: i32) {
+        self.minutes = minutes;
+    }
 }
 
-impl HasMinutes for DualAxisEntry {
+impl HasMinutes for MountEntry {
     fn minutes(&self) -> i32 {
         self.minutes
     }
-}
 
-fn find_bracketing_entries<E: HasMinutes>(
-    entries: &[E],
-    interval_minutes: i32,
-    minutes: i32,
-) -> Option<(&E, Option<&E>, f64)> {
-    if entries.is_empty() {
-        return None;
+    fn set_minutes(&mut self, minutes: i32) {
+        self.minutes = minutes;
     }
-    let first_minutes = entries[0].minutes();
-    let last_minutes = entries.last().unwrap().minutes();
-    if minutes < first_minutes || minutes > last_minutes {
-        return None;
+}
+
+impl<T> HasMinutes for StepEntry<T> {
+    fn minutes(&self) -> i32 {
+        self.minutes
     }
 
-    let idx_before = ((minutes - first_minutes) / interval_minutes).min(entries.len() as i32 - 1) as usize;
-    let entry_before = &entries[idx_before];
-    let entry_after = entries.get(idx_before + 1);
-    let t0 = entry_before.minutes();
+    fn set_minutes(&mut self, minutes: i32) {
+        self.minutes = minutes;
+    }
+}
 
-    if entry_after.is_none() || minutes == t0 {
-        return Some((entry_before, None, 0.0));
+impl HasMinutes for DualAxisEntry {
+    fn minutes(&self) -> i32 {
+        self.minutes
     }
 
-    let t1 = entry_after.unwrap().minutes();
-    let fraction = (minutes - t0) as f64 / (t1 - t0) as f64;
-    Some((entry_before, entry_after, fraction))
+    fn set_minutes(&mut self, minutes: i32) {
+        self.minutes = minutes;
+    }
 }
 
 /// Lightweight solar angles for table generation hot path.
-struct FastAngles {
-    hour_angle: f64,
-    zenith: f64,
-    azimuth: f64,
+pub(crate) struct FastAngles {
+    pub(crate) hour_angle: f64,
+    pub(crate) zenith: f64,
+    pub(crate) azimuth: f64,
 }
 
 fn compute_angles_fast(
@@ -132,13 +242,13 @@ fn compute_angles_fast(
     let lst = (utc_hours + correction).rem_euclid(24.0);
     let ha = angles::DEGREES_PER_HOUR * (lst - 12.0);
     let ha_rad = angles::deg_to_rad(ha);
-    let cos_ha = ha_rad.cos();
-    let sin_ha = ha_rad.sin();
+    let cos_ha = math::cos(ha_rad);
+    let sin_ha = math::sin(ha_rad);
     let cos_z = sin_lat * sin_dec + cos_lat * cos_dec * cos_ha;
-    let zenith = angles::rad_to_deg(cos_z.clamp(-1.0, 1.0).acos());
+    let zenith = angles::rad_to_deg(math::acos(cos_z.clamp(-1.0, 1.0)));
     let sin_az = -cos_dec * sin_ha;
     let cos_az = sin_dec * cos_lat - cos_dec * sin_lat * cos_ha;
-    let azimuth = angles::normalize_angle(angles::rad_to_deg(sin_az.atan2(cos_az)));
+    let azimuth = angles::normalize_angle(angles::rad_to_deg(math::atan2(sin_az, cos_az)));
     FastAngles {
         hour_angle: ha,
         zenith,
@@ -146,68 +256,136 @@ fn compute_angles_fast(
     }
 }
 
-fn generate_table<E, F>(config: &LookupTableConfig, entry_fn: F, bytes_per_entry: usize) -> LookupTable<E>
+// Hours from a table key to local apparent solar time on a day with
+// equation of time `eot`: the longitude and EoT correction for UTC minute
+// keys, nothing for hour-angle keys, which already count solar time.
+pub(crate) fn key_correction(config: &LookupTableConfig, eot: f64) -> f64 {
+    match config.table_key {
+        TableKey::UtcMinutes => angles::utc_lst_correction(config.longitude, eot),
+        TableKey::HourAngle => 0.0,
+    }
+}
+
+// The angles `generate_table` evaluates at a key, for adjusting a looked-up
+// entry without regenerating its day.
+pub(crate) fn angles_at_key(
+    config: &LookupTableConfig,
+    day_of_year: i32,
+    minutes: i32,
+) -> FastAngles {
+    let eot = angles::equation_of_time_with_model(
+        day_of_year,
+        config.year,
+        config.equation_of_time_model,
+    );
+    let decl = angles::declination_in_year(day_of_year, config.year, config.declination_model);
+    let (sin_lat, cos_lat) = math::sin_cos(angles::deg_to_rad(config.latitude));
+    let (sin_dec, cos_dec) = math::sin_cos(angles::deg_to_rad(decl));
+    let correction = key_correction(config, eot);
+    compute_angles_fast(sin_lat, cos_lat, sin_dec, cos_dec, correction, minutes as f64 / 60.0)
+}
+
+// One day of a table: the entries at each interval from sunrise to sunset,
+// widened by the config's buffers.
+fn generate_day<E, F>(
+    config: &LookupTableConfig,
+    doy: i32,
+    (sin_lat, cos_lat): (f64, f64),
+    entry_fn: &F,
+) -> DayData<E>
 where
     F: Fn(i32, &FastAngles, bool) -> E,
 {
     let n_intervals = intervals_per_day(config.interval_minutes);
-    let n_days = if angles::leap_year(config.year) { 366 } else { 365 };
-    let mut days: Vec<DayData<E>> = Vec::with_capacity(n_days as usize);
-
-    let lat_rad = angles::deg_to_rad(config.latitude);
-    let sin_lat = lat_rad.sin();
-    let cos_lat = lat_rad.cos();
-
-    for doy in 1..=n_days {
-        let ss = estimate_sunrise_sunset(config.latitude, doy);
-        let eot = angles::equation_of_time(doy);
-        let decl = angles::solar_declination(doy);
-        let dec_rad = angles::deg_to_rad(decl);
-        let sin_dec = dec_rad.sin();
-        let cos_dec = dec_rad.cos();
-        let correction = angles::utc_lst_correction(config.longitude, eot);
-        let correction_minutes = correction * 60.0;
-
-        let sunrise_utc = (ss.sunrise as f64 - correction_minutes) as i32;
-        let sunset_utc = (ss.sunset as f64 - correction_minutes) as i32;
-
-        let start_minute = 0.max(sunrise_utc - config.sunrise_buffer_minutes);
-        let end_minute = 1439.min(sunset_utc + config.sunset_buffer_minutes);
+    let ss = table_sunrise_sunset(config, doy);
+    let eot = angles::equation_of_time_with_model(doy, config.year, config.equation_of_time_model);
+    let decl = angles::declination_in_year(doy, config.year, config.declination_model);
+    let dec_rad = angles::deg_to_rad(decl);
+    let sin_dec = math::sin(dec_rad);
+    let cos_dec = math::cos(dec_rad);
+    let correction = key_correction(config, eot);
+    let correction_minutes = correction * 60.0;
+
+    let sunrise_utc = (ss.sunrise as f64 - correction_minutes) as i32;
+    let sunset_utc = (ss.sunset as f64 - correction_minutes) as i32;
+
+    let start_minute = 0.max(sunrise_utc - config.sunrise_buffer_minutes);
+    let end_minute = 1439.min(sunset_utc + config.sunset_buffer_minutes);
+
+    // Ceiling division for first interval
+    let first_interval = (start_minute + config.interval_minutes - 1) / config.interval_minutes;
+    let last_interval = (end_minute / config.interval_minutes).min(n_intervals - 1);
+
+    let capacity = if last_interval >= first_interval {
+        (last_interval - first_interval + 1) as usize
+    } else {
+        0
+    };
+    let mut entries = Vec::with_capacity(capacity);
+    for interval in first_interval..=last_interval {
+        let mins = interval * config.interval_minutes;
+        let utc_hours = mins as f64 / 60.0;
+        let pos = compute_angles_fast(
+            sin_lat, cos_lat, sin_dec, cos_dec, correction, utc_hours,
+        );
+        let local_minutes = (mins as f64 + correction_minutes) as i32;
+        let is_daylight = local_minutes >= ss.sunrise && local_minutes <= ss.sunset;
+        entries.push(entry_fn(mins, &pos, is_daylight));
+    }
 
-        // Ceiling division for first interval
-        let first_interval = (start_minute + config.interval_minutes - 1) / config.interval_minutes;
-        let last_interval = (end_minute / config.interval_minutes).min(n_intervals - 1);
+    DayData {
+        day_of_year: doy,
+        sunrise_minutes: ss.sunrise,
+        sunset_minutes: ss.sunset,
+        solar_noon_minutes: (720.0 - correction_minutes).round() as i32,
+        entries,
+    }
+}
 
-        let capacity = if last_interval >= first_interval {
-            (last_interval - first_interval + 1) as usize
-        } else {
-            0
-        };
-        let mut entries = Vec::with_capacity(capacity);
-        for interval in first_interval..=last_interval {
-            let mins = interval * config.interval_minutes;
-            let utc_hours = mins as f64 / 60.0;
-            let pos = compute_angles_fast(
-                sin_lat, cos_lat, sin_dec, cos_dec, correction, utc_hours,
-            );
-            let local_minutes = (mins as f64 + correction_minutes) as i32;
-            let is_daylight = local_minutes >= ss.sunrise && local_minutes <= ss.sunset;
-            entries.push(entry_fn(mins, &pos, is_daylight));
+// Days and entries per calendar month of `year`, in month order, skipping
+// months the days do not reach.
+pub(crate) fn month_statistics<E>(year: i32, days: &[DayData<E>]) -> Vec<MonthStatistics> {
+    let mut months: Vec<MonthStatistics> = Vec::new();
+    for day in days {
+        let (month, _) = doy_to_month_day(year, day.day_of_year);
+        match months.iter_mut().find(|m| m.month == month) {
+            Some(stats) => {
+                stats.days += 1;
+                stats.entries += day.entries.len();
+            }
+            None => months.push(MonthStatistics {
+                month,
+                days: 1,
+                entries: day.entries.len(),
+            }),
         }
-
-        days.push(DayData {
-            day_of_year: doy,
-            sunrise_minutes: ss.sunrise,
-            sunset_minutes: ss.sunset,
-            entries,
-        });
     }
+    months.sort_by_key(|m| m.month);
+    months
+}
+
+pub(crate) fn generate_table<E, F>(
+    config: &LookupTableConfig,
+    entry_fn: F,
+    bytes_per_entry: usize,
+) -> LookupTable<E>
+where
+    F: Fn(i32, &FastAngles, bool) -> E,
+{
+    let n_days = if angles::leap_year(config.year) { 366 } else { 365 };
+    let lat_rad = angles::deg_to_rad(config.latitude);
+    let lat = (math::sin(lat_rad), math::cos(lat_rad));
+    let days: Vec<DayData<E>> = (1..=n_days)
+        .map(|doy| generate_day(config, doy, lat, &entry_fn))
+        .collect();
 
     let total_entries: usize = days.iter().map(|d| d.entries.len()).sum();
     let storage_kb = (total_entries * bytes_per_entry) as f64 / 1024.0;
 
     let generated_at = format_utc_now();
 
+    let months = month_statistics(config.year, &days);
+
     LookupTable {
         config: *config,
         days,
@@ -215,110 +393,561 @@ where
             generated_at,
             total_entries,
             storage_estimate_kb: storage_kb,
+            project: None,
+            months,
         },
     }
 }
 
-fn format_utc_now() -> String {
+// Without the `clock` feature there is no wall clock to read (`SystemTime`
+// panics on wasm32-unknown-unknown), so tables and bundles record an empty
+// generation time for the caller to fill in.
+#[cfg(feature = "clock")]
+pub(crate) fn format_utc_now() -> String {
     Utc::now().format("%Y-%m-%dT%H:%M:%S+00:00").to_string()
 }
 
+#[cfg(not(feature = "clock"))]
+pub(crate) fn format_utc_now() -> String {
+    String::new()
+}
+
+fn single_axis_entry(
+    minutes: i32,
+    rotation: Option<(f64, bool)>,
+    surface_axis: Option<(f64, f64)>,
+) -> SingleAxisEntry {
+    match rotation {
+        Some((rotation, clamped)) => SingleAxisEntry {
+            minutes,
+            rotation: Some(rotation),
+            clamped,
+            surface: surface_axis
+                .map(|(tilt, azimuth)| angles::surface_orientation(rotation, tilt, azimuth)),
+        },
+        None => SingleAxisEntry {
+            minutes,
+            rotation: None,
+            clamped: false,
+            surface: None,
+        },
+    }
+}
+
+fn single_axis_bytes(config: &LookupTableConfig) -> usize {
+    if config.include_surface_orientation {
+        8
+    } else {
+        4
+    }
+}
+
+// The entry `generate_single_axis_table` makes at each key, shared with the
+// one-day reference the monthly budget compares against.
+fn single_axis_entry_fn(
+    config: &LookupTableConfig,
+) -> impl Fn(i32, &FastAngles, bool) -> SingleAxisEntry {
+    let cos_lat = math::cos(angles::deg_to_rad(config.latitude));
+    let gcr = config.gcr;
+    let (min_rotation, max_rotation) = (config.min_rotation, config.max_rotation);
+    // On sloped ground, or with an as-built axis off north-south, the axis
+    // follows the terrain so the rotation comes from the exact tilted-axis
+    // solution and backtracking sees the cross-axis slope. A surveyed axis
+    // tilt takes precedence over the one implied by terrain.
+    let axis_azimuth = config.axis_azimuth.unwrap_or(180.0);
+    let exact = config.terrain.is_some() || config.axis_azimuth.is_some();
+    let exact_axis = (exact || config.axis_tilt.is_some()).then(|| {
+        let (terrain_tilt, cross_axis) = config.terrain.map_or((0.0, 0.0), |terrain| {
+            let axis_tilt = angles::terrain_axis_tilt(&terrain, axis_azimuth);
+            (axis_tilt, angles::cross_axis_slope(&terrain, axis_azimuth))
+        });
+        (config.axis_tilt.unwrap_or(terrain_tilt), cross_axis)
+    });
+    let axis_tilt = exact_axis.map_or(0.0, |(axis_tilt, _)| axis_tilt);
+    let surface_axis = config.include_surface_orientation.then_some((axis_tilt, axis_azimuth));
+    move |minutes, angles: &FastAngles, is_daylight| {
+        if !is_daylight {
+            return single_axis_entry(minutes, None, None);
+        }
+        let (ideal, cross_axis) = match exact_axis {
+            Some((axis_tilt, cross_axis)) => {
+                let (zenith, azimuth) = (angles.zenith, angles.azimuth);
+                let ideal =
+                    angles::tilted_axis_rotation_at(zenith, azimuth, axis_tilt, axis_azimuth);
+                (ideal, cross_axis)
+            }
+            None => {
+                let ha_rad = angles::deg_to_rad(angles.hour_angle);
+                (angles::rad_to_deg(math::atan2(math::tan(ha_rad), cos_lat)), 0.0)
+            }
+        };
+        let rotation = match gcr {
+            Some(gcr) if angles.zenith < 90.0 => {
+                angles::slope_aware_backtrack(ideal, gcr, cross_axis)
+            }
+            Some(_) => 0.0,
+            None => ideal,
+        };
+        let clamped = angles::clamp_rotation(rotation, min_rotation, max_rotation);
+        single_axis_entry(minutes, Some(clamped), surface_axis)
+    }
+}
+
 pub fn generate_single_axis_table(config: &LookupTableConfig) -> SingleAxisTable {
-    let cos_lat = angles::deg_to_rad(config.latitude).cos();
+    generate_table(config, single_axis_entry_fn(config), single_axis_bytes(config))
+}
+
+// One day of `config`'s single-axis table at one-minute keys, without
+// generating the rest of the year.
+pub(crate) fn single_axis_minute_day(
+    config: &LookupTableConfig,
+    day_of_year: i32,
+) -> DayData<SingleAxisEntry> {
+    let minute_config = LookupTableConfig {
+        interval_minutes: 1,
+        ..*config
+    };
+    let lat_rad = angles::deg_to_rad(config.latitude);
+    let lat = (math::sin(lat_rad), math::cos(lat_rad));
+    generate_day(&minute_config, day_of_year, lat, &single_axis_entry_fn(config))
+}
+
+pub fn generate_polar_axis_table(config: &LookupTableConfig) -> SingleAxisTable {
+    let (min_rotation, max_rotation) = (config.min_rotation, config.max_rotation);
+    let axis_azimuth = if config.latitude >= 0.0 { 180.0 } else { 0.0 };
+    let surface_axis = config
+        .include_surface_orientation
+        .then_some((config.latitude.abs(), axis_azimuth));
     generate_table(config, move |minutes, angles, is_daylight| {
-        let rotation = if is_daylight {
-            let ha_rad = angles::deg_to_rad(angles.hour_angle);
-            Some(angles::rad_to_deg(ha_rad.tan().atan2(cos_lat)))
-        } else {
-            None
-        };
-        SingleAxisEntry { minutes, rotation }
-    }, 4)
+        let rotation = is_daylight
+            .then(|| angles::clamp_rotation(angles.hour_angle, min_rotation, max_rotation));
+        single_axis_entry(minutes, rotation, surface_axis)
+    }, single_axis_bytes(config))
+}
+
+pub fn generate_ew_axis_table(config: &LookupTableConfig) -> SingleAxisTable {
+    let (min_rotation, max_rotation) = (config.min_rotation, config.max_rotation);
+    let surface_axis = config.include_surface_orientation.then_some((0.0, 90.0));
+    generate_table(config, move |minutes, angles, is_daylight| {
+        let rotation = is_daylight.then(|| {
+            let tilt = angles::ew_axis_tilt_at(angles.zenith, angles.azimuth);
+            angles::clamp_rotation(tilt, min_rotation, max_rotation)
+        });
+        single_axis_entry(minutes, rotation, surface_axis)
+    }, single_axis_bytes(config))
 }
 
 pub fn generate_dual_axis_table(config: &LookupTableConfig) -> DualAxisTable {
-    generate_table(config, |minutes, angles, is_daylight| {
+    let (min_rotation, max_rotation) = (config.min_rotation, config.max_rotation);
+    let declination = config.magnetic_declination.unwrap_or(0.0);
+    let encoding = config.azimuth_encoding;
+    generate_table(config, move |minutes, angles, is_daylight| {
         if is_daylight {
+            let (tilt, adjusted) = angles::clamp_rotation(angles.zenith, min_rotation, max_rotation);
+            let panel_azimuth = angles::normalize_angle(angles.azimuth + 180.0);
+            let magnetic_azimuth = magnetic::magnetic_bearing(panel_azimuth, declination);
             DualAxisEntry {
                 minutes,
-                tilt: Some(angles.zenith),
-                panel_azimuth: Some(angles::normalize_angle(angles.azimuth + 180.0)),
+                tilt: Some(tilt),
+                panel_azimuth: Some(encoding.encode(magnetic_azimuth)),
+                adjusted,
             }
         } else {
             DualAxisEntry {
                 minutes,
                 tilt: None,
                 panel_azimuth: None,
+                adjusted: false,
             }
         }
     }, 8)
 }
 
+pub fn generate_mount_table(config: &LookupTableConfig) -> MountTable {
+    let (latitude, mount) = (config.latitude, config.dual_axis_mount);
+    generate_table(config, move |minutes, angles, is_daylight| {
+        let mount_angles = is_daylight.then(|| {
+            let (zenith, azimuth) = (angles.zenith, angles.azimuth);
+            angles::mount_angles_from(zenith, azimuth, angles.hour_angle, latitude, mount)
+        });
+        MountEntry {
+            minutes,
+            primary: mount_angles.map(|a| a.primary),
+            secondary: mount_angles.map(|a| a.secondary),
+        }
+    }, 8)
+}
+
+// The tracker axis as (tilt, azimuth), recovered from two entries: both
+// panel normals lie in the plane square to the axis, so their cross product
+// is the axis scaled by the sine of the rotation between them. None when the
+// rotations are too close to tell it apart.
+fn rotation_axis(
+    before: (f64, &SurfaceOrientation),
+    after: (f64, &SurfaceOrientation),
+) -> Option<(f64, f64)> {
+    let sin_delta = math::sin(angles::deg_to_rad(after.0 - before.0));
+    if sin_delta.abs() < 1e-6 {
+        return None;
+    }
+    let (a, b) = (angles::surface_normal(before.1), angles::surface_normal(after.1));
+    let axis = [
+        (a[1] * b[2] - a[2] * b[1]) / sin_delta,
+        (a[2] * b[0] - a[0] * b[2]) / sin_delta,
+        (a[0] * b[1] - a[1] * b[0]) / sin_delta,
+    ];
+    let tilt = angles::rad_to_deg(math::asin((-axis[2]).clamp(-1.0, 1.0)));
+    let azimuth = angles::normalize_angle(angles::rad_to_deg(math::atan2(axis[0], axis[1])));
+    Some((tilt, azimuth))
+}
+
+// Only the rotation is interpolated; the surface orientation is rebuilt from
+// it about the entries' own axis, so the two always describe the same pose
+// (across solar noon the surface azimuth swings through 180° while the
+// rotation passes through zero).
+pub(crate) fn blend_single_axis(
+    before: &SingleAxisEntry,
+    after: &SingleAxisEntry,
+    fraction: f64,
+    minutes: i32,
+) -> SingleAxisEntry {
+    let rotation = interpolate_linear(before.rotation, after.rotation, fraction);
+    let surface = match (before.rotation, before.surface, after.rotation, after.surface) {
+        (Some(r0), Some(s0), Some(r1), Some(s1)) => {
+            match (rotation, rotation_axis((r0, &s0), (r1, &s1))) {
+                (Some(rotation), Some((axis_tilt, axis_azimuth))) => {
+                    Some(angles::surface_orientation(rotation, axis_tilt, axis_azimuth))
+                }
+                // The two poses are all but identical
+                _ => Some(if fraction < 0.5 { s0 } else { s1 }),
+            }
+        }
+        _ => None,
+    };
+    SingleAxisEntry {
+        minutes,
+        rotation,
+        clamped: before.clamped || after.clamped,
+        surface,
+    }
+}
+
+// Interpolates the short way round whatever the encoding, then stores the
+// result back in it.
+pub(crate) fn blend_dual_axis(
+    before: &DualAxisEntry,
+    after: &DualAxisEntry,
+    fraction: f64,
+    minutes: i32,
+    encoding: AzimuthEncoding,
+) -> DualAxisEntry {
+    let panel_azimuth = interpolate_angle(before.panel_azimuth, after.panel_azimuth, fraction);
+    DualAxisEntry {
+        minutes,
+        tilt: interpolate_linear(before.tilt, after.tilt, fraction),
+        panel_azimuth: panel_azimuth.map(|a| encoding.encode(a)),
+        adjusted: before.adjusted || after.adjusted,
+    }
+}
+
+// None for a day the table does not hold, including days <= 0, so hostile
+// day-of-year values never index out of bounds. Everything on the lookup path
+// from here down borrows the table and returns entries by value, so lookups
+// never touch the heap and are safe to call from allocation-free firmware
+// loops; tests/test_no_alloc.rs holds them to that.
+pub(crate) fn day_entries<E>(table: &LookupTable<E>, day_of_year: i32) -> Option<&[E]> {
+    let index = usize::try_from(day_of_year.checked_sub(1)?).ok()?;
+    table.days.get(index).map(|day| day.entries.as_slice())
+}
+
 pub fn lookup_single_axis(
     table: &SingleAxisTable,
     day_of_year: i32,
-    minutes: i32,
+    minutes: MinuteOfDay,
 ) -> Option<SingleAxisEntry> {
-    let entries = &table.days[(day_of_year - 1) as usize].entries;
+    let minutes = minutes.get();
+    let entries = day_entries(table, day_of_year)?;
     let interval_minutes = table.config.interval_minutes;
     let (before, after, fraction) = find_bracketing_entries(entries, interval_minutes, minutes)?;
     match after {
-        None => Some(SingleAxisEntry {
-            minutes,
-            rotation: before.rotation,
-        }),
-        Some(after) => Some(SingleAxisEntry {
-            minutes,
-            rotation: interpolate_linear(before.rotation, after.rotation, fraction),
-        }),
+        None => Some(SingleAxisEntry { minutes, ..*before }),
+        Some(after) => Some(blend_single_axis(before, after, fraction, minutes)),
     }
 }
 
 pub fn lookup_dual_axis(
     table: &DualAxisTable,
     day_of_year: i32,
-    minutes: i32,
+    minutes: MinuteOfDay,
 ) -> Option<DualAxisEntry> {
-    let entries = &table.days[(day_of_year - 1) as usize].entries;
+    let minutes = minutes.get();
+    let entries = day_entries(table, day_of_year)?;
     let interval_minutes = table.config.interval_minutes;
     let (before, after, fraction) = find_bracketing_entries(entries, interval_minutes, minutes)?;
     match after {
-        None => Some(DualAxisEntry {
+        None => Some(DualAxisEntry { minutes, ..*before }),
+        Some(after) => Some(blend_dual_axis(
+            before,
+            after,
+            fraction,
             minutes,
-            tilt: before.tilt,
-            panel_azimuth: before.panel_azimuth,
-        }),
-        Some(after) => Some(DualAxisEntry {
-            minutes,
-            tilt: interpolate_linear(before.tilt, after.tilt, fraction),
-            panel_azimuth: interpolate_angle(
-                before.panel_azimuth,
-                after.panel_azimuth,
-                fraction,
-            ),
-        }),
+            table.config.azimuth_encoding,
+        )),
     }
 }
 
-pub fn single_axis_table_to_compact(table: &SingleAxisTable) -> Vec<Vec<Option<f64>>> {
+// The UTC instant a wall-clock reading in `time_base` names. A zone clock
+// resolves the repeated fall-back hour to its first pass and has no instant
+// for the skipped spring-forward hour.
+fn utc_from_clock(time_base: TimeBase, clock: NaiveDateTime) -> Option<NaiveDateTime> {
+    match time_base {
+        TimeBase::Utc => Some(clock),
+        TimeBase::FixedOffset { offset_minutes } => {
+            clock.checked_sub_signed(Duration::minutes(offset_minutes as i64))
+        }
+        #[cfg(feature = "tz")]
+        TimeBase::Zone(tz) => tz
+            .from_local_datetime(&clock)
+            .earliest()
+            .map(|dt| dt.naive_utc()),
+    }
+}
+
+// Local apparent solar date and minute at `longitude` for a UTC instant,
+// with the config's equation-of-time model: the keys of an hour-angle table
+// for a site at `longitude`, which need not be the table's own.
+pub fn hour_angle_keys(
+    config: &LookupTableConfig,
+    longitude: f64,
+    utc: NaiveDateTime,
+) -> Option<(i32, MinuteOfDay)> {
+    let eot = angles::equation_of_time_with_model(
+        utc.ordinal() as i32,
+        utc.year(),
+        config.equation_of_time_model,
+    );
+    let correction = angles::utc_lst_correction(longitude, eot);
+    let solar = utc.checked_add_signed(Duration::milliseconds(
+        (correction * 3_600_000.0).round() as i64,
+    ))?;
+    Some((solar.ordinal() as i32, MinuteOfDay::from_time(solar.time())))
+}
+
+// The keys a UTC instant maps to in tables generated from `config`.
+pub(crate) fn table_keys_at_utc(
+    config: &LookupTableConfig,
+    utc: NaiveDateTime,
+) -> Option<(i32, MinuteOfDay)> {
+    match config.table_key {
+        TableKey::UtcMinutes => Some((utc.ordinal() as i32, MinuteOfDay::from_time(utc.time()))),
+        TableKey::HourAngle => hour_angle_keys(config, config.longitude, utc),
+    }
+}
+
+// The (day_of_year, minutes) keys a consumer's clock reading maps to under
+// the table's time base and key, so callers never apply an offset, DST or
+// the solar-time correction by hand.
+pub fn table_keys_at_clock(
+    config: &LookupTableConfig,
+    clock: NaiveDateTime,
+) -> Option<(i32, MinuteOfDay)> {
+    table_keys_at_utc(config, utc_from_clock(config.time_base, clock)?)
+}
+
+// The inverse: the consumer's clock reading at a table key, for schedules
+// shown in local time. None for a day outside the config's year.
+pub fn clock_at_table_keys(
+    config: &LookupTableConfig,
+    day_of_year: i32,
+    minutes: MinuteOfDay,
+) -> Option<NaiveDateTime> {
+    let minutes = minutes.get();
+    let midnight = date_from_day_of_year(config.year, day_of_year)?.and_time(NaiveTime::MIN);
+    let mut utc = midnight + Duration::minutes(minutes as i64);
+    if config.table_key == TableKey::HourAngle {
+        let eot = angles::equation_of_time_with_model(
+            day_of_year,
+            config.year,
+            config.equation_of_time_model,
+        );
+        let correction = angles::utc_lst_correction(config.longitude, eot);
+        utc -= Duration::milliseconds((correction * 3_600_000.0).round() as i64);
+    }
+    match config.time_base {
+        TimeBase::Utc => Some(utc),
+        TimeBase::FixedOffset { offset_minutes } => {
+            utc.checked_add_signed(Duration::minutes(offset_minutes as i64))
+        }
+        #[cfg(feature = "tz")]
+        TimeBase::Zone(tz) => Some(tz.from_utc_datetime(&utc).naive_local()),
+    }
+}
+
+pub fn lookup_single_axis_at_clock(
+    table: &SingleAxisTable,
+    clock: NaiveDateTime,
+) -> Option<SingleAxisEntry> {
+    let (day_of_year, minutes) = table_keys_at_clock(&table.config, clock)?;
+    lookup_single_axis(table, day_of_year, minutes)
+}
+
+pub fn lookup_dual_axis_at_clock(
+    table: &DualAxisTable,
+    clock: NaiveDateTime,
+) -> Option<DualAxisEntry> {
+    let (day_of_year, minutes) = table_keys_at_clock(&table.config, clock)?;
+    lookup_dual_axis(table, day_of_year, minutes)
+}
+
+pub fn lookup_mount(table: &MountTable, day_of_year: i32, minutes: MinuteOfDay) -> Option<MountEntry> {
+    let minutes = minutes.get();
+    let entries = day_entries(table, day_of_year)?;
+    let interval_minutes = table.config.interval_minutes;
+    let (before, after, fraction) = find_bracketing_entries(entries, interval_minutes, minutes)?;
+    let Some(after) = after else {
+        return Some(MountEntry { minutes, ..*before });
+    };
+    // Only the azimuth axis wraps; roll and hour angle stay within ±180°
+    let primary = match table.config.dual_axis_mount {
+        DualAxisMount::AzimuthElevation => {
+            interpolate_angle(before.primary, after.primary, fraction)
+        }
+        _ => interpolate_linear(before.primary, after.primary, fraction),
+    };
+    Some(MountEntry {
+        minutes,
+        primary,
+        secondary: interpolate_linear(before.secondary, after.secondary, fraction),
+    })
+}
+
+// Schedule-style entries are not interpolated: the nearer entry wins, ties
+// going to the earlier one. Allocation-free whenever cloning `E` is, as for
+// every `Copy` entry type.
+pub fn lookup_nearest<E: HasMinutes + Clone>(
+    table: &LookupTable<E>,
+    day_of_year: i32,
+    minutes: MinuteOfDay,
+) -> Option<E> {
+    let minutes = minutes.get();
+    let entries = day_entries(table, day_of_year)?;
+    let interval_minutes = table.config.interval_minutes;
+    let (before, after, fraction) = find_bracketing_entries(entries, interval_minutes, minutes)?;
+    let mut nearest = match after {
+        Some(after) if fraction > 0.5 => after.clone(),
+        _ => before.clone(),
+    };
+    nearest.set_minutes(minutes);
+    Some(nearest)
+}
+
+pub fn step_table<E, T, F>(table: &LookupTable<E>, value_fn: F) -> StepTable<T>
+where
+    F: Fn(&E) -> T,
+    E: HasMinutes,
+{
+    LookupTable {
+        config: table.config,
+        days: table
+            .days
+            .iter()
+            .map(|day| DayData {
+                day_of_year: day.day_of_year,
+                sunrise_minutes: day.sunrise_minutes,
+                sunset_minutes: day.sunset_minutes,
+                solar_noon_minutes: day.solar_noon_minutes,
+                entries: day
+                    .entries
+                    .iter()
+                    .map(|e| StepEntry {
+                        minutes: e.minutes(),
+                        value: value_fn(e),
+                    })
+                    .collect(),
+            })
+            .collect(),
+        metadata: table.metadata.clone(),
+    }
+}
+
+// Moving east by one degree of longitude brings every solar event 4 minutes
+// earlier in UTC, so shifting entry times by the rounded offset reproduces the
+// table for the new site. Residual errors: the shift is rounded to whole
+// minutes (at most 0.125° of hour angle), declination and equation of time
+// are evaluated at the original instants (negligible below ~5° of longitude),
+// and entries pushed past midnight UTC are dropped rather than carried into
+// the neighbouring day.
+fn shift_table<E: HasMinutes + Clone>(table: &LookupTable<E>, longitude: f64) -> LookupTable<E> {
+    // Hour-angle keys do not depend on longitude
+    let shift = match table.config.table_key {
+        TableKey::UtcMinutes => (4.0 * (longitude - table.config.longitude)).round() as i32,
+        TableKey::HourAngle => 0,
+    };
+    let days: Vec<DayData<E>> = table
+        .days
+        .iter()
+        .map(|day| DayData {
+            entries: day
+                .entries
+                .iter()
+                .filter_map(|e| {
+                    let minutes = e.minutes() - shift;
+                    (0..1440).contains(&minutes).then(|| {
+                        let mut e = e.clone();
+                        e.set_minutes(minutes);
+                        e
+                    })
+                })
+                .collect(),
+            solar_noon_minutes: day.solar_noon_minutes - shift,
+            ..*day
+        })
+        .collect();
+    let total_entries: usize = days.iter().map(|d| d.entries.len()).sum();
+    let bytes_per_entry = table.metadata.storage_estimate_kb * 1024.0
+        / table.metadata.total_entries.max(1) as f64;
+    let months = month_statistics(table.config.year, &days);
+    LookupTable {
+        config: LookupTableConfig {
+            longitude,
+            ..table.config
+        },
+        days,
+        metadata: TableMetadata {
+            generated_at: table.metadata.generated_at.clone(),
+            total_entries,
+            storage_estimate_kb: total_entries as f64 * bytes_per_entry / 1024.0,
+            project: table.metadata.project.clone(),
+            months,
+        },
+    }
+}
+
+pub fn shift_single_axis_table(table: &SingleAxisTable, longitude: f64) -> SingleAxisTable {
+    shift_table(table, longitude)
+}
+
+pub fn shift_dual_axis_table(table: &DualAxisTable, longitude: f64) -> DualAxisTable {
+    shift_table(table, longitude)
+}
+
+pub fn table_to_compact<E, T, F>(table: &LookupTable<E>, value_fn: F) -> Vec<Vec<T>>
+where
+    F: Fn(&E) -> T,
+{
     table
         .days
         .iter()
-        .map(|day| day.entries.iter().map(|e| e.rotation).collect())
+        .map(|day| day.entries.iter().map(&value_fn).collect())
         .collect()
 }
 
+pub fn single_axis_table_to_compact(table: &SingleAxisTable) -> Vec<Vec<Option<f64>>> {
+    table_to_compact(table, |e| e.rotation)
+}
+
 pub fn dual_axis_table_to_compact(
     table: &DualAxisTable,
 ) -> Vec<Vec<(Option<f64>, Option<f64>)>> {
-    table
-        .days
-        .iter()
-        .map(|day| {
-            day.entries
-                .iter()
-                .map(|e| (e.tilt, e.panel_azimuth))
-                .collect()
-        })
-        .collect()
+    table_to_compact(table, |e| (e.tilt, e.panel_azimuth))
 }
@@ -0,0 +1,13 @@
+use crate::angles::normalize_angle;
+
+// Declination is positive when magnetic north lies east of true north. No
+// built-in field model: a dipole approximation is 10–15° off the WMM over
+// much of Europe, Asia and the US east coast, which is worse than asking
+// for a value from a chart or the NOAA calculator.
+pub fn magnetic_bearing(true_azimuth: f64, declination: f64) -> f64 {
+    normalize_angle(true_azimuth - declination)
+}
+
+pub fn true_bearing(magnetic_azimuth: f64, declination: f64) -> f64 {
+    normalize_angle(magnetic_azimuth + declination)
+}
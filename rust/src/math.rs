@@ -0,0 +1,58 @@
+// Trig used by `angles` and `lookup_table`. With the `deterministic`
+// feature these route through `portable_math`, so tables generated on one
+// target compare bit-identically on another; otherwise the platform libm
+// is used.
+
+macro_rules! unary {
+    ($($name:ident),*) => {$(
+        #[inline]
+        pub(crate) fn $name(x: f64) -> f64 {
+            #[cfg(feature = "deterministic")]
+            {
+                crate::portable_math::$name(x)
+            }
+            #[cfg(not(feature = "deterministic"))]
+            {
+                x.$name()
+            }
+        }
+    )*};
+}
+
+unary!(sin, cos, tan, atan, asin, acos);
+
+#[inline]
+pub(crate) fn sin_cos(x: f64) -> (f64, f64) {
+    #[cfg(feature = "deterministic")]
+    {
+        crate::portable_math::sin_cos(x)
+    }
+    #[cfg(not(feature = "deterministic"))]
+    {
+        x.sin_cos()
+    }
+}
+
+#[inline]
+pub(crate) fn atan2(y: f64, x: f64) -> f64 {
+    #[cfg(feature = "deterministic")]
+    {
+        crate::portable_math::atan2(y, x)
+    }
+    #[cfg(not(feature = "deterministic"))]
+    {
+        y.atan2(x)
+    }
+}
+
+#[inline]
+pub(crate) fn hypot(x: f64, y: f64) -> f64 {
+    #[cfg(feature = "deterministic")]
+    {
+        crate::portable_math::hypot(x, y)
+    }
+    #[cfg(not(feature = "deterministic"))]
+    {
+        x.hypot(y)
+    }
+}
@@ -0,0 +1,84 @@
+use crate::angles;
+use crate::interp::find_bracketing_entries;
+use crate::lookup_table::{blend_single_axis, generate_single_axis_table};
+use crate::types::{
+    LookupTableConfig, MinuteOfDay, NoonRelativeDay, NoonRelativeTable, SingleAxisEntry, TableKey,
+};
+
+// Solar-noon-relative tables: every day is sampled on the same grid of
+// minutes from its own solar noon, so adjacent days hold nearly the same
+// curve and only the declination moves it. UTC-keyed days slide by up to
+// half an hour over the year with the equation of time, which is what costs
+// delta and Chebyshev encodings their ratio.
+
+// Generated as an hour-angle table (see `TableKey::HourAngle`) and relabelled
+// around noon, so entries match that table exactly and do not depend on
+// longitude. The stored config says so.
+pub fn generate_noon_relative_table(config: &LookupTableConfig) -> NoonRelativeTable {
+    let config = LookupTableConfig {
+        table_key: TableKey::HourAngle,
+        ..*config
+    };
+    let table = generate_single_axis_table(&config);
+    let days = table
+        .days
+        .into_iter()
+        .map(|day| NoonRelativeDay {
+            day_of_year: day.day_of_year,
+            solar_noon_minutes: utc_solar_noon_minutes(&config, day.day_of_year),
+            entries: day
+                .entries
+                .into_iter()
+                .map(|e| SingleAxisEntry {
+                    minutes: e.minutes - 720,
+                    ..e
+                })
+                .collect(),
+        })
+        .collect();
+    NoonRelativeTable { config, days }
+}
+
+fn utc_solar_noon_minutes(config: &LookupTableConfig, day_of_year: i32) -> f64 {
+    let eot = angles::equation_of_time_with_model(
+        day_of_year,
+        config.year,
+        config.equation_of_time_model,
+    );
+    720.0 - angles::utc_lst_correction(config.longitude, eot) * 60.0
+}
+
+// Interpolated like `lookup_single_axis`; the returned entry's minutes are
+// the offset asked for. None outside the day's entries.
+pub fn lookup_noon_relative(
+    table: &NoonRelativeTable,
+    day_of_year: i32,
+    offset_minutes: i32,
+) -> Option<SingleAxisEntry> {
+    let index = usize::try_from(day_of_year.checked_sub(1)?).ok()?;
+    let entries = &table.days.get(index)?.entries;
+    let (before, after, fraction) =
+        find_bracketing_entries(entries, table.config.interval_minutes, offset_minutes)?;
+    match after {
+        None => Some(SingleAxisEntry {
+            minutes: offset_minutes,
+            ..*before
+        }),
+        Some(after) => Some(blend_single_axis(before, after, fraction, offset_minutes)),
+    }
+}
+
+// The same lookup from UTC keys, with the offset rounded to the minute. The
+// returned entry carries the UTC minutes.
+pub fn lookup_noon_relative_at_utc(
+    table: &NoonRelativeTable,
+    day_of_year: i32,
+    minutes: MinuteOfDay,
+) -> Option<SingleAxisEntry> {
+    let minutes = minutes.get();
+    let index = usize::try_from(day_of_year.checked_sub(1)?).ok()?;
+    let noon = table.days.get(index)?.solar_noon_minutes;
+    let offset = (minutes as f64 - noon).round() as i32;
+    let entry = lookup_noon_relative(table, day_of_year, offset)?;
+    Some(SingleAxisEntry { minutes, ..entry })
+}
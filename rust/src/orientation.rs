@@ -0,0 +1,220 @@
+use crate::angles::{day_of_year, normalize_angle};
+use crate::irradiance::clear_sky_components;
+use crate::simulation::{for_each_step, step_minutes};
+use crate::transposition::poa_irradiance;
+use crate::types::{
+    AdjustmentFrequency, FixedOrientation, IrradianceComponents, MinuteOfDay,
+    OrientationSearch, SimulationConfig, SolarPosition, SurfaceOrientation, TiltAdjustment,
+};
+
+// Best fixed (tilt, azimuth) for the site under clear skies.
+pub fn optimize_fixed_orientation(
+    config: &SimulationConfig,
+    search: &OrientationSearch,
+) -> FixedOrientation {
+    optimize_fixed_orientation_with(config, search, |_, _, pos| clear_sky_components(pos))
+}
+
+// The year is sampled once with `config`'s step and irradiance source
+// (`config.strategy` is ignored), then candidates are scored against those
+// samples: a coarse grid first, then a compass search halving the step down
+// to `search.resolution`. Annual energy over a fixed orientation is smooth
+// and single-peaked, so the refinement cannot get stuck on a side peak
+// unless hourly weights carve one out; a finer `coarse_step` guards
+// against that.
+pub fn optimize_fixed_orientation_with<F>(
+    config: &SimulationConfig,
+    search: &OrientationSearch,
+    irradiance: F,
+) -> FixedOrientation
+where
+    F: FnMut(i32, MinuteOfDay, &SolarPosition) -> Option<IrradianceComponents>,
+{
+    let samples = sample_year(config, search.hourly_weights, irradiance);
+    best_orientation(config, &samples, search)
+}
+
+type Sample = (SolarPosition, IrradianceComponents, f64);
+
+fn sample_year<F>(
+    config: &SimulationConfig,
+    weights: Option<[f64; 24]>,
+    irradiance: F,
+) -> Vec<Sample>
+where
+    F: FnMut(i32, MinuteOfDay, &SolarPosition) -> Option<IrradianceComponents>,
+{
+    let mut samples = Vec::new();
+    for_each_step(config, irradiance, |pos, components| {
+        let weight = weights.map_or(1.0, |weights| {
+            weights[(pos.local_solar_time.max(0.0) as usize).min(23)]
+        });
+        if weight > 0.0 {
+            samples.push((*pos, *components, weight));
+        }
+    });
+    samples
+}
+
+fn best_orientation(
+    config: &SimulationConfig,
+    samples: &[Sample],
+    search: &OrientationSearch,
+) -> FixedOrientation {
+    let kwh_per_sample = step_minutes(config) as f64 / 60.0 / 1000.0;
+    let score = |tilt: f64, azimuth: f64| {
+        let surface = SurfaceOrientation {
+            surface_tilt: tilt,
+            surface_azimuth: azimuth,
+        };
+        let wh: f64 = samples
+            .iter()
+            .map(|(pos, components, weight)| {
+                let poa =
+                    poa_irradiance(pos, &surface, components, config.albedo, config.sky_model);
+                weight * poa.total
+            })
+            .sum();
+        FixedOrientation {
+            tilt,
+            azimuth,
+            weighted_kwh_per_m2: wh * kwh_per_sample,
+        }
+    };
+
+    let full_circle = search.max_azimuth - search.min_azimuth >= 360.0;
+    let place = |tilt: f64, azimuth: f64| {
+        let tilt = tilt.clamp(search.min_tilt, search.max_tilt);
+        let azimuth = if full_circle {
+            normalize_angle(azimuth)
+        } else {
+            azimuth.clamp(search.min_azimuth, search.max_azimuth)
+        };
+        (tilt, azimuth)
+    };
+
+    let resolution = search.resolution.max(0.01);
+    let coarse = search.coarse_step.max(resolution);
+    let grid = |min: f64, max: f64| {
+        let n = ((max - min) / coarse).floor().max(0.0) as usize;
+        (0..=n).map(move |i| min + i as f64 * coarse)
+    };
+    let mut best = score(search.min_tilt, search.min_azimuth);
+    for tilt in grid(search.min_tilt, search.max_tilt) {
+        for azimuth in grid(search.min_azimuth, search.max_azimuth) {
+            let (tilt, azimuth) = place(tilt, azimuth);
+            let candidate = score(tilt, azimuth);
+            if candidate.weighted_kwh_per_m2 > best.weighted_kwh_per_m2 {
+                best = candidate;
+            }
+        }
+    }
+
+    let mut step = coarse / 2.0;
+    while step >= resolution {
+        let mut improved = true;
+        while improved {
+            improved = false;
+            for (dt, da) in [(step, 0.0), (-step, 0.0), (0.0, step), (0.0, -step)] {
+                let (tilt, azimuth) = place(best.tilt + dt, best.azimuth + da);
+                let candidate = score(tilt, azimuth);
+                if candidate.weighted_kwh_per_m2 > best.weighted_kwh_per_m2 {
+                    best = candidate;
+                    improved = true;
+                }
+            }
+        }
+        step /= 2.0;
+    }
+    best
+}
+
+// Change dates per frequency as (month, day). Twice a year switches at the
+// equinoxes, quarterly at the cross-quarter days so each setting is centred
+// on a solstice or equinox, and monthly on the first of the month.
+const TWICE: [(u32, u32); 2] = [(3, 20), (9, 22)];
+const QUARTERLY: [(u32, u32); 4] = [(2, 4), (5, 6), (8, 7), (11, 7)];
+const MONTHLY: [(u32, u32); 12] = [
+    (1, 1),
+    (2, 1),
+    (3, 1),
+    (4, 1),
+    (5, 1),
+    (6, 1),
+    (7, 1),
+    (8, 1),
+    (9, 1),
+    (10, 1),
+    (11, 1),
+    (12, 1),
+];
+
+// When to move a manually adjusted, equator-facing rack and what tilt to set,
+// under clear skies.
+pub fn seasonal_tilt_schedule(
+    config: &SimulationConfig,
+    frequency: AdjustmentFrequency,
+) -> Vec<TiltAdjustment> {
+    seasonal_tilt_schedule_with(config, frequency, |_, _, pos| clear_sky_components(pos))
+}
+
+// Each setting is the tilt (0°–90°, to 0.5°) maximizing insolation from its
+// change date until the next one, wrapping over the new year. The azimuth is
+// fixed facing the equator, so near the equator the summer setting bottoms
+// out at flat rather than turning to face the pole.
+pub fn seasonal_tilt_schedule_with<F>(
+    config: &SimulationConfig,
+    frequency: AdjustmentFrequency,
+    irradiance: F,
+) -> Vec<TiltAdjustment>
+where
+    F: FnMut(i32, MinuteOfDay, &SolarPosition) -> Option<IrradianceComponents>,
+{
+    let dates: &[(u32, u32)] = match frequency {
+        AdjustmentFrequency::Twice => &TWICE,
+        AdjustmentFrequency::Quarterly => &QUARTERLY,
+        AdjustmentFrequency::Monthly => &MONTHLY,
+    };
+    let azimuth = if config.latitude >= 0.0 { 180.0 } else { 0.0 };
+    let search = OrientationSearch {
+        min_azimuth: azimuth,
+        max_azimuth: azimuth,
+        coarse_step: 5.0,
+        ..OrientationSearch::default()
+    };
+    let samples = sample_year(config, None, irradiance);
+    let starts: Vec<i32> = dates
+        .iter()
+        .map(|&(month, day)| day_of_year(config.year, month, day))
+        .collect();
+
+    dates
+        .iter()
+        .zip(&starts)
+        .enumerate()
+        .map(|(i, (&(month, day), &start))| {
+            let end = starts[(i + 1) % starts.len()];
+            let in_period = |doy: i32| {
+                if start < end {
+                    (start..end).contains(&doy)
+                } else {
+                    doy >= start || doy < end
+                }
+            };
+            let period: Vec<Sample> = samples
+                .iter()
+                .filter(|(pos, _, _)| in_period(pos.day_of_year))
+                .copied()
+                .collect();
+            let best = best_orientation(config, &period, &search);
+            TiltAdjustment {
+                month,
+                day,
+                day_of_year: start,
+                tilt: best.tilt,
+                azimuth: best.azimuth,
+                kwh_per_m2: best.weighted_kwh_per_m2,
+            }
+        })
+        .collect()
+}
@@ -0,0 +1,193 @@
+use crate::angles;
+use crate::irradiance::clear_sky_ghi;
+use crate::lookup_table::{
+    doy_to_month_day, generate_single_axis_table, key_correction, lookup_single_axis,
+    month_statistics, single_axis_minute_day,
+};
+use crate::types::{
+    LookupTableConfig, MinuteOfDay, MonthBudget, PlannerConfig, SingleAxisTable, StorageFormat,
+    TableLayout,
+};
+
+const CANDIDATE_INTERVALS: [i32; 12] = [1, 2, 3, 4, 5, 6, 10, 12, 15, 20, 30, 60];
+const FORMATS: [StorageFormat; 3] = [
+    StorageFormat::Float32,
+    StorageFormat::Centidegrees16,
+    StorageFormat::HalfDegree8,
+];
+
+impl StorageFormat {
+    pub const fn bytes(self) -> usize {
+        match self {
+            StorageFormat::Float32 => 4,
+            StorageFormat::Centidegrees16 => 2,
+            StorageFormat::HalfDegree8 => 1,
+        }
+    }
+
+    pub const fn quantize(self, angle: f64) -> f64 {
+        match self {
+            StorageFormat::Float32 => angle as f32 as f64,
+            StorageFormat::Centidegrees16 => (angle * 100.0).round() / 100.0,
+            // A signed byte only spans ±63.5°; larger rotations saturate
+            StorageFormat::HalfDegree8 => (angle * 2.0).round().clamp(-128.0, 127.0) / 2.0,
+        }
+    }
+}
+
+fn values_per_entry(config: &LookupTableConfig) -> usize {
+    if config.include_surface_orientation {
+        2
+    } else {
+        1
+    }
+}
+
+// Fraction of clear-sky energy lost to rotation error against a one-minute
+// reference: a panel off by δ in its rotation plane collects cos δ of the
+// beam, weighted by clear-sky GHI as a stand-in for available irradiance.
+pub fn cosine_loss(
+    reference: &SingleAxisTable,
+    candidate: &SingleAxisTable,
+    format: StorageFormat,
+    sample_day_step: i32,
+) -> f64 {
+    let config = &reference.config;
+    let (mut lost, mut total) = (0.0, 0.0);
+    for day in reference
+        .days
+        .iter()
+        .step_by(sample_day_step.max(1) as usize)
+    {
+        let doy = day.day_of_year;
+        let decl = angles::declination_in_year(doy, config.year, config.declination_model);
+        let correction = key_correction(
+            config,
+            angles::equation_of_time_with_model(doy, config.year, config.equation_of_time_model),
+        );
+        for entry in &day.entries {
+            let Some(exact) = entry.rotation else {
+                continue;
+            };
+            let approx = MinuteOfDay::new(entry.minutes)
+                .and_then(|minutes| lookup_single_axis(candidate, doy, minutes))
+                .and_then(|e| e.rotation);
+            let Some(approx) = approx else {
+                continue;
+            };
+            let utc_hours = entry.minutes as f64 / 60.0;
+            let (_, _, zenith, _, _) =
+                angles::solar_angles_at(config.latitude, decl, correction, utc_hours);
+            let weight = clear_sky_ghi(zenith);
+            let error = angles::deg_to_rad(format.quantize(approx) - exact);
+            lost += weight * (1.0 - error.cos());
+            total += weight;
+        }
+    }
+    if total > 0.0 {
+        lost / total
+    } else {
+        0.0
+    }
+}
+
+// Every interval/format combination, evaluated against a one-minute table.
+pub fn sweep_table_layouts(base: &LookupTableConfig, planner: &PlannerConfig) -> Vec<TableLayout> {
+    let reference = generate_single_axis_table(&LookupTableConfig {
+        interval_minutes: 1,
+        ..*base
+    });
+    CANDIDATE_INTERVALS
+        .iter()
+        .flat_map(|&interval_minutes| {
+            let candidate = generate_single_axis_table(&LookupTableConfig {
+                interval_minutes,
+                ..*base
+            });
+            let values = candidate.metadata.total_entries * values_per_entry(base);
+            FORMATS
+                .iter()
+                .map(|&format| TableLayout {
+                    interval_minutes,
+                    format,
+                    storage_bytes: values * format.bytes(),
+                    energy_loss: cosine_loss(
+                        &reference,
+                        &candidate,
+                        format,
+                        planner.sample_day_step,
+                    ),
+                })
+                .collect::<Vec<_>>()
+        })
+        .collect()
+}
+
+// Smallest layout inside both the flash budget and the loss limit; ties on
+// size go to the lower loss.
+pub fn recommend_table_layout(
+    base: &LookupTableConfig,
+    planner: &PlannerConfig,
+) -> Option<TableLayout> {
+    sweep_table_layouts(base, planner)
+        .into_iter()
+        .filter(|l| {
+            l.storage_bytes <= planner.flash_budget_bytes
+                && l.energy_loss <= planner.max_energy_loss
+        })
+        .min_by(|a, b| {
+            a.storage_bytes
+                .cmp(&b.storage_bytes)
+                .then(a.energy_loss.total_cmp(&b.energy_loss))
+        })
+}
+
+// Per-month flash use and worst-case error of `table` stored as `format`, so
+// firmware teams can see which months dominate either. Counts come from the
+// table's metadata. The error covers interpolation and quantization
+// together: the table is looked up at every minute and compared with the
+// exact rotation for its config, one day at a time. Minutes where the exact
+// rotation exists but the table has none are counted in `uncovered_minutes`
+// rather than dropped.
+pub fn monthly_storage_budget(table: &SingleAxisTable, format: StorageFormat) -> Vec<MonthBudget> {
+    let config = &table.config;
+    // Tables serialized before per-month statistics carry none
+    let statistics = if table.metadata.months.is_empty() {
+        month_statistics(config.year, &table.days)
+    } else {
+        table.metadata.months.clone()
+    };
+    let mut months: Vec<MonthBudget> = statistics
+        .iter()
+        .map(|stats| MonthBudget {
+            month: stats.month,
+            days: stats.days,
+            entries: stats.entries,
+            storage_bytes: stats.entries * values_per_entry(config) * format.bytes(),
+            max_interpolation_error: 0.0,
+            uncovered_minutes: 0,
+        })
+        .collect();
+    for day in &table.days {
+        let doy = day.day_of_year;
+        let (month, _) = doy_to_month_day(config.year, doy);
+        let Some(budget) = months.iter_mut().find(|b| b.month == month) else {
+            continue;
+        };
+        for entry in &single_axis_minute_day(config, doy).entries {
+            let Some(exact) = entry.rotation else {
+                continue;
+            };
+            let approx = MinuteOfDay::new(entry.minutes)
+                .and_then(|minutes| lookup_single_axis(table, doy, minutes))
+                .and_then(|e| e.rotation);
+            let Some(approx) = approx else {
+                budget.uncovered_minutes += 1;
+                continue;
+            };
+            let error = (format.quantize(approx) - exact).abs();
+            budget.max_interpolation_error = budget.max_interpolation_error.max(error);
+        }
+    }
+    months
+}
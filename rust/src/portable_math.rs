@@ -0,0 +1,180 @@
+// Trig built only from IEEE-754 +, -, *, / and sqrt, which every target
+// rounds identically, so results are bit-identical across platforms. The
+// kernels are the fdlibm ones (within about 1 ulp of the platform libm).
+// Argument reduction is Cody-Waite with a three-part π/2, accurate well
+// beyond the few-turn radians the angle code produces.
+
+// The fdlibm constants are kept digit-for-digit as published.
+#![allow(clippy::excessive_precision)]
+
+use std::f64::consts::{FRAC_2_PI, FRAC_PI_2, FRAC_PI_4, PI};
+
+pub(crate) const PIO2_1: f64 = 1.57079632673412561417e+00;
+pub(crate) const PIO2_2: f64 = 6.07710050630396597660e-11;
+pub(crate) const PIO2_3: f64 = 2.02226624871116645580e-21;
+
+pub(crate) const S: [f64; 6] = [
+    -1.66666666666666324348e-01,
+    8.33333333332248946124e-03,
+    -1.98412698298579493134e-04,
+    2.75573137070700676789e-06,
+    -2.50507602534068634195e-08,
+    1.58969099521155010221e-10,
+];
+pub(crate) const C: [f64; 6] = [
+    4.16666666666666019037e-02,
+    -1.38888888888741095749e-03,
+    2.48015872894767294178e-05,
+    -2.75573143513906633035e-07,
+    2.08757232129817482790e-09,
+    -1.13596475577881948265e-11,
+];
+
+// Low part of π/4, for the atan reduction around 1
+pub(crate) const FRAC_PI_4_LO: f64 = 3.06161699786838301793e-17;
+
+const ATAN_HI: [f64; 4] = [
+    4.63647609000806093515e-01,
+    FRAC_PI_4,
+    9.82793723247329054082e-01,
+    FRAC_PI_2,
+];
+const ATAN_LO: [f64; 4] = [
+    2.26987774529616870924e-17,
+    FRAC_PI_4_LO,
+    1.39033110312309984516e-17,
+    6.12323399573676603587e-17,
+];
+pub(crate) const AT: [f64; 11] = [
+    3.33333333333329318027e-01,
+    -1.99999999998764832476e-01,
+    1.42857142725034663711e-01,
+    -1.11111104054623557880e-01,
+    9.09088713343650656196e-02,
+    -7.69187620504482999495e-02,
+    6.66107313738753120669e-02,
+    -5.83357013379057348645e-02,
+    4.97687799461593236017e-02,
+    -3.65315727442169155270e-02,
+    1.62858201153657823623e-02,
+];
+
+const PI_LO: f64 = 1.2246467991473531772e-16;
+
+// x = n·π/2 + r with |r| <= π/4; returns (n mod 4, r).
+fn reduce(x: f64) -> (u8, f64) {
+    let n = (x * FRAC_2_PI).round();
+    let r = ((x - n * PIO2_1) - n * PIO2_2) - n * PIO2_3;
+    ((n.rem_euclid(4.0)) as u8, r)
+}
+
+fn kernel_sin(x: f64) -> f64 {
+    let z = x * x;
+    x + x * z * (S[0] + z * (S[1] + z * (S[2] + z * (S[3] + z * (S[4] + z * S[5])))))
+}
+
+fn kernel_cos(x: f64) -> f64 {
+    let z = x * x;
+    let r = z * (C[0] + z * (C[1] + z * (C[2] + z * (C[3] + z * (C[4] + z * C[5])))));
+    let hz = 0.5 * z;
+    let w = 1.0 - hz;
+    w + (((1.0 - w) - hz) + z * r)
+}
+
+pub fn sin_cos(x: f64) -> (f64, f64) {
+    if !x.is_finite() {
+        return (f64::NAN, f64::NAN);
+    }
+    let (quadrant, r) = reduce(x);
+    let (s, c) = (kernel_sin(r), kernel_cos(r));
+    match quadrant {
+        0 => (s, c),
+        1 => (c, -s),
+        2 => (-s, -c),
+        _ => (-c, s),
+    }
+}
+
+pub fn sin(x: f64) -> f64 {
+    sin_cos(x).0
+}
+
+pub fn cos(x: f64) -> f64 {
+    sin_cos(x).1
+}
+
+pub fn tan(x: f64) -> f64 {
+    let (s, c) = sin_cos(x);
+    s / c
+}
+
+pub fn atan(x: f64) -> f64 {
+    if x.is_nan() {
+        return x;
+    }
+    let ax = x.abs();
+    if ax.is_infinite() {
+        return FRAC_PI_2.copysign(x);
+    }
+    let (id, t) = if ax < 0.4375 {
+        (None, ax)
+    } else if ax < 0.6875 {
+        (Some(0), (2.0 * ax - 1.0) / (2.0 + ax))
+    } else if ax < 1.1875 {
+        (Some(1), (ax - 1.0) / (ax + 1.0))
+    } else if ax < 2.4375 {
+        (Some(2), (ax - 1.5) / (1.0 + 1.5 * ax))
+    } else {
+        (Some(3), -1.0 / ax)
+    };
+    let z = t * t;
+    let w = z * z;
+    let s1 = z * (AT[0] + w * (AT[2] + w * (AT[4] + w * (AT[6] + w * (AT[8] + w * AT[10])))));
+    let s2 = w * (AT[1] + w * (AT[3] + w * (AT[5] + w * (AT[7] + w * AT[9]))));
+    let result = match id {
+        None => t - t * (s1 + s2),
+        Some(i) => ATAN_HI[i] - ((t * (s1 + s2) - ATAN_LO[i]) - t),
+    };
+    result.copysign(x)
+}
+
+pub fn atan2(y: f64, x: f64) -> f64 {
+    if x.is_nan() || y.is_nan() {
+        return f64::NAN;
+    }
+    if y == 0.0 {
+        return if x.is_sign_negative() { PI.copysign(y) } else { y };
+    }
+    if x == 0.0 {
+        return FRAC_PI_2.copysign(y);
+    }
+    if x.is_infinite() {
+        let a = if y.is_infinite() { 0.25 * PI } else { 0.0 };
+        let a = if x < 0.0 { PI - a } else { a };
+        return a.copysign(y);
+    }
+    if y.is_infinite() {
+        return FRAC_PI_2.copysign(y);
+    }
+    let a = atan((y / x).abs());
+    let a = if x < 0.0 { PI - (a - PI_LO) } else { a };
+    a.copysign(y)
+}
+
+pub fn asin(x: f64) -> f64 {
+    if !(-1.0..=1.0).contains(&x) {
+        return f64::NAN;
+    }
+    atan2(x, ((1.0 - x) * (1.0 + x)).sqrt())
+}
+
+pub fn acos(x: f64) -> f64 {
+    if !(-1.0..=1.0).contains(&x) {
+        return f64::NAN;
+    }
+    atan2(((1.0 - x) * (1.0 + x)).sqrt(), x)
+}
+
+pub fn hypot(x: f64, y: f64) -> f64 {
+    (x * x + y * y).sqrt()
+}
@@ -0,0 +1,163 @@
+use std::fmt;
+
+use crate::fixed_mount::inverter_events;
+use crate::lookup_table::day_entries;
+use crate::shading::shade_events;
+use crate::types::{
+    FixedMountConfig, FixedTable, InverterEvent, ProgramStep, ShadeEvent, ShadeTable,
+    SingleAxisTable,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ScheduleFull {
+    pub capacity: usize,
+    pub required: usize,
+}
+
+impl fmt::Display for ScheduleFull {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "schedule needs {} slots but holds {}",
+            self.required, self.capacity
+        )
+    }
+}
+
+impl std::error::Error for ScheduleFull {}
+
+// Fixed-capacity list with the capacity in the type, like `EventLog`, kept
+// in a `heapless::Vec` so slots carry no per-item tag: building a schedule
+// never allocates and it can sit in a static. Unlike the log it never
+// overwrites; a push past capacity is refused.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FixedSchedule<T: Copy, const N: usize> {
+    items: heapless::Vec<T, N>,
+}
+
+impl<T: Copy, const N: usize> FixedSchedule<T, N> {
+    pub const fn new() -> Self {
+        Self {
+            items: heapless::Vec::new(),
+        }
+    }
+
+    // Gives the item back when full.
+    pub fn push(&mut self, item: T) -> Result<(), T> {
+        self.items.push(item)
+    }
+
+    pub fn len(&self) -> usize {
+        self.items.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+
+    pub const fn capacity(&self) -> usize {
+        N
+    }
+
+    pub fn get(&self, index: usize) -> Option<&T> {
+        self.items.get(index)
+    }
+
+    pub fn first(&self) -> Option<&T> {
+        self.items.first()
+    }
+
+    pub fn last(&self) -> Option<&T> {
+        self.items.last()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &T> + '_ {
+        self.items.iter()
+    }
+
+    pub fn as_slice(&self) -> &[T] {
+        &self.items
+    }
+
+    pub fn clear(&mut self) {
+        self.items.clear();
+    }
+
+    // Fills from `items`; on overflow the error reports how many slots the
+    // whole sequence needed so callers can size N.
+    pub fn try_from_iter<I>(items: I) -> Result<Self, ScheduleFull>
+    where
+        I: IntoIterator<Item = T>,
+    {
+        let mut schedule = Self::new();
+        let mut required = 0;
+        for item in items {
+            required += 1;
+            let _ = schedule.push(item);
+        }
+        if required > N {
+            return Err(ScheduleFull {
+                capacity: N,
+                required,
+            });
+        }
+        Ok(schedule)
+    }
+
+    pub fn into_inner(self) -> heapless::Vec<T, N> {
+        self.items
+    }
+}
+
+impl<T: Copy, const N: usize> Default for FixedSchedule<T, N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+pub type DailyProgram<const N: usize> = FixedSchedule<ProgramStep, N>;
+pub type ShadeProgram<const N: usize> = FixedSchedule<ShadeEvent, N>;
+pub type InverterProgram<const N: usize> = FixedSchedule<InverterEvent, N>;
+
+// Setpoints for one day of a single-axis table: the first daylight entry,
+// then every entry that moves more than `deadband` degrees from the last
+// setpoint sent. Night entries are skipped; the controller's night stow
+// covers them. An unknown day gives an empty program.
+pub fn daily_program<const N: usize>(
+    table: &SingleAxisTable,
+    day_of_year: i32,
+    deadband: f64,
+) -> Result<DailyProgram<N>, ScheduleFull> {
+    let mut last: Option<f64> = None;
+    let steps = day_entries(table, day_of_year)
+        .unwrap_or_default()
+        .iter()
+        .filter_map(move |entry| {
+            let rotation = entry.rotation?;
+            if last.is_some_and(|l| (rotation - l).abs() <= deadband) {
+                return None;
+            }
+            last = Some(rotation);
+            Some(ProgramStep {
+                minutes: entry.minutes,
+                rotation: rotation as f32,
+            })
+        });
+    DailyProgram::try_from_iter(steps)
+}
+
+pub fn shade_program<const N: usize>(
+    table: &ShadeTable,
+    day_of_year: i32,
+) -> Result<ShadeProgram<N>, ScheduleFull> {
+    ShadeProgram::try_from_iter(shade_events(table, day_of_year))
+}
+
+pub fn inverter_program<const N: usize>(
+    table: &FixedTable,
+    mount: &FixedMountConfig,
+    day_of_year: i32,
+    min_poa: f64,
+) -> Result<InverterProgram<N>, ScheduleFull> {
+    InverterProgram::try_from_iter(inverter_events(table, mount, day_of_year, min_poa))
+}
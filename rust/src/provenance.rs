@@ -0,0 +1,59 @@
+use crate::types::{DiagnosticBundle, LookupTable, ProjectMetadata, YieldReport};
+
+// Artifacts that can carry project metadata. Tables keep it in
+// `TableMetadata`, so anything derived from a stamped table (shifted and
+// step tables, difference tables, diagnostic bundles) inherits the stamp.
+pub trait Stamped {
+    fn project(&self) -> Option<&ProjectMetadata>;
+    fn set_project(&mut self, project: Option<ProjectMetadata>);
+}
+
+impl<E> Stamped for LookupTable<E> {
+    fn project(&self) -> Option<&ProjectMetadata> {
+        self.metadata.project.as_ref()
+    }
+
+    fn set_project(&mut self, project: Option<ProjectMetadata>) {
+        self.metadata.project = project;
+    }
+}
+
+impl Stamped for YieldReport {
+    fn project(&self) -> Option<&ProjectMetadata> {
+        self.project.as_ref()
+    }
+
+    fn set_project(&mut self, project: Option<ProjectMetadata>) {
+        self.project = project;
+    }
+}
+
+impl Stamped for DiagnosticBundle {
+    fn project(&self) -> Option<&ProjectMetadata> {
+        self.metadata.project.as_ref()
+    }
+
+    fn set_project(&mut self, project: Option<ProjectMetadata>) {
+        self.metadata.project = project;
+    }
+}
+
+// Builder-style stamping for freshly generated artifacts:
+// `stamp(generate_single_axis_table(&config), &project)`.
+pub fn stamp<T: Stamped>(mut artifact: T, project: &ProjectMetadata) -> T {
+    artifact.set_project(Some(project.clone()));
+    artifact
+}
+
+// Labelled fields that are set, in a fixed order, for report headers.
+pub fn project_fields(project: &ProjectMetadata) -> Vec<(&'static str, &str)> {
+    [
+        ("Site", &project.site_name),
+        ("Project", &project.project_id),
+        ("Operator", &project.operator),
+        ("License", &project.license),
+    ]
+    .into_iter()
+    .filter_map(|(label, value)| Some((label, value.as_deref()?)))
+    .collect()
+}
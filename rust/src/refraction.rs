@@ -0,0 +1,51 @@
+use chrono::{DateTime, TimeZone};
+
+use crate::angles::{deg_to_rad, solar_position};
+use crate::math;
+use crate::types::{Atmosphere, SolarPosition};
+
+// Refraction at the horizon under the standard atmosphere, and the solar
+// radius, in arcminutes. Together they make `SUNRISE_ALTITUDE`.
+const HORIZON_REFRACTION_ARCMIN: f64 = 34.0;
+const SOLAR_RADIUS_ARCMIN: f64 = 16.0;
+
+// Degrees of lift for a true (geometric) altitude, from Sæmundsson's
+// formula scaled by `atmosphere`. As in NREL SPA, nothing is applied once
+// the sun's centre is below `sunrise_altitude`: the formula is fitted to
+// the visible sky and diverges further down.
+pub fn atmospheric_refraction(altitude: f64, atmosphere: &Atmosphere) -> f64 {
+    if altitude < sunrise_altitude(atmosphere) {
+        return 0.0;
+    }
+    let arcmin = 1.02 / math::tan(deg_to_rad(altitude + 10.3 / (altitude + 5.11)));
+    (arcmin * atmosphere.density_factor() / 60.0).max(0.0)
+}
+
+pub fn apparent_altitude(altitude: f64, atmosphere: &Atmosphere) -> f64 {
+    altitude + atmospheric_refraction(altitude, atmosphere)
+}
+
+// The true altitude of the sun's centre at upper-limb sunrise: the horizon
+// refraction scaled by `atmosphere`, plus the solar radius. Equal to
+// `SUNRISE_ALTITUDE` under the default atmosphere; thin mountain air lowers
+// the refraction and so raises it.
+pub fn sunrise_altitude(atmosphere: &Atmosphere) -> f64 {
+    -(HORIZON_REFRACTION_ARCMIN * atmosphere.density_factor() + SOLAR_RADIUS_ARCMIN) / 60.0
+}
+
+// `solar_position` with altitude and zenith as seen through the atmosphere.
+// Azimuth, hour angle and declination are unchanged.
+pub fn solar_position_refracted<Tz: TimeZone>(
+    latitude: f64,
+    longitude: f64,
+    dt: &DateTime<Tz>,
+    atmosphere: &Atmosphere,
+) -> SolarPosition {
+    let pos = solar_position(latitude, longitude, dt);
+    let altitude = apparent_altitude(pos.altitude, atmosphere);
+    SolarPosition {
+        altitude,
+        zenith: 90.0 - altitude,
+        ..pos
+    }
+}
@@ -0,0 +1,221 @@
+use std::fmt::Write;
+
+use crate::lookup_table::{clock_at_table_keys, day_entries};
+use crate::provenance::{project_fields, Stamped};
+use crate::types::{
+    ClockFormat, CommissioningIssue, CommissioningReport, CommissioningStep, CommissioningTarget,
+    MinuteOfDay, MonthBudget, ProjectMetadata, ReportLocale, SingleAxisTable, SweepRow,
+    SweepStrategy,
+};
+
+// Fixed-point with the locale's decimal separator. No digit grouping: a
+// thousands separator is ambiguous between locales and breaks CSV parsing.
+pub fn format_number(value: f64, decimals: usize, locale: &ReportLocale) -> String {
+    let text = format!("{value:.decimals$}");
+    if locale.decimal_separator == '.' {
+        text
+    } else {
+        text.replace('.', &locale.decimal_separator.to_string())
+    }
+}
+
+// Minutes past midnight, wrapped into a single day. 12-hour times follow the
+// usual convention that midnight is 12:00 AM and noon is 12:00 PM.
+pub fn format_time(minutes: i32, locale: &ReportLocale) -> String {
+    let time = MinuteOfDay::wrapping(minutes);
+    let (hour, minute) = (time.hour(), time.minute());
+    match locale.clock {
+        ClockFormat::H24 => format!("{hour:02}:{minute:02}"),
+        ClockFormat::H12 => {
+            let suffix = if hour < 12 { "AM" } else { "PM" };
+            let hour = match hour % 12 {
+                0 => 12,
+                h => h,
+            };
+            format!("{hour}:{minute:02} {suffix}")
+        }
+    }
+}
+
+// Project fields go in '#' comment lines, which `parse_survey_csv` and most
+// spreadsheet importers skip, so free text never has to be quoted.
+// Line breaks in a value become spaces so it cannot end its comment line
+// and start a data row.
+fn write_project_header(out: &mut String, project: Option<&ProjectMetadata>) {
+    for (label, value) in project.map(project_fields).unwrap_or_default() {
+        let value = value.replace(['\r', '\n'], " ");
+        let _ = writeln!(out, "# {label}: {value}");
+    }
+}
+
+fn target_name(target: CommissioningTarget) -> &'static str {
+    match target {
+        CommissioningTarget::EastLimit => "east_limit",
+        CommissioningTarget::Flat => "flat",
+        CommissioningTarget::WestLimit => "west_limit",
+    }
+}
+
+// One row per daylight entry: clock time and rotation in degrees. Times are
+// read on the table's time base, so a UTC table lists UTC and a zoned table
+// local time, whichever minutes the entries are keyed by. A day the table
+// does not hold gives just the header.
+pub fn schedule_csv(table: &SingleAxisTable, day_of_year: i32, locale: &ReportLocale) -> String {
+    let sep = locale.field_separator;
+    let mut out = String::new();
+    write_project_header(&mut out, table.project());
+    let _ = writeln!(out, "time{sep}rotation{sep}clamped");
+    for entry in day_entries(table, day_of_year).unwrap_or_default() {
+        if let Some(rotation) = entry.rotation {
+            let _ = writeln!(
+                out,
+                "{}{sep}{}{sep}{}",
+                format_time(clock_minutes(table, day_of_year, entry.minutes), locale),
+                format_number(rotation, 2, locale),
+                entry.clamped
+            );
+        }
+    }
+    out
+}
+
+fn clock_minutes(table: &SingleAxisTable, day_of_year: i32, minutes: i32) -> i32 {
+    MinuteOfDay::new(minutes)
+        .and_then(|key| clock_at_table_keys(&table.config, day_of_year, key))
+        .map_or(minutes, |clock| MinuteOfDay::from_time(clock.time()).get())
+}
+
+// One row per commissioning step, with the status taken from the report's
+// per-target issues. Structural issues (axis not moving, inverted sign, zero
+// offset) stop verification before the per-step checks, so the rows are then
+// marked unchecked and the issue is listed once after them.
+pub fn commissioning_csv(
+    steps: &[CommissioningStep],
+    readings: &[(CommissioningTarget, f64)],
+    report: &CommissioningReport,
+    locale: &ReportLocale,
+    project: Option<&ProjectMetadata>,
+) -> String {
+    let sep = locale.field_separator;
+    let number = |value: f64| format_number(value, 2, locale);
+    let mut out = String::new();
+    write_project_header(&mut out, project);
+    let _ = writeln!(
+        out,
+        "target{sep}expected{sep}measured{sep}deviation{sep}status"
+    );
+    let structural = report.issues.iter().any(|issue| {
+        matches!(
+            issue,
+            CommissioningIssue::AxisNotMoving { .. }
+                | CommissioningIssue::SignInverted
+                | CommissioningIssue::ZeroOffset { .. }
+        )
+    });
+    let unflagged = if structural { "unchecked" } else { "ok" };
+    for step in steps {
+        let measured = readings
+            .iter()
+            .find(|(t, _)| *t == step.target)
+            .map(|&(_, m)| m);
+        let status = report
+            .issues
+            .iter()
+            .find_map(|issue| match *issue {
+                CommissioningIssue::MissingReading { target } if target == step.target => {
+                    Some("missing")
+                }
+                CommissioningIssue::OutOfTolerance { target, .. } if target == step.target => {
+                    Some("out_of_tolerance")
+                }
+                _ => None,
+            })
+            .unwrap_or(unflagged);
+        let (measured, deviation) = match measured {
+            Some(m) => (number(m), number(m - step.rotation)),
+            None => (String::new(), String::new()),
+        };
+        let _ = writeln!(
+            out,
+            "{}{sep}{}{sep}{measured}{sep}{deviation}{sep}{status}",
+            target_name(step.target),
+            number(step.rotation)
+        );
+    }
+    for issue in &report.issues {
+        match *issue {
+            CommissioningIssue::AxisNotMoving { span } => {
+                let _ = writeln!(out, "# Issue: axis not moving (span {})", number(span));
+            }
+            CommissioningIssue::SignInverted => {
+                let _ = writeln!(out, "# Issue: rotation sign inverted");
+            }
+            CommissioningIssue::ZeroOffset { offset } => {
+                let _ = writeln!(out, "# Issue: zero offset {}", number(offset));
+            }
+            CommissioningIssue::MissingReading { .. }
+            | CommissioningIssue::OutOfTolerance { .. } => {}
+        }
+    }
+    let result = if report.passed { "PASS" } else { "FAIL" };
+    let _ = writeln!(out, "# Result: {result}");
+    out
+}
+
+fn strategy_name(strategy: SweepStrategy) -> &'static str {
+    match strategy {
+        SweepStrategy::Fixed => "fixed",
+        SweepStrategy::SingleAxis => "single_axis",
+        SweepStrategy::Backtracking => "backtracking",
+        SweepStrategy::DualAxis => "dual_axis",
+    }
+}
+
+// One row per design from `yield_sweep`, parameters a strategy does not use
+// left empty, so the file loads straight into a dataframe or pivot table.
+pub fn sweep_csv(rows: &[SweepRow], locale: &ReportLocale) -> String {
+    let sep = locale.field_separator;
+    let optional =
+        |value: Option<f64>| value.map_or(String::new(), |v| format_number(v, 2, locale));
+    let mut out = String::new();
+    let _ = writeln!(
+        out,
+        "strategy{sep}tilt{sep}azimuth{sep}gcr{sep}annual_kwh_per_m2"
+    );
+    for row in rows {
+        let _ = writeln!(
+            out,
+            "{}{sep}{}{sep}{}{sep}{}{sep}{}",
+            strategy_name(row.strategy),
+            optional(row.tilt),
+            optional(row.azimuth),
+            optional(row.gcr),
+            format_number(row.annual_kwh_per_m2, 1, locale)
+        );
+    }
+    out
+}
+
+// One row per month from `monthly_storage_budget`, errors in degrees to the
+// nearest thousandth.
+pub fn monthly_budget_csv(rows: &[MonthBudget], locale: &ReportLocale) -> String {
+    let sep = locale.field_separator;
+    let mut out = String::new();
+    let _ = writeln!(
+        out,
+        "month{sep}days{sep}entries{sep}storage_bytes{sep}max_interpolation_error{sep}uncovered_minutes"
+    );
+    for row in rows {
+        let _ = writeln!(
+            out,
+            "{}{sep}{}{sep}{}{sep}{}{sep}{}{sep}{}",
+            row.month,
+            row.days,
+            row.entries,
+            row.storage_bytes,
+            format_number(row.max_interpolation_error, 3, locale),
+            row.uncovered_minutes
+        );
+    }
+    out
+}
@@ -0,0 +1,37 @@
+use crate::angles::{deg_to_rad, tilted_axis_rotation};
+use crate::math;
+use crate::types::SolarPosition;
+
+// Fraction of a tracker row's width shaded by the row in front, for long
+// rows on flat ground. `projected_zenith` is the sun's angle from vertical
+// in the rotation plane, which is the true-tracking rotation
+// (`tilted_axis_rotation`), and `gcr` is collector width over row pitch.
+// Projected onto a line across the sun's rays each row spans
+// width·cos(rotation − ψ) and neighbours sit pitch·cos ψ apart; the overlap
+// is the shadow. Zero when the sun is behind the panels; at the backtracking
+// rotation it is exactly zero.
+pub fn shaded_fraction(gcr: f64, rotation: f64, projected_zenith: f64) -> f64 {
+    let facing = math::cos(deg_to_rad(rotation - projected_zenith));
+    if facing <= 0.0 || gcr <= 0.0 {
+        return 0.0;
+    }
+    let spacing = math::cos(deg_to_rad(projected_zenith));
+    (1.0 - spacing / (gcr * facing)).clamp(0.0, 1.0)
+}
+
+// Shaded fraction for rows `pitch` apart (axis to axis) carrying collectors
+// `width` wide, with horizontal axes along `axis_azimuth`. Zero with the
+// sun down.
+pub fn row_shaded_fraction(
+    pitch: f64,
+    width: f64,
+    rotation: f64,
+    axis_azimuth: f64,
+    pos: &SolarPosition,
+) -> f64 {
+    if pos.zenith >= 90.0 || pitch <= 0.0 {
+        return 0.0;
+    }
+    let projected_zenith = tilted_axis_rotation(pos, 0.0, axis_azimuth);
+    shaded_fraction(width / pitch, rotation, projected_zenith)
+}
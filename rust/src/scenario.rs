@@ -0,0 +1,172 @@
+use chrono::{Duration, NaiveDate};
+
+use crate::angles;
+use crate::irradiance::{clear_sky_ghi, decompose_ghi};
+use crate::types::{
+    IrradianceComponents, MinuteOfDay, Scenario, ScenarioConfig, SolarPosition, WeatherForecast,
+};
+
+// Independent streams per subsystem, so changing the wind settings leaves
+// the cloud sequence for the same seed untouched.
+const CLOUD_STREAM: u64 = 0x636c_6f75_6473;
+const WIND_STREAM: u64 = 0x7769_6e64;
+
+// Gust factor over the mean wind speed.
+const GUST_FACTOR: f64 = 1.4;
+
+// SplitMix64. Every draw is integer arithmetic plus exactly rounded float
+// operations, so a seed gives bit-identical sequences on every platform,
+// whatever libm is linked.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct SplitMix64(u64);
+
+impl SplitMix64 {
+    fn new(seed: u64, stream: u64) -> Self {
+        Self(seed ^ stream.wrapping_mul(0x9e37_79b9_7f4a_7c15))
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    // Uniform in [0, 1) with 53 random bits.
+    fn uniform(&mut self) -> f64 {
+        (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64
+    }
+
+    // Approximately standard normal (Irwin-Hall, twelve uniforms). Avoids the
+    // ln and cos of Box-Muller, which are not reproducible across libms.
+    fn normal(&mut self) -> f64 {
+        (0..12).map(|_| self.uniform()).sum::<f64>() - 6.0
+    }
+}
+
+fn steps_per_year(config: &ScenarioConfig) -> usize {
+    let days = if angles::leap_year(config.year) {
+        366
+    } else {
+        365
+    };
+    days * 1440 / config.step_minutes.max(1) as usize
+}
+
+// Two-state Markov chain. Leaving a cloudy spell has probability
+// step / mean_cloud_minutes per step; entering one is scaled so the chain
+// spends `cloudy_fraction` of its time cloudy.
+fn cloud_sequence(config: &ScenarioConfig) -> Vec<f64> {
+    let mut rng = SplitMix64::new(config.seed, CLOUD_STREAM);
+    let step = config.step_minutes.max(1) as f64;
+    let cloudy_fraction = config.cloudy_fraction.clamp(0.0, 1.0);
+    let leave = (step / config.mean_cloud_minutes.max(step)).min(1.0);
+    let enter = if cloudy_fraction < 1.0 {
+        (leave * cloudy_fraction / (1.0 - cloudy_fraction)).min(1.0)
+    } else {
+        1.0
+    };
+    let mut cloudy = rng.uniform() < cloudy_fraction;
+    (0..steps_per_year(config))
+        .map(|_| {
+            let switch = if cloudy { leave } else { enter };
+            if rng.uniform() < switch {
+                cloudy = !cloudy;
+            }
+            if cloudy {
+                let spread = 0.1 * rng.normal();
+                (config.cloud_transmittance + spread).clamp(0.0, 1.0)
+            } else {
+                1.0
+            }
+        })
+        .collect()
+}
+
+fn wind_sequence(config: &ScenarioConfig) -> Vec<WeatherForecast> {
+    let mut rng = SplitMix64::new(config.seed, WIND_STREAM);
+    let n = steps_per_year(config);
+    let step = config.step_minutes.max(1);
+    let start = NaiveDate::from_ymd_opt(config.year, 1, 1)
+        .and_then(|d| d.and_hms_opt(0, 0, 0))
+        .unwrap_or_default()
+        .and_utc();
+    let event_probability = (config.wind_events_per_year / n as f64).clamp(0.0, 1.0);
+    let mut event_left = 0;
+    let mut event_gust = 0.0;
+    (0..n)
+        .map(|i| {
+            if event_left == 0 && rng.uniform() < event_probability {
+                event_left = (config.wind_event_minutes / step).max(1);
+                event_gust = config.wind_event_gust * (0.8 + 0.4 * rng.uniform());
+            }
+            let wind_speed = config.mean_wind_speed * (0.5 + rng.uniform());
+            let (wind_speed, wind_gust) = if event_left > 0 {
+                event_left -= 1;
+                (event_gust / GUST_FACTOR, event_gust)
+            } else {
+                (wind_speed, wind_speed * GUST_FACTOR)
+            };
+            WeatherForecast {
+                time: start + Duration::minutes(i as i64 * step as i64),
+                wind_speed,
+                wind_gust,
+            }
+        })
+        .collect()
+}
+
+// A year of clouds and wind from `config.seed`. The same seed and settings
+// give the same scenario on every machine and run.
+pub fn generate_scenario(config: &ScenarioConfig) -> Scenario {
+    Scenario {
+        config: *config,
+        transmittance: cloud_sequence(config),
+        wind: wind_sequence(config),
+    }
+}
+
+// Irradiance source for `simulate_yield_with`: clear-sky GHI scaled by the
+// step's transmittance and split with Erbs, so cloudy steps turn diffuse.
+// Out-of-year days reuse the last step.
+pub fn scenario_irradiance(
+    scenario: &Scenario,
+) -> impl Fn(i32, MinuteOfDay, &SolarPosition) -> Option<IrradianceComponents> + Sync + '_ {
+    let step = scenario.config.step_minutes.max(1) as usize;
+    move |day_of_year, minutes, pos| {
+        let minute_of_year = (day_of_year.max(1) as usize - 1) * 1440 + minutes.get() as usize;
+        let last = scenario.transmittance.len().checked_sub(1)?;
+        let transmittance = scenario.transmittance[(minute_of_year / step).min(last)];
+        let ghi = clear_sky_ghi(pos.zenith) * transmittance;
+        let split = decompose_ghi(ghi, pos)?;
+        Some(IrradianceComponents {
+            ghi,
+            dni: split.dni,
+            dhi: split.dhi,
+        })
+    }
+}
+
+// Angle sensor with seeded Gaussian noise and a fixed bias, for replaying
+// the same noisy readings into a controller run after run.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct NoisySensor {
+    rng: SplitMix64,
+    sigma: f64,
+    bias: f64,
+}
+
+impl NoisySensor {
+    pub fn new(seed: u64, sigma: f64, bias: f64) -> Self {
+        Self {
+            rng: SplitMix64::new(seed, 0),
+            sigma,
+            bias,
+        }
+    }
+
+    pub fn read(&mut self, true_value: f64) -> f64 {
+        true_value + self.bias + self.sigma * self.rng.normal()
+    }
+}
@@ -0,0 +1,74 @@
+use crate::interp::HasMinutes;
+use crate::irradiance::clear_sky_ghi;
+use crate::lookup_table::{day_entries, generate_table, lookup_nearest};
+use crate::types::{LookupTableConfig, MinuteOfDay, ShadeConfig, ShadeEntry, ShadeEvent, ShadeTable};
+
+impl HasMinutes for ShadeEntry {
+    fn minutes(&self) -> i32 {
+        self.minutes
+    }
+
+    fn set_minutes(&mut self, minutes: i32) {
+        self.minutes = minutes;
+    }
+}
+
+// Azimuth ranges may wrap through north, e.g. (300, 60).
+pub(crate) fn azimuth_in_range(azimuth: f64, (from, to): (f64, f64)) -> bool {
+    if from <= to {
+        (from..=to).contains(&azimuth)
+    } else {
+        azimuth >= from || azimuth <= to
+    }
+}
+
+pub fn screen_closed(shade: &ShadeConfig, zenith: f64, azimuth: f64) -> bool {
+    90.0 - zenith >= shade.min_altitude
+        && shade
+            .azimuth_range
+            .is_none_or(|range| azimuth_in_range(azimuth, range))
+        && clear_sky_ghi(zenith) >= shade.min_clear_sky_ghi
+}
+
+pub fn generate_shade_table(config: &LookupTableConfig, shade: &ShadeConfig) -> ShadeTable {
+    let shade = *shade;
+    generate_table(
+        config,
+        move |minutes, angles, is_daylight| ShadeEntry {
+            minutes,
+            closed: is_daylight && screen_closed(&shade, angles.zenith, angles.azimuth),
+        },
+        1,
+    )
+}
+
+pub fn lookup_shade(
+    table: &ShadeTable,
+    day_of_year: i32,
+    minutes: MinuteOfDay,
+) -> Option<ShadeEntry> {
+    lookup_nearest(table, day_of_year, minutes)
+}
+
+// Open/close transitions for one day, starting open. Borrows the table and
+// allocates nothing, so controllers can walk it on the control path;
+// `shade_schedule` collects the same events.
+pub fn shade_events(table: &ShadeTable, day_of_year: i32) -> impl Iterator<Item = ShadeEvent> + '_ {
+    let mut closed = false;
+    day_entries(table, day_of_year)
+        .unwrap_or_default()
+        .iter()
+        .filter(move |entry| {
+            let changed = entry.closed != closed;
+            closed = entry.closed;
+            changed
+        })
+        .map(|entry| ShadeEvent {
+            minutes: entry.minutes,
+            closed: entry.closed,
+        })
+}
+
+pub fn shade_schedule(table: &ShadeTable, day_of_year: i32) -> Vec<ShadeEvent> {
+    shade_events(table, day_of_year).collect()
+}
@@ -0,0 +1,165 @@
+use crate::lookup_table::{blend_single_axis, month_statistics};
+use crate::types::{
+    DayData, MinuteOfDay, SimplifiedTable, SimplifyReport, SingleAxisEntry, SingleAxisTable,
+};
+
+// Each kept entry also stores its minute once spacing is irregular.
+const MINUTES_BYTES: usize = 2;
+
+fn value_bytes(table: &SingleAxisTable) -> usize {
+    if table.config.include_surface_orientation {
+        8
+    } else {
+        4
+    }
+}
+
+fn rotation(entries: &[SingleAxisEntry], i: usize) -> f64 {
+    entries[i].rotation.unwrap_or(0.0)
+}
+
+// Douglas-Peucker over one run of daylight entries, measuring the error in
+// rotation at each dropped entry's minute, which is what `lookup_simplified`
+// will interpolate. Marks kept entries and returns the worst error left.
+fn douglas_peucker(entries: &[SingleAxisEntry], max_error: f64, keep: &mut [bool]) -> f64 {
+    let mut worst_dropped = 0.0f64;
+    let mut stack = vec![(0, entries.len() - 1)];
+    keep[0] = true;
+    keep[entries.len() - 1] = true;
+    while let Some((first, last)) = stack.pop() {
+        if last <= first + 1 {
+            continue;
+        }
+        let (t0, t1) = (entries[first].minutes as f64, entries[last].minutes as f64);
+        let (r0, r1) = (rotation(entries, first), rotation(entries, last));
+        let (worst, error) = (first + 1..last)
+            .map(|i| {
+                let fraction = (entries[i].minutes as f64 - t0) / (t1 - t0);
+                (i, (r0 + fraction * (r1 - r0) - rotation(entries, i)).abs())
+            })
+            .max_by(|a, b| a.1.total_cmp(&b.1))
+            .unwrap_or((first, 0.0));
+        if error > max_error {
+            keep[worst] = true;
+            stack.push((first, worst));
+            stack.push((worst, last));
+        } else {
+            worst_dropped = worst_dropped.max(error);
+        }
+    }
+    worst_dropped
+}
+
+// Drops every entry that interpolation between its kept neighbours
+// reconstructs within `max_error_deg`, day by day. Night runs keep only their
+// ends, so the day's daylight edges are unchanged. The result is no longer
+// evenly spaced, so it comes back as a `SimplifiedTable` for
+// `lookup_simplified`.
+pub fn simplify_table(
+    table: &SingleAxisTable,
+    max_error_deg: f64,
+) -> (SimplifiedTable, SimplifyReport) {
+    let mut max_error = 0.0f64;
+    let days: Vec<DayData<SingleAxisEntry>> = table
+        .days
+        .iter()
+        .map(|day| {
+            let entries = &day.entries;
+            let mut keep = vec![false; entries.len()];
+            let mut start = 0;
+            while start < entries.len() {
+                let daylight = entries[start].rotation.is_some();
+                let end = entries[start..]
+                    .iter()
+                    .position(|e| e.rotation.is_some() != daylight)
+                    .map_or(entries.len(), |n| start + n);
+                let run = &entries[start..end];
+                if daylight {
+                    let error = douglas_peucker(run, max_error_deg, &mut keep[start..end]);
+                    max_error = max_error.max(error);
+                } else {
+                    keep[start] = true;
+                    keep[end - 1] = true;
+                }
+                start = end;
+            }
+            DayData {
+                day_of_year: day.day_of_year,
+                sunrise_minutes: day.sunrise_minutes,
+                sunset_minutes: day.sunset_minutes,
+                solar_noon_minutes: day.solar_noon_minutes,
+                entries: entries
+                    .iter()
+                    .zip(&keep)
+                    .filter(|(_, &k)| k)
+                    .map(|(e, _)| *e)
+                    .collect(),
+            }
+        })
+        .collect();
+
+    let kept_entries: usize = days.iter().map(|d| d.entries.len()).sum();
+    let report = SimplifyReport {
+        original_entries: table.metadata.total_entries,
+        kept_entries,
+        original_bytes: table.metadata.total_entries * value_bytes(table),
+        simplified_bytes: kept_entries * (value_bytes(table) + MINUTES_BYTES),
+        max_error,
+    };
+    let mut metadata = table.metadata.clone();
+    metadata.total_entries = kept_entries;
+    metadata.storage_estimate_kb = report.simplified_bytes as f64 / 1024.0;
+    metadata.months = month_statistics(table.config.year, &days);
+    let simplified = SimplifiedTable {
+        config: table.config,
+        days,
+        metadata,
+    };
+    (simplified, report)
+}
+
+// Tightest error bound whose simplified table fits in `max_bytes`, found by
+// bisection to 0.001°. None when even keeping only each run's ends does not
+// fit.
+pub fn simplify_to_budget(
+    table: &SingleAxisTable,
+    max_bytes: usize,
+) -> Option<(SimplifiedTable, SimplifyReport)> {
+    let (mut lo, mut hi) = (0.0, 180.0);
+    let mut best = simplify_table(table, hi);
+    if best.1.simplified_bytes > max_bytes {
+        return None;
+    }
+    while hi - lo > 0.001 {
+        let mid = (lo + hi) / 2.0;
+        let trial = simplify_table(table, mid);
+        if trial.1.simplified_bytes <= max_bytes {
+            hi = mid;
+            best = trial;
+        } else {
+            lo = mid;
+        }
+    }
+    Some(best)
+}
+
+// Linear between the kept entries around `minutes`, found by binary search;
+// None outside the day's entries or for a day the table does not hold.
+// Allocation-free, like the other lookups.
+pub fn lookup_simplified(
+    table: &SimplifiedTable,
+    day_of_year: i32,
+    minutes: MinuteOfDay,
+) -> Option<SingleAxisEntry> {
+    let minutes = minutes.get();
+    let index = usize::try_from(day_of_year.checked_sub(1)?).ok()?;
+    let entries = &table.days.get(index)?.entries;
+    let after = entries.partition_point(|e| e.minutes < minutes);
+    let next = entries.get(after)?;
+    if next.minutes == minutes {
+        return Some(*next);
+    }
+    let before = &entries[after.checked_sub(1)?];
+    let fraction = (minutes - before.minutes) as f64 / (next.minutes - before.minutes) as f64;
+    Some(blend_single_axis(before, next, fraction, minutes))
+}
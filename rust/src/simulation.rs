@@ -0,0 +1,273 @@
+use chrono::{DateTime, Datelike, Duration, TimeZone};
+
+use crate::angles;
+use crate::irradiance::clear_sky_components;
+use crate::lookup_table::date_from_day_of_year;
+use crate::row_shading::shaded_fraction;
+use crate::transposition::poa_irradiance;
+use crate::types::{
+    DeclinationModel, IrradianceComponents, MinuteOfDay, SimulationConfig, SkyDiffuseModel, SolarPosition,
+    SurfaceOrientation, TrackingStrategy, YieldReport,
+};
+
+fn single_axis_rotation(
+    axis_tilt: f64,
+    axis_azimuth: f64,
+    max_rotation: Option<f64>,
+    gcr: Option<f64>,
+    pos: &SolarPosition,
+) -> f64 {
+    let ideal = angles::tilted_axis_rotation(pos, axis_tilt, axis_azimuth);
+    let rotation = gcr.map_or(ideal, |gcr| angles::backtrack(ideal, gcr));
+    let limit = max_rotation.map(f64::abs);
+    angles::clamp_rotation(rotation, limit.map(|l| -l), limit).0
+}
+
+// Surface the strategy would hold with the sun at `pos`.
+pub fn strategy_surface(strategy: &TrackingStrategy, pos: &SolarPosition) -> SurfaceOrientation {
+    match *strategy {
+        TrackingStrategy::Fixed { tilt, azimuth } => SurfaceOrientation {
+            surface_tilt: tilt,
+            surface_azimuth: azimuth,
+        },
+        TrackingStrategy::SingleAxis {
+            axis_tilt,
+            axis_azimuth,
+            max_rotation,
+            gcr,
+        } => {
+            let rotation = single_axis_rotation(axis_tilt, axis_azimuth, max_rotation, gcr, pos);
+            angles::surface_orientation(rotation, axis_tilt, axis_azimuth)
+        }
+        TrackingStrategy::DualAxis => SurfaceOrientation {
+            surface_tilt: pos.zenith,
+            surface_azimuth: pos.azimuth,
+        },
+    }
+}
+
+// Fraction of single-axis beam lost to the row in front at `config.row_gcr`.
+// Electrical mismatch from partial shading is not modelled.
+fn row_shading(config: &SimulationConfig, pos: &SolarPosition) -> f64 {
+    match (config.row_gcr, config.strategy) {
+        (
+            Some(row_gcr),
+            TrackingStrategy::SingleAxis {
+                axis_tilt,
+                axis_azimuth,
+                max_rotation,
+                gcr,
+            },
+        ) => {
+            let rotation = single_axis_rotation(axis_tilt, axis_azimuth, max_rotation, gcr, pos);
+            let projected = angles::tilted_axis_rotation(pos, axis_tilt, axis_azimuth);
+            shaded_fraction(row_gcr, rotation, projected)
+        }
+        _ => 0.0,
+    }
+}
+
+// Angle in [lo, hi] maximizing `score`: a 1° scan, then 0.1° around the
+// best. Plane-of-array irradiance is smooth in the angle, so this lands
+// within 0.05° of the optimum.
+fn best_angle(lo: f64, hi: f64, score: impl Fn(f64) -> f64) -> f64 {
+    let scan = |from: f64, to: f64, step: f64| {
+        let n = ((to - from) / step).round().max(0.0) as usize;
+        (0..=n)
+            .map(|i| (from + i as f64 * step).min(to))
+            .map(|angle| (angle, score(angle)))
+            .max_by(|a, b| a.1.total_cmp(&b.1))
+            .map_or(from, |(angle, _)| angle)
+    };
+    let coarse = scan(lo, hi, 1.0);
+    scan((coarse - 1.0).max(lo), (coarse + 1.0).min(hi), 0.1)
+}
+
+// Rotation of a single-axis tracker that collects the most plane-of-array
+// irradiance from `irradiance` as given, rather than pointing at the sun.
+// Under an overcast sky that is near flat: isotropic diffuse and a ground
+// albedo below one always favour horizontal, while the Perez and HDKR
+// circumsolar terms lean it slightly toward the sun. Backtracking is not
+// applied; None for other strategies.
+pub fn diffuse_optimal_rotation(
+    strategy: &TrackingStrategy,
+    pos: &SolarPosition,
+    irradiance: &IrradianceComponents,
+    albedo: f64,
+    model: SkyDiffuseModel,
+) -> Option<f64> {
+    let TrackingStrategy::SingleAxis {
+        axis_tilt,
+        axis_azimuth,
+        max_rotation,
+        ..
+    } = *strategy
+    else {
+        return None;
+    };
+    let limit = max_rotation.map_or(90.0, f64::abs).min(90.0);
+    Some(best_angle(-limit, limit, |rotation| {
+        let surface = angles::surface_orientation(rotation, axis_tilt, axis_azimuth);
+        poa_irradiance(pos, &surface, irradiance, albedo, model).total
+    }))
+}
+
+// Surface the strategy would hold in diffuse-optimal mode. Dual-axis
+// trackers tilt toward the sun's azimuth by whatever angle scores best;
+// fixed arrays cannot move.
+pub fn diffuse_optimal_surface(
+    strategy: &TrackingStrategy,
+    pos: &SolarPosition,
+    irradiance: &IrradianceComponents,
+    albedo: f64,
+    model: SkyDiffuseModel,
+) -> SurfaceOrientation {
+    match *strategy {
+        TrackingStrategy::Fixed { .. } => strategy_surface(strategy, pos),
+        TrackingStrategy::SingleAxis {
+            axis_tilt,
+            axis_azimuth,
+            ..
+        } => {
+            let rotation =
+                diffuse_optimal_rotation(strategy, pos, irradiance, albedo, model).unwrap_or(0.0);
+            angles::surface_orientation(rotation, axis_tilt, axis_azimuth)
+        }
+        TrackingStrategy::DualAxis => {
+            let toward_sun = |surface_tilt| SurfaceOrientation {
+                surface_tilt,
+                surface_azimuth: pos.azimuth,
+            };
+            let tilt = best_angle(0.0, 90.0, |tilt| {
+                poa_irradiance(pos, &toward_sun(tilt), irradiance, albedo, model).total
+            });
+            toward_sun(tilt)
+        }
+    }
+}
+
+// Clear-sky plane-of-array insolation for the year.
+pub fn simulate_yield(config: &SimulationConfig) -> YieldReport {
+    simulate_yield_with(config, |_, _, pos| clear_sky_components(pos))
+}
+
+pub(crate) fn step_minutes(config: &SimulationConfig) -> i32 {
+    config.step_minutes.clamp(1, 1440)
+}
+
+fn days_in_year(year: i32) -> usize {
+    if angles::leap_year(year) {
+        366
+    } else {
+        365
+    }
+}
+
+// Visits every sun-up step of the year with its irradiance, sampling the sun
+// at the middle of each step so the midpoint rule integrates the day. Shared
+// by the yield sweep and the orientation optimizer so both integrate the
+// same way.
+pub(crate) fn for_each_step<F, V>(config: &SimulationConfig, mut irradiance: F, mut visit: V)
+where
+    F: FnMut(i32, MinuteOfDay, &SolarPosition) -> Option<IrradianceComponents>,
+    V: FnMut(&SolarPosition, &IrradianceComponents),
+{
+    let step = step_minutes(config);
+    for doy in 1..=days_in_year(config.year) as i32 {
+        let eot = angles::equation_of_time_in_year(doy, config.year);
+        let decl = angles::declination_in_year(doy, config.year, DeclinationModel::Cooper);
+        let correction = angles::utc_lst_correction(config.longitude, eot);
+        for minutes in (0..1440).step_by(step as usize) {
+            let utc_hours = (minutes as f64 + step as f64 / 2.0) / 60.0;
+            let (lst, ha, zenith, altitude, azimuth) =
+                angles::solar_angles_at(config.latitude, decl, correction, utc_hours);
+            if zenith >= 90.0 {
+                continue;
+            }
+            let pos = SolarPosition {
+                day_of_year: doy,
+                declination: decl,
+                equation_of_time: eot,
+                local_solar_time: lst,
+                hour_angle: ha,
+                zenith,
+                altitude,
+                azimuth,
+            };
+            if let Some(components) = irradiance(doy, MinuteOfDay::wrapping(minutes), &pos) {
+                visit(&pos, &components);
+            }
+        }
+    }
+}
+
+// Sweeps the year at `step_minutes` (clamped to 1..=1440). The irradiance
+// source gets (day_of_year, UTC minute at the step start, sun position) and
+// may return None for no data, which counts as zero; steps with the sun
+// down are skipped without asking it.
+pub fn simulate_yield_with<F>(config: &SimulationConfig, irradiance: F) -> YieldReport
+where
+    F: FnMut(i32, MinuteOfDay, &SolarPosition) -> Option<IrradianceComponents>,
+{
+    let step_hours = step_minutes(config) as f64 / 60.0;
+    let mut daily_wh = vec![0.0; days_in_year(config.year)];
+    for_each_step(config, irradiance, |pos, components| {
+        let surface = strategy_surface(&config.strategy, pos);
+        let poa = poa_irradiance(pos, &surface, components, config.albedo, config.sky_model);
+        let total = poa.total - row_shading(config, pos) * poa.beam;
+        daily_wh[(pos.day_of_year - 1) as usize] += total * step_hours;
+    });
+
+    let mut report = YieldReport {
+        daily_kwh_per_m2: Vec::with_capacity(daily_wh.len()),
+        monthly_kwh_per_m2: [0.0; 12],
+        annual_kwh_per_m2: 0.0,
+        project: None,
+    };
+    for (doy, wh) in (1..).zip(daily_wh) {
+        let daily = wh / 1000.0;
+        report.daily_kwh_per_m2.push(daily);
+        if let Some(date) = date_from_day_of_year(config.year, doy) {
+            report.monthly_kwh_per_m2[date.month0() as usize] += daily;
+        }
+        report.annual_kwh_per_m2 += daily;
+    }
+    report
+}
+
+const REMAINING_STEP_MINUTES: i64 = 5;
+
+// Clear-sky plane-of-array energy (kWh/m²) still to come from `now` until
+// the sun next sets, with the same sky model and albedo as the default
+// simulation. After sunset this is zero; before sunrise it is the whole
+// coming day; under the midnight sun it stops after 24 hours. Samples every
+// five minutes at step midpoints.
+pub fn remaining_clearsky_energy<Tz: TimeZone>(
+    now: &DateTime<Tz>,
+    latitude: f64,
+    longitude: f64,
+    strategy: &TrackingStrategy,
+) -> f64 {
+    let defaults = SimulationConfig::default();
+    let step = Duration::minutes(REMAINING_STEP_MINUTES);
+    let step_hours = REMAINING_STEP_MINUTES as f64 / 60.0;
+    let mut t = now.clone() + step / 2;
+    let mut wh = 0.0;
+    let mut risen = false;
+    for _ in 0..(1440 / REMAINING_STEP_MINUTES) {
+        let pos = angles::solar_position(latitude, longitude, &t);
+        if pos.zenith < 90.0 {
+            risen = true;
+            if let Some(components) = clear_sky_components(&pos) {
+                let surface = strategy_surface(strategy, &pos);
+                let (albedo, model) = (defaults.albedo, defaults.sky_model);
+                let poa = poa_irradiance(&pos, &surface, &components, albedo, model);
+                wh += poa.total * step_hours;
+            }
+        } else if risen || pos.local_solar_time >= 12.0 {
+            break;
+        }
+        t += step;
+    }
+    wh / 1000.0
+}
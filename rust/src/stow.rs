@@ -0,0 +1,174 @@
+use chrono::{DateTime, Duration, Utc};
+
+use crate::magnetic;
+use crate::types::{
+    ControllerMode, DualAxisTable, SingleAxisTable, StowAngles, StowConfig, StowMode,
+    StowPlanConfig, StowReason, StowWindow, TrackerKind, WeatherForecast,
+};
+
+pub trait WeatherProvider {
+    fn forecast(&self, from: DateTime<Utc>, horizon_minutes: i64) -> Vec<WeatherForecast>;
+}
+
+impl WeatherProvider for [WeatherForecast] {
+    fn forecast(&self, from: DateTime<Utc>, horizon_minutes: i64) -> Vec<WeatherForecast> {
+        let until = from + Duration::minutes(horizon_minutes);
+        self.iter()
+            .filter(|f| f.time >= from && f.time <= until)
+            .copied()
+            .collect()
+    }
+}
+
+impl WeatherProvider for Vec<WeatherForecast> {
+    fn forecast(&self, from: DateTime<Utc>, horizon_minutes: i64) -> Vec<WeatherForecast> {
+        self.as_slice().forecast(from, horizon_minutes)
+    }
+}
+
+pub fn plan_stow(forecast: &[WeatherForecast], config: &StowPlanConfig) -> Vec<StowWindow> {
+    let mut samples = forecast.to_vec();
+    samples.sort_by_key(|f| f.time);
+
+    let lead = Duration::minutes(config.lead_minutes);
+    let hold = Duration::minutes(config.hold_minutes);
+    let mut windows: Vec<StowWindow> = Vec::new();
+    let mut event: Option<(DateTime<Utc>, DateTime<Utc>, f64)> = None;
+
+    // Enter on the trigger threshold, leave only once gusts fall to the
+    // (lower) release threshold.
+    for f in &samples {
+        event = match event {
+            None if f.wind_gust >= config.gust_threshold => Some((f.time, f.time, f.wind_gust)),
+            None => None,
+            Some((start, _, peak)) if f.wind_gust > config.release_threshold => {
+                Some((start, f.time, peak.max(f.wind_gust)))
+            }
+            Some((start, last, peak)) => {
+                push_window(&mut windows, start - lead, last + hold, peak, config);
+                None
+            }
+        };
+    }
+    if let Some((start, last, peak)) = event {
+        push_window(&mut windows, start - lead, last + hold, peak, config);
+    }
+    windows
+}
+
+fn push_window(
+    windows: &mut Vec<StowWindow>,
+    enter: DateTime<Utc>,
+    exit: DateTime<Utc>,
+    peak_gust: f64,
+    config: &StowPlanConfig,
+) {
+    // Merge with the previous window if unstowing in between would be too brief
+    if let Some(prev) = windows.last_mut() {
+        if enter - prev.exit < Duration::minutes(config.min_gap_minutes) {
+            prev.exit = prev.exit.max(exit);
+            prev.peak_gust = prev.peak_gust.max(peak_gust);
+            return;
+        }
+    }
+    windows.push(StowWindow {
+        enter,
+        exit,
+        peak_gust,
+    });
+}
+
+pub fn plan_stow_from<P: WeatherProvider + ?Sized>(
+    provider: &P,
+    now: DateTime<Utc>,
+    config: &StowPlanConfig,
+) -> Vec<StowWindow> {
+    let forecast = provider.forecast(now, config.horizon_minutes);
+    plan_stow(&forecast, config)
+}
+
+pub fn stow_active(plan: &[StowWindow], at: DateTime<Utc>) -> bool {
+    plan.iter().any(|w| at >= w.enter && at < w.exit)
+}
+
+impl From<StowReason> for StowMode {
+    fn from(reason: StowReason) -> Self {
+        match reason {
+            StowReason::Snow => StowMode::Snow,
+            StowReason::Hail => StowMode::Hail,
+            StowReason::Wind
+            | StowReason::Lightning
+            | StowReason::CommunicationLoss
+            | StowReason::Manual => StowMode::Wind,
+        }
+    }
+}
+
+pub fn controller_stow_mode(mode: ControllerMode) -> Option<StowMode> {
+    match mode {
+        ControllerMode::Stowed(reason) => Some(reason.into()),
+        ControllerMode::Night => Some(StowMode::Night),
+        _ => None,
+    }
+}
+
+// Single-axis stow positions are stored as signed rotations; the returned
+// tilt is the rotation magnitude and the azimuth the side the panel faces
+// (for a north-south axis).
+pub fn stow_angles_with(config: &StowConfig, mode: StowMode, kind: TrackerKind) -> (f64, f64) {
+    match kind {
+        TrackerKind::SingleAxis => {
+            let rotation = single_axis_stow_rotation(config, mode);
+            let azimuth = if rotation < 0.0 { 90.0 } else { 270.0 };
+            (rotation.abs(), azimuth)
+        }
+        TrackerKind::DualAxis => {
+            let angles = dual_axis_stow_angles(config, mode);
+            (angles.tilt, angles.azimuth)
+        }
+    }
+}
+
+pub fn stow_angles(mode: StowMode, kind: TrackerKind) -> (f64, f64) {
+    stow_angles_with(&StowConfig::default(), mode, kind)
+}
+
+pub fn single_axis_stow_rotation(config: &StowConfig, mode: StowMode) -> f64 {
+    match mode {
+        StowMode::Wind => config.single_axis_wind,
+        StowMode::Snow => config.single_axis_snow,
+        StowMode::Hail => config.single_axis_hail,
+        StowMode::Night => config.single_axis_night,
+    }
+}
+
+pub fn dual_axis_stow_angles(config: &StowConfig, mode: StowMode) -> StowAngles {
+    match mode {
+        StowMode::Wind => config.dual_axis_wind,
+        StowMode::Snow => config.dual_axis_snow,
+        StowMode::Hail => config.dual_axis_hail,
+        StowMode::Night => config.dual_axis_night,
+    }
+}
+
+pub fn fill_single_axis_stow(table: &mut SingleAxisTable, rotation: f64) {
+    for entry in table.days.iter_mut().flat_map(|d| d.entries.iter_mut()) {
+        if entry.rotation.is_none() {
+            entry.rotation = Some(rotation);
+        }
+    }
+}
+
+pub fn fill_dual_axis_stow(table: &mut DualAxisTable, angles: StowAngles) {
+    // Stored like the daylight entries: a magnetic bearing when the install
+    // is compass-aligned, then in the table's azimuth encoding
+    let declination = table.config.magnetic_declination.unwrap_or(0.0);
+    let magnetic_azimuth = magnetic::magnetic_bearing(angles.azimuth, declination);
+    let azimuth = table.config.azimuth_encoding.encode(magnetic_azimuth);
+    for entry in table.days.iter_mut().flat_map(|d| d.entries.iter_mut()) {
+        if entry.tilt.is_none() {
+            entry.tilt = Some(angles.tilt);
+            entry.panel_azimuth = Some(azimuth);
+        }
+    }
+}
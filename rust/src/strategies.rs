@@ -0,0 +1,89 @@
+use chrono::{DateTime, Duration, NaiveDate, Utc};
+use proptest::prelude::*;
+
+use crate::angles::VALID_YEARS;
+use crate::types::LookupTableConfig;
+
+// proptest strategies over the inputs this crate supports, so downstream
+// crates can property-test their integrations against the same domain.
+// Each draws uniformly with extra weight on the edges where solar geometry
+// degenerates (the poles, the equator, the antimeridian). Every config
+// passes `validate_config`.
+
+fn first_day() -> NaiveDate {
+    NaiveDate::from_ymd_opt(*VALID_YEARS.start(), 1, 1).unwrap_or(NaiveDate::MIN)
+}
+
+fn last_day() -> NaiveDate {
+    NaiveDate::from_ymd_opt(*VALID_YEARS.end(), 12, 31).unwrap_or(NaiveDate::MAX)
+}
+
+pub fn latitudes() -> impl Strategy<Value = f64> {
+    prop_oneof![
+        8 => -90.0..=90.0,
+        1 => Just(90.0),
+        1 => Just(-90.0),
+        1 => Just(0.0),
+    ]
+}
+
+pub fn longitudes() -> impl Strategy<Value = f64> {
+    prop_oneof![
+        8 => -180.0..=180.0,
+        1 => Just(180.0),
+        1 => Just(-180.0),
+        1 => Just(0.0),
+    ]
+}
+
+pub fn years() -> impl Strategy<Value = i32> {
+    VALID_YEARS
+}
+
+// Whole dates in `VALID_YEARS`, shrinking toward its first day.
+pub fn dates() -> impl Strategy<Value = NaiveDate> {
+    let first = first_day();
+    (0..=(last_day() - first).num_days()).prop_map(move |days| first + Duration::days(days))
+}
+
+// UTC instants to the second in `VALID_YEARS`.
+pub fn datetimes() -> impl Strategy<Value = DateTime<Utc>> {
+    let first = first_day().and_time(chrono::NaiveTime::MIN).and_utc();
+    let seconds = (last_day() - first_day()).num_seconds() + 86_399;
+    (0..=seconds).prop_map(move |s| first + Duration::seconds(s))
+}
+
+// Divisors of a day from 5 minutes to 2 hours, where a table is small
+// enough to generate once per case.
+pub fn interval_minutes() -> impl Strategy<Value = i32> {
+    prop::sample::select(vec![5, 10, 15, 20, 30, 60, 120])
+}
+
+pub fn configs() -> impl Strategy<Value = LookupTableConfig> {
+    (
+        latitudes(),
+        longitudes(),
+        interval_minutes(),
+        years(),
+        prop::option::of(0.1..=0.9),
+        prop::option::of(15.0..=90.0),
+        0..=60,
+        0..=60,
+    )
+        .prop_map(
+            |(latitude, longitude, interval_minutes, year, gcr, limit, sunrise, sunset)| {
+                LookupTableConfig {
+                    latitude,
+                    longitude,
+                    interval_minutes,
+                    year,
+                    gcr,
+                    min_rotation: limit.map(|l: f64| -l),
+                    max_rotation: limit,
+                    sunrise_buffer_minutes: sunrise,
+                    sunset_buffer_minutes: sunset,
+                    ..Default::default()
+                }
+            },
+        )
+}
@@ -0,0 +1,57 @@
+use crate::angles;
+use crate::lookup_table::precise_sunrise_sunset;
+use crate::types::{SunPathCurve, SunPathPoint};
+
+// (month, day) of the usual solstice/equinox curves on a sun-path chart.
+const KEY_DATES: [(u32, u32); 4] = [(3, 20), (6, 21), (9, 22), (12, 21)];
+
+pub fn key_sun_path_days(year: i32) -> Vec<i32> {
+    KEY_DATES
+        .iter()
+        .map(|&(month, day)| angles::day_of_year(year, month, day))
+        .collect()
+}
+
+pub fn monthly_sun_path_days(year: i32) -> Vec<i32> {
+    (1..=12)
+        .map(|month| angles::day_of_year(year, month, 21))
+        .collect()
+}
+
+// Samples run in local solar time from sunrise to sunset, so curves for
+// different longitudes coincide. Sunrise and sunset are always included as
+// the end points; polar night yields an empty curve.
+pub fn sun_path(latitude: f64, day_of_year: i32, step_minutes: i32) -> SunPathCurve {
+    let decl = angles::solar_declination(day_of_year);
+    let sun = precise_sunrise_sunset(latitude, day_of_year);
+    let mut minutes: Vec<i32> = Vec::new();
+    if sun.sunset > sun.sunrise {
+        let step = step_minutes.max(1);
+        let first = (sun.sunrise / step + 1) * step;
+        minutes.push(sun.sunrise);
+        minutes.extend((first..sun.sunset).step_by(step as usize));
+        minutes.push(sun.sunset);
+    }
+    let points = minutes
+        .into_iter()
+        .map(|m| {
+            let (_, _, _, altitude, azimuth) =
+                angles::solar_angles_at(latitude, decl, 0.0, m as f64 / 60.0);
+            SunPathPoint {
+                minutes: m,
+                altitude,
+                azimuth,
+            }
+        })
+        .collect();
+    SunPathCurve {
+        day_of_year,
+        points,
+    }
+}
+
+pub fn sun_path_diagram(latitude: f64, days: &[i32], step_minutes: i32) -> Vec<SunPathCurve> {
+    days.iter()
+        .map(|&doy| sun_path(latitude, doy, step_minutes))
+        .collect()
+}
@@ -0,0 +1,97 @@
+use std::fmt;
+
+use crate::types::{LookupTableConfig, SurveyRow};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SurveyError {
+    MissingColumn(&'static str),
+    MissingField { line: usize, column: &'static str },
+    InvalidNumber { line: usize, column: &'static str },
+}
+
+impl fmt::Display for SurveyError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SurveyError::MissingColumn(column) => write!(f, "missing column {column}"),
+            SurveyError::MissingField { line, column } => {
+                write!(f, "line {line}: missing {column}")
+            }
+            SurveyError::InvalidNumber { line, column } => {
+                write!(f, "line {line}: invalid number in {column}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for SurveyError {}
+
+const ID: &str = "row_id";
+const AXIS_AZIMUTH: &str = "axis_azimuth";
+const AXIS_TILT: &str = "axis_tilt";
+const GCR_EAST: &str = "gcr_east";
+const GCR_WEST: &str = "gcr_west";
+
+// Plain comma-separated values with a header row naming the columns in any
+// order. The GCR columns are optional and may be left blank for rows at the
+// edge of a block. Blank lines and lines starting with '#' are skipped.
+pub fn parse_survey_csv(input: &str) -> Result<Vec<SurveyRow>, SurveyError> {
+    let mut lines = input
+        .lines()
+        .enumerate()
+        .map(|(i, line)| (i + 1, line.trim()))
+        .filter(|(_, line)| !line.is_empty() && !line.starts_with('#'));
+    let Some((_, header)) = lines.next() else {
+        return Ok(Vec::new());
+    };
+    let columns: Vec<&str> = header.split(',').map(str::trim).collect();
+    let index = |name: &'static str| columns.iter().position(|c| *c == name);
+    let required = |name: &'static str| index(name).ok_or(SurveyError::MissingColumn(name));
+    let (id_col, azimuth_col, tilt_col) =
+        (required(ID)?, required(AXIS_AZIMUTH)?, required(AXIS_TILT)?);
+    let (east_col, west_col) = (index(GCR_EAST), index(GCR_WEST));
+
+    lines
+        .map(|(line, text)| {
+            let fields: Vec<&str> = text.split(',').map(str::trim).collect();
+            let field = |col: usize, column: &'static str| {
+                fields
+                    .get(col)
+                    .copied()
+                    .filter(|f| !f.is_empty())
+                    .ok_or(SurveyError::MissingField { line, column })
+            };
+            let number = |col: usize, column: &'static str| {
+                field(col, column)?
+                    .parse::<f64>()
+                    .map_err(|_| SurveyError::InvalidNumber { line, column })
+            };
+            let optional = |col: Option<usize>, column: &'static str| match col {
+                Some(col) if field(col, column).is_ok() => number(col, column).map(Some),
+                _ => Ok(None),
+            };
+            Ok(SurveyRow {
+                id: field(id_col, ID)?.to_string(),
+                axis_azimuth: number(azimuth_col, AXIS_AZIMUTH)?,
+                axis_tilt: number(tilt_col, AXIS_TILT)?,
+                gcr_east: optional(east_col, GCR_EAST)?,
+                gcr_west: optional(west_col, GCR_WEST)?,
+            })
+        })
+        .collect()
+}
+
+// Tables backtrack symmetrically, so the tighter of the two neighbour
+// spacings is used; rows without neighbours keep the site GCR.
+pub fn survey_row_config(site: &LookupTableConfig, row: &SurveyRow) -> LookupTableConfig {
+    let gcr = match (row.gcr_east, row.gcr_west) {
+        (Some(east), Some(west)) => Some(east.max(west)),
+        (Some(gcr), None) | (None, Some(gcr)) => Some(gcr),
+        (None, None) => site.gcr,
+    };
+    LookupTableConfig {
+        axis_azimuth: Some(row.axis_azimuth),
+        axis_tilt: Some(row.axis_tilt),
+        gcr,
+        ..*site
+    }
+}
@@ -0,0 +1,130 @@
+use std::num::NonZeroUsize;
+use std::panic;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::thread;
+
+use crate::irradiance::clear_sky_components;
+use crate::simulation::simulate_yield_with;
+use crate::types::{
+    IrradianceComponents, MinuteOfDay, SimulationConfig, SolarPosition, SweepRow, SweepStrategy,
+    TrackingStrategy, YieldSweep,
+};
+
+// Design points in output order: strategies as listed, then tilt, azimuth
+// and GCR. Fixed arrays ignore GCR and dual-axis trackers ignore all three,
+// so they appear once per distinct setting rather than once per grid cell.
+fn design_points(sweep: &YieldSweep) -> Vec<SweepRow> {
+    let row = |strategy, tilt, azimuth, gcr| SweepRow {
+        strategy,
+        tilt,
+        azimuth,
+        gcr,
+        annual_kwh_per_m2: 0.0,
+    };
+    let mut rows = Vec::new();
+    for &strategy in &sweep.strategies {
+        if strategy == SweepStrategy::DualAxis {
+            rows.push(row(strategy, None, None, None));
+            continue;
+        }
+        for &tilt in &sweep.tilts {
+            for &azimuth in &sweep.azimuths {
+                match strategy {
+                    SweepStrategy::Fixed => {
+                        rows.push(row(strategy, Some(tilt), Some(azimuth), None))
+                    }
+                    // Unshaded when no GCR is given
+                    SweepStrategy::SingleAxis if sweep.gcrs.is_empty() => {
+                        rows.push(row(strategy, Some(tilt), Some(azimuth), None))
+                    }
+                    _ => rows.extend(
+                        sweep
+                            .gcrs
+                            .iter()
+                            .map(|&gcr| row(strategy, Some(tilt), Some(azimuth), Some(gcr))),
+                    ),
+                }
+            }
+        }
+    }
+    rows
+}
+
+fn design_config(base: &SimulationConfig, row: &SweepRow) -> SimulationConfig {
+    let max_rotation = match base.strategy {
+        TrackingStrategy::SingleAxis { max_rotation, .. } => max_rotation,
+        _ => Some(60.0),
+    };
+    let (tilt, azimuth) = (row.tilt.unwrap_or(0.0), row.azimuth.unwrap_or(180.0));
+    let single_axis = |gcr| TrackingStrategy::SingleAxis {
+        axis_tilt: tilt,
+        axis_azimuth: azimuth,
+        max_rotation,
+        gcr,
+    };
+    let strategy = match row.strategy {
+        SweepStrategy::Fixed => TrackingStrategy::Fixed { tilt, azimuth },
+        SweepStrategy::SingleAxis => single_axis(None),
+        SweepStrategy::Backtracking => single_axis(row.gcr),
+        SweepStrategy::DualAxis => TrackingStrategy::DualAxis,
+    };
+    SimulationConfig {
+        strategy,
+        row_gcr: row.gcr,
+        ..*base
+    }
+}
+
+// Clear-sky sweep; see `yield_sweep_with`.
+pub fn yield_sweep(config: &SimulationConfig, sweep: &YieldSweep) -> Vec<SweepRow> {
+    yield_sweep_with(config, sweep, |_, _, pos| clear_sky_components(pos))
+}
+
+// Annual yield for every design in the grid, one row each, with the site,
+// step, sky model and albedo taken from `config`. Single-axis trackers keep
+// `config`'s rotation limit when it has one. Designs are simulated on
+// `sweep.threads` worker threads sharing one queue; the rows come back in
+// `design_points` order whatever the thread count, so results are
+// reproducible.
+pub fn yield_sweep_with<F>(
+    config: &SimulationConfig,
+    sweep: &YieldSweep,
+    irradiance: F,
+) -> Vec<SweepRow>
+where
+    F: Fn(i32, MinuteOfDay, &SolarPosition) -> Option<IrradianceComponents> + Sync,
+{
+    let mut rows = design_points(sweep);
+    let threads = sweep
+        .threads
+        .unwrap_or_else(|| thread::available_parallelism().map_or(1, NonZeroUsize::get))
+        .clamp(1, rows.len().max(1));
+    let next = AtomicUsize::new(0);
+    let results: Vec<(usize, f64)> = thread::scope(|scope| {
+        let workers: Vec<_> = (0..threads)
+            .map(|_| {
+                scope.spawn(|| {
+                    let mut done = Vec::new();
+                    loop {
+                        let i = next.fetch_add(1, Ordering::Relaxed);
+                        let Some(row) = rows.get(i) else {
+                            break;
+                        };
+                        let design = design_config(config, row);
+                        let report = simulate_yield_with(&design, &irradiance);
+                        done.push((i, report.annual_kwh_per_m2));
+                    }
+                    done
+                })
+            })
+            .collect();
+        workers
+            .into_iter()
+            .flat_map(|worker| worker.join().unwrap_or_else(|e| panic::resume_unwind(e)))
+            .collect()
+    });
+    for (i, annual) in results {
+        rows[i].annual_kwh_per_m2 = annual;
+    }
+    rows
+}
@@ -0,0 +1,114 @@
+use crate::angles::{deg_to_rad, sun_vector, surface_normal, vector_dot};
+use crate::irradiance::{extraterrestrial_irradiance, relative_air_mass};
+use crate::types::{
+    IrradianceComponents, PoaIrradiance, SkyDiffuseModel, SolarPosition, SurfaceOrientation,
+};
+
+// Perez et al. (1990) "allsitescomposite1990" coefficients, one row per
+// sky-clearness bin: f11, f12, f13, f21, f22, f23.
+const PEREZ_COEFFICIENTS: [[f64; 6]; 8] = [
+    [-0.008, 0.588, -0.062, -0.060, 0.072, -0.022],
+    [0.130, 0.683, -0.151, -0.019, 0.066, -0.029],
+    [0.330, 0.487, -0.221, 0.055, -0.064, -0.026],
+    [0.568, 0.187, -0.295, 0.109, -0.152, -0.014],
+    [0.873, -0.392, -0.362, 0.226, -0.462, 0.001],
+    [1.132, -1.237, -0.412, 0.288, -0.823, 0.056],
+    [1.060, -1.600, -0.359, 0.264, -1.127, 0.131],
+    [0.678, -0.327, -0.250, 0.156, -1.377, 0.251],
+];
+
+// Upper edges of the first seven clearness bins.
+const PEREZ_EPSILON_BINS: [f64; 7] = [1.065, 1.23, 1.5, 1.95, 2.8, 4.5, 6.2];
+
+// Floor on cos(zenith) in the circumsolar ratio, as in the published models.
+const MIN_COS_ZENITH: f64 = 0.0872; // cos 85°
+
+fn isotropic_sky(dhi: f64, cos_tilt: f64) -> f64 {
+    dhi * (1.0 + cos_tilt) / 2.0
+}
+
+// Hay-Davies anisotropy index with Reindl's horizon brightening.
+fn hdkr_sky(
+    irradiance: &IrradianceComponents,
+    pos: &SolarPosition,
+    cos_aoi: f64,
+    surface_tilt: f64,
+) -> f64 {
+    let cos_z = deg_to_rad(pos.zenith).cos();
+    let anisotropy =
+        (irradiance.dni / extraterrestrial_irradiance(pos.day_of_year)).clamp(0.0, 1.0);
+    let rb = cos_aoi.max(0.0) / cos_z.max(MIN_COS_ZENITH);
+    let beam_horizontal = (irradiance.dni * cos_z).max(0.0);
+    let brightening = if irradiance.ghi > 0.0 {
+        1.0 + (beam_horizontal / irradiance.ghi).sqrt()
+            * deg_to_rad(surface_tilt / 2.0).sin().powi(3)
+    } else {
+        1.0
+    };
+    let cos_tilt = deg_to_rad(surface_tilt).cos();
+    irradiance.dhi * (anisotropy * rb + (1.0 - anisotropy) * (1.0 + cos_tilt) / 2.0 * brightening)
+}
+
+fn perez_sky(
+    irradiance: &IrradianceComponents,
+    pos: &SolarPosition,
+    cos_aoi: f64,
+    surface_tilt: f64,
+) -> f64 {
+    let dhi = irradiance.dhi;
+    let Some(air_mass) = relative_air_mass(pos.zenith) else {
+        return isotropic_sky(dhi, deg_to_rad(surface_tilt).cos());
+    };
+    let z = deg_to_rad(pos.zenith);
+    let kappa_z3 = 1.041 * z.powi(3);
+    let epsilon = ((dhi + irradiance.dni) / dhi + kappa_z3) / (1.0 + kappa_z3);
+    let delta = dhi * air_mass / extraterrestrial_irradiance(pos.day_of_year);
+    let bin = PEREZ_EPSILON_BINS
+        .iter()
+        .position(|&edge| epsilon < edge)
+        .unwrap_or(PEREZ_EPSILON_BINS.len());
+    let [f11, f12, f13, f21, f22, f23] = PEREZ_COEFFICIENTS[bin];
+    let f1 = (f11 + f12 * delta + f13 * z).max(0.0);
+    let f2 = f21 + f22 * delta + f23 * z;
+    let (sin_tilt, cos_tilt) = deg_to_rad(surface_tilt).sin_cos();
+    let circumsolar = cos_aoi.max(0.0) / z.cos().max(MIN_COS_ZENITH);
+    (dhi * ((1.0 - f1) * (1.0 + cos_tilt) / 2.0 + f1 * circumsolar + f2 * sin_tilt)).max(0.0)
+}
+
+// Plane-of-array irradiance for a surface. With the sun at or below the
+// horizon the beam is dropped and the sky is treated as isotropic, since
+// the anisotropic terms are undefined there.
+pub fn poa_irradiance(
+    pos: &SolarPosition,
+    surface: &SurfaceOrientation,
+    irradiance: &IrradianceComponents,
+    albedo: f64,
+    model: SkyDiffuseModel,
+) -> PoaIrradiance {
+    let cos_tilt = deg_to_rad(surface.surface_tilt).cos();
+    let cos_aoi = vector_dot(surface_normal(surface), sun_vector(pos));
+    let sun_up = pos.zenith < 90.0;
+    let beam = if sun_up {
+        irradiance.dni.max(0.0) * cos_aoi.max(0.0)
+    } else {
+        0.0
+    };
+    let dhi = irradiance.dhi.max(0.0);
+    let sky_diffuse = if !sun_up || dhi == 0.0 {
+        isotropic_sky(dhi, cos_tilt)
+    } else {
+        let irradiance = IrradianceComponents { dhi, ..*irradiance };
+        match model {
+            SkyDiffuseModel::Isotropic => isotropic_sky(dhi, cos_tilt),
+            SkyDiffuseModel::Hdkr => hdkr_sky(&irradiance, pos, cos_aoi, surface.surface_tilt),
+            SkyDiffuseModel::Perez => perez_sky(&irradiance, pos, cos_aoi, surface.surface_tilt),
+        }
+    };
+    let ground_reflected = irradiance.ghi.max(0.0) * albedo * (1.0 - cos_tilt) / 2.0;
+    PoaIrradiance {
+        beam,
+        sky_diffuse,
+        ground_reflected,
+        total: beam + sky_diffuse + ground_reflected,
+    }
+}
@@ -1,4 +1,11 @@
+use std::fmt;
+
+use chrono::{DateTime, NaiveDate, NaiveTime, Timelike, Utc};
+
+use crate::angles::{angle_difference, normalize_angle};
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Season {
     Summer,
     Winter,
@@ -7,6 +14,7 @@ pub enum Season {
 }
 
 #[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct SolarPosition {
     pub day_of_year: i32,
     pub declination: f64,
@@ -18,47 +26,255 @@ pub struct SolarPosition {
     pub azimuth: f64,
 }
 
+// `intermediate_angle_b` is in radians, `eot_terms` are the constant, cos B,
+// sin B, cos 2B and sin 2B contributions in minutes, `correction` is in
+// hours, and `cos_zenith` is before clamping to [-1, 1].
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SolarPositionDebug {
+    pub day_of_year: i32,
+    pub utc_hours: f64,
+    pub intermediate_angle_b: f64,
+    pub eot_terms: [f64; 5],
+    pub equation_of_time: f64,
+    pub declination: f64,
+    pub correction: f64,
+    pub local_solar_time: f64,
+    pub hour_angle: f64,
+    pub cos_zenith: f64,
+    pub zenith: f64,
+    pub altitude: f64,
+    pub sin_azimuth: f64,
+    pub cos_azimuth: f64,
+    pub azimuth: f64,
+}
+
+// One step of an explained calculation: the formula applied, the named
+// values it read and what it produced. Angles are in degrees unless `unit`
+// says otherwise.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ExplainStep {
+    pub name: String,
+    pub formula: String,
+    pub inputs: Vec<(String, f64)>,
+    pub output: f64,
+    pub unit: String,
+}
+
 #[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct DualAxisAngles {
     pub tilt: f64,
     pub panel_azimuth: f64,
 }
 
+// A minute of the day, 0..1440, that cannot hold anything else: built by
+// checked or wrapping construction, so arithmetic on table keys never runs
+// off either end of the day. Lookups and key conversions take and return
+// this; table entries keep `i32` minutes because noon-relative tables store
+// signed offsets there. Serializes as the plain minute count and refuses
+// out-of-range counts on the way back in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(try_from = "i32", into = "i32"))]
+pub struct MinuteOfDay(u16);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MinuteOutOfRange(pub i32);
+
+impl fmt::Display for MinuteOutOfRange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "minute {} outside 0..1440", self.0)
+    }
+}
+
+impl std::error::Error for MinuteOutOfRange {}
+
+impl MinuteOfDay {
+    pub const MIDNIGHT: Self = Self(0);
+    pub const NOON: Self = Self(720);
+    pub const MINUTES_PER_DAY: i32 = 1440;
+
+    pub const fn new(minutes: i32) -> Option<Self> {
+        if minutes >= 0 && minutes < Self::MINUTES_PER_DAY {
+            Some(Self(minutes as u16))
+        } else {
+            None
+        }
+    }
+
+    // Any count, folded into the day: -1 is 23:59 and 1440 is midnight.
+    pub const fn wrapping(minutes: i32) -> Self {
+        Self(minutes.rem_euclid(Self::MINUTES_PER_DAY) as u16)
+    }
+
+    pub const fn from_hm(hour: u32, minute: u32) -> Option<Self> {
+        if hour < 24 && minute < 60 {
+            Some(Self((hour * 60 + minute) as u16))
+        } else {
+            None
+        }
+    }
+
+    pub const fn get(self) -> i32 {
+        self.0 as i32
+    }
+
+    pub const fn hour(self) -> u32 {
+        self.0 as u32 / 60
+    }
+
+    pub const fn minute(self) -> u32 {
+        self.0 as u32 % 60
+    }
+
+    // Past midnight the clock comes round again.
+    pub const fn wrapping_add(self, minutes: i32) -> Self {
+        Self::wrapping(self.get() + minutes % Self::MINUTES_PER_DAY)
+    }
+
+    // None once the result leaves the day.
+    pub const fn checked_add(self, minutes: i32) -> Option<Self> {
+        match self.get().checked_add(minutes) {
+            Some(sum) => Self::new(sum),
+            None => None,
+        }
+    }
+
+    // Minutes forward from `self` to `later`, across midnight if need be:
+    // 23:00 to 01:00 is 120.
+    pub const fn minutes_until(self, later: Self) -> i32 {
+        (later.get() - self.get()).rem_euclid(Self::MINUTES_PER_DAY)
+    }
+
+    // Seconds are dropped, not rounded, matching how table keys are taken.
+    pub fn from_time(time: NaiveTime) -> Self {
+        Self((time.hour() * 60 + time.minute()) as u16)
+    }
+
+    pub fn to_time(self) -> NaiveTime {
+        NaiveTime::from_hms_opt(self.hour(), self.minute(), 0).unwrap_or(NaiveTime::MIN)
+    }
+}
+
+impl fmt::Display for MinuteOfDay {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:02}:{:02}", self.hour(), self.minute())
+    }
+}
+
+impl From<MinuteOfDay> for i32 {
+    fn from(minute: MinuteOfDay) -> Self {
+        minute.get()
+    }
+}
+
+impl TryFrom<i32> for MinuteOfDay {
+    type Error = MinuteOutOfRange;
+
+    fn try_from(minutes: i32) -> Result<Self, Self::Error> {
+        Self::new(minutes).ok_or(MinuteOutOfRange(minutes))
+    }
+}
+
+impl From<NaiveTime> for MinuteOfDay {
+    fn from(time: NaiveTime) -> Self {
+        Self::from_time(time)
+    }
+}
+
+impl From<MinuteOfDay> for NaiveTime {
+    fn from(minute: MinuteOfDay) -> Self {
+        minute.to_time()
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct SunriseSunset {
     pub sunrise: i32,
     pub sunset: i32,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct SingleAxisEntry {
     pub minutes: i32,
     pub rotation: Option<f64>,
+    pub clamped: bool,
+    pub surface: Option<SurfaceOrientation>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SurfaceOrientation {
+    pub surface_tilt: f64,
+    pub surface_azimuth: f64,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct DualAxisEntry {
     pub minutes: i32,
     pub tilt: Option<f64>,
     pub panel_azimuth: Option<f64>,
+    // Set where the command differs from the raw sun-pointing angles: tilt
+    // clamped to the rotation limits, or azimuth held, flipped or rate
+    // limited by `condition_dual_axis_table`. Tables saved before the rename
+    // load their `clamped` flag here.
+    #[cfg_attr(feature = "serde", serde(alias = "clamped"))]
+    pub adjusted: bool,
 }
 
 #[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct DayData<E> {
     pub day_of_year: i32,
     pub sunrise_minutes: i32,
     pub sunset_minutes: i32,
+    pub solar_noon_minutes: i32,
     pub entries: Vec<E>,
 }
 
 #[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct TableMetadata {
     pub generated_at: String,
     pub total_entries: usize,
     pub storage_estimate_kb: f64,
+    // Defaulted so tables serialized before stamping existed still load
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub project: Option<ProjectMetadata>,
+    // Defaulted so tables serialized before per-month statistics still load
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub months: Vec<MonthStatistics>,
+}
+
+// Days and entries a table holds in one calendar month, for breaking flash
+// use down by month without walking the days.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MonthStatistics {
+    pub month: u32,
+    pub days: usize,
+    pub entries: usize,
+}
+
+// User-supplied provenance stamped onto generated artifacts. `license` names
+// the terms the artifact is released under, which need not match the
+// crate's own.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ProjectMetadata {
+    pub site_name: Option<String>,
+    pub project_id: Option<String>,
+    pub operator: Option<String>,
+    pub license: Option<String>,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct LookupTableConfig {
     pub interval_minutes: i32,
     pub latitude: f64,
@@ -66,6 +282,31 @@ pub struct LookupTableConfig {
     pub year: i32,
     pub sunrise_buffer_minutes: i32,
     pub sunset_buffer_minutes: i32,
+    pub gcr: Option<f64>,
+    pub min_rotation: Option<f64>,
+    pub max_rotation: Option<f64>,
+    // Defaulted so configs saved before the field existed still load
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub include_surface_orientation: bool,
+    pub magnetic_declination: Option<f64>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub dual_axis_mount: DualAxisMount,
+    pub terrain: Option<TerrainSlope>,
+    pub axis_azimuth: Option<f64>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub precise_sunrise_sunset: bool,
+    pub axis_tilt: Option<f64>,
+    // Defaulted so configs saved before the choice existed still load
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub declination_model: DeclinationModel,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub equation_of_time_model: EquationOfTimeModel,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub time_base: TimeBase,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub azimuth_encoding: AzimuthEncoding,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub table_key: TableKey,
 }
 
 impl Default for LookupTableConfig {
@@ -77,16 +318,1428 @@ impl Default for LookupTableConfig {
             year: 2026,
             sunrise_buffer_minutes: 30,
             sunset_buffer_minutes: 30,
+            gcr: None,
+            min_rotation: None,
+            max_rotation: None,
+            include_surface_orientation: false,
+            magnetic_declination: None,
+            dual_axis_mount: DualAxisMount::AzimuthElevation,
+            terrain: None,
+            axis_azimuth: None,
+            precise_sunrise_sunset: false,
+            axis_tilt: None,
+            declination_model: DeclinationModel::Cooper,
+            equation_of_time_model: EquationOfTimeModel::Fourier,
+            time_base: TimeBase::Utc,
+            azimuth_encoding: AzimuthEncoding::Bearing,
+            table_key: TableKey::UtcMinutes,
+        }
+    }
+}
+
+impl LookupTableConfig {
+    // Sets every per-day treatment the generators read from one model.
+    pub const fn with_position_model(self, model: PositionModel) -> Self {
+        Self {
+            declination_model: model.declination_model(),
+            equation_of_time_model: model.equation_of_time_model(),
+            precise_sunrise_sunset: model.refracted_horizon(),
+            ..self
+        }
+    }
+}
+
+// Cooper (1969) is the single sinusoid the library has always used, good to
+// about ±0.3°; Spencer (1971) is a seven-term Fourier series good to about
+// 0.035°.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum DeclinationModel {
+    #[default]
+    Cooper,
+    Spencer,
+}
+
+// `Fourier` is the four-term day-of-year series the library has always
+// used, off by up to about 47 seconds; `Ecliptic` derives the equation of
+// time from the Sun's mean longitude, the orbit's eccentricity and the
+// obliquity (Meeus 28.3), good to a few seconds.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum EquationOfTimeModel {
+    #[default]
+    Fourier,
+    Ecliptic,
+}
+
+// The clock a table's consumers read. Entries are keyed by UTC day of year
+// and either UTC minutes or, with `TableKey::HourAngle`, apparent solar
+// minutes; neither depends on the consumer's clock, and the time base says
+// how a wall-clock reading maps onto whichever keys the table uses.
+// `FixedOffset` is a clock that never changes (standard time all year, east
+// positive); `Zone` follows the zone's daylight-saving rules.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum TimeBase {
+    #[default]
+    Utc,
+    FixedOffset {
+        offset_minutes: i32,
+    },
+    #[cfg(feature = "tz")]
+    Zone(chrono_tz::Tz),
+}
+
+// What a table's minute keys count. `UtcMinutes` is minutes past UTC
+// midnight. `HourAngle` is local apparent solar time in minutes, 720 at
+// solar noon and four minutes per degree of hour angle: entries then depend
+// only on latitude and date, so one table serves nearby longitudes, and
+// each day's morning mirrors its afternoon about 720.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum TableKey {
+    #[default]
+    UtcMinutes,
+    HourAngle,
+}
+
+// Site air for refraction. The default is the 1010 hPa, 10 °C atmosphere
+// refraction formulas and `SUNRISE_ALTITUDE` assume.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Atmosphere {
+    pub pressure_hpa: f64,
+    pub temperature_c: f64,
+}
+
+impl Default for Atmosphere {
+    fn default() -> Self {
+        Self {
+            pressure_hpa: 1010.0,
+            temperature_c: 10.0,
+        }
+    }
+}
+
+impl Atmosphere {
+    // Pressure from the standard-atmosphere barometric formula, for sites
+    // with no barometer. Good to a few percent below about 5000 m.
+    pub fn at_elevation(elevation_m: f64, temperature_c: f64) -> Self {
+        Self {
+            pressure_hpa: 1013.25 * (1.0 - 2.25577e-5 * elevation_m).powf(5.25588),
+            temperature_c,
+        }
+    }
+
+    // Refraction scales with air density relative to the 1010 hPa, 10 °C
+    // atmosphere the formulas are fitted to.
+    pub fn density_factor(&self) -> f64 {
+        (self.pressure_hpa / 1010.0) * (283.0 / (273.0 + self.temperature_c))
+    }
+}
+
+// How dual-axis tables store `panel_azimuth`. `Bearing` is the library's
+// 0–360° bearing, which sits opposite the sun. The offsets instead give the
+// direction the panel faces as a signed angle in (-180°, 180°] from due
+// south or due north, west positive: the convention of controllers that
+// keep azimuth in a signed integer register. Pick the reference the panels
+// face at noon, south in the northern hemisphere and north in the southern,
+// and the day's swing never crosses the ±180° wrap. With a magnetic
+// declination the reference is magnetic south or north.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum AzimuthEncoding {
+    #[default]
+    Bearing,
+    SouthOffset,
+    NorthOffset,
+}
+
+impl AzimuthEncoding {
+    pub const fn encode(self, panel_azimuth: f64) -> f64 {
+        // The panel faces the opposite way to `panel_azimuth`
+        match self {
+            AzimuthEncoding::Bearing => normalize_angle(panel_azimuth),
+            AzimuthEncoding::SouthOffset => angle_difference(0.0, panel_azimuth),
+            AzimuthEncoding::NorthOffset => angle_difference(180.0, panel_azimuth),
+        }
+    }
+
+    pub const fn decode(self, stored: f64) -> f64 {
+        match self {
+            AzimuthEncoding::Bearing | AzimuthEncoding::SouthOffset => normalize_angle(stored),
+            AzimuthEncoding::NorthOffset => normalize_angle(stored + 180.0),
+        }
+    }
+}
+
+// One knob for the accuracy/cost trade-off, so positions, tables and
+// sunrise/sunset are not computed from mismatched formulas. `Fast` is the
+// library's long-standing set: Cooper declination, the Fourier equation of
+// time and a geometric horizon. `Standard` moves to Spencer, the ecliptic
+// equation of time and the refracted horizon (`SUNRISE_ALTITUDE`).
+// `Precise` computes single positions from the Meeus ephemeris; per-day
+// paths (tables, sunrise/sunset) use the `Standard` formulas, which are
+// already finer than a once-a-day sample. Positions are unrefracted in
+// every model.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum PositionModel {
+    #[default]
+    Fast,
+    Standard,
+    Precise,
+}
+
+impl PositionModel {
+    pub const fn declination_model(self) -> DeclinationModel {
+        match self {
+            PositionModel::Fast => DeclinationModel::Cooper,
+            PositionModel::Standard | PositionModel::Precise => DeclinationModel::Spencer,
+        }
+    }
+
+    pub const fn equation_of_time_model(self) -> EquationOfTimeModel {
+        match self {
+            PositionModel::Fast => EquationOfTimeModel::Fourier,
+            PositionModel::Standard | PositionModel::Precise => EquationOfTimeModel::Ecliptic,
         }
     }
+
+    pub const fn refracted_horizon(self) -> bool {
+        !matches!(self, PositionModel::Fast)
+    }
 }
 
 #[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct LookupTable<E> {
     pub config: LookupTableConfig,
     pub days: Vec<DayData<E>>,
     pub metadata: TableMetadata,
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct StepEntry<T> {
+    pub minutes: i32,
+    pub value: T,
+}
+
+pub type StepTable<T> = LookupTable<StepEntry<T>>;
+
 pub type SingleAxisTable = LookupTable<SingleAxisEntry>;
+
+// `difference` is candidate minus reference rotation.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DifferenceEntry {
+    pub minutes: i32,
+    pub reference: Option<f64>,
+    pub candidate: Option<f64>,
+    pub difference: Option<f64>,
+}
+
+pub type DifferenceTable = LookupTable<DifferenceEntry>;
+
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DifferenceSummary {
+    pub max_abs_difference: f64,
+    pub max_day_of_year: i32,
+    pub max_minutes: i32,
+    pub mean_abs_difference: f64,
+    pub intervals_over_threshold: usize,
+}
 pub type DualAxisTable = LookupTable<DualAxisEntry>;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum TrackingMode {
+    SunPointing,
+    DiffuseOptimal,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ClearnessConfig {
+    pub smoothing_alpha: f64,
+    pub diffuse_threshold: f64,
+    pub sun_pointing_threshold: f64,
+    pub min_clear_sky_ghi: f64,
+}
+
+impl Default for ClearnessConfig {
+    fn default() -> Self {
+        Self {
+            smoothing_alpha: 0.2,
+            diffuse_threshold: 0.3,
+            sun_pointing_threshold: 0.5,
+            min_clear_sky_ghi: 50.0,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct IrradianceComponents {
+    pub ghi: f64,
+    pub dni: f64,
+    pub dhi: f64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum SkyDiffuseModel {
+    Isotropic,
+    Hdkr,
+    Perez,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PoaIrradiance {
+    pub beam: f64,
+    pub sky_diffuse: f64,
+    pub ground_reflected: f64,
+    pub total: f64,
+}
+
+// Rotation limits and backtracking apply to `SingleAxis` only; `DualAxis`
+// points the surface normal straight at the sun.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum TrackingStrategy {
+    Fixed {
+        tilt: f64,
+        azimuth: f64,
+    },
+    SingleAxis {
+        axis_tilt: f64,
+        axis_azimuth: f64,
+        max_rotation: Option<f64>,
+        gcr: Option<f64>,
+    },
+    DualAxis,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SimulationConfig {
+    pub latitude: f64,
+    pub longitude: f64,
+    pub year: i32,
+    pub step_minutes: i32,
+    pub strategy: TrackingStrategy,
+    pub sky_model: SkyDiffuseModel,
+    pub albedo: f64,
+    // Ground coverage ratio of the tracker rows. When set, single-axis beam
+    // is reduced by the row-to-row shaded fraction, which is how the cost of
+    // tracking without backtracking shows up in the yield.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub row_gcr: Option<f64>,
+}
+
+impl Default for SimulationConfig {
+    fn default() -> Self {
+        Self {
+            latitude: 39.8,
+            longitude: -89.6,
+            year: 2026,
+            step_minutes: 15,
+            strategy: TrackingStrategy::SingleAxis {
+                axis_tilt: 0.0,
+                axis_azimuth: 180.0,
+                max_rotation: Some(60.0),
+                gcr: None,
+            },
+            sky_model: SkyDiffuseModel::Perez,
+            albedo: 0.2,
+            row_gcr: None,
+        }
+    }
+}
+
+// `Backtracking` is a single-axis tracker backtracking at the row GCR;
+// `SingleAxis` tracks the sun regardless and takes the shading loss.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum SweepStrategy {
+    Fixed,
+    SingleAxis,
+    Backtracking,
+    DualAxis,
+}
+
+// Grid for `yield_sweep`. Tilts and azimuths are the panel's for fixed
+// arrays and the axis's for single-axis trackers. `threads: None` uses the
+// available parallelism.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct YieldSweep {
+    pub tilts: Vec<f64>,
+    pub azimuths: Vec<f64>,
+    pub gcrs: Vec<f64>,
+    pub strategies: Vec<SweepStrategy>,
+    pub threads: Option<usize>,
+}
+
+impl Default for YieldSweep {
+    fn default() -> Self {
+        Self {
+            tilts: vec![0.0, 10.0, 20.0, 30.0, 40.0],
+            azimuths: vec![180.0],
+            gcrs: vec![0.3, 0.4, 0.5],
+            strategies: vec![
+                SweepStrategy::Fixed,
+                SweepStrategy::SingleAxis,
+                SweepStrategy::Backtracking,
+                SweepStrategy::DualAxis,
+            ],
+            threads: None,
+        }
+    }
+}
+
+// One simulated design. Parameters a strategy does not use are None.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SweepRow {
+    pub strategy: SweepStrategy,
+    pub tilt: Option<f64>,
+    pub azimuth: Option<f64>,
+    pub gcr: Option<f64>,
+    pub annual_kwh_per_m2: f64,
+}
+
+// One hour of a typical-year weather file. `hour` is the start of the
+// interval in local standard time (0..=23); irradiance is the hourly mean in
+// W/m². Temperature (dry bulb, °C) and wind speed (m/s) are None where the
+// file marks them missing.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct WeatherRecord {
+    pub day_of_year: i32,
+    pub hour: u32,
+    pub ghi: f64,
+    pub dni: f64,
+    pub dhi: f64,
+    pub temperature: Option<f64>,
+    pub wind_speed: Option<f64>,
+}
+
+// Site header and hourly records of a TMY3 or EPW file, sorted by day and
+// hour.
+// `utc_offset_hours` is the file's standard-time zone, east positive.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct WeatherFile {
+    pub latitude: f64,
+    pub longitude: f64,
+    pub utc_offset_hours: f64,
+    pub elevation: f64,
+    pub records: Vec<WeatherRecord>,
+}
+
+// Settings for `generate_scenario`. Skies switch between clear and cloudy
+// spells; `cloudy_fraction` is the long-run share of cloudy time and
+// `cloud_transmittance` the mean share of clear-sky GHI that gets through a
+// cloud. Wind events arrive at random at `wind_events_per_year`, each
+// lasting `wind_event_minutes` with gusts around `wind_event_gust` (m/s).
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ScenarioConfig {
+    pub seed: u64,
+    pub year: i32,
+    pub step_minutes: i32,
+    pub cloudy_fraction: f64,
+    pub mean_cloud_minutes: f64,
+    pub cloud_transmittance: f64,
+    pub mean_wind_speed: f64,
+    pub wind_events_per_year: f64,
+    pub wind_event_minutes: i32,
+    pub wind_event_gust: f64,
+}
+
+impl Default for ScenarioConfig {
+    fn default() -> Self {
+        Self {
+            seed: 0,
+            year: 2026,
+            step_minutes: 15,
+            cloudy_fraction: 0.4,
+            mean_cloud_minutes: 120.0,
+            cloud_transmittance: 0.3,
+            mean_wind_speed: 4.0,
+            wind_events_per_year: 12.0,
+            wind_event_minutes: 180,
+            wind_event_gust: 25.0,
+        }
+    }
+}
+
+// One year of generated weather at `config.step_minutes`, starting at
+// 00:00 UTC on 1 January. `transmittance` is the share of clear-sky GHI per
+// step; `wind` holds one sample per step.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Scenario {
+    pub config: ScenarioConfig,
+    pub transmittance: Vec<f64>,
+    pub wind: Vec<WeatherForecast>,
+}
+
+// Search box and step sizes for `optimize_fixed_orientation`. An azimuth
+// range spanning 360° or more is searched as a full circle. Hourly weights
+// are indexed by local solar hour, so 0.5 for hours 6..9 halves the value of
+// foggy mornings and 0.0 ignores those hours entirely.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct OrientationSearch {
+    pub min_tilt: f64,
+    pub max_tilt: f64,
+    pub min_azimuth: f64,
+    pub max_azimuth: f64,
+    pub coarse_step: f64,
+    pub resolution: f64,
+    pub hourly_weights: Option<[f64; 24]>,
+}
+
+impl Default for OrientationSearch {
+    fn default() -> Self {
+        Self {
+            min_tilt: 0.0,
+            max_tilt: 90.0,
+            min_azimuth: 0.0,
+            max_azimuth: 360.0,
+            coarse_step: 10.0,
+            resolution: 0.5,
+            hourly_weights: None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct FixedOrientation {
+    pub tilt: f64,
+    pub azimuth: f64,
+    pub weighted_kwh_per_m2: f64,
+}
+
+// Annual plane-of-array insolation for the best fixed array and for single-
+// and dual-axis trackers at the same site, with the trackers' gains over
+// fixed in percent.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TrackerGainReport {
+    pub fixed: FixedOrientation,
+    pub fixed_kwh_per_m2: f64,
+    pub single_axis_kwh_per_m2: f64,
+    pub dual_axis_kwh_per_m2: f64,
+    pub single_axis_gain_percent: f64,
+    pub dual_axis_gain_percent: f64,
+}
+
+// How often a manually adjusted rack is re-angled: 2, 4 or 12 times a year.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum AdjustmentFrequency {
+    Twice,
+    Quarterly,
+    Monthly,
+}
+
+impl AdjustmentFrequency {
+    pub const fn per_year(self) -> usize {
+        match self {
+            AdjustmentFrequency::Twice => 2,
+            AdjustmentFrequency::Quarterly => 4,
+            AdjustmentFrequency::Monthly => 12,
+        }
+    }
+}
+
+// Set the rack to `tilt` on (month, day) and hold it until the next
+// adjustment. `kwh_per_m2` is the plane-of-array insolation over that span.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TiltAdjustment {
+    pub month: u32,
+    pub day: u32,
+    pub day_of_year: i32,
+    pub tilt: f64,
+    pub azimuth: f64,
+    pub kwh_per_m2: f64,
+}
+
+// Plane-of-array insolation; `daily_kwh_per_m2[i]` is day of year i + 1.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct YieldReport {
+    pub daily_kwh_per_m2: Vec<f64>,
+    pub monthly_kwh_per_m2: [f64; 12],
+    pub annual_kwh_per_m2: f64,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub project: Option<ProjectMetadata>,
+}
+
+// `clearness_index` here is kt against extraterrestrial irradiance, not the
+// clear-sky ratio used by `ClearnessMonitor`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct GhiDecomposition {
+    pub clearness_index: f64,
+    pub diffuse_fraction: f64,
+    pub dni: f64,
+    pub dhi: f64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ClearnessSample {
+    pub clear_sky_ghi: f64,
+    pub clearness_index: Option<f64>,
+    pub smoothed_index: Option<f64>,
+    pub mode: TrackingMode,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct WeatherForecast {
+    pub time: DateTime<Utc>,
+    pub wind_speed: f64,
+    pub wind_gust: f64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct StowPlanConfig {
+    pub gust_threshold: f64,
+    pub release_threshold: f64,
+    pub lead_minutes: i64,
+    pub hold_minutes: i64,
+    pub min_gap_minutes: i64,
+    pub horizon_minutes: i64,
+}
+
+impl Default for StowPlanConfig {
+    fn default() -> Self {
+        Self {
+            gust_threshold: 18.0,
+            release_threshold: 13.0,
+            lead_minutes: 15,
+            hold_minutes: 30,
+            min_gap_minutes: 30,
+            horizon_minutes: 360,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct StowWindow {
+    pub enter: DateTime<Utc>,
+    pub exit: DateTime<Utc>,
+    pub peak_gust: f64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct FallbackPolicy {
+    pub comm_timeout_minutes: i64,
+    pub lightning_hold_minutes: i64,
+    pub conservative_max_rotation: f64,
+    pub stow_at_night: bool,
+}
+
+impl Default for FallbackPolicy {
+    fn default() -> Self {
+        Self {
+            comm_timeout_minutes: 15,
+            lightning_hold_minutes: 30,
+            conservative_max_rotation: 30.0,
+            stow_at_night: true,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct LinkStatus {
+    pub minutes_since_contact: i64,
+    pub minutes_since_lightning: Option<i64>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum FallbackAction {
+    Normal,
+    Conservative { max_rotation: f64 },
+    Stow(StowReason),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum StowReason {
+    Wind,
+    Snow,
+    Hail,
+    Lightning,
+    CommunicationLoss,
+    Manual,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ControllerMode {
+    Tracking,
+    Backtracking,
+    Stowed(StowReason),
+    Maintenance,
+    Fault,
+    Night,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ControllerInputs {
+    pub altitude: f64,
+    pub backtracking: bool,
+    pub link: LinkStatus,
+    pub stow_request: Option<StowReason>,
+    pub fault: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum StowMode {
+    Wind,
+    Snow,
+    Hail,
+    Night,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum TrackerKind {
+    SingleAxis,
+    DualAxis,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct StowAngles {
+    pub tilt: f64,
+    pub azimuth: f64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct StowConfig {
+    pub single_axis_wind: f64,
+    pub single_axis_snow: f64,
+    pub single_axis_hail: f64,
+    pub single_axis_night: f64,
+    pub dual_axis_wind: StowAngles,
+    pub dual_axis_snow: StowAngles,
+    pub dual_axis_hail: StowAngles,
+    pub dual_axis_night: StowAngles,
+}
+
+impl Default for StowConfig {
+    fn default() -> Self {
+        Self {
+            single_axis_wind: 0.0,
+            single_axis_snow: 60.0,
+            single_axis_hail: 60.0,
+            single_axis_night: 0.0,
+            dual_axis_wind: StowAngles {
+                tilt: 0.0,
+                azimuth: 180.0,
+            },
+            dual_axis_snow: StowAngles {
+                tilt: 60.0,
+                azimuth: 180.0,
+            },
+            dual_axis_hail: StowAngles {
+                tilt: 60.0,
+                azimuth: 180.0,
+            },
+            dual_axis_night: StowAngles {
+                tilt: 0.0,
+                azimuth: 180.0,
+            },
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum EventKind {
+    ModeChange {
+        from: ControllerMode,
+        to: ControllerMode,
+    },
+    Move {
+        target: f32,
+    },
+    Fault {
+        code: u16,
+    },
+    Stow {
+        reason: StowReason,
+    },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Event {
+    pub timestamp: u32,
+    pub kind: EventKind,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CalibrationOffsets {
+    pub axis_azimuth_error: f64,
+    pub zero_offset: f64,
+    pub scale: f64,
+}
+
+impl Default for CalibrationOffsets {
+    fn default() -> Self {
+        Self {
+            axis_azimuth_error: 0.0,
+            zero_offset: 0.0,
+            scale: 1.0,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct AngleReading {
+    pub predicted: f64,
+    pub reported: f64,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DiagnosticBundle {
+    pub generated_at: String,
+    pub config: LookupTableConfig,
+    pub metadata: TableMetadata,
+    pub mode: ControllerMode,
+    pub events: Vec<Event>,
+    pub dropped_events: u32,
+    pub calibration: CalibrationOffsets,
+    pub reading: Option<AngleReading>,
+    pub angle_error: Option<f64>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum CommissioningTarget {
+    EastLimit,
+    Flat,
+    WestLimit,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CommissioningStep {
+    pub target: CommissioningTarget,
+    pub rotation: f64,
+    pub facing_azimuth: Option<f64>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum CommissioningIssue {
+    MissingReading {
+        target: CommissioningTarget,
+    },
+    AxisNotMoving {
+        span: f64,
+    },
+    SignInverted,
+    ZeroOffset {
+        offset: f64,
+    },
+    OutOfTolerance {
+        target: CommissioningTarget,
+        expected: f64,
+        measured: f64,
+    },
+}
+
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CommissioningReport {
+    pub passed: bool,
+    pub issues: Vec<CommissioningIssue>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ClockFormat {
+    H24,
+    H12,
+}
+
+// Number and time style for generated reports. With a decimal comma the CSV
+// field separator must differ, which is why both are carried explicitly.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ReportLocale {
+    pub decimal_separator: char,
+    pub field_separator: char,
+    pub clock: ClockFormat,
+}
+
+impl ReportLocale {
+    pub const US: Self = Self {
+        decimal_separator: '.',
+        field_separator: ',',
+        clock: ClockFormat::H12,
+    };
+    pub const EUROPEAN: Self = Self {
+        decimal_separator: ',',
+        field_separator: ';',
+        clock: ClockFormat::H24,
+    };
+}
+
+impl Default for ReportLocale {
+    fn default() -> Self {
+        Self::US
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct LatitudeBandLibrary<E> {
+    pub min_latitude: f64,
+    pub band_step: f64,
+    pub tables: Vec<LookupTable<E>>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum VectorFrame {
+    Enu,
+    Ned,
+    Nwu,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct AzimuthFlipConfig {
+    pub max_azimuth_rate: f64,
+    pub flat_tilt_threshold: f64,
+    pub allow_negative_tilt: bool,
+}
+
+impl Default for AzimuthFlipConfig {
+    fn default() -> Self {
+        Self {
+            max_azimuth_rate: 1.0,
+            flat_tilt_threshold: 5.0,
+            allow_negative_tilt: false,
+        }
+    }
+}
+
+// A day the sun passes (nearest to) straight overhead at solar noon, with
+// how far from the zenith it culminates in the per-day model, in degrees.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct OverheadSunDay {
+    pub day_of_year: i32,
+    pub noon_zenith: f64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum DualAxisMount {
+    #[default]
+    AzimuthElevation,
+    TiltRoll,
+    PolarDeclination,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MountAngles {
+    pub primary: f64,
+    pub secondary: f64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MountEntry {
+    pub minutes: i32,
+    pub primary: Option<f64>,
+    pub secondary: Option<f64>,
+}
+
+pub type MountTable = LookupTable<MountEntry>;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct GimbalAxes {
+    pub outer: [f64; 3],
+    pub inner: [f64; 3],
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct GimbalSolution {
+    pub outer_angle: f64,
+    pub inner_angle: f64,
+    pub singularity_margin: f64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum GimbalAvoidance {
+    None,
+    HoldOuter,
+    UseAlternate,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct GimbalCommand {
+    pub solution: GimbalSolution,
+    pub avoidance: GimbalAvoidance,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct GimbalConfig {
+    pub lock_margin: f64,
+    pub max_inner_angle: f64,
+}
+
+impl Default for GimbalConfig {
+    fn default() -> Self {
+        Self {
+            lock_margin: 2.0,
+            max_inner_angle: 90.0,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TerrainSlope {
+    pub slope: f64,
+    pub aspect: f64,
+}
+
+#[derive(Debug, Clone, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TrackerOverride {
+    pub id: String,
+    pub axis_azimuth: Option<f64>,
+    pub min_rotation: Option<f64>,
+    pub max_rotation: Option<f64>,
+    pub calibration: Option<CalibrationOffsets>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct FleetTable {
+    pub id: String,
+    pub calibration: CalibrationOffsets,
+    pub table: SingleAxisTable,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SurveyRow {
+    pub id: String,
+    pub axis_azimuth: f64,
+    pub axis_tilt: f64,
+    pub gcr_east: Option<f64>,
+    pub gcr_west: Option<f64>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum StorageFormat {
+    Float32,
+    Centidegrees16,
+    HalfDegree8,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PlannerConfig {
+    pub flash_budget_bytes: usize,
+    pub max_energy_loss: f64,
+    pub sample_day_step: i32,
+}
+
+impl Default for PlannerConfig {
+    fn default() -> Self {
+        Self {
+            flash_budget_bytes: 256 * 1024,
+            max_energy_loss: 0.001,
+            sample_day_step: 7,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TableLayout {
+    pub interval_minutes: i32,
+    pub format: StorageFormat,
+    pub storage_bytes: usize,
+    pub energy_loss: f64,
+}
+
+// One calendar month of a table: what it costs in flash in the chosen
+// format and the worst rotation error, in degrees, that lookups in it make
+// against the exact rotation at every minute. `uncovered_minutes` counts the
+// minutes, at the sunrise and sunset edges, where the tracker should have a
+// rotation but the table gives none.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MonthBudget {
+    pub month: u32,
+    pub days: usize,
+    pub entries: usize,
+    pub storage_bytes: usize,
+    pub max_interpolation_error: f64,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub uncovered_minutes: usize,
+}
+
+// `minutes` is local solar time.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SunPathPoint {
+    pub minutes: i32,
+    pub altitude: f64,
+    pub azimuth: f64,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SunPathCurve {
+    pub day_of_year: i32,
+    pub points: Vec<SunPathPoint>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DaylightMetrics {
+    pub sun_up_hours: f64,
+    pub hours_above_threshold: f64,
+    pub peak_sun_hours: f64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ShadeEntry {
+    pub minutes: i32,
+    pub closed: bool,
+}
+
+pub type ShadeTable = LookupTable<ShadeEntry>;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ShadeConfig {
+    pub min_altitude: f64,
+    pub azimuth_range: Option<(f64, f64)>,
+    pub min_clear_sky_ghi: f64,
+}
+
+impl Default for ShadeConfig {
+    fn default() -> Self {
+        Self {
+            min_altitude: 25.0,
+            azimuth_range: None,
+            min_clear_sky_ghi: 500.0,
+        }
+    }
+}
+
+// Terrain skyline as (azimuth, elevation) pairs in degrees, in any order.
+// Elevations between points are interpolated linearly around the compass;
+// an empty profile is a flat horizon.
+#[derive(Debug, Clone, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct HorizonProfile {
+    pub points: Vec<(f64, f64)>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct HorizonEntry {
+    pub minutes: i32,
+    pub blocked: bool,
+}
+
+pub type HorizonTable = LookupTable<HorizonEntry>;
+
+// A façade, window or field and the patch of sky its sun comes from.
+// `azimuth_range` may wrap through north, as in `ShadeConfig`; None is an
+// open field. `min_altitude` is the horizon: the height of the roofs, trees
+// or hills in the way.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SunExposure {
+    pub latitude: f64,
+    pub longitude: f64,
+    pub azimuth_range: Option<(f64, f64)>,
+    pub min_altitude: f64,
+}
+
+impl Default for SunExposure {
+    fn default() -> Self {
+        Self {
+            latitude: 39.8,
+            longitude: -89.6,
+            azimuth_range: None,
+            min_altitude: 0.0,
+        }
+    }
+}
+
+// One day of a micro-mode single-axis curve: the ideal rotation is
+// atan(num / den) with num = sin h + c0 cos h + c1 and
+// den = c2 sin h + c3 cos h + c4, h the local hour angle. Coefficients are
+// fixed point, `DAILY_FIT_SCALE` per unit. `max_error` (degrees) is the
+// worst gap to the full table over the day, found at fit time.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DailyFit {
+    pub day_of_year: i32,
+    pub coefficients: [i16; 5],
+    pub max_error: f64,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DailyFitTable {
+    pub config: LookupTableConfig,
+    pub days: Vec<DailyFit>,
+}
+
+// A run of daylight entries as a Chebyshev series in the minute of day.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ChebyshevSegment {
+    pub start_minutes: i32,
+    pub end_minutes: i32,
+    pub coefficients: Vec<f32>,
+}
+
+// One day of a Chebyshev-compressed single-axis table. Segments break where
+// the source curve jumps (the ±90° fold of the default north-south formula)
+// or is parked; a day with no daylight has none. `max_error` (degrees) is
+// what the truncation achieved against the source table.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ChebyshevDay {
+    pub day_of_year: i32,
+    pub segments: Vec<ChebyshevSegment>,
+    pub max_error: f64,
+}
+
+// Rotation limits are kept so lookups clamp like the source table.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ChebyshevTable {
+    pub error_bound: f64,
+    pub min_rotation: Option<f64>,
+    pub max_rotation: Option<f64>,
+    pub days: Vec<ChebyshevDay>,
+}
+
+// One day of a solar-noon-relative table: entries keyed by minutes from the
+// day's solar noon, negative before it. `solar_noon_minutes` is the UTC
+// minute of that noon, unrounded, for converting lookups back.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct NoonRelativeDay {
+    pub day_of_year: i32,
+    pub solar_noon_minutes: f64,
+    pub entries: Vec<SingleAxisEntry>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct NoonRelativeTable {
+    pub config: LookupTableConfig,
+    pub days: Vec<NoonRelativeDay>,
+}
+
+// A single-axis table after `simplify_table`. Its kept entries are no longer
+// evenly spaced, which `lookup_single_axis` relies on to bracket a minute, so
+// it is a type of its own that only `lookup_simplified` reads.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SimplifiedTable {
+    pub config: LookupTableConfig,
+    pub days: Vec<DayData<SingleAxisEntry>>,
+    pub metadata: TableMetadata,
+}
+
+// Outcome of `simplify_table`. Bytes count the rotation values (and surface
+// orientation when the table has it) plus, for the simplified table, a u16
+// minute per kept entry now that entries are no longer evenly spaced.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SimplifyReport {
+    pub original_entries: usize,
+    pub kept_entries: usize,
+    pub original_bytes: usize,
+    pub simplified_bytes: usize,
+    pub max_error: f64,
+}
+
+// A fixed (non-tracking) array for `generate_fixed_table`. `azimuth` is the
+// compass direction the panel faces, as in `SurfaceOrientation`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct FixedMountConfig {
+    pub tilt: f64,
+    pub azimuth: f64,
+    pub min_altitude: f64,
+}
+
+impl Default for FixedMountConfig {
+    fn default() -> Self {
+        Self {
+            tilt: 30.0,
+            azimuth: 180.0,
+            min_altitude: 0.0,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct FixedEntry {
+    pub minutes: i32,
+    pub above_threshold: bool,
+    pub aoi: Option<f64>,
+}
+
+pub type FixedTable = LookupTable<FixedEntry>;
+
+// Rotation is stored as f32, like `EventKind::Move`, to halve static RAM on
+// MCUs; the rounding is far below actuator resolution.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ProgramStep {
+    pub minutes: i32,
+    pub rotation: f32,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ShadeEvent {
+    pub minutes: i32,
+    pub closed: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct InverterEvent {
+    pub minutes: i32,
+    pub awake: bool,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct GoldenConfig {
+    pub latitudes: Vec<f64>,
+    pub longitudes: Vec<f64>,
+    pub dates: Vec<NaiveDate>,
+    pub utc_hours: Vec<u32>,
+    pub table_interval_minutes: i32,
+}
+
+impl Default for GoldenConfig {
+    fn default() -> Self {
+        let date = |m, d| NaiveDate::from_ymd_opt(2026, m, d).unwrap();
+        Self {
+            latitudes: vec![-33.9, 0.0, 39.8, 64.8],
+            longitudes: vec![-89.6, 0.0, 151.2],
+            dates: vec![
+                date(1, 1),
+                date(3, 21),
+                date(6, 21),
+                date(9, 22),
+                date(12, 21),
+            ],
+            utc_hours: (0..24).step_by(3).collect(),
+            table_interval_minutes: 60,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct GoldenCase {
+    pub latitude: f64,
+    pub longitude: f64,
+    pub timestamp: String,
+    pub position: SolarPosition,
+    pub single_axis_tilt: f64,
+    pub dual_axis: DualAxisAngles,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct GoldenTableRow {
+    pub latitude: f64,
+    pub longitude: f64,
+    pub day_of_year: i32,
+    pub minutes: i32,
+    pub rotation: Option<f64>,
+    pub tilt: Option<f64>,
+    pub panel_azimuth: Option<f64>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct GoldenDataset {
+    pub cases: Vec<GoldenCase>,
+    pub table_rows: Vec<GoldenTableRow>,
+}
+
+// How one output moved between two golden datasets. `output` names the
+// function and field, e.g. "solar_position.zenith" or
+// "single_axis_table.rotation". `max_delta` is in the output's own unit
+// (degrees, or minutes for the equation of time); `max_delta_at` keys the
+// case or row it came from. Values that appear or vanish (a table entry
+// crossing sunrise) count in `availability_changed`, not the delta.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct GoldenOutputChange {
+    pub output: String,
+    pub compared: usize,
+    pub changed: usize,
+    pub availability_changed: usize,
+    pub max_delta: f64,
+    pub max_delta_at: Option<String>,
+}
+
+// Cases and rows only one dataset has are counted, not compared.
+#[derive(Debug, Clone, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct GoldenChangeReport {
+    pub tolerance: f64,
+    pub outputs: Vec<GoldenOutputChange>,
+    pub unmatched_old: usize,
+    pub unmatched_new: usize,
+}
@@ -0,0 +1,262 @@
+use std::fmt;
+
+use chrono::NaiveDate;
+
+use crate::angles;
+use crate::types::{IrradianceComponents, MinuteOfDay, SolarPosition, WeatherFile, WeatherRecord};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum WeatherFileError {
+    MissingHeader,
+    MissingColumn(&'static str),
+    MissingField { line: usize, column: &'static str },
+    InvalidNumber { line: usize, column: &'static str },
+    InvalidDate { line: usize },
+}
+
+impl fmt::Display for WeatherFileError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            WeatherFileError::MissingHeader => write!(f, "missing site header"),
+            WeatherFileError::MissingColumn(column) => write!(f, "missing column {column}"),
+            WeatherFileError::MissingField { line, column } => {
+                write!(f, "line {line}: missing {column}")
+            }
+            WeatherFileError::InvalidNumber { line, column } => {
+                write!(f, "line {line}: invalid number in {column}")
+            }
+            WeatherFileError::InvalidDate { line } => write!(f, "line {line}: invalid date"),
+        }
+    }
+}
+
+impl std::error::Error for WeatherFileError {}
+
+// Typical-year files hold 365 days whatever years the months were drawn
+// from, so dates are numbered in a common year and 29 February is dropped.
+const TYPICAL_YEAR: i32 = 2026;
+
+const SITE: &str = "site header";
+const DATE: &str = "date";
+const TIME: &str = "time";
+const GHI: &str = "GHI";
+const DNI: &str = "DNI";
+const DHI: &str = "DHI";
+const TEMPERATURE: &str = "dry bulb";
+const WIND_SPEED: &str = "wind speed";
+
+// EPW marks missing irradiance with 9999, temperature with 99.9 and wind
+// speed with 999.
+const EPW_MISSING_IRRADIANCE: f64 = 9999.0;
+const EPW_MISSING_TEMPERATURE: f64 = 99.9;
+const EPW_MISSING_WIND: f64 = 999.0;
+
+// Splits a CSV line, trimming fields and dropping the quotes TMY3 puts
+// around station names (which never contain commas).
+fn fields(line: &str) -> Vec<&str> {
+    line.split(',')
+        .map(|f| f.trim().trim_matches('"'))
+        .collect()
+}
+
+fn number(
+    fields: &[&str],
+    col: usize,
+    line: usize,
+    column: &'static str,
+) -> Result<f64, WeatherFileError> {
+    let field = fields
+        .get(col)
+        .copied()
+        .filter(|f| !f.is_empty())
+        .ok_or(WeatherFileError::MissingField { line, column })?;
+    field
+        .parse::<f64>()
+        .map_err(|_| WeatherFileError::InvalidNumber { line, column })
+}
+
+// Day of year and interval-start hour from a month, day and the files'
+// hour-ending convention (1..=24). None for 29 February.
+fn day_and_hour(
+    month: u32,
+    day: u32,
+    hour_ending: u32,
+    line: usize,
+) -> Result<Option<(i32, u32)>, WeatherFileError> {
+    if month == 2 && day == 29 {
+        return Ok(None);
+    }
+    if NaiveDate::from_ymd_opt(TYPICAL_YEAR, month, day).is_none()
+        || !(1..=24).contains(&hour_ending)
+    {
+        return Err(WeatherFileError::InvalidDate { line });
+    }
+    Ok(Some((
+        angles::day_of_year(TYPICAL_YEAR, month, day),
+        hour_ending - 1,
+    )))
+}
+
+// NREL TMY3 CSV: a site line (USAF, name, state, time zone, latitude,
+// longitude, elevation), a header row, then one row per hour with
+// MM/DD/YYYY dates and HH:MM hour-ending times. Columns are found by their
+// header names, so the uncertainty and source columns are ignored.
+pub fn parse_tmy3(input: &str) -> Result<WeatherFile, WeatherFileError> {
+    let mut lines = input
+        .lines()
+        .enumerate()
+        .map(|(i, line)| (i + 1, line.trim()))
+        .filter(|(_, line)| !line.is_empty());
+    let (site_line, site) = lines.next().ok_or(WeatherFileError::MissingHeader)?;
+    let site = fields(site);
+    let site_number = |col| number(&site, col, site_line, SITE);
+    let (utc_offset_hours, latitude) = (site_number(3)?, site_number(4)?);
+    let (longitude, elevation) = (site_number(5)?, site_number(6)?);
+
+    let (_, header) = lines.next().ok_or(WeatherFileError::MissingHeader)?;
+    let columns = fields(header);
+    let column = |prefix: &str, name: &'static str| {
+        columns
+            .iter()
+            .position(|c| c.starts_with(prefix))
+            .ok_or(WeatherFileError::MissingColumn(name))
+    };
+    let (date_col, time_col) = (column("Date", DATE)?, column("Time", TIME)?);
+    let (ghi_col, dni_col) = (column("GHI (", GHI)?, column("DNI (", DNI)?);
+    let (dhi_col, temp_col) = (column("DHI (", DHI)?, column("Dry-bulb (", TEMPERATURE)?);
+    let wind_col = column("Wspd (", WIND_SPEED)?;
+
+    let mut records = Vec::new();
+    for (line, text) in lines {
+        let row = fields(text);
+        let field = |col: usize, column: &'static str| {
+            row.get(col)
+                .copied()
+                .ok_or(WeatherFileError::MissingField { line, column })
+        };
+        let date: Vec<&str> = field(date_col, DATE)?.split('/').collect();
+        let hour = field(time_col, TIME)?.split(':').next().unwrap_or_default();
+        let int = |text: Option<&&str>| text.and_then(|t| t.parse::<u32>().ok());
+        let (Some(month), Some(day), Some(hour)) =
+            (int(date.first()), int(date.get(1)), int(Some(&hour)))
+        else {
+            return Err(WeatherFileError::InvalidDate { line });
+        };
+        let Some((day_of_year, hour)) = day_and_hour(month, day, hour, line)? else {
+            continue;
+        };
+        let value = |col, column| number(&row, col, line, column);
+        records.push(WeatherRecord {
+            day_of_year,
+            hour,
+            ghi: value(ghi_col, GHI)?,
+            dni: value(dni_col, DNI)?,
+            dhi: value(dhi_col, DHI)?,
+            temperature: Some(value(temp_col, TEMPERATURE)?),
+            wind_speed: Some(value(wind_col, WIND_SPEED)?),
+        });
+    }
+    records.sort_by_key(|r| (r.day_of_year, r.hour));
+    Ok(WeatherFile {
+        latitude,
+        longitude,
+        utc_offset_hours,
+        elevation,
+        records,
+    })
+}
+
+// EnergyPlus EPW: a LOCATION line (city, state, country, source, WMO,
+// latitude, longitude, time zone, elevation), seven more header lines up to
+// DATA PERIODS, then fixed-position hourly fields. Hours with missing
+// irradiance are dropped, so the simulation counts them as zero.
+pub fn parse_epw(input: &str) -> Result<WeatherFile, WeatherFileError> {
+    let mut lines = input
+        .lines()
+        .enumerate()
+        .map(|(i, line)| (i + 1, line.trim()))
+        .filter(|(_, line)| !line.is_empty());
+    let (site_line, site) = lines
+        .by_ref()
+        .find(|(_, line)| line.starts_with("LOCATION"))
+        .ok_or(WeatherFileError::MissingHeader)?;
+    let site = fields(site);
+    let site_number = |col| number(&site, col, site_line, SITE);
+    let (latitude, longitude) = (site_number(6)?, site_number(7)?);
+    let (utc_offset_hours, elevation) = (site_number(8)?, site_number(9)?);
+    lines
+        .by_ref()
+        .find(|(_, line)| line.starts_with("DATA PERIODS"))
+        .ok_or(WeatherFileError::MissingHeader)?;
+
+    let mut records = Vec::new();
+    for (line, text) in lines {
+        let row = fields(text);
+        let value = |col, column| number(&row, col, line, column);
+        let int = |col| value(col, DATE).map(|v| v as u32);
+        let Some((day_of_year, hour)) = day_and_hour(int(1)?, int(2)?, int(3)?, line)? else {
+            continue;
+        };
+        let (ghi, dni, dhi) = (value(13, GHI)?, value(14, DNI)?, value(15, DHI)?);
+        if [ghi, dni, dhi].iter().any(|&v| v >= EPW_MISSING_IRRADIANCE) {
+            continue;
+        }
+        let temperature = value(6, TEMPERATURE)?;
+        let wind_speed = value(21, WIND_SPEED)?;
+        records.push(WeatherRecord {
+            day_of_year,
+            hour,
+            ghi,
+            dni,
+            dhi,
+            temperature: (temperature < EPW_MISSING_TEMPERATURE).then_some(temperature),
+            wind_speed: (wind_speed < EPW_MISSING_WIND).then_some(wind_speed),
+        });
+    }
+    records.sort_by_key(|r| (r.day_of_year, r.hour));
+    Ok(WeatherFile {
+        latitude,
+        longitude,
+        utc_offset_hours,
+        elevation,
+        records,
+    })
+}
+
+// The hour covering a UTC day of year and minute, after shifting to the
+// file's standard time; wraps around the year, so the first UTC hours of
+// 1 January read the file's last local hours. None where the file has no
+// record.
+pub fn weather_record(
+    file: &WeatherFile,
+    day_of_year: i32,
+    utc_minutes: MinuteOfDay,
+) -> Option<&WeatherRecord> {
+    let offset = (file.utc_offset_hours * 60.0).round() as i32;
+    let minute = ((day_of_year - 1) * 1440 + utc_minutes.get() + offset).rem_euclid(365 * 1440);
+    let key = (minute / 1440 + 1, (minute % 1440 / 60) as u32);
+    let i = file
+        .records
+        .binary_search_by_key(&key, |r| (r.day_of_year, r.hour))
+        .ok()?;
+    Some(&file.records[i])
+}
+
+// Irradiance source for `simulate_yield_with` and `yield_sweep_with`. The
+// simulation's site should match the file's; day 366 of a leap year has no
+// data.
+pub fn weather_irradiance(
+    file: &WeatherFile,
+) -> impl Fn(i32, MinuteOfDay, &SolarPosition) -> Option<IrradianceComponents> + Sync + '_ {
+    move |day_of_year, minutes, _| {
+        if day_of_year > 365 {
+            return None;
+        }
+        let record = weather_record(file, day_of_year, minutes)?;
+        Some(IrradianceComponents {
+            ghi: record.ghi,
+            dni: record.dni,
+            dhi: record.dhi,
+        })
+    }
+}
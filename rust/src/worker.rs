@@ -0,0 +1,83 @@
+use chrono::{DateTime, Utc};
+
+use crate::angles;
+use crate::config::{validate_config, ConfigError};
+use crate::lookup_table::{
+    generate_dual_axis_table, generate_single_axis_table, lookup_dual_axis, lookup_single_axis,
+    table_keys_at_utc,
+};
+use crate::types::{
+    DualAxisEntry, DualAxisTable, LookupTableConfig, MinuteOfDay, SingleAxisEntry, SingleAxisTable,
+    SolarPosition,
+};
+
+// Entry points for wasm32 Web Workers and Cloudflare Workers: plain numbers
+// in, Option or Result out. Nothing here reads a clock, prints, or panics,
+// whatever the input. Times are JavaScript epoch milliseconds (`Date.now()`),
+// so bindings can pass them straight through.
+
+fn utc_from_epoch_millis(epoch_millis: f64) -> Option<DateTime<Utc>> {
+    let millis = epoch_millis.round();
+    if !millis.is_finite() || millis.abs() >= i64::MAX as f64 {
+        return None;
+    }
+    DateTime::from_timestamp_millis(millis as i64)
+}
+
+// The table's keys for an instant: UTC day of year and minute of day, or
+// solar ones for an hour-angle table.
+fn table_keys(config: &LookupTableConfig, epoch_millis: f64) -> Option<(i32, MinuteOfDay)> {
+    table_keys_at_utc(config, utc_from_epoch_millis(epoch_millis)?.naive_utc())
+}
+
+// None for non-finite input, coordinates off the globe, or a time chrono
+// cannot represent.
+pub fn position_at_epoch_millis(
+    latitude: f64,
+    longitude: f64,
+    epoch_millis: f64,
+) -> Option<SolarPosition> {
+    if !(-90.0..=90.0).contains(&latitude) || !(-180.0..=180.0).contains(&longitude) {
+        return None;
+    }
+    let utc = utc_from_epoch_millis(epoch_millis)?;
+    Some(angles::solar_position(latitude, longitude, &utc))
+}
+
+pub fn lookup_single_axis_at_epoch_millis(
+    table: &SingleAxisTable,
+    epoch_millis: f64,
+) -> Option<SingleAxisEntry> {
+    let (day_of_year, minutes) = table_keys(&table.config, epoch_millis)?;
+    lookup_single_axis(table, day_of_year, minutes)
+}
+
+pub fn lookup_dual_axis_at_epoch_millis(
+    table: &DualAxisTable,
+    epoch_millis: f64,
+) -> Option<DualAxisEntry> {
+    let (day_of_year, minutes) = table_keys(&table.config, epoch_millis)?;
+    lookup_dual_axis(table, day_of_year, minutes)
+}
+
+// Validates first, since the generators assume a checked config, and takes
+// the generation time from the caller rather than a clock.
+pub fn generate_single_axis_table_checked(
+    config: &LookupTableConfig,
+    generated_at: &str,
+) -> Result<SingleAxisTable, ConfigError> {
+    validate_config(config)?;
+    let mut table = generate_single_axis_table(config);
+    table.metadata.generated_at = generated_at.to_string();
+    Ok(table)
+}
+
+pub fn generate_dual_axis_table_checked(
+    config: &LookupTableConfig,
+    generated_at: &str,
+) -> Result<DualAxisTable, ConfigError> {
+    validate_config(config)?;
+    let mut table = generate_dual_axis_table(config);
+    table.metadata.generated_at = generated_at.to_string();
+    Ok(table)
+}
@@ -1,6 +1,9 @@
-use chrono::{FixedOffset, TimeZone};
+use chrono::{Datelike, FixedOffset, NaiveDate, TimeZone, Utc};
 
-use solar_tracker::types::{Season, SolarPosition};
+use solar_tracker::types::{
+    DeclinationModel, DualAxisMount, EquationOfTimeModel, PositionModel, Season, SolarPosition,
+    TerrainSlope, VectorFrame,
+};
 use solar_tracker::angles::*;
 
 macro_rules! assert_approx {
@@ -119,6 +122,39 @@ fn test_solar_declination_bounded_all_days() {
     }
 }
 
+#[test]
+fn test_spencer_declination_equinoxes() {
+    // 2026 equinoxes fall on 20 March (day 79) and 23 September (day 266)
+    assert_approx!(spencer_declination(79), 0.0, 0.5);
+    assert_approx!(spencer_declination(266), 0.0, 0.3);
+    assert!(solar_declination(266).abs() > 0.9);
+}
+
+#[test]
+fn test_spencer_declination_solstices() {
+    assert_approx!(spencer_declination(172), 23.44, 0.05);
+    assert_approx!(spencer_declination(355), -23.44, 0.05);
+}
+
+#[test]
+fn test_declination_dispatches_on_model() {
+    for n in [1, 100, 200, 300] {
+        assert_eq!(declination(n, DeclinationModel::Cooper), solar_declination(n));
+        assert_eq!(declination(n, DeclinationModel::Spencer), spencer_declination(n));
+    }
+}
+
+#[test]
+fn test_solar_position_with_model() {
+    let t = dt(2026, 9, 23, 12, 0, -6);
+    let cooper = solar_position_with_model(39.8, -89.6, &t, DeclinationModel::Cooper);
+    let spencer = solar_position_with_model(39.8, -89.6, &t, DeclinationModel::Spencer);
+    assert_eq!(cooper, solar_position(39.8, -89.6, &t));
+    assert_eq!(spencer.declination, spencer_declination(266));
+    assert_eq!(spencer.equation_of_time, cooper.equation_of_time);
+    assert!((spencer.altitude - cooper.altitude).abs() > 0.5);
+}
+
 // ── SolarPosition — Springfield Equinox ──
 
 fn springfield_equinox() -> SolarPosition {
@@ -198,6 +234,555 @@ fn test_single_axis_positive_afternoon() {
     assert!(single_axis_tilt(&pos, 39.8) > 0.0);
 }
 
+// ── Backtracking ──
+
+#[test]
+fn test_backtrack_no_change_at_high_sun() {
+    assert_approx!(backtrack(10.0, 0.4), 10.0, 1e-12);
+    assert_approx!(backtrack(-30.0, 0.4), -30.0, 1e-12);
+}
+
+#[test]
+fn test_backtrack_reduces_low_sun_rotation() {
+    let bt = backtrack(80.0, 0.4);
+    assert_approx!(bt, 15.7, 0.1);
+    let bt_neg = backtrack(-80.0, 0.4);
+    assert_approx!(bt_neg, -15.7, 0.1);
+}
+
+#[test]
+fn test_backtrack_threshold_matches_gcr() {
+    // Backtracking starts where cos(rotation) == gcr
+    let threshold = rad_to_deg(0.4_f64.acos());
+    assert_approx!(backtrack(threshold - 0.01, 0.4), threshold - 0.01, 1e-9);
+    assert!(backtrack(threshold + 1.0, 0.4) < threshold);
+}
+
+#[test]
+fn test_backtracking_rotation_morning_flattens() {
+    let pos = solar_position(39.8, -89.6, &dt(2026, 6, 21, 6, 0, -6));
+    let ideal = single_axis_tilt(&pos, 39.8);
+    let bt = backtracking_rotation(&pos, 39.8, 0.4, 60.0);
+    assert!(ideal < 0.0);
+    assert!(bt > ideal, "bt={} ideal={}", bt, ideal);
+    assert!(bt <= 0.0);
+}
+
+#[test]
+fn test_backtracking_rotation_respects_max_angle() {
+    let pos = solar_position(39.8, -89.6, &dt(2026, 6, 21, 9, 0, -6));
+    let bt = backtracking_rotation(&pos, 39.8, 0.1, 20.0);
+    assert!(bt.abs() <= 20.0);
+}
+
+#[test]
+fn test_backtracking_rotation_flat_at_night() {
+    let pos = solar_position(39.8, -89.6, &dt(2026, 6, 21, 0, 0, -6));
+    assert_eq!(backtracking_rotation(&pos, 39.8, 0.4, 60.0), 0.0);
+}
+
+// ── Terrain slope ──
+
+#[test]
+fn test_tilted_axis_rotation_flat_axis_faces_sun() {
+    let pos = solar_position(39.8, -89.6, &dt(2026, 6, 21, 9, 0, -6));
+    let rotation = tilted_axis_rotation(&pos, 0.0, 180.0);
+    assert!(rotation < 0.0);
+    let normal = surface_normal(&surface_orientation(rotation, 0.0, 180.0));
+    // No other rotation gets the panel closer to the sun
+    for delta in [-1.0, 1.0] {
+        let other = surface_normal(&surface_orientation(rotation + delta, 0.0, 180.0));
+        assert!(angle_between(normal, sun_vector(&pos)) < angle_between(other, sun_vector(&pos)));
+    }
+}
+
+#[test]
+fn test_terrain_axis_tilt_and_cross_slope() {
+    let south = TerrainSlope { slope: 10.0, aspect: 180.0 };
+    assert_approx!(terrain_axis_tilt(&south, 180.0), 10.0, 1e-9);
+    assert_approx!(cross_axis_slope(&south, 180.0), 0.0, 1e-9);
+
+    let west = TerrainSlope { slope: 10.0, aspect: 270.0 };
+    assert_approx!(terrain_axis_tilt(&west, 180.0), 0.0, 1e-9);
+    assert_approx!(cross_axis_slope(&west, 180.0), 10.0, 1e-9);
+    let east = TerrainSlope { slope: 10.0, aspect: 90.0 };
+    assert_approx!(cross_axis_slope(&east, 180.0), -10.0, 1e-9);
+}
+
+#[test]
+fn test_slope_aware_backtrack_flat_matches_backtrack() {
+    for rotation in [-75.0, -40.0, 10.0, 65.0] {
+        assert_approx!(slope_aware_backtrack(rotation, 0.4, 0.0), backtrack(rotation, 0.4), 1e-9);
+    }
+}
+
+#[test]
+fn test_slope_aware_backtrack_rows_just_touch() {
+    // Sun in the rotation plane perpendicular to the ideal panel; with row
+    // pitch 1/gcr on a ground line rising by the cross-axis slope, the
+    // backtracked panel's shadow should end exactly at the next row.
+    let gcr = 0.4;
+    for (ideal, cross) in [(-70.0, 10.0), (-80.0, -8.0), (60.0, -10.0)] {
+        let rotation = slope_aware_backtrack(ideal, gcr, cross);
+        assert!(rotation != ideal);
+        let (sin_i, cos_i) = deg_to_rad(ideal).sin_cos();
+        let perp = [cos_i, sin_i];
+        let pitch = [1.0 / gcr, deg_to_rad(cross).tan() / gcr];
+        let (sin_r, cos_r) = deg_to_rad(rotation).sin_cos();
+        let spacing = (pitch[0] * perp[0] + pitch[1] * perp[1]).abs();
+        let footprint = (cos_r * perp[0] + sin_r * perp[1]).abs();
+        assert_approx!(spacing, footprint, 1e-9);
+    }
+}
+
+#[test]
+fn test_fixed_tilt_on_slope() {
+    let flat = TerrainSlope { slope: 0.0, aspect: 180.0 };
+    assert_approx!(fixed_tilt_on_slope(39.8, &flat), optimal_fixed_tilt(39.8), 1e-9);
+    let south = TerrainSlope { slope: 10.0, aspect: 180.0 };
+    assert_approx!(fixed_tilt_on_slope(39.8, &south), optimal_fixed_tilt(39.8) - 10.0, 1e-9);
+    let north = TerrainSlope { slope: 10.0, aspect: 0.0 };
+    assert_approx!(fixed_tilt_on_slope(39.8, &north), optimal_fixed_tilt(39.8) + 10.0, 1e-9);
+}
+
+// ── Rotation limits ──
+
+#[test]
+fn test_clamp_rotation_within_limits() {
+    assert_eq!(clamp_rotation(30.0, Some(-45.0), Some(45.0)), (30.0, false));
+}
+
+#[test]
+fn test_clamp_rotation_flags_clamped() {
+    assert_eq!(clamp_rotation(60.0, Some(-45.0), Some(45.0)), (45.0, true));
+    assert_eq!(clamp_rotation(-60.0, Some(-45.0), Some(45.0)), (-45.0, true));
+}
+
+#[test]
+fn test_clamp_rotation_unbounded() {
+    assert_eq!(clamp_rotation(89.0, None, None), (89.0, false));
+    assert_eq!(clamp_rotation(-89.0, None, Some(10.0)), (-89.0, false));
+}
+
+#[test]
+fn test_clamp_rotation_unchecked_limits() {
+    assert_eq!(clamp_rotation(10.0, Some(20.0), Some(-20.0)), (10.0, false));
+    assert_eq!(clamp_rotation(30.0, Some(20.0), Some(-20.0)), (20.0, true));
+    assert_eq!(clamp_rotation(10.0, Some(f64::NAN), None), (10.0, false));
+    assert_eq!(clamp_rotation(-70.0, Some(f64::NAN), Some(f64::NAN)), (-70.0, false));
+    assert_eq!(clamp_rotation(70.0, Some(f64::NAN), Some(60.0)), (60.0, true));
+}
+
+#[test]
+fn test_single_axis_tilt_clamped_morning() {
+    let pos = solar_position(39.8, -89.6, &dt(2026, 6, 21, 6, 0, -6));
+    let (rotation, clamped) = single_axis_tilt_clamped(&pos, 39.8, Some(-45.0), Some(45.0));
+    assert!(clamped);
+    assert_eq!(rotation, -45.0);
+}
+
+#[test]
+fn test_single_axis_tilt_clamped_noon_untouched() {
+    let pos = solar_position(39.8, -89.6, &dt(2026, 6, 21, 12, 0, -6));
+    let (rotation, clamped) = single_axis_tilt_clamped(&pos, 39.8, Some(-45.0), Some(45.0));
+    assert!(!clamped);
+    assert_approx!(rotation, single_axis_tilt(&pos, 39.8), 1e-12);
+}
+
+#[test]
+fn test_dual_axis_angles_clamped_tilt() {
+    let pos = solar_position(39.8, -89.6, &dt(2026, 12, 21, 8, 0, -6));
+    let (da, clamped) = dual_axis_angles_clamped(&pos, Some(0.0), Some(75.0));
+    assert!(clamped);
+    assert_eq!(da.tilt, 75.0);
+    assert_approx!(da.panel_azimuth, dual_axis_angles(&pos).panel_azimuth, 1e-12);
+}
+
+// ── Polar axis ──
+
+#[test]
+fn test_polar_axis_rotation_zero_at_solar_noon() {
+    let pos = solar_position(39.8, -89.6, &dt(2026, 3, 21, 12, 0, -6));
+    let rotation = polar_axis_rotation(&pos);
+    assert_approx!(rotation, pos.hour_angle, 1e-12);
+    assert_approx!(rotation, 0.0, 5.0);
+}
+
+#[test]
+fn test_polar_axis_rotation_15_deg_per_hour() {
+    let pos_10 = solar_position(39.8, -89.6, &dt(2026, 6, 21, 10, 0, -6));
+    let pos_11 = solar_position(39.8, -89.6, &dt(2026, 6, 21, 11, 0, -6));
+    assert_approx!(polar_axis_rotation(&pos_11) - polar_axis_rotation(&pos_10), 15.0, 1e-9);
+}
+
+#[test]
+fn test_polar_axis_incidence_equals_declination() {
+    let pos = solar_position(39.8, -89.6, &dt(2026, 12, 21, 10, 0, -6));
+    assert_approx!(polar_axis_incidence(&pos), 23.45, 0.5);
+    let pos = solar_position(39.8, -89.6, &dt(2026, 3, 21, 10, 0, -6));
+    assert_approx!(polar_axis_incidence(&pos), 0.0, 1.0);
+}
+
+// ── Surface orientation ──
+
+#[test]
+fn test_surface_orientation_flat() {
+    let s = surface_orientation(0.0, 0.0, 180.0);
+    assert_approx!(s.surface_tilt, 0.0, 1e-9);
+}
+
+#[test]
+fn test_surface_orientation_horizontal_axis_east_west() {
+    let east = surface_orientation(-30.0, 0.0, 180.0);
+    assert_approx!(east.surface_tilt, 30.0, 1e-9);
+    assert_approx!(east.surface_azimuth, 90.0, 1e-9);
+    let west = surface_orientation(30.0, 0.0, 180.0);
+    assert_approx!(west.surface_tilt, 30.0, 1e-9);
+    assert_approx!(west.surface_azimuth, 270.0, 1e-9);
+}
+
+#[test]
+fn test_surface_orientation_tilted_axis_at_zero_rotation() {
+    let s = surface_orientation(0.0, 20.0, 180.0);
+    assert_approx!(s.surface_tilt, 20.0, 1e-9);
+    assert_approx!(s.surface_azimuth, 180.0, 1e-9);
+}
+
+#[test]
+fn test_surface_orientation_tilt_formula() {
+    // cos(surface_tilt) = cos(rotation) * cos(axis_tilt)
+    for &(r, bt) in &[(10.0, 20.0), (-45.0, 30.0), (60.0, 5.0)] {
+        let s = surface_orientation(r, bt, 180.0);
+        let expected = rad_to_deg((deg_to_rad(r).cos() * deg_to_rad(bt).cos()).acos());
+        assert_approx!(s.surface_tilt, expected, 1e-9);
+    }
+}
+
+#[test]
+fn test_surface_orientation_tilted_axis_west_rotation_turns_west() {
+    let s = surface_orientation(40.0, 20.0, 180.0);
+    assert!(s.surface_azimuth > 180.0 && s.surface_azimuth < 270.0, "{}", s.surface_azimuth);
+}
+
+// ── East-west axis ──
+
+#[test]
+fn test_ew_axis_tilt_noon_equals_zenith_facing_south() {
+    let pos = solar_position(39.8, -89.6, &dt(2026, 12, 21, 12, 0, -6));
+    let tilt = ew_axis_tilt(&pos);
+    assert!(tilt > 0.0);
+    assert_approx!(tilt, pos.zenith, 1.5);
+}
+
+#[test]
+fn test_ew_axis_tilt_faces_north_when_sun_north() {
+    // Summer morning at mid-latitude: sun rises north of east
+    let pos = solar_position(39.8, -89.6, &dt(2026, 6, 21, 6, 0, -6));
+    assert!(pos.azimuth < 90.0);
+    assert!(ew_axis_tilt(&pos) < 0.0);
+}
+
+#[test]
+fn test_ew_axis_tilt_southern_hemisphere_faces_north() {
+    let pos = solar_position(-33.9, 18.4, &dt(2026, 6, 21, 12, 0, 2));
+    assert!(ew_axis_tilt(&pos) < 0.0);
+}
+
+#[test]
+fn test_ew_axis_tilt_minimizes_incidence() {
+    let pos = solar_position(39.8, -89.6, &dt(2026, 3, 21, 9, 0, -6));
+    let tilt = ew_axis_tilt(&pos);
+    let incidence = |beta: f64| {
+        let (z, az, b) = (deg_to_rad(pos.zenith), deg_to_rad(pos.azimuth), deg_to_rad(beta));
+        -b.sin() * z.sin() * az.cos() + b.cos() * z.cos()
+    };
+    assert!(incidence(tilt) >= incidence(tilt + 1.0));
+    assert!(incidence(tilt) >= incidence(tilt - 1.0));
+}
+
+// ── Debug breakdown ──
+
+#[test]
+fn test_debug_breakdown_matches_solar_position() {
+    for hour in [0, 6, 9, 12, 15, 18] {
+        let t = dt(2026, 6, 21, hour, 20, -6);
+        let pos = solar_position(39.8, -89.6, &t);
+        let dbg = solar_position_debug(39.8, -89.6, &t);
+        assert_eq!(dbg.day_of_year, pos.day_of_year);
+        assert_eq!(dbg.equation_of_time, pos.equation_of_time);
+        assert_eq!(dbg.declination, pos.declination);
+        assert_eq!(dbg.local_solar_time, pos.local_solar_time);
+        assert_eq!(dbg.hour_angle, pos.hour_angle);
+        assert_eq!(dbg.zenith, pos.zenith);
+        assert_eq!(dbg.altitude, pos.altitude);
+        assert_eq!(dbg.azimuth, pos.azimuth);
+    }
+}
+
+#[test]
+fn test_debug_breakdown_intermediates() {
+    let dbg = solar_position_debug(39.8, -89.6, &dt(2026, 3, 21, 12, 0, -6));
+    assert_approx!(dbg.utc_hours, 18.0, 1e-12);
+    assert_approx!(dbg.intermediate_angle_b, intermediate_angle_b(80), 1e-12);
+    assert_approx!(dbg.eot_terms.iter().sum::<f64>(), dbg.equation_of_time, 1e-9);
+    assert_approx!(dbg.correction, utc_lst_correction(-89.6, dbg.equation_of_time), 1e-12);
+    assert_approx!(dbg.cos_zenith, deg_to_rad(dbg.zenith).cos(), 1e-12);
+    assert_approx!(dbg.sin_azimuth.hypot(dbg.cos_azimuth), deg_to_rad(dbg.zenith).sin(), 1e-12);
+}
+
+// ── Analemma ──
+
+#[test]
+fn test_analemma_sample_count() {
+    assert_eq!(analemma(39.8, -89.6, 18.0, 2026).len(), 365);
+    assert_eq!(analemma(39.8, -89.6, 18.0, 2028).len(), 366);
+}
+
+#[test]
+fn test_analemma_matches_solar_position() {
+    let points = analemma(39.8, -89.6, 18.0, 2026);
+    let pos = solar_position(39.8, -89.6, &dt(2026, 6, 21, 12, 0, -6));
+    assert_approx!(points[171].0, pos.altitude, 1e-9);
+    assert_approx!(points[171].1, pos.azimuth, 1e-9);
+}
+
+#[test]
+fn test_analemma_noon_figure_eight() {
+    // Near local solar noon the altitude swings by twice the axial tilt
+    // and the azimuth wanders either side of due south with the EoT
+    let points = analemma(39.8, -89.6, 18.0, 2026);
+    let max_alt = points.iter().map(|p| p.0).fold(f64::MIN, f64::max);
+    let min_alt = points.iter().map(|p| p.0).fold(f64::MAX, f64::min);
+    assert_approx!(max_alt - min_alt, 2.0 * EARTH_AXIAL_TILT, 1.5);
+    assert!(points.iter().any(|p| p.1 < 180.0) && points.iter().any(|p| p.1 > 180.0));
+}
+
+// ── Shadows ──
+
+fn sky(altitude: f64, azimuth: f64) -> SolarPosition {
+    SolarPosition {
+        day_of_year: 80,
+        declination: 0.0,
+        equation_of_time: 0.0,
+        local_solar_time: 12.0,
+        hour_angle: 0.0,
+        zenith: 90.0 - altitude,
+        altitude,
+        azimuth,
+    }
+}
+
+#[test]
+fn test_shadow_of_pole_at_45_degrees() {
+    let pos = sky(45.0, 135.0);
+    let (length, bearing) = shadow_of_pole(2.0, &pos).unwrap();
+    assert_approx!(length, 2.0, 1e-9);
+    assert_approx!(bearing, 315.0, 1e-9);
+}
+
+#[test]
+fn test_shadow_of_pole_noon_points_north() {
+    let pos = solar_position(39.8, -89.6, &dt(2026, 12, 21, 12, 0, -6));
+    let (length, bearing) = shadow_of_pole(1.0, &pos).unwrap();
+    assert!(bearing.min(360.0 - bearing) < 2.0, "{bearing}");
+    assert_approx!(length, 1.0 / deg_to_rad(pos.altitude).tan(), 1e-9);
+    // Winter noon shadows are longer than the pole
+    assert!(length > 1.0);
+}
+
+#[test]
+fn test_shadow_of_pole_below_horizon() {
+    let pos = sky(-5.0, 180.0);
+    assert!(shadow_of_pole(1.0, &pos).is_none());
+}
+
+#[test]
+fn test_panel_edge_shadow_flat_panel_overhead_sun() {
+    let pos = sky(90.0, 180.0);
+    let (near, far) = panel_edge_shadow(2.0, 1.5, 0.0, 0.0, &pos).unwrap();
+    assert_approx!(near, -1.0, 1e-9);
+    assert_approx!(far, 1.0, 1e-9);
+}
+
+#[test]
+fn test_panel_edge_shadow_morning_falls_west() {
+    // Sun low in the east, panel facing it: the shadow lies to the west and
+    // is longer than the panel chord
+    let pos = sky(20.0, 90.0);
+    let (near, far) = panel_edge_shadow(2.0, 1.5, -60.0, 0.0, &pos).unwrap();
+    assert!(far < 0.0);
+    assert!(far - near > 2.0);
+    // Swapping the axis direction mirrors the shadow
+    let (near_m, far_m) = panel_edge_shadow(2.0, 1.5, 60.0, 180.0, &pos).unwrap();
+    assert_approx!(near_m, -far, 1e-9);
+    assert_approx!(far_m, -near, 1e-9);
+}
+
+#[test]
+fn test_panel_edge_shadow_sun_down() {
+    let pos = sky(-1.0, 80.0);
+    assert!(panel_edge_shadow(2.0, 1.5, 0.0, 0.0, &pos).is_none());
+}
+
+// ── Angle difference / circular mean ──
+
+#[test]
+fn test_angle_difference_wraps() {
+    assert_approx!(angle_difference(350.0, 10.0), 20.0, 1e-9);
+    assert_approx!(angle_difference(10.0, 350.0), -20.0, 1e-9);
+    assert_approx!(angle_difference(-170.0, 170.0), -20.0, 1e-9);
+    assert_approx!(angle_difference(720.0, 45.0), 45.0, 1e-9);
+}
+
+#[test]
+fn test_angle_difference_half_turn_is_positive() {
+    assert_approx!(angle_difference(0.0, 180.0), 180.0, 1e-9);
+    assert_approx!(angle_difference(180.0, 0.0), 180.0, 1e-9);
+}
+
+#[test]
+fn test_circular_mean_across_north() {
+    let mean = circular_mean(&[350.0, 10.0]).unwrap();
+    assert_approx!(angle_difference(0.0, mean), 0.0, 1e-9);
+    assert_approx!(circular_mean(&[80.0, 90.0, 100.0]).unwrap(), 90.0, 1e-9);
+    assert_approx!(circular_mean(&[-90.0]).unwrap(), 270.0, 1e-9);
+}
+
+#[test]
+fn test_circular_mean_undefined() {
+    assert!(circular_mean(&[]).is_none());
+    assert!(circular_mean(&[0.0, 180.0]).is_none());
+}
+
+#[test]
+fn test_circular_mean_weighted() {
+    let mean = circular_mean_weighted(&[(0.0, 3.0), (90.0, 1.0)]).unwrap();
+    assert_approx!(mean, rad_to_deg(1.0_f64.atan2(3.0)), 1e-9);
+}
+
+// ── Sun vector ──
+
+fn norm(v: [f64; 3]) -> f64 {
+    (v[0] * v[0] + v[1] * v[1] + v[2] * v[2]).sqrt()
+}
+
+#[test]
+fn test_sun_vector_unit_length() {
+    for hour in 0..24 {
+        let pos = solar_position(39.8, -89.6, &dt(2026, 6, 21, hour, 0, -6));
+        assert_approx!(norm(sun_vector(&pos)), 1.0, 1e-12);
+    }
+}
+
+#[test]
+fn test_sun_vector_up_component_is_sin_altitude() {
+    let pos = solar_position(39.8, -89.6, &dt(2026, 6, 21, 12, 0, -6));
+    let v = sun_vector(&pos);
+    assert_approx!(v[2], deg_to_rad(pos.altitude).sin(), 1e-12);
+    assert!(v[1] < 0.0, "noon sun should be south: {:?}", v);
+}
+
+#[test]
+fn test_sun_vector_morning_east() {
+    let pos = solar_position(39.8, -89.6, &dt(2026, 3, 21, 8, 0, -6));
+    assert!(sun_vector(&pos)[0] > 0.0);
+}
+
+#[test]
+fn test_sun_vector_frames_consistent() {
+    let pos = solar_position(39.8, -89.6, &dt(2026, 3, 21, 15, 0, -6));
+    let [e, n, u] = sun_vector_in(&pos, VectorFrame::Enu);
+    assert_eq!(sun_vector_in(&pos, VectorFrame::Ned), [n, e, -u]);
+    assert_eq!(sun_vector_in(&pos, VectorFrame::Nwu), [n, -e, u]);
+}
+
+// ── Panel normal ──
+
+#[test]
+fn test_panel_normal_flat_points_up() {
+    assert_eq!(panel_normal(0.0, 123.0), [0.0, 0.0, 1.0]);
+}
+
+#[test]
+fn test_panel_normal_matches_dual_axis_target() {
+    for hour in 7..18 {
+        let pos = solar_position(39.8, -89.6, &dt(2026, 6, 21, hour, 0, -6));
+        let da = dual_axis_angles(&pos);
+        assert_approx!(incidence_angle(&pos, da.tilt, da.panel_azimuth), 0.0, 1e-4);
+        let normal = panel_normal(da.tilt, da.panel_azimuth);
+        assert_approx!(vector_dot(normal, sun_vector(&pos)), 1.0, 1e-9);
+    }
+}
+
+#[test]
+fn test_dual_axis_target_minimizes_incidence() {
+    let pos = solar_position(39.8, -89.6, &dt(2026, 9, 10, 10, 0, -6));
+    let da = dual_axis_angles(&pos);
+    for (dt_, daz) in [(2.0, 0.0), (-2.0, 0.0), (0.0, 3.0), (0.0, -3.0)] {
+        assert!(incidence_angle(&pos, da.tilt + dt_, da.panel_azimuth + daz) > 0.5);
+    }
+}
+
+#[test]
+fn test_surface_normal_matches_single_axis_incidence() {
+    let pos = solar_position(39.8, -89.6, &dt(2026, 6, 21, 9, 0, -6));
+    // Exact true-tracking rotation for a horizontal N-S axis
+    let (z, az) = (deg_to_rad(pos.zenith), deg_to_rad(pos.azimuth));
+    let rotation = rad_to_deg((-z.sin() * az.sin()).atan2(z.cos()));
+    let surface = surface_orientation(rotation, 0.0, 180.0);
+    let incidence = angle_between(surface_normal(&surface), sun_vector(&pos));
+    // A horizontal N-S axis can only remove the east-west component
+    let axis = [0.0, 1.0, 0.0];
+    let residual = 90.0 - angle_between(axis, sun_vector(&pos));
+    assert_approx!(incidence, residual.abs(), 0.05);
+}
+
+#[test]
+fn test_angle_between_orthogonal() {
+    assert_approx!(angle_between([1.0, 0.0, 0.0], [0.0, 2.0, 0.0]), 90.0, 1e-12);
+}
+
+// ── Mount parameterizations ──
+
+#[test]
+fn test_mount_azimuth_elevation_matches_dual_axis() {
+    let pos = solar_position(39.8, -89.6, &dt(2026, 6, 21, 10, 0, -6));
+    let da = dual_axis_angles(&pos);
+    let m = mount_angles(&pos, 39.8, DualAxisMount::AzimuthElevation);
+    assert_approx!(m.primary, da.panel_azimuth, 1e-12);
+    assert_approx!(m.secondary, da.tilt, 1e-12);
+}
+
+#[test]
+fn test_mount_tilt_roll_points_at_sun() {
+    for hour in 7..18 {
+        let pos = solar_position(39.8, -89.6, &dt(2026, 6, 21, hour, 0, -6));
+        let m = mount_angles(&pos, 39.8, DualAxisMount::TiltRoll);
+        let (sin_r, cos_r) = deg_to_rad(m.primary).sin_cos();
+        let (sin_t, cos_t) = deg_to_rad(m.secondary).sin_cos();
+        let normal = [-sin_r * cos_t, -sin_t, cos_r * cos_t];
+        assert_approx!(angle_between(normal, sun_vector(&pos)), 0.0, 1e-4);
+    }
+}
+
+#[test]
+fn test_mount_tilt_roll_signs() {
+    let morning = solar_position(39.8, -89.6, &dt(2026, 3, 21, 8, 0, -6));
+    let m = mount_angles(&morning, 39.8, DualAxisMount::TiltRoll);
+    assert!(m.primary < 0.0, "morning roll should face east: {}", m.primary);
+    assert!(m.secondary > 0.0, "equinox tilt should face south: {}", m.secondary);
+}
+
+#[test]
+fn test_mount_polar_declination() {
+    for (lat, month) in [(39.8, 6), (39.8, 12), (-33.9, 6)] {
+        let pos = solar_position(lat, -89.6, &dt(2026, month, 21, 11, 0, -6));
+        let m = mount_angles(&pos, lat, DualAxisMount::PolarDeclination);
+        assert_approx!(m.primary, polar_axis_rotation(&pos), 1e-12);
+        assert_approx!(m.secondary, pos.declination, 1e-6);
+    }
+}
+
 // ── DualAxisAngles ──
 
 #[test]
@@ -280,6 +865,79 @@ fn test_seasonal_tilt_equator() {
     assert_approx!(seasonal_tilt_adjustment(0.0, Season::Spring), 0.0, 0.01);
 }
 
+#[test]
+fn test_season_from_date_astronomical() {
+    assert_eq!(Season::from_date(40.0, 3, 19), Season::Winter);
+    assert_eq!(Season::from_date(40.0, 3, 20), Season::Spring);
+    assert_eq!(Season::from_date(40.0, 6, 21), Season::Summer);
+    assert_eq!(Season::from_date(40.0, 9, 22), Season::Fall);
+    assert_eq!(Season::from_date(40.0, 12, 21), Season::Winter);
+    assert_eq!(Season::from_date(40.0, 1, 1), Season::Winter);
+}
+
+#[test]
+fn test_season_from_date_southern_flips() {
+    assert_eq!(Season::from_date(-33.9, 7, 15), Season::Winter);
+    assert_eq!(Season::from_date(-33.9, 1, 15), Season::Summer);
+    assert_eq!(Season::from_date(-33.9, 4, 15), Season::Fall);
+    assert_eq!(Season::from_date(-33.9, 10, 15), Season::Spring);
+    assert_eq!(Season::from_date(0.0, 7, 15), Season::Summer);
+}
+
+#[test]
+fn test_season_from_month_meteorological() {
+    let north: Vec<Season> = (1..=12).map(|m| Season::from_month_meteorological(40.0, m)).collect();
+    use Season::*;
+    assert_eq!(
+        north,
+        [Winter, Winter, Spring, Spring, Spring, Summer, Summer, Summer, Fall, Fall, Fall, Winter]
+    );
+    assert_eq!(Season::from_month_meteorological(-40.0, 12), Summer);
+    assert_eq!(Season::from_month_meteorological(-40.0, 3), Fall);
+}
+
+#[test]
+fn test_season_feeds_tilt_adjustment() {
+    let season = Season::from_date(-33.9, 7, 15);
+    assert_approx!(seasonal_tilt_adjustment(-33.9, season), 48.9, 1e-9);
+}
+
+#[test]
+fn test_seasonal_tilt_for_day_northern() {
+    assert_approx!(seasonal_tilt_for_day(40.0, 172), 25.0, 1e-9);
+    assert_approx!(seasonal_tilt_for_day(40.0, 355), 55.0, 1e-9);
+    assert_approx!(seasonal_tilt_for_day(40.0, 80), 40.0, 1e-9);
+    assert_approx!(seasonal_tilt_for_day(40.0, 266), 40.0, 1e-9);
+}
+
+#[test]
+fn test_seasonal_tilt_for_day_southern_mirrors() {
+    // Sydney: steep in the July winter, shallow in the December summer
+    assert_approx!(seasonal_tilt_for_day(-33.9, 196), 48.9, 1e-9);
+    assert_approx!(seasonal_tilt_for_day(-33.9, 355), 18.9, 1e-9);
+    let winter = |lat: f64| (1..=365).filter(|&d| seasonal_tilt_for_day(lat, d) > 50.0).count();
+    assert_approx!(winter(-40.0), winter(40.0), 2.0);
+}
+
+#[test]
+fn test_seasonal_tilt_for_day_quarters_centred_on_solstices() {
+    let summer: Vec<i32> = (1..=365).filter(|&d| seasonal_tilt_for_day(40.0, d) < 30.0).collect();
+    assert!((88..=96).contains(&summer.len()), "{} days", summer.len());
+    assert!(summer.contains(&172));
+    assert!((120..=132).contains(&summer[0]), "starts {}", summer[0]);
+}
+
+#[test]
+fn test_seasonal_tilt_for_day_clamps_near_equator() {
+    for doy in 1..=365 {
+        for lat in [0.0, 5.0, -5.0, 14.9] {
+            assert!(seasonal_tilt_for_day(lat, doy) >= 0.0);
+        }
+    }
+    assert_approx!(seasonal_tilt_for_day(0.0, 172), 0.0, 1e-9);
+    assert_approx!(seasonal_tilt_for_day(5.0, 355), 20.0, 1e-9);
+}
+
 // ── HourAngle ──
 
 #[test]
@@ -419,6 +1077,198 @@ fn test_equation_of_time_bounded() {
     }
 }
 
+// ── Leap-year fractional year ──
+
+#[test]
+fn test_fractional_year_matches_b_in_common_years() {
+    for n in [1, 80, 200, 365] {
+        assert_eq!(fractional_year(n, 2026), intermediate_angle_b(n));
+        assert_eq!(equation_of_time_in_year(n, 2026), equation_of_time(n));
+        assert_eq!(
+            declination_in_year(n, 2026, DeclinationModel::Cooper),
+            solar_declination(n)
+        );
+    }
+}
+
+#[test]
+fn test_fractional_year_day_366_stays_in_year() {
+    let gamma = fractional_year(366, 2028);
+    assert!(gamma < 2.0 * std::f64::consts::PI);
+    // The 365-day angle wraps day 366 back onto 1 January
+    assert_approx!(intermediate_angle_b(366), 2.0 * std::f64::consts::PI, 1e-12);
+}
+
+#[test]
+fn test_leap_year_new_years_eve_matches_common_year() {
+    // 31 December is day 366 in 2028 and day 365 in 2026: nearly the same sun
+    assert_approx!(equation_of_time_in_year(366, 2028), equation_of_time(365), 0.05);
+    for model in [DeclinationModel::Cooper, DeclinationModel::Spencer] {
+        assert_approx!(
+            declination_in_year(366, 2028, model),
+            declination(365, model),
+            0.1
+        );
+    }
+    // Without the year, day 366 reads as 1 January
+    assert_approx!(equation_of_time(366), equation_of_time(1), 1e-9);
+}
+
+#[test]
+fn test_solar_position_leap_year_december() {
+    let leap = solar_position(39.8, -89.6, &dt(2028, 12, 31, 12, 0, -6));
+    let common = solar_position(39.8, -89.6, &dt(2026, 12, 31, 12, 0, -6));
+    assert_eq!(leap.day_of_year, 366);
+    assert_approx!(leap.declination, common.declination, 0.1);
+    assert_approx!(leap.equation_of_time, common.equation_of_time, 0.05);
+}
+
+// ── Equation of time model ──
+
+#[test]
+fn test_equation_of_time_model_dispatch() {
+    for n in [1, 45, 200, 366] {
+        assert_eq!(
+            equation_of_time_with_model(n, 2028, EquationOfTimeModel::Fourier),
+            equation_of_time_in_year(n, 2028)
+        );
+        assert_eq!(
+            equation_of_time_with_model(n, 2028, EquationOfTimeModel::Ecliptic),
+            solar_tracker::ephemeris::equation_of_time_for_day(n, 2028)
+        );
+    }
+}
+
+#[test]
+fn test_solar_position_with_models() {
+    let t = dt(2026, 1, 12, 12, 0, -6);
+    let fourier = solar_position_with_models(
+        39.8, -89.6, &t, DeclinationModel::Cooper, EquationOfTimeModel::Fourier,
+    );
+    assert_eq!(fourier, solar_position(39.8, -89.6, &t));
+    let ecliptic = solar_position_with_models(
+        39.8, -89.6, &t, DeclinationModel::Cooper, EquationOfTimeModel::Ecliptic,
+    );
+    assert_eq!(ecliptic.declination, fourier.declination);
+    assert_approx!(
+        ecliptic.equation_of_time,
+        equation_of_time_with_model(12, 2026, EquationOfTimeModel::Ecliptic),
+        1e-12
+    );
+    // Four minutes of equation of time per degree of hour angle
+    assert_approx!(
+        ecliptic.hour_angle - fourier.hour_angle,
+        (ecliptic.equation_of_time - fourier.equation_of_time) / 4.0,
+        1e-9
+    );
+}
+
+// ── Position model ──
+
+#[test]
+fn test_position_model_fast_is_solar_position() {
+    let t = dt(2026, 6, 21, 13, 0, -5);
+    assert_eq!(
+        solar_position_with_position_model(39.8, -89.6, &t, PositionModel::Fast),
+        solar_position(39.8, -89.6, &t)
+    );
+    assert_eq!(PositionModel::default(), PositionModel::Fast);
+}
+
+#[test]
+fn test_position_model_standard_uses_its_formulas() {
+    let t = dt(2026, 9, 22, 12, 0, -5);
+    let standard = solar_position_with_position_model(39.8, -89.6, &t, PositionModel::Standard);
+    let expected = solar_position_with_models(
+        39.8, -89.6, &t, DeclinationModel::Spencer, EquationOfTimeModel::Ecliptic,
+    );
+    assert_eq!(standard, expected);
+}
+
+#[test]
+fn test_position_model_precise_is_ephemeris() {
+    let t = dt(2026, 9, 22, 12, 0, -5);
+    let precise = solar_position_with_position_model(39.8, -89.6, &t, PositionModel::Precise);
+    assert_eq!(
+        precise,
+        solar_tracker::ephemeris::solar_position_precise(39.8, -89.6, &t, None)
+    );
+    // Each step up the ladder moves the declination towards the ephemeris.
+    // The per-day series are sampled at the start of the UTC day, so compare
+    // there.
+    let (mut fast_worst, mut standard_worst) = (0.0_f64, 0.0_f64);
+    for n in (1..=365).step_by(7) {
+        let t = dt(2026, 1, 1, 0, 0, 0) + chrono::Duration::days(n as i64 - 1);
+        let precise = solar_position_with_position_model(0.0, 0.0, &t, PositionModel::Precise);
+        let fast = solar_position(0.0, 0.0, &t);
+        let standard = solar_position_with_position_model(0.0, 0.0, &t, PositionModel::Standard);
+        fast_worst = fast_worst.max((fast.declination - precise.declination).abs());
+        standard_worst = standard_worst.max((standard.declination - precise.declination).abs());
+    }
+    assert!(standard_worst < 0.3 && standard_worst < fast_worst / 3.0, "{standard_worst}");
+}
+
+// ── Julian day ──
+
+#[test]
+fn test_julian_day_meeus_examples() {
+    // Meeus, Astronomical Algorithms, example 7.a and table 7.a
+    assert_eq!(julian_day(&dt(2000, 1, 1, 12, 0, 0)), JULIAN_DAY_J2000);
+    assert_eq!(julian_day(&dt(1987, 6, 19, 12, 0, 0)), 2_446_966.0);
+    let sputnik = chrono::Utc.with_ymd_and_hms(1957, 10, 4, 19, 26, 24).unwrap();
+    assert_approx!(julian_day(&sputnik), 2_436_116.31, 1e-6);
+    let date = |y, m, d| chrono::NaiveDate::from_ymd_opt(y, m, d).unwrap();
+    assert_eq!(julian_day_from_date(date(1999, 1, 1)), 2_451_179.5);
+    assert_eq!(julian_day_from_date(date(1900, 1, 1)), 2_415_020.5);
+    assert_eq!(julian_day_from_date(date(1600, 1, 1)), 2_305_447.5);
+}
+
+#[test]
+fn test_julian_day_uses_utc_instant() {
+    // 6 am in Chicago is noon UTC
+    assert_eq!(julian_day(&dt(2000, 1, 1, 6, 0, -6)), JULIAN_DAY_J2000);
+}
+
+#[test]
+fn test_julian_day_round_trip() {
+    let at = chrono::Utc.with_ymd_and_hms(2026, 3, 21, 17, 45, 12).unwrap()
+        + chrono::Duration::milliseconds(250);
+    assert_eq!(datetime_from_julian_day(julian_day(&at)), Some(at));
+    assert_eq!(datetime_from_julian_day(f64::NAN), None);
+    assert_eq!(datetime_from_julian_day(1e30), None);
+}
+
+#[test]
+fn test_julian_century() {
+    assert_eq!(julian_century(JULIAN_DAY_J2000), 0.0);
+    // 1987 April 10, 0h TD: Meeus example 22.a
+    assert_approx!(julian_century(2_446_895.5), -0.127296372348, 1e-12);
+    assert_approx!(julian_day_from_century(julian_century(2_460_000.25)), 2_460_000.25, 1e-9);
+}
+
+// ── Earth-sun distance ──
+
+#[test]
+fn test_earth_sun_distance_perihelion_aphelion() {
+    // Perihelion early January, aphelion early July
+    assert_approx!(earth_sun_distance(3), 0.983, 0.002);
+    assert_approx!(earth_sun_distance(185), 1.017, 0.002);
+    let nearest = (1..=365)
+        .min_by(|&a, &b| earth_sun_distance(a).total_cmp(&earth_sun_distance(b)))
+        .unwrap();
+    assert!(nearest <= 10, "{nearest}");
+}
+
+#[test]
+fn test_eccentricity_correction_inverse_square() {
+    for n in [1, 80, 172, 266, 355] {
+        let r = earth_sun_distance(n);
+        assert_approx!(eccentricity_correction(n) * r * r, 1.0, 1e-12);
+    }
+    let mean: f64 = (1..=365).map(eccentricity_correction).sum::<f64>() / 365.0;
+    assert_approx!(mean, 1.000110, 1e-4);
+}
+
 // ── Zenith non-negative ──
 
 #[test]
@@ -488,3 +1338,224 @@ fn test_morning_afternoon_symmetry() {
     assert!(pos_9am.azimuth < 180.0);
     assert!(pos_3pm.azimuth > 180.0);
 }
+
+// ── Unusual timestamps ──
+
+#[test]
+fn test_leap_second_matches_last_whole_second() {
+    let leap = chrono::NaiveDate::from_ymd_opt(2016, 12, 31)
+        .unwrap()
+        .and_hms_milli_opt(23, 59, 59, 1_500)
+        .unwrap()
+        .and_utc();
+    let before = chrono::Utc.with_ymd_and_hms(2016, 12, 31, 23, 59, 59).unwrap();
+    assert_eq!(solar_position(-33.9, 151.2, &leap), solar_position(-33.9, 151.2, &before));
+}
+
+#[test]
+fn test_year_boundary_is_continuous() {
+    for year in [2024, 2025] {
+        let before = dt(year, 12, 31, 23, 59, 0);
+        let after = dt(year + 1, 1, 1, 0, 0, 0);
+        let (a, b) = (solar_position(-33.9, 151.2, &before), solar_position(-33.9, 151.2, &after));
+        assert_eq!(b.day_of_year, 1);
+        assert_approx!(a.declination, b.declination, 0.5);
+        assert_approx!(a.altitude, b.altitude, 0.5);
+    }
+}
+
+#[test]
+fn test_day_of_year_follows_utc_date() {
+    // New Year's morning in Auckland is still 31 December in UTC
+    let auckland = dt(2027, 1, 1, 10, 0, 13);
+    assert_eq!(solar_position(-36.8, 174.8, &auckland).day_of_year, 365);
+}
+
+#[test]
+fn test_checked_accepts_valid_years() {
+    for year in [*VALID_YEARS.start(), 2026, *VALID_YEARS.end()] {
+        let t = dt(year, 6, 21, 12, 0, 0);
+        assert_eq!(solar_position_checked(39.8, -89.6, &t), Ok(solar_position(39.8, -89.6, &t)));
+    }
+}
+
+#[test]
+fn test_checked_rejects_far_past_and_future() {
+    for year in [-500, 1066, 1900, 2100, 9999] {
+        let t = dt(year, 6, 21, 12, 0, 0);
+        assert_eq!(
+            solar_position_checked(39.8, -89.6, &t),
+            Err(PositionError::YearOutOfRange { year })
+        );
+    }
+}
+
+#[test]
+fn test_checked_uses_utc_year() {
+    // Still 2099 locally, already 2100 in UTC
+    let t = dt(2099, 12, 31, 20, 0, -5);
+    assert_eq!(
+        solar_position_checked(39.8, -89.6, &t),
+        Err(PositionError::YearOutOfRange { year: 2100 })
+    );
+    assert!(PositionError::YearOutOfRange { year: 2100 }.to_string().contains("1901-2099"));
+}
+
+#[test]
+fn test_checked_rejects_bad_coordinates() {
+    let t = dt(2026, 6, 21, 12, 0, 0);
+    for latitude in [200.0, -90.5, f64::NAN, f64::INFINITY] {
+        let err = solar_position_checked(latitude, -89.6, &t).unwrap_err();
+        assert!(matches!(err, PositionError::LatitudeOutOfRange { .. }), "{latitude}");
+    }
+    for longitude in [180.5, -360.0, f64::NAN] {
+        let err = solar_position_checked(39.8, longitude, &t).unwrap_err();
+        assert!(matches!(err, PositionError::LongitudeOutOfRange { .. }), "{longitude}");
+    }
+    // The poles and the antimeridian are on the globe
+    for (latitude, longitude) in [(90.0, 0.0), (-90.0, 0.0), (0.0, 180.0), (0.0, -180.0)] {
+        assert!(solar_position_checked(latitude, longitude, &t).is_ok());
+    }
+    let err = PositionError::LatitudeOutOfRange { latitude: 200.0 };
+    assert_eq!(err.to_string(), "latitude 200 outside -90..=90");
+}
+
+#[test]
+fn test_day_of_year_checked() {
+    assert_eq!(day_of_year_checked(2026, 3, 21), Ok(day_of_year(2026, 3, 21)));
+    assert_eq!(day_of_year_checked(2024, 12, 31), Ok(366));
+    for (month, day) in [(13, 1), (0, 1), (2, 29), (4, 31), (1, 0)] {
+        assert_eq!(
+            day_of_year_checked(2026, month, day),
+            Err(PositionError::InvalidDate { year: 2026, month, day })
+        );
+    }
+    // Unchecked, month 13 counts on into the next year
+    assert!(day_of_year(2026, 13, 1) > 365);
+}
+
+// ── Unix epoch seconds ──
+
+#[test]
+fn test_days_to_ymd_matches_chrono() {
+    let epoch = NaiveDate::from_ymd_opt(1970, 1, 1).unwrap();
+    for days in [-719_468, -1, 0, 59, 365, 11_016, 19_782, 20_454, 47_540, 2_932_896] {
+        let date = epoch + chrono::Duration::days(days);
+        assert_eq!(days_to_ymd(days), (date.year() as i64, date.month(), date.day()), "{days}");
+    }
+}
+
+#[test]
+fn test_solar_position_unix_matches_chrono() {
+    for t in [dt(2026, 3, 20, 12, 0, -6), dt(2024, 2, 29, 23, 59, 0), dt(2099, 12, 31, 6, 7, 0)] {
+        let unix = solar_position_unix(39.8, -89.6, t.timestamp() as u64);
+        assert_eq!(unix, solar_position(39.8, -89.6, &t));
+    }
+    let with_seconds = Utc.with_ymd_and_hms(2026, 6, 21, 17, 42, 37).unwrap();
+    assert_eq!(
+        solar_position_unix(-33.9, 151.2, with_seconds.timestamp() as u64),
+        solar_position(-33.9, 151.2, &with_seconds)
+    );
+    assert_eq!(solar_position_unix(0.0, 0.0, 0).day_of_year, 1);
+}
+
+#[test]
+fn test_solar_position_unix_far_future_is_finite() {
+    let pos = solar_position_unix(39.8, -89.6, u64::MAX);
+    assert!(pos.altitude.is_finite() && pos.azimuth.is_finite());
+}
+
+// ── Batch positions ──
+
+#[test]
+fn test_solar_positions_match_single_calls() {
+    let start = dt(2026, 12, 31, 15, 0, -6);
+    // Crosses into the next UTC year and back again, so the per-day cache
+    // has to notice every change of date
+    let mut times: Vec<_> = (0..200).map(|i| start + chrono::Duration::minutes(7 * i)).collect();
+    times.push(start);
+    times.push(dt(2024, 2, 29, 12, 0, 0));
+    let batch = solar_positions(39.8, -89.6, &times);
+    assert_eq!(batch.len(), times.len());
+    for (t, pos) in times.iter().zip(&batch) {
+        assert_eq!(*pos, solar_position(39.8, -89.6, t), "{t}");
+    }
+    assert!(solar_positions(39.8, -89.6, &[] as &[chrono::DateTime<Utc>]).is_empty());
+}
+
+// ── Position tracker ──
+
+#[test]
+fn test_tracker_matches_solar_position_every_poll() {
+    // A controller polling every 5 s across local midnight, which at +10 is
+    // not a change of UTC date
+    let mut tracker = PositionTracker::new(-33.9, 151.2);
+    let start = dt(2026, 6, 20, 23, 0, 10);
+    for i in 0..2000 {
+        let t = start + chrono::Duration::seconds(5 * i);
+        assert_eq!(tracker.position(&t), solar_position(-33.9, 151.2, &t), "{t}");
+    }
+    // 23:00 +10 on the 20th is 13:00 UTC; 2000 polls end before 16:00 UTC
+    assert_eq!(tracker.recomputes(), 1);
+    assert_eq!((tracker.latitude(), tracker.longitude()), (-33.9, 151.2));
+}
+
+#[test]
+fn test_tracker_recomputes_once_per_utc_date() {
+    let mut tracker = PositionTracker::new(39.8, -89.6);
+    let t = dt(2026, 3, 1, 17, 0, -6);
+    tracker.position(&t);
+    tracker.position(&(t + chrono::Duration::hours(1)));
+    assert_eq!(tracker.recomputes(), 2);
+    // Back to the earlier date: another recompute, still the same answer
+    assert_eq!(tracker.position(&t), solar_position(39.8, -89.6, &t));
+    assert_eq!(tracker.recomputes(), 3);
+}
+
+// ── Position series ──
+
+#[test]
+fn test_series_steps_from_start() {
+    let start = dt(2026, 6, 21, 6, 0, -5);
+    let series: Vec<_> =
+        solar_position_series(39.8, -89.6, &start, chrono::Duration::minutes(30), 5).collect();
+    assert_eq!(series.len(), 5);
+    for (i, (t, pos)) in series.iter().enumerate() {
+        let expected = start.with_timezone(&Utc) + chrono::Duration::minutes(30 * i as i64);
+        assert_eq!(*t, expected);
+        assert_eq!(*pos, solar_position(39.8, -89.6, t));
+    }
+}
+
+#[test]
+fn test_series_until_includes_end() {
+    let start = Utc.with_ymd_and_hms(2026, 3, 20, 0, 0, 0).unwrap();
+    let end = Utc.with_ymd_and_hms(2026, 3, 21, 0, 0, 0).unwrap();
+    let hourly = chrono::Duration::hours(1);
+    let series: Vec<_> = solar_position_series_until(39.8, -89.6, &start, hourly, &end).collect();
+    assert_eq!(series.len(), 25);
+    assert_eq!(series.last().unwrap().0, end);
+    // Iterator chains instead of loops: the hours the sun is up
+    let daylight = solar_position_series_until(39.8, -89.6, &start, hourly, &end)
+        .filter(|(_, pos)| pos.altitude > 0.0)
+        .count();
+    assert!((11..=13).contains(&daylight), "{daylight}");
+}
+
+#[test]
+fn test_series_edge_steps() {
+    let start = Utc.with_ymd_and_hms(2026, 3, 20, 0, 0, 0).unwrap();
+    let end = start + chrono::Duration::hours(1);
+    for step in [chrono::Duration::zero(), chrono::Duration::minutes(-5)] {
+        assert_eq!(solar_position_series_until(0.0, 0.0, &start, step, &end).count(), 0);
+    }
+    // A counted series may stand still or run backwards
+    let backwards: Vec<_> =
+        solar_position_series(0.0, 0.0, &start, chrono::Duration::minutes(-5), 3).collect();
+    assert_eq!(backwards[2].0, start - chrono::Duration::minutes(10));
+    assert_eq!(solar_position_series(0.0, 0.0, &start, chrono::Duration::zero(), 0).count(), 0);
+    // Stops at the end of chrono's range instead of overflowing
+    let near_max = chrono::DateTime::<Utc>::MAX_UTC - chrono::Duration::days(1);
+    let day = chrono::Duration::days(1);
+    assert_eq!(solar_position_series(0.0, 0.0, &near_max, day, 10).count(), 2);
+}
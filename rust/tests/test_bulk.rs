@@ -0,0 +1,87 @@
+#![cfg(feature = "simd")]
+
+use solar_tracker::angles::*;
+use solar_tracker::bulk::*;
+use solar_tracker::types::*;
+
+macro_rules! assert_approx {
+    ($left:expr, $right:expr, $tol:expr) => {
+        let (l, r) = ($left as f64, $right as f64);
+        assert!(
+            (l - r).abs() <= $tol,
+            "assert_approx failed: left={}, right={}, diff={}, tol={}",
+            l, r, (l - r).abs(), $tol
+        );
+    };
+}
+
+// ── Agreement with the scalar path ──
+
+#[test]
+fn test_bulk_matches_scalar_angles() {
+    let hour_angles: Vec<f64> = (0..=1440).map(|i| -180.0 + i as f64 * 0.25).collect();
+    for latitude in [-90.0, -66.5, -33.9, 0.0, 23.44, 39.8, 78.2, 90.0] {
+        for declination in [-23.44, -7.0, 0.0, 12.3, 23.44] {
+            let bulk = bulk_zenith_azimuth(latitude, declination, &hour_angles);
+            for (&ha, &(zenith, azimuth)) in hour_angles.iter().zip(&bulk) {
+                assert_approx!(zenith, solar_zenith_angle(latitude, declination, ha), 1e-9);
+                let scalar = solar_azimuth(latitude, declination, ha);
+                // At the poles and the zenith azimuth is degenerate
+                if zenith > 1e-6 && latitude.abs() < 90.0 {
+                    assert_approx!(angle_difference(scalar, azimuth), 0.0, 1e-9);
+                }
+                // normalize_angle sends -1e-17 to 360, as the scalar path does
+                assert!((0.0..=360.0).contains(&azimuth), "{azimuth}");
+            }
+        }
+    }
+}
+
+// Lengths that leave a partial last chunk, or none at all
+#[test]
+fn test_bulk_handles_partial_chunks() {
+    for len in [0, 1, LANES - 1, LANES, LANES + 1, 3 * LANES + 2] {
+        let hour_angles: Vec<f64> = (0..len).map(|i| -60.0 + 7.5 * i as f64).collect();
+        let bulk = bulk_zenith_azimuth(39.8, 12.3, &hour_angles);
+        assert_eq!(bulk.len(), len);
+        for (&ha, &(zenith, _)) in hour_angles.iter().zip(&bulk) {
+            assert_approx!(zenith, solar_zenith_angle(39.8, 12.3, ha), 1e-9);
+        }
+    }
+}
+
+// ── Day sweeps ──
+
+#[test]
+fn test_day_angles_match_solar_position() {
+    let config = LookupTableConfig { interval_minutes: 1, ..Default::default() };
+    let day = bulk_day_angles(&config, 172);
+    assert_eq!(day.len(), 1440);
+    for &(minutes, zenith, azimuth) in day.iter().step_by(37) {
+        let at = chrono::NaiveDate::from_yo_opt(config.year, 172)
+            .unwrap()
+            .and_hms_opt(0, 0, 0)
+            .unwrap()
+            .and_utc()
+            + chrono::Duration::minutes(minutes.get() as i64);
+        let pos = solar_position(config.latitude, config.longitude, &at);
+        assert_approx!(zenith, pos.zenith, 1e-9);
+        assert_approx!(angle_difference(pos.azimuth, azimuth), 0.0, 1e-9);
+    }
+}
+
+#[test]
+fn test_day_angles_follow_table_key() {
+    let config = LookupTableConfig {
+        interval_minutes: 30,
+        table_key: TableKey::HourAngle,
+        ..Default::default()
+    };
+    let day = bulk_day_angles(&config, 80);
+    assert_eq!(day.len(), 48);
+    // Hour-angle keys put solar noon at minute 720: the sun due south
+    let &(_, zenith, azimuth) = day.iter().find(|(m, ..)| *m == MinuteOfDay::NOON).unwrap();
+    assert_approx!(azimuth, 180.0, 1e-9);
+    let decl = declination_in_year(80, config.year, config.declination_model);
+    assert_approx!(zenith, config.latitude - decl, 1e-9);
+}
@@ -0,0 +1,122 @@
+use std::sync::LazyLock;
+
+use solar_tracker::calibration::*;
+use solar_tracker::chebyshev::{compress_chebyshev, lookup_chebyshev};
+use solar_tracker::fleet::{apply_calibration, tracker_config};
+use solar_tracker::lookup_table::*;
+use solar_tracker::types::*;
+
+fn minute_of_day(minutes: i32) -> MinuteOfDay {
+    MinuteOfDay::new(minutes).unwrap()
+}
+
+macro_rules! assert_approx {
+    ($left:expr, $right:expr, $tol:expr) => {
+        let (l, r) = ($left as f64, $right as f64);
+        assert!(
+            (l - r).abs() <= $tol,
+            "assert_approx failed: left={}, right={}, diff={}, tol={}",
+            l, r, (l - r).abs(), $tol
+        );
+    };
+}
+
+fn site() -> LookupTableConfig {
+    LookupTableConfig {
+        interval_minutes: 15,
+        min_rotation: Some(-60.0),
+        max_rotation: Some(60.0),
+        ..Default::default()
+    }
+}
+
+static TABLE: LazyLock<SingleAxisTable> = LazyLock::new(|| generate_single_axis_table(&site()));
+
+const FIELD: CalibrationOffsets =
+    CalibrationOffsets { axis_azimuth_error: 0.0, zero_offset: 1.5, scale: 0.98 };
+
+// ── Rotation frame ──
+
+#[test]
+fn test_calibrate_round_trip() {
+    for rotation in [-60.0, -12.5, 0.0, 33.0] {
+        let actuator = calibrate_rotation(rotation, &FIELD);
+        assert_approx!(actuator, rotation * 0.98 + 1.5, 1e-12);
+        assert_approx!(uncalibrate_rotation(actuator, &FIELD).unwrap(), rotation, 1e-12);
+    }
+    let stuck = CalibrationOffsets { scale: 0.0, ..FIELD };
+    assert!(uncalibrate_rotation(1.5, &stuck).is_none());
+    assert_eq!(calibrate_rotation(17.0, &CalibrationOffsets::default()), 17.0);
+}
+
+// ── Lookup layer ──
+
+#[test]
+fn test_query_time_matches_calibrated_table() {
+    let mut flashed = TABLE.clone();
+    apply_calibration(&mut flashed, &FIELD);
+    for (doy, minutes) in [(80, 900), (172, 1087), (355, 1200), (172, 0)] {
+        let at_query = lookup_single_axis_calibrated(&TABLE, doy, minute_of_day(minutes), &FIELD);
+        let expected = lookup_single_axis(&flashed, doy, minute_of_day(minutes));
+        // The two paths round differently, so rotations match to within an ulp or so
+        let (Some(at_query), Some(expected)) = (at_query, expected) else {
+            assert_eq!(at_query.is_some(), expected.is_some(), "{doy} {minutes}");
+            continue;
+        };
+        assert_eq!((at_query.minutes, at_query.clamped), (expected.minutes, expected.clamped));
+        assert_eq!(at_query.rotation.is_some(), expected.rotation.is_some(), "{doy} {minutes}");
+        if let (Some(a), Some(b)) = (at_query.rotation, expected.rotation) {
+            assert_approx!(a, b, 1e-9);
+        }
+    }
+}
+
+// Surveyed axes use the exact tilted-axis rotation, the same one the
+// correction is taken from, so the two agree away from the limits
+#[test]
+fn test_axis_azimuth_error_matches_regenerated_table() {
+    let surveyed = LookupTableConfig { axis_azimuth: Some(180.0), ..site() };
+    let nominal = generate_single_axis_table(&surveyed);
+    let calibration = CalibrationOffsets { axis_azimuth_error: 4.0, ..Default::default() };
+    let tracker = TrackerOverride { calibration: Some(calibration), ..Default::default() };
+    let regenerated = generate_single_axis_table(&tracker_config(&surveyed, &tracker));
+    let mut compared = 0;
+    for doy in [15, 80, 172, 264] {
+        let day = &nominal.days[doy as usize - 1];
+        for entry in day.entries.iter().filter(|e| e.rotation.is_some() && !e.clamped) {
+            let adjusted = calibrate_single_axis_entry(&surveyed, doy, entry, &calibration);
+            let expected = lookup_single_axis(&regenerated, doy, minute_of_day(entry.minutes)).unwrap();
+            if expected.clamped {
+                continue;
+            }
+            assert_approx!(adjusted.rotation.unwrap(), expected.rotation.unwrap(), 1e-6);
+            compared += 1;
+        }
+    }
+    assert!(compared > 100, "{compared}");
+}
+
+#[test]
+fn test_axis_azimuth_error_respects_limits() {
+    let calibration = CalibrationOffsets { axis_azimuth_error: 20.0, ..Default::default() };
+    for day in &TABLE.days {
+        for entry in &day.entries {
+            let adjusted =
+                calibrate_single_axis_entry(&site(), day.day_of_year, entry, &calibration);
+            assert_eq!(adjusted.rotation.is_some(), entry.rotation.is_some());
+            assert!(adjusted.rotation.is_none_or(|r| r.abs() <= 60.0));
+            assert!(adjusted.clamped || !entry.clamped);
+        }
+    }
+}
+
+// Any lookup result can go through the layer, not just the plain table
+#[test]
+fn test_calibrates_compressed_lookup() {
+    let compressed = compress_chebyshev(&TABLE, 0.05).unwrap();
+    let entry = lookup_chebyshev(&compressed, 172, minute_of_day(1080)).unwrap();
+    let adjusted = calibrate_single_axis_entry(&site(), 172, &entry, &FIELD);
+    let expected = calibrate_rotation(entry.rotation.unwrap(), &FIELD);
+    assert_approx!(adjusted.rotation.unwrap(), expected, 1e-12);
+    assert_eq!(adjusted.minutes, 1080);
+}
@@ -0,0 +1,221 @@
+use std::sync::LazyLock;
+
+use solar_tracker::chebyshev::*;
+use solar_tracker::lookup_table::*;
+use solar_tracker::types::*;
+
+fn minute_of_day(minutes: i32) -> MinuteOfDay {
+    MinuteOfDay::new(minutes).unwrap()
+}
+
+macro_rules! assert_approx {
+    ($left:expr, $right:expr, $tol:expr) => {
+        let (l, r) = ($left as f64, $right as f64);
+        assert!(
+            (l - r).abs() <= $tol,
+            "assert_approx failed: left={}, right={}, diff={}, tol={}",
+            l, r, (l - r).abs(), $tol
+        );
+    };
+}
+
+// London, so the whole day fits the UTC grid
+static TABLE: LazyLock<SingleAxisTable> = LazyLock::new(|| {
+    generate_single_axis_table(&LookupTableConfig {
+        latitude: 51.5,
+        longitude: -0.1,
+        ..Default::default()
+    })
+});
+
+static BACKTRACKING: LazyLock<SingleAxisTable> = LazyLock::new(|| {
+    generate_single_axis_table(&LookupTableConfig {
+        latitude: 51.5,
+        longitude: -0.1,
+        gcr: Some(0.4),
+        min_rotation: Some(-60.0),
+        max_rotation: Some(60.0),
+        ..Default::default()
+    })
+});
+
+static COMPRESSED: LazyLock<ChebyshevTable> =
+    LazyLock::new(|| compress_chebyshev(&TABLE, 0.1).unwrap());
+
+fn max_lookup_error(source: &SingleAxisTable, compressed: &ChebyshevTable) -> f64 {
+    let mut worst = 0.0f64;
+    for day in &source.days {
+        for entry in &day.entries {
+            let Some(expected) = entry.rotation else {
+                continue;
+            };
+            let got = lookup_chebyshev(compressed, day.day_of_year, minute_of_day(entry.minutes))
+                .and_then(|e| e.rotation)
+                .unwrap();
+            worst = worst.max((got - expected).abs());
+        }
+    }
+    worst
+}
+
+// ── Compression ──
+
+#[test]
+fn test_error_bound_holds() {
+    assert!(COMPRESSED.days.iter().all(|d| d.max_error <= 0.1));
+    assert!(max_lookup_error(&TABLE, &COMPRESSED) <= 0.1 + 1e-9);
+}
+
+#[test]
+fn test_smaller_than_source() {
+    let source_bytes = TABLE.metadata.total_entries * 4;
+    assert!(chebyshev_storage_bytes(&COMPRESSED) * 4 < source_bytes);
+}
+
+#[test]
+fn test_tighter_bound_needs_more_coefficients() {
+    let tight = compress_chebyshev(&TABLE, 0.01).unwrap();
+    assert!(chebyshev_storage_bytes(&tight) > chebyshev_storage_bytes(&COMPRESSED));
+    assert!(max_lookup_error(&TABLE, &tight) <= 0.01 + 1e-9);
+}
+
+#[test]
+fn test_backtracking_within_bound() {
+    let compressed = compress_chebyshev(&BACKTRACKING, 0.5).unwrap();
+    let worst = compressed.days.iter().map(|d| d.max_error).fold(0.0, f64::max);
+    assert!(worst <= 0.5);
+    assert!(max_lookup_error(&BACKTRACKING, &compressed) <= 0.5 + 1e-9);
+    assert_eq!(compressed.max_rotation, Some(60.0));
+}
+
+#[test]
+fn test_day_spans_daylight_entries() {
+    let day = &COMPRESSED.days[0];
+    let source = &TABLE.days[0];
+    let first = source.entries.iter().find(|e| e.rotation.is_some()).unwrap();
+    let last = source.entries.iter().rev().find(|e| e.rotation.is_some()).unwrap();
+    assert_eq!(day.day_of_year, 1);
+    assert_eq!(day.segments.len(), 1);
+    assert_eq!(day.segments[0].start_minutes, first.minutes);
+    assert_eq!(day.segments[0].end_minutes, last.minutes);
+}
+
+#[test]
+fn test_summer_fold_splits_segments() {
+    // Sunrise before 6 am local: the north-south formula jumps through ±90°
+    let day = &COMPRESSED.days[171];
+    assert_eq!(day.segments.len(), 3);
+    assert!(day.max_error <= 0.1);
+}
+
+#[test]
+fn test_rejects_unusable_error_bound() {
+    for bound in [0.0, -0.1, f64::NAN, f64::INFINITY] {
+        assert!(
+            matches!(compress_chebyshev(&TABLE, bound), Err(ChebyshevError::InvalidErrorBound(_))),
+            "{bound}"
+        );
+    }
+}
+
+// ── Lookup ──
+
+#[test]
+fn test_lookup_between_entries() {
+    let expected = lookup_single_axis(&TABLE, 100, minute_of_day(722)).unwrap().rotation.unwrap();
+    let got = lookup_chebyshev(&COMPRESSED, 100, minute_of_day(722)).unwrap().rotation.unwrap();
+    assert_approx!(got, expected, 0.1);
+}
+
+#[test]
+fn test_lookup_night_and_bounds() {
+    assert_eq!(lookup_chebyshev(&COMPRESSED, 1, minute_of_day(60)).unwrap().rotation, None);
+    assert_eq!(lookup_chebyshev(&COMPRESSED, 0, minute_of_day(720)), None);
+    assert_eq!(lookup_chebyshev(&COMPRESSED, 366, minute_of_day(720)), None);
+}
+
+#[test]
+fn test_lookup_clamps_to_limits() {
+    let compressed = compress_chebyshev(&BACKTRACKING, 0.5).unwrap();
+    for minutes in (0..1440).step_by(5) {
+        if let Some(rotation) = lookup_chebyshev(&compressed, 172, minute_of_day(minutes)).unwrap().rotation {
+            assert!(rotation.abs() <= 60.0);
+        }
+    }
+}
+
+// ── Binary format ──
+
+#[test]
+fn test_encode_decode_round_trip() {
+    let bytes = encode_chebyshev(&COMPRESSED).unwrap();
+    assert_eq!(bytes.len(), chebyshev_storage_bytes(&COMPRESSED));
+    assert_eq!(&bytes[..4], b"STCH");
+    let decoded = decode_chebyshev(&bytes).unwrap();
+    assert_eq!(decoded.days.len(), COMPRESSED.days.len());
+    assert_eq!(decoded.days[50].segments, COMPRESSED.days[50].segments);
+    assert_eq!(decoded.days[171].segments, COMPRESSED.days[171].segments);
+    assert_eq!(decoded.min_rotation, None);
+    assert_eq!(
+        lookup_chebyshev(&decoded, 200, minute_of_day(700)),
+        lookup_chebyshev(&COMPRESSED, 200, minute_of_day(700))
+    );
+}
+
+#[test]
+fn test_round_trip_keeps_limits() {
+    let compressed = compress_chebyshev(&BACKTRACKING, 0.5).unwrap();
+    let decoded = decode_chebyshev(&encode_chebyshev(&compressed).unwrap()).unwrap();
+    assert_eq!(decoded.min_rotation, Some(-60.0));
+    assert_eq!(decoded.max_rotation, Some(60.0));
+    assert_approx!(decoded.error_bound, 0.5, 1e-9);
+}
+
+#[test]
+fn test_decode_errors() {
+    let bytes = encode_chebyshev(&COMPRESSED).unwrap();
+    assert_eq!(decode_chebyshev(b"NOPE"), Err(ChebyshevDecodeError::BadMagic));
+    assert_eq!(decode_chebyshev(&bytes[..2]), Err(ChebyshevDecodeError::BadMagic));
+    assert_eq!(
+        decode_chebyshev(&bytes[..bytes.len() - 1]),
+        Err(ChebyshevDecodeError::Truncated)
+    );
+    let mut future = bytes.clone();
+    future[4] = 9;
+    assert_eq!(decode_chebyshev(&future), Err(ChebyshevDecodeError::UnsupportedVersion(9)));
+}
+
+#[test]
+fn test_hostile_input_is_an_error() {
+    // Limits that would make `lookup_chebyshev` panic when clamping
+    let bytes = encode_chebyshev(&compress_chebyshev(&BACKTRACKING, 0.5).unwrap()).unwrap();
+    let with_limits = |min: f32, max: f32| {
+        let mut hostile = bytes.clone();
+        hostile[9..13].copy_from_slice(&min.to_le_bytes());
+        hostile[13..17].copy_from_slice(&max.to_le_bytes());
+        decode_chebyshev(&hostile).map(|table| (table.min_rotation, table.max_rotation))
+    };
+    let invalid = Err(ChebyshevDecodeError::InvalidRotationLimits);
+    assert_eq!(with_limits(10.0, -10.0), invalid);
+    assert_eq!(with_limits(f32::NEG_INFINITY, 60.0), invalid);
+    assert_eq!(with_limits(-60.0, f32::INFINITY), invalid);
+    assert_eq!(with_limits(f32::NAN, -10.0), Ok((None, Some(-10.0))));
+    assert_eq!(with_limits(5.0, 5.0), Ok((Some(5.0), Some(5.0))));
+}
+
+#[test]
+fn test_encode_refuses_counts_past_a_byte() {
+    let mut crowded = COMPRESSED.clone();
+    let segment = crowded.days[9].segments[0].clone();
+    crowded.days[9].segments = vec![segment.clone(); 300];
+    assert_eq!(
+        encode_chebyshev(&crowded),
+        Err(ChebyshevError::TooManySegments { day_of_year: 10, segments: 300 })
+    );
+    let mut long = COMPRESSED.clone();
+    long.days[0].segments[0].coefficients = vec![0.0; 256];
+    assert_eq!(
+        encode_chebyshev(&long),
+        Err(ChebyshevError::TooManyCoefficients { day_of_year: 1, coefficients: 256 })
+    );
+}
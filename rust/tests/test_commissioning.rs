@@ -0,0 +1,95 @@
+use solar_tracker::commissioning::*;
+use solar_tracker::types::*;
+
+fn readings(east: f64, flat: f64, west: f64) -> Vec<(CommissioningTarget, f64)> {
+    vec![
+        (CommissioningTarget::EastLimit, east),
+        (CommissioningTarget::Flat, flat),
+        (CommissioningTarget::WestLimit, west),
+    ]
+}
+
+// ── Sequence ──
+
+#[test]
+fn test_sequence_order_and_signs() {
+    let steps = commissioning_sequence(-45.0, 45.0);
+    let targets: Vec<_> = steps.iter().map(|s| s.target).collect();
+    assert_eq!(
+        targets,
+        vec![
+            CommissioningTarget::EastLimit,
+            CommissioningTarget::Flat,
+            CommissioningTarget::WestLimit
+        ]
+    );
+    assert!(steps[0].rotation < 0.0);
+    assert_eq!(steps[1].rotation, 0.0);
+    assert!(steps[2].rotation > 0.0);
+    assert_eq!(steps[0].facing_azimuth, Some(90.0));
+    assert_eq!(steps[2].facing_azimuth, Some(270.0));
+}
+
+// ── Verification ──
+
+#[test]
+fn test_correct_wiring_passes() {
+    let steps = commissioning_sequence(-45.0, 45.0);
+    let report = verify_commissioning(&steps, &readings(-44.6, 0.3, 45.2), 1.0);
+    assert!(report.passed, "{:?}", report.issues);
+}
+
+#[test]
+fn test_inverted_sign_detected() {
+    let steps = commissioning_sequence(-45.0, 45.0);
+    let report = verify_commissioning(&steps, &readings(45.0, 0.0, -45.0), 1.0);
+    assert!(!report.passed);
+    assert_eq!(report.issues, vec![CommissioningIssue::SignInverted]);
+}
+
+#[test]
+fn test_stuck_axis_detected() {
+    let steps = commissioning_sequence(-45.0, 45.0);
+    let report = verify_commissioning(&steps, &readings(2.0, 2.1, 2.3), 1.0);
+    assert!(matches!(
+        report.issues.as_slice(),
+        [CommissioningIssue::AxisNotMoving { .. }]
+    ));
+}
+
+#[test]
+fn test_zero_offset_detected() {
+    let steps = commissioning_sequence(-45.0, 45.0);
+    let report = verify_commissioning(&steps, &readings(-42.0, 3.0, 48.0), 1.0);
+    assert_eq!(report.issues, vec![CommissioningIssue::ZeroOffset { offset: 3.0 }]);
+}
+
+#[test]
+fn test_out_of_tolerance_step() {
+    let steps = commissioning_sequence(-45.0, 45.0);
+    let report = verify_commissioning(&steps, &readings(-45.0, 0.0, 38.0), 1.0);
+    assert_eq!(
+        report.issues,
+        vec![CommissioningIssue::OutOfTolerance {
+            target: CommissioningTarget::WestLimit,
+            expected: 45.0,
+            measured: 38.0,
+        }]
+    );
+}
+
+#[test]
+fn test_missing_reading_reported() {
+    let steps = commissioning_sequence(-45.0, 45.0);
+    let partial = vec![
+        (CommissioningTarget::EastLimit, -45.0),
+        (CommissioningTarget::WestLimit, 45.0),
+    ];
+    let report = verify_commissioning(&steps, &partial, 1.0);
+    assert_eq!(
+        report.issues,
+        vec![CommissioningIssue::MissingReading {
+            target: CommissioningTarget::Flat
+        }]
+    );
+}
@@ -0,0 +1,89 @@
+use std::sync::LazyLock;
+
+use solar_tracker::comparison::*;
+use solar_tracker::orientation::optimize_fixed_orientation;
+use solar_tracker::simulation::simulate_yield;
+use solar_tracker::types::*;
+
+macro_rules! assert_approx {
+    ($left:expr, $right:expr, $tol:expr) => {
+        let (l, r) = ($left as f64, $right as f64);
+        assert!(
+            (l - r).abs() <= $tol,
+            "assert_approx failed: left={}, right={}, diff={}, tol={}",
+            l, r, (l - r).abs(), $tol
+        );
+    };
+}
+
+fn config() -> SimulationConfig {
+    SimulationConfig { step_minutes: 60, ..Default::default() }
+}
+
+static SPRINGFIELD: LazyLock<TrackerGainReport> = LazyLock::new(|| tracker_gain_report(&config()));
+
+// ── Clear-sky gains ──
+
+#[test]
+fn test_trackers_gain_over_fixed() {
+    let report = *SPRINGFIELD;
+    assert!(report.single_axis_gain_percent > 10.0, "{report:?}");
+    assert!(report.dual_axis_gain_percent > report.single_axis_gain_percent, "{report:?}");
+    assert!(report.dual_axis_gain_percent < 60.0, "{report:?}");
+}
+
+#[test]
+fn test_report_matches_individual_runs() {
+    let report = *SPRINGFIELD;
+    let fixed = optimize_fixed_orientation(&config(), &OrientationSearch::default());
+    assert_eq!(report.fixed, fixed);
+    assert_eq!(report.fixed_kwh_per_m2, fixed.weighted_kwh_per_m2);
+    let single = simulate_yield(&config()).annual_kwh_per_m2;
+    assert_approx!(report.single_axis_kwh_per_m2, single, 1e-9);
+    let dual = SimulationConfig { strategy: TrackingStrategy::DualAxis, ..config() };
+    assert_approx!(report.dual_axis_kwh_per_m2, simulate_yield(&dual).annual_kwh_per_m2, 1e-9);
+    let expected = (report.dual_axis_kwh_per_m2 / report.fixed_kwh_per_m2 - 1.0) * 100.0;
+    assert_approx!(report.dual_axis_gain_percent, expected, 1e-9);
+}
+
+#[test]
+fn test_fixed_strategy_falls_back_to_default_tracker() {
+    let fixed_config = SimulationConfig {
+        strategy: TrackingStrategy::Fixed { tilt: 10.0, azimuth: 90.0 },
+        ..config()
+    };
+    let report = tracker_gain_report(&fixed_config);
+    assert_approx!(report.single_axis_kwh_per_m2, SPRINGFIELD.single_axis_kwh_per_m2, 1e-9);
+}
+
+#[test]
+fn test_unlimited_tracker_gains_more() {
+    let unlimited = SimulationConfig {
+        strategy: TrackingStrategy::SingleAxis {
+            axis_tilt: 0.0,
+            axis_azimuth: 180.0,
+            max_rotation: None,
+            gcr: None,
+        },
+        ..config()
+    };
+    let report = tracker_gain_report(&unlimited);
+    assert!(report.single_axis_gain_percent > SPRINGFIELD.single_axis_gain_percent);
+}
+
+// ── Supplied irradiance ──
+
+#[test]
+fn test_overcast_site_gains_nothing() {
+    let overcast = SimulationConfig { sky_model: SkyDiffuseModel::Isotropic, ..config() };
+    let mut calls = 0;
+    let report = tracker_gain_report_with(&overcast, |_, _, _| {
+        calls += 1;
+        Some(IrradianceComponents { ghi: 200.0, dni: 0.0, dhi: 200.0 })
+    });
+    assert!(report.single_axis_gain_percent <= 1e-9, "{report:?}");
+    assert!(report.dual_axis_gain_percent < 0.0, "{report:?}");
+    // One identical sweep each for the optimizer and the two trackers
+    assert!(calls > 0);
+    assert_eq!(calls % 3, 0);
+}
@@ -0,0 +1,135 @@
+use solar_tracker::config::*;
+use solar_tracker::types::*;
+
+fn rejects(config: LookupTableConfig, field: &str) {
+    match validate_config(&config) {
+        Err(ConfigError::OutOfRange { field: f, .. }) => assert_eq!(f, field),
+        other => panic!("expected {field} out of range, got {other:?}"),
+    }
+}
+
+// ── Validation ──
+
+#[test]
+fn test_default_config_is_valid() {
+    assert_eq!(validate_config(&LookupTableConfig::default()), Ok(()));
+}
+
+#[test]
+fn test_rejects_bad_interval() {
+    for interval_minutes in [i32::MIN, -5, 0, 1441] {
+        rejects(LookupTableConfig { interval_minutes, ..Default::default() }, "interval_minutes");
+    }
+}
+
+#[test]
+fn test_rejects_non_finite_coordinates() {
+    for latitude in [f64::NAN, f64::INFINITY, 90.5] {
+        rejects(LookupTableConfig { latitude, ..Default::default() }, "latitude");
+    }
+    for longitude in [f64::NAN, f64::NEG_INFINITY, -181.0] {
+        rejects(LookupTableConfig { longitude, ..Default::default() }, "longitude");
+    }
+}
+
+#[test]
+fn test_rejects_bad_buffers_and_year() {
+    let config = LookupTableConfig { sunrise_buffer_minutes: i32::MAX, ..Default::default() };
+    rejects(config, "sunrise_buffer_minutes");
+    let config = LookupTableConfig { sunset_buffer_minutes: -1, ..Default::default() };
+    rejects(config, "sunset_buffer_minutes");
+    rejects(LookupTableConfig { year: i32::MAX, ..Default::default() }, "year");
+}
+
+#[test]
+fn test_rejects_bad_optional_fields() {
+    rejects(LookupTableConfig { gcr: Some(0.0), ..Default::default() }, "gcr");
+    rejects(LookupTableConfig { gcr: Some(f64::NAN), ..Default::default() }, "gcr");
+    let config = LookupTableConfig { axis_tilt: Some(f64::INFINITY), ..Default::default() };
+    rejects(config, "axis_tilt");
+    let terrain = Some(TerrainSlope { slope: f64::NAN, aspect: 180.0 });
+    rejects(LookupTableConfig { terrain, ..Default::default() }, "terrain.slope");
+}
+
+#[test]
+fn test_time_base_offset_range() {
+    for offset_minutes in [-720, 0, 345, 840] {
+        let time_base = TimeBase::FixedOffset { offset_minutes };
+        assert_eq!(validate_config(&LookupTableConfig { time_base, ..Default::default() }), Ok(()));
+    }
+    for offset_minutes in [-1081, 1440, i32::MAX] {
+        let time_base = TimeBase::FixedOffset { offset_minutes };
+        rejects(LookupTableConfig { time_base, ..Default::default() }, "time_base.offset_minutes");
+    }
+}
+
+#[test]
+fn test_rejects_inverted_rotation_limits() {
+    let config = LookupTableConfig {
+        min_rotation: Some(30.0),
+        max_rotation: Some(-30.0),
+        ..Default::default()
+    };
+    assert_eq!(
+        validate_config(&config),
+        Err(ConfigError::InvertedRotationLimits { min: 30.0, max: -30.0 })
+    );
+}
+
+#[test]
+fn test_error_display() {
+    let err = ConfigError::OutOfRange { field: "latitude", value: 91.0 };
+    assert_eq!(err.to_string(), "latitude out of range: 91");
+}
+
+// ── JSON ──
+
+#[cfg(feature = "json")]
+#[test]
+fn test_config_from_json_roundtrip() {
+    let config = LookupTableConfig { gcr: Some(0.4), ..Default::default() };
+    let json = serde_json::to_string(&config).unwrap();
+    assert_eq!(config_from_json(&json), Ok(config));
+}
+
+// Configs saved before the time base existed load as UTC tables
+#[cfg(feature = "json")]
+#[test]
+fn test_config_from_json_defaults_time_base() {
+    let mut value = serde_json::to_value(LookupTableConfig::default()).unwrap();
+    value.as_object_mut().unwrap().remove("time_base");
+    let config = config_from_json(&value.to_string()).unwrap();
+    assert_eq!(config.time_base, TimeBase::Utc);
+}
+
+#[cfg(all(feature = "json", feature = "tz"))]
+#[test]
+fn test_config_from_json_zone_time_base() {
+    let config = LookupTableConfig {
+        time_base: TimeBase::Zone(chrono_tz::America::Chicago),
+        ..Default::default()
+    };
+    let json = serde_json::to_string(&config).unwrap();
+    assert!(json.contains("\"America/Chicago\""), "{json}");
+    assert_eq!(config_from_json(&json), Ok(config));
+}
+
+#[cfg(feature = "json")]
+#[test]
+fn test_config_from_json_rejects_garbage() {
+    for input in ["", "{", "null", "[1, 2]", "{\"interval_minutes\": 5}", "\u{0}\u{ffff}"] {
+        assert!(matches!(config_from_json(input), Err(ConfigLoadError::Json(_))), "{input:?}");
+    }
+}
+
+#[cfg(feature = "json")]
+#[test]
+fn test_config_from_json_validates() {
+    let json = serde_json::to_string(&LookupTableConfig::default())
+        .unwrap()
+        .replace("\"interval_minutes\":5", "\"interval_minutes\":0");
+    assert!(matches!(
+        config_from_json(&json),
+        Err(ConfigLoadError::Invalid(ConfigError::OutOfRange { field: "interval_minutes", .. }))
+    ));
+}
@@ -0,0 +1,87 @@
+use solar_tracker::angles::*;
+use solar_tracker::config::validate_config;
+use solar_tracker::lookup_table::{intervals_per_day, minutes_to_time, time_to_minutes};
+use solar_tracker::types::*;
+
+// Everything below is evaluated by the compiler; a failing assert here is a
+// build error, which is exactly what firmware configs want.
+
+const SPRINGFIELD: LookupTableConfig = LookupTableConfig {
+    interval_minutes: 10,
+    latitude: 39.8,
+    longitude: -89.6,
+    year: 2026,
+    sunrise_buffer_minutes: 30,
+    sunset_buffer_minutes: 30,
+    gcr: Some(0.4),
+    min_rotation: Some(-60.0),
+    max_rotation: Some(60.0),
+    include_surface_orientation: false,
+    magnetic_declination: None,
+    dual_axis_mount: DualAxisMount::AzimuthElevation,
+    terrain: None,
+    axis_azimuth: None,
+    precise_sunrise_sunset: false,
+    axis_tilt: None,
+    declination_model: DeclinationModel::Cooper,
+    equation_of_time_model: EquationOfTimeModel::Fourier,
+    time_base: TimeBase::FixedOffset { offset_minutes: -360 },
+    azimuth_encoding: AzimuthEncoding::SouthOffset,
+    table_key: TableKey::UtcMinutes,
+};
+
+const _: () = assert!(validate_config(&SPRINGFIELD).is_ok());
+const _: () = assert!(
+    validate_config(&LookupTableConfig { interval_minutes: 0, ..SPRINGFIELD }).is_err()
+);
+const _: () = assert!(day_of_year(2026, 3, 21) == 80);
+const _: () = assert!(day_of_year(2028, 12, 31) == 366);
+const _: () = assert!(leap_year(2000) && !leap_year(2100));
+const _: () = assert!(intervals_per_day(SPRINGFIELD.interval_minutes) == 144);
+const _: () = assert!(time_to_minutes(minutes_to_time(754)) == 754);
+const _: () = assert!(normalize_angle(-90.0) == 270.0);
+const _: () = assert!(angle_difference(350.0, 10.0) == 20.0);
+const _: () = assert!(hour_angle(15.0) == 45.0);
+const _: () = assert!(solar_altitude(30.0) == 60.0);
+const _: () = assert!(StorageFormat::Centidegrees16.bytes() == 2);
+const _: () = assert!(StorageFormat::HalfDegree8.quantize(100.0) == 63.5);
+const _: () = assert!(AzimuthEncoding::SouthOffset.encode(270.0) == -90.0);
+const _: () = assert!(MinuteOfDay::wrapping(-1).get() == 1439);
+const _: () = assert!(MinuteOfDay::new(1440).is_none());
+
+// ── Const results match the runtime ──
+
+#[test]
+fn test_normalize_angle_matches_rem_euclid() {
+    let mut angle = -1000.0;
+    while angle < 1000.0 {
+        assert_eq!(normalize_angle(angle).to_bits(), angle.rem_euclid(360.0).to_bits(), "{angle}");
+        angle += 0.37;
+    }
+    for angle in [-0.0, 0.0, -1e-20, 360.0, -360.0, 1e300, f64::NAN, f64::INFINITY] {
+        assert_eq!(normalize_angle(angle).to_bits(), angle.rem_euclid(360.0).to_bits(), "{angle}");
+    }
+}
+
+#[test]
+fn test_day_of_year_matches_chrono() {
+    for year in [2026, 2028, 2100] {
+        for (month, &days) in (1..).zip(days_in_months(year).iter()) {
+            for day in 1..=days {
+                let date = chrono::NaiveDate::from_ymd_opt(year, month, day).unwrap();
+                assert_eq!(day_of_year(year, month, day), day_of_year_from_date(date));
+            }
+        }
+    }
+}
+
+#[test]
+fn test_const_and_runtime_agree() {
+    const QUANTIZED: f64 = StorageFormat::Centidegrees16.quantize(12.3456);
+    let runtime = std::hint::black_box(StorageFormat::Centidegrees16);
+    assert_eq!(QUANTIZED, runtime.quantize(12.3456));
+    const CORRECTION: f64 = utc_lst_correction(-89.6, 1.5);
+    assert_eq!(CORRECTION, utc_lst_correction(std::hint::black_box(-89.6), 1.5));
+    const RADIANS: f64 = deg_to_rad(180.0);
+    assert_eq!(RADIANS, std::f64::consts::PI);
+}
@@ -0,0 +1,293 @@
+use solar_tracker::controller::*;
+use solar_tracker::irradiance::{clear_sky_ghi, ClearnessMonitor};
+use solar_tracker::types::*;
+
+fn link(contact: i64, lightning: Option<i64>) -> LinkStatus {
+    LinkStatus {
+        minutes_since_contact: contact,
+        minutes_since_lightning: lightning,
+    }
+}
+
+// ── Fallback policy ──
+
+#[test]
+fn test_normal_with_recent_contact() {
+    let policy = FallbackPolicy::default();
+    assert_eq!(fallback_action(&policy, &link(2, None), 30.0), FallbackAction::Normal);
+    assert_eq!(fallback_action(&policy, &link(2, None), -10.0), FallbackAction::Normal);
+}
+
+#[test]
+fn test_comm_loss_daytime_conservative() {
+    let policy = FallbackPolicy::default();
+    assert_eq!(
+        fallback_action(&policy, &link(20, None), 30.0),
+        FallbackAction::Conservative { max_rotation: 30.0 }
+    );
+}
+
+#[test]
+fn test_comm_loss_night_stows() {
+    let policy = FallbackPolicy::default();
+    assert_eq!(
+        fallback_action(&policy, &link(20, None), -5.0),
+        FallbackAction::Stow(StowReason::CommunicationLoss)
+    );
+}
+
+#[test]
+fn test_comm_loss_night_without_night_stow() {
+    let policy = FallbackPolicy {
+        stow_at_night: false,
+        ..Default::default()
+    };
+    assert!(matches!(
+        fallback_action(&policy, &link(20, None), -5.0),
+        FallbackAction::Conservative { .. }
+    ));
+}
+
+#[test]
+fn test_timeout_boundary() {
+    let policy = FallbackPolicy::default();
+    assert_eq!(fallback_action(&policy, &link(14, None), 30.0), FallbackAction::Normal);
+    assert_ne!(fallback_action(&policy, &link(15, None), 30.0), FallbackAction::Normal);
+}
+
+#[test]
+fn test_lightning_overrides_everything() {
+    let policy = FallbackPolicy::default();
+    assert_eq!(
+        fallback_action(&policy, &link(0, Some(5)), 60.0),
+        FallbackAction::Stow(StowReason::Lightning)
+    );
+}
+
+#[test]
+fn test_lightning_hold_expires() {
+    let policy = FallbackPolicy::default();
+    assert_eq!(fallback_action(&policy, &link(0, Some(30)), 60.0), FallbackAction::Normal);
+}
+
+// ── Fallback rotation ──
+
+#[test]
+fn test_fallback_rotation() {
+    assert_eq!(fallback_rotation(FallbackAction::Normal, 50.0, 0.0), 50.0);
+    assert_eq!(
+        fallback_rotation(FallbackAction::Conservative { max_rotation: 30.0 }, 50.0, 0.0),
+        30.0
+    );
+    assert_eq!(
+        fallback_rotation(FallbackAction::Conservative { max_rotation: 30.0 }, -50.0, 0.0),
+        -30.0
+    );
+    assert_eq!(
+        fallback_rotation(FallbackAction::Stow(StowReason::Lightning), 50.0, 0.0),
+        0.0
+    );
+}
+
+// ── Sky mode ──
+
+#[test]
+fn test_mode_rotation() {
+    assert_eq!(mode_rotation(TrackingMode::SunPointing, -45.0, -8.0), -45.0);
+    assert_eq!(mode_rotation(TrackingMode::DiffuseOptimal, -45.0, -8.0), -8.0);
+}
+
+#[test]
+fn test_overcast_switches_to_diffuse_rotation() {
+    let pos = SolarPosition {
+        day_of_year: 172,
+        declination: 23.44,
+        equation_of_time: -1.7,
+        local_solar_time: 9.0,
+        hour_angle: -45.0,
+        zenith: 40.0,
+        altitude: 50.0,
+        azimuth: 110.0,
+    };
+    let mut monitor = ClearnessMonitor::default();
+    let clear = monitor.update(clear_sky_ghi(pos.zenith), &pos);
+    assert_eq!(mode_rotation(clear.mode, -45.0, -8.0), -45.0);
+    let mut mode = clear.mode;
+    for _ in 0..20 {
+        mode = monitor.update(0.2 * clear_sky_ghi(pos.zenith), &pos).mode;
+    }
+    assert_eq!(mode_rotation(mode, -45.0, -8.0), -8.0);
+}
+
+// ── Transitions ──
+
+#[test]
+fn test_fault_only_exits_to_maintenance() {
+    assert!(!can_transition(ControllerMode::Fault, ControllerMode::Tracking));
+    assert!(!can_transition(ControllerMode::Fault, ControllerMode::Night));
+    assert!(can_transition(ControllerMode::Fault, ControllerMode::Maintenance));
+}
+
+#[test]
+fn test_maintenance_resumes_through_night() {
+    assert!(can_transition(ControllerMode::Maintenance, ControllerMode::Night));
+    assert!(!can_transition(ControllerMode::Maintenance, ControllerMode::Tracking));
+    assert!(can_transition(
+        ControllerMode::Maintenance,
+        ControllerMode::Stowed(StowReason::Manual)
+    ));
+}
+
+#[test]
+fn test_any_mode_can_fault() {
+    for mode in [
+        ControllerMode::Tracking,
+        ControllerMode::Backtracking,
+        ControllerMode::Stowed(StowReason::Wind),
+        ControllerMode::Maintenance,
+        ControllerMode::Night,
+    ] {
+        assert!(can_transition(mode, ControllerMode::Fault), "{:?}", mode);
+    }
+}
+
+#[test]
+fn test_transition_rejects_invalid() {
+    let mut controller = Controller::with_mode(FallbackPolicy::default(), ControllerMode::Fault);
+    let err = controller.transition(ControllerMode::Tracking).unwrap_err();
+    assert_eq!(err.from, ControllerMode::Fault);
+    assert_eq!(err.to, ControllerMode::Tracking);
+    assert_eq!(controller.mode(), ControllerMode::Fault);
+}
+
+#[test]
+fn test_transition_returns_previous_mode() {
+    let mut controller = Controller::default();
+    let prev = controller.transition(ControllerMode::Tracking).unwrap();
+    assert_eq!(prev, ControllerMode::Night);
+    assert_eq!(controller.mode(), ControllerMode::Tracking);
+}
+
+// ── Mode codes ──
+
+#[test]
+fn test_mode_code_roundtrip() {
+    let reasons = [
+        StowReason::Wind,
+        StowReason::Snow,
+        StowReason::Hail,
+        StowReason::Lightning,
+        StowReason::CommunicationLoss,
+        StowReason::Manual,
+    ];
+    let mut modes = vec![
+        ControllerMode::Tracking,
+        ControllerMode::Backtracking,
+        ControllerMode::Maintenance,
+        ControllerMode::Fault,
+        ControllerMode::Night,
+    ];
+    modes.extend(reasons.iter().map(|&r| ControllerMode::Stowed(r)));
+    for mode in modes {
+        assert_eq!(ControllerMode::from_code(mode.code()), Some(mode));
+    }
+}
+
+#[test]
+fn test_mode_from_invalid_code() {
+    assert_eq!(ControllerMode::from_code(0x2f), None);
+    assert_eq!(ControllerMode::from_code(0xff), None);
+}
+
+// ── Step ──
+
+fn inputs(altitude: f64) -> ControllerInputs {
+    ControllerInputs {
+        altitude,
+        backtracking: false,
+        link: link(0, None),
+        stow_request: None,
+        fault: false,
+    }
+}
+
+#[test]
+fn test_step_day_night_cycle() {
+    let mut controller = Controller::default();
+    controller.step(&inputs(-5.0));
+    assert_eq!(controller.mode(), ControllerMode::Night);
+    controller.step(&ControllerInputs {
+        backtracking: true,
+        ..inputs(3.0)
+    });
+    assert_eq!(controller.mode(), ControllerMode::Backtracking);
+    controller.step(&inputs(40.0));
+    assert_eq!(controller.mode(), ControllerMode::Tracking);
+    controller.step(&inputs(-1.0));
+    assert_eq!(controller.mode(), ControllerMode::Night);
+}
+
+#[test]
+fn test_step_stow_request() {
+    let mut controller = Controller::default();
+    let action = controller.step(&ControllerInputs {
+        stow_request: Some(StowReason::Wind),
+        ..inputs(40.0)
+    });
+    assert_eq!(controller.mode(), ControllerMode::Stowed(StowReason::Wind));
+    assert_eq!(action, FallbackAction::Stow(StowReason::Wind));
+    controller.step(&inputs(40.0));
+    assert_eq!(controller.mode(), ControllerMode::Tracking);
+}
+
+#[test]
+fn test_step_comm_loss_night_stows() {
+    let mut controller = Controller::default();
+    controller.step(&ControllerInputs {
+        link: link(60, None),
+        ..inputs(-10.0)
+    });
+    assert_eq!(
+        controller.mode(),
+        ControllerMode::Stowed(StowReason::CommunicationLoss)
+    );
+}
+
+#[test]
+fn test_step_comm_loss_day_tracks_conservatively() {
+    let mut controller = Controller::default();
+    let action = controller.step(&ControllerInputs {
+        link: link(60, None),
+        ..inputs(30.0)
+    });
+    assert_eq!(controller.mode(), ControllerMode::Tracking);
+    assert_eq!(action, FallbackAction::Conservative { max_rotation: 30.0 });
+}
+
+#[test]
+fn test_step_lightning_stows() {
+    let mut controller = Controller::default();
+    controller.step(&ControllerInputs {
+        link: link(0, Some(1)),
+        ..inputs(30.0)
+    });
+    assert_eq!(controller.mode(), ControllerMode::Stowed(StowReason::Lightning));
+}
+
+#[test]
+fn test_step_fault_is_sticky() {
+    let mut controller = Controller::default();
+    controller.step(&ControllerInputs {
+        fault: true,
+        ..inputs(30.0)
+    });
+    assert_eq!(controller.mode(), ControllerMode::Fault);
+    controller.step(&inputs(30.0));
+    assert_eq!(controller.mode(), ControllerMode::Fault);
+    controller.transition(ControllerMode::Maintenance).unwrap();
+    controller.step(&inputs(30.0));
+    assert_eq!(controller.mode(), ControllerMode::Maintenance);
+    controller.transition(ControllerMode::Night).unwrap();
+    controller.step(&inputs(30.0));
+    assert_eq!(controller.mode(), ControllerMode::Tracking);
+}
@@ -0,0 +1,121 @@
+use std::sync::LazyLock;
+
+use solar_tracker::daily_fit::*;
+use solar_tracker::lookup_table::*;
+use solar_tracker::types::*;
+
+fn minute_of_day(minutes: i32) -> MinuteOfDay {
+    MinuteOfDay::new(minutes).unwrap()
+}
+
+macro_rules! assert_approx {
+    ($left:expr, $right:expr, $tol:expr) => {
+        let (l, r) = ($left as f64, $right as f64);
+        assert!(
+            (l - r).abs() <= $tol,
+            "assert_approx failed: left={}, right={}, diff={}, tol={}",
+            l, r, (l - r).abs(), $tol
+        );
+    };
+}
+
+// London, so the whole day fits the UTC grid
+static CONFIG: LazyLock<LookupTableConfig> = LazyLock::new(|| LookupTableConfig {
+    interval_minutes: 10,
+    latitude: 51.5,
+    longitude: -0.1,
+    ..Default::default()
+});
+
+static FIT: LazyLock<DailyFitTable> = LazyLock::new(|| fit_daily_curves(&CONFIG));
+
+// ── Fitting ──
+
+#[test]
+fn test_one_fit_per_day() {
+    assert_eq!(FIT.days.len(), 365);
+    assert_eq!(FIT.days[0].day_of_year, 1);
+    assert_eq!(FIT.days[364].day_of_year, 365);
+}
+
+#[test]
+fn test_year_fits_in_under_four_kilobytes() {
+    assert_eq!(fit_storage_bytes(&FIT), 3650);
+}
+
+#[test]
+fn test_default_curve_is_exact_form() {
+    // atan(tan h / cos φ): only the cos h term of the denominator is used
+    let day = &FIT.days[100];
+    let expected = (51.5f64.to_radians().cos() * DAILY_FIT_SCALE).round() as i16;
+    assert_eq!(day.coefficients[3], expected);
+    assert!(day.coefficients[0].abs() <= 1);
+    assert!(day.coefficients[4].abs() <= 1);
+}
+
+#[test]
+fn test_default_fit_matches_table() {
+    let summary = fit_error(&FIT, 0.05);
+    assert!(summary.max_abs_difference < 0.05, "{summary:?}");
+    assert_eq!(summary.intervals_over_threshold, 0);
+    assert!(FIT.days.iter().all(|d| d.max_error < 0.05));
+}
+
+#[test]
+fn test_backtracking_and_limits_reapplied() {
+    let config = LookupTableConfig {
+        gcr: Some(0.4),
+        min_rotation: Some(-55.0),
+        max_rotation: Some(55.0),
+        ..*CONFIG
+    };
+    let fit = fit_daily_curves(&config);
+    let summary = fit_error(&fit, 0.05);
+    assert!(summary.max_abs_difference < 0.05, "{summary:?}");
+    let table = fit_rotation_table(&fit);
+    let entry = lookup_nearest(&table, 172, minute_of_day(300)).unwrap();
+    assert_approx!(entry.rotation.unwrap().abs(), 0.0, 30.0);
+}
+
+#[test]
+fn test_tilted_axis_fit() {
+    let config = LookupTableConfig {
+        axis_tilt: Some(20.0),
+        axis_azimuth: Some(170.0),
+        ..*CONFIG
+    };
+    let fit = fit_daily_curves(&config);
+    let summary = fit_error(&fit, 0.1);
+    assert!(summary.max_abs_difference < 0.1, "{summary:?}");
+}
+
+// ── Evaluation ──
+
+#[test]
+fn test_evaluate_matches_lookup() {
+    let table = generate_single_axis_table(&CONFIG);
+    for (doy, minutes) in [(15, 720), (172, 480), (172, 1000), (300, 600)] {
+        let expected = lookup_nearest(&table, doy, minute_of_day(minutes)).unwrap().rotation.unwrap();
+        assert_approx!(evaluate_fit(&FIT, doy, minute_of_day(minutes)).unwrap(), expected, 0.05);
+    }
+}
+
+#[test]
+fn test_evaluate_none_at_night() {
+    assert_eq!(evaluate_fit(&FIT, 1, minute_of_day(60)), None);
+    assert_eq!(evaluate_fit(&FIT, 172, minute_of_day(1400)), None);
+}
+
+#[test]
+fn test_evaluate_unknown_day() {
+    assert_eq!(evaluate_fit(&FIT, 366, minute_of_day(720)), None);
+    assert_eq!(evaluate_fit(&FIT, 0, minute_of_day(720)), None);
+}
+
+#[test]
+fn test_rotation_table_grid_matches() {
+    let fitted = fit_rotation_table(&FIT);
+    let table = generate_single_axis_table(&CONFIG);
+    assert_eq!(fitted.metadata.total_entries, table.metadata.total_entries);
+    assert_approx!(fitted.metadata.storage_estimate_kb, 3650.0 / 1024.0, 1e-9);
+}
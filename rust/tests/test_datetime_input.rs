@@ -0,0 +1,123 @@
+use chrono::{DateTime, FixedOffset, TimeZone, Utc};
+
+use solar_tracker::datetime_input::*;
+
+fn utc(y: i32, mo: u32, d: u32, h: u32, mi: u32, s: u32) -> DateTime<Utc> {
+    Utc.with_ymd_and_hms(y, mo, d, h, mi, s).unwrap()
+}
+
+// ── Formats ──
+
+#[test]
+fn test_rfc3339() {
+    let dt = parse_datetime("2026-03-21T14:30:00-05:00").unwrap();
+    assert_eq!(dt, utc(2026, 3, 21, 19, 30, 0));
+    assert_eq!(dt.offset(), &FixedOffset::west_opt(5 * 3600).unwrap());
+    assert_eq!(parse_datetime("2026-03-21T14:30:00Z").unwrap(), utc(2026, 3, 21, 14, 30, 0));
+}
+
+#[test]
+fn test_space_separated_without_offset_is_utc() {
+    let expected = utc(2026, 3, 21, 14, 30, 0);
+    assert_eq!(parse_datetime("2026-03-21 14:30").unwrap(), expected);
+    assert_eq!(parse_datetime("2026-03-21T14:30").unwrap(), expected);
+    assert_eq!(parse_datetime("  2026-03-21 14:30:00  ").unwrap(), expected);
+    assert_eq!(parse_datetime("2026-03-21 14:30:00.5").unwrap().timestamp_subsec_millis(), 500);
+}
+
+#[test]
+fn test_minutes_with_offset_or_z() {
+    assert_eq!(parse_datetime("2026-03-21 14:30Z").unwrap(), utc(2026, 3, 21, 14, 30, 0));
+    assert_eq!(parse_datetime("2026-03-21 14:30+02:00").unwrap(), utc(2026, 3, 21, 12, 30, 0));
+}
+
+#[test]
+fn test_leap_second() {
+    let dt = parse_datetime("2016-12-31T23:59:60Z").unwrap();
+    assert_eq!(dt.timestamp(), utc(2016, 12, 31, 23, 59, 59).timestamp());
+    assert!(dt.timestamp_subsec_nanos() >= 1_000_000_000);
+    assert_eq!(parse_datetime("2016-12-31 23:59:60").unwrap(), dt);
+}
+
+#[test]
+fn test_date_only_is_midnight_utc() {
+    assert_eq!(parse_datetime("2026-03-21").unwrap(), utc(2026, 3, 21, 0, 0, 0));
+}
+
+#[test]
+fn test_utc_zone_name() {
+    assert_eq!(parse_datetime("2026-03-21 14:30 UTC").unwrap(), utc(2026, 3, 21, 14, 30, 0));
+    assert_eq!(parse_datetime("2026-03-21 14:30[UTC]").unwrap(), utc(2026, 3, 21, 14, 30, 0));
+}
+
+// ── Errors ──
+
+#[test]
+fn test_errors() {
+    assert_eq!(parse_datetime("   "), Err(DateTimeInputError::Empty));
+    for bad in ["21/03/2026 14:30", "2026-02-30 12:00", "yesterday"] {
+        assert!(
+            matches!(parse_datetime(bad), Err(DateTimeInputError::InvalidFormat(_))),
+            "{bad}"
+        );
+    }
+    assert!(matches!(
+        parse_datetime("2026-03-21 14:30 Mars/Olympus_Mons"),
+        Err(DateTimeInputError::UnknownZone(_))
+    ));
+}
+
+#[test]
+fn test_hostile_input_is_an_error() {
+    let long = "9".repeat(10_000);
+    let hostile = [
+        "[",
+        "]",
+        "[]",
+        " []",
+        "2026-03-21 [",
+        "\u{0}",
+        "2026-03-21T\u{1F600}:00",
+        "+262143-12-31T23:59:59Z",
+        "262144-01-01 00:00",
+        "2026-03-21 14:30 \u{fffd}/\u{fffd}",
+        long.as_str(),
+    ];
+    for input in hostile {
+        assert!(parse_datetime(input).is_err(), "{input:?}");
+    }
+}
+
+// ── IANA zones ──
+
+#[cfg(feature = "tz")]
+#[test]
+fn test_iana_zone_wall_clock() {
+    // CDT in effect after 8 March 2026
+    let dt = parse_datetime("2026-03-21 14:30 America/Chicago").unwrap();
+    assert_eq!(dt, utc(2026, 3, 21, 19, 30, 0));
+    assert_eq!(dt.offset().local_minus_utc(), -5 * 3600);
+    let bracketed = parse_datetime("2026-01-15T08:00[America/Chicago]").unwrap();
+    assert_eq!(bracketed, utc(2026, 1, 15, 14, 0, 0));
+}
+
+#[cfg(feature = "tz")]
+#[test]
+fn test_iana_zone_with_offset_keeps_instant() {
+    let dt = parse_datetime("2026-03-21T14:30:00Z[America/Chicago]").unwrap();
+    assert_eq!(dt, utc(2026, 3, 21, 14, 30, 0));
+    assert_eq!(dt.offset().local_minus_utc(), -5 * 3600);
+}
+
+#[cfg(feature = "tz")]
+#[test]
+fn test_iana_zone_dst_transitions() {
+    // 1 Nov 2026 01:30 happens twice in Chicago; the earlier (CDT) wins
+    let dt = parse_datetime("2026-11-01 01:30 America/Chicago").unwrap();
+    assert_eq!(dt, utc(2026, 11, 1, 6, 30, 0));
+    // 8 Mar 2026 02:30 is skipped
+    assert!(matches!(
+        parse_datetime("2026-03-08 02:30 America/Chicago"),
+        Err(DateTimeInputError::NonexistentLocalTime(_))
+    ));
+}
@@ -0,0 +1,168 @@
+use chrono::{Datelike, Duration, FixedOffset, NaiveDate, NaiveTime};
+
+use solar_tracker::daylight::*;
+
+fn date(y: i32, m: u32, d: u32) -> NaiveDate {
+    NaiveDate::from_ymd_opt(y, m, d).unwrap()
+}
+
+// ── Date queries ──
+
+#[test]
+fn test_first_sunrise_before_half_past_five_cdt() {
+    // Springfield, IL: sunrise first drops below 05:30 CDT in late May
+    let cdt = FixedOffset::west_opt(5 * 3600).unwrap();
+    let half_five = NaiveTime::from_hms_opt(5, 30, 0).unwrap();
+    let found = first_sunrise_before(39.8, -89.6, date(2026, 3, 1), half_five, cdt).unwrap();
+    assert!(
+        found > date(2026, 5, 15) && found < date(2026, 6, 10),
+        "{found}"
+    );
+    // Once past the solstice it takes until next spring
+    let next = first_sunrise_before(39.8, -89.6, date(2026, 7, 15), half_five, cdt).unwrap();
+    assert_eq!(next.year(), 2027, "{next}");
+}
+
+#[test]
+fn test_first_date_after_excludes_start_and_polar_days() {
+    let d = date(2026, 6, 1);
+    assert_eq!(
+        first_date_after(39.8, -89.6, d, |_, _| true),
+        Some(date(2026, 6, 2))
+    );
+    // Midnight sun at 85°N lasts from early April into September
+    let polar = first_date_after(85.0, 0.0, date(2026, 5, 1), |_, _| true).unwrap();
+    assert!(
+        polar > date(2026, 9, 1) && polar < date(2026, 9, 20),
+        "{polar}"
+    );
+    assert_eq!(first_date_after(39.8, -89.6, d, |_, _| false), None);
+}
+
+#[test]
+fn test_day_length_over_14h() {
+    let dates = dates_with_day_length_over(39.8, -89.6, 2026, Duration::hours(14));
+    assert!(!dates.is_empty());
+    assert!(dates.contains(&date(2026, 6, 21)));
+    assert!(!dates.contains(&date(2026, 3, 20)));
+    // One contiguous run around the solstice
+    for pair in dates.windows(2) {
+        assert_eq!(pair[1] - pair[0], Duration::days(1));
+    }
+    assert!(dates_with_day_length_over(0.0, 0.0, 2026, Duration::hours(14)).is_empty());
+}
+
+#[test]
+fn test_dates_between_inclusive() {
+    let all = dates_between(39.8, -89.6, date(2026, 1, 1), date(2026, 1, 10), |_, _| {
+        true
+    });
+    assert_eq!(all.len(), 10);
+}
+
+// ── Day length ──
+
+#[test]
+fn test_day_length_matches_sunrise_sunset() {
+    use solar_tracker::lookup_table::sunrise_sunset;
+    for doy in [1, 80, 172, 300] {
+        let d = NaiveDate::from_yo_opt(2026, doy as u32).unwrap();
+        let (rise, set) = sunrise_sunset(39.8, -89.6, d).unwrap();
+        assert!((day_length(39.8, doy) - (set - rise)).num_seconds().abs() <= 1);
+    }
+}
+
+#[test]
+fn test_day_length_polar() {
+    assert_eq!(day_length(80.0, 172), Duration::hours(24));
+    assert_eq!(day_length(80.0, 355), Duration::zero());
+}
+
+#[test]
+fn test_day_lengths_year_series() {
+    let series: Vec<(i32, Duration)> = day_lengths(39.8, 2026).collect();
+    assert_eq!(series.len(), 365);
+    assert_eq!(day_lengths(39.8, 2028).count(), 366);
+    let (longest, _) = series.iter().max_by_key(|(_, len)| *len).unwrap();
+    let (shortest, _) = series.iter().min_by_key(|(_, len)| *len).unwrap();
+    assert!((168..=176).contains(longest), "longest day {longest}");
+    assert!((350..=360).contains(shortest), "shortest day {shortest}");
+}
+
+// ── Sunrise/sunset azimuth ──
+
+#[test]
+fn test_sunrise_azimuth_equinox_due_east() {
+    let (rise, set) = sunrise_sunset_azimuth(0.0, 80).unwrap();
+    assert!((rise - 90.0).abs() < 1.0, "{rise}");
+    assert!((rise + set - 360.0).abs() < 1e-9);
+}
+
+#[test]
+fn test_sunrise_azimuth_solstices_springfield() {
+    // Roughly 58° at the June solstice and 122° in December at 40°N
+    let (june, _) = sunrise_sunset_azimuth(39.8, 172).unwrap();
+    let (december, _) = sunrise_sunset_azimuth(39.8, 355).unwrap();
+    assert!((56.0..60.0).contains(&june), "{june}");
+    assert!((120.0..124.0).contains(&december), "{december}");
+}
+
+#[test]
+fn test_sunrise_azimuth_matches_solar_position() {
+    use chrono::Timelike;
+    use solar_tracker::angles::solar_position;
+    use solar_tracker::lookup_table::sunrise_sunset;
+
+    let d = NaiveDate::from_ymd_opt(2026, 5, 10).unwrap();
+    let (rise, _) = sunrise_sunset(39.8, -89.6, d).unwrap();
+    let pos = solar_position(39.8, -89.6, &rise.with_nanosecond(0).unwrap());
+    let (azimuth, _) = sunrise_sunset_azimuth(39.8, 130).unwrap();
+    assert!((pos.altitude + 0.833).abs() < 0.1, "{}", pos.altitude);
+    assert!((pos.azimuth - azimuth).abs() < 0.2, "{} vs {azimuth}", pos.azimuth);
+}
+
+#[test]
+fn test_sunrise_azimuth_polar() {
+    assert!(sunrise_sunset_azimuth(80.0, 172).is_none());
+    assert!(sunrise_sunset_azimuth(80.0, 355).is_none());
+}
+
+// ── Accumulated metrics ──
+
+#[test]
+fn test_monthly_metrics_sum_to_annual() {
+    let annual = annual_daylight_metrics(39.8, 2026, 10.0);
+    let monthly = monthly_daylight_metrics(39.8, 2026, 10.0);
+    assert_eq!(monthly.len(), 12);
+    let sun_up: f64 = monthly.iter().map(|m| m.sun_up_hours).sum();
+    let above: f64 = monthly.iter().map(|m| m.hours_above_threshold).sum();
+    let peak: f64 = monthly.iter().map(|m| m.peak_sun_hours).sum();
+    assert!((sun_up - annual.sun_up_hours).abs() < 1e-6);
+    assert!((above - annual.hours_above_threshold).abs() < 1e-6);
+    assert!((peak - annual.peak_sun_hours).abs() < 1e-6);
+}
+
+#[test]
+fn test_annual_metrics_plausible() {
+    let m = annual_daylight_metrics(39.8, 2026, 10.0);
+    // A little over half the year once refraction is counted
+    assert!((4400.0..4500.0).contains(&m.sun_up_hours), "{m:?}");
+    assert!(m.hours_above_threshold < m.sun_up_hours);
+    assert!((1500.0..2500.0).contains(&m.peak_sun_hours), "{m:?}");
+}
+
+#[test]
+fn test_monthly_metrics_seasonal() {
+    let monthly = monthly_daylight_metrics(39.8, 2026, 30.0);
+    assert!(monthly[5].hours_above_threshold > 2.0 * monthly[11].hours_above_threshold);
+    assert!(monthly[5].peak_sun_hours > monthly[11].peak_sun_hours);
+    let south = monthly_daylight_metrics(-33.9, 2026, 30.0);
+    assert!(south[11].peak_sun_hours > south[5].peak_sun_hours);
+}
+
+#[test]
+fn test_metrics_threshold_never_reached() {
+    let m = daylight_metrics(80.0, 330..=340, 5.0);
+    assert_eq!(m.sun_up_hours, 0.0);
+    assert_eq!(m.hours_above_threshold, 0.0);
+}
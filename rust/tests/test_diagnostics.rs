@@ -0,0 +1,104 @@
+use solar_tracker::diagnostics::*;
+use solar_tracker::event_log::EventLog;
+use solar_tracker::lookup_table::generate_single_axis_table;
+use solar_tracker::types::*;
+
+fn small_table() -> SingleAxisTable {
+    let config = LookupTableConfig {
+        interval_minutes: 60,
+        ..Default::default()
+    };
+    generate_single_axis_table(&config)
+}
+
+fn log_with_events() -> EventLog<4> {
+    let mut log = EventLog::new();
+    for t in 0..6 {
+        log.push(Event {
+            timestamp: t,
+            kind: EventKind::Move { target: t as f32 },
+        });
+    }
+    log
+}
+
+#[test]
+fn test_bundle_carries_table_config_and_metadata() {
+    let table = small_table();
+    let bundle = diagnostic_bundle(
+        &table,
+        &log_with_events(),
+        ControllerMode::Tracking,
+        CalibrationOffsets::default(),
+        None,
+    );
+    assert_eq!(bundle.config, table.config);
+    assert_eq!(bundle.metadata, table.metadata);
+    assert_eq!(bundle.mode, ControllerMode::Tracking);
+    // Without a clock the caller stamps the bundle
+    assert_eq!(bundle.generated_at.is_empty(), !cfg!(feature = "clock"));
+}
+
+#[test]
+fn test_bundle_events_oldest_first_with_dropped_count() {
+    let bundle = diagnostic_bundle(
+        &small_table(),
+        &log_with_events(),
+        ControllerMode::Night,
+        CalibrationOffsets::default(),
+        None,
+    );
+    let stamps: Vec<u32> = bundle.events.iter().map(|e| e.timestamp).collect();
+    assert_eq!(stamps, vec![2, 3, 4, 5]);
+    assert_eq!(bundle.dropped_events, 2);
+}
+
+#[test]
+fn test_bundle_angle_error() {
+    let bundle = diagnostic_bundle(
+        &small_table(),
+        &log_with_events(),
+        ControllerMode::Tracking,
+        CalibrationOffsets::default(),
+        Some(AngleReading {
+            predicted: 20.0,
+            reported: 21.5,
+        }),
+    );
+    assert_eq!(bundle.angle_error, Some(1.5));
+}
+
+#[test]
+fn test_bundle_without_reading_has_no_error() {
+    let bundle = diagnostic_bundle(
+        &small_table(),
+        &log_with_events(),
+        ControllerMode::Tracking,
+        CalibrationOffsets::default(),
+        None,
+    );
+    assert_eq!(bundle.angle_error, None);
+}
+
+#[cfg(feature = "json")]
+#[test]
+fn test_bundle_json_roundtrip() {
+    let bundle = diagnostic_bundle(
+        &small_table(),
+        &log_with_events(),
+        ControllerMode::Stowed(StowReason::Snow),
+        CalibrationOffsets {
+            zero_offset: 0.4,
+            ..Default::default()
+        },
+        Some(AngleReading {
+            predicted: -10.0,
+            reported: -9.0,
+        }),
+    );
+    let json = diagnostic_bundle_json(&bundle);
+    assert!(json.contains("\"calibration\""));
+    assert!(json.contains("\"Snow\""));
+    let parsed: DiagnosticBundle = serde_json::from_str(&json).unwrap();
+    assert_eq!(parsed, bundle);
+}
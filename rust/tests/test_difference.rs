@@ -0,0 +1,111 @@
+use std::sync::LazyLock;
+
+use solar_tracker::difference::*;
+use solar_tracker::lookup_table::{generate_single_axis_table, lookup_nearest};
+use solar_tracker::types::*;
+
+fn minute_of_day(minutes: i32) -> MinuteOfDay {
+    MinuteOfDay::new(minutes).unwrap()
+}
+
+macro_rules! assert_approx {
+    ($left:expr, $right:expr, $tol:expr) => {
+        let (l, r) = ($left as f64, $right as f64);
+        assert!(
+            (l - r).abs() <= $tol,
+            "assert_approx failed: left={}, right={}, diff={}, tol={}",
+            l, r, (l - r).abs(), $tol
+        );
+    };
+}
+
+static BACKTRACK_CONFIG: LazyLock<LookupTableConfig> = LazyLock::new(|| LookupTableConfig {
+    interval_minutes: 15,
+    gcr: Some(0.4),
+    ..Default::default()
+});
+
+static BACKTRACK_DIFF: LazyLock<DifferenceTable> =
+    LazyLock::new(|| backtracking_difference_table(&BACKTRACK_CONFIG));
+
+// ── Difference table ──
+
+#[test]
+fn test_identical_tables_have_zero_difference() {
+    let table = generate_single_axis_table(&BACKTRACK_CONFIG);
+    let diff = difference_table(&table, &table);
+    assert_eq!(diff.metadata.total_entries, table.metadata.total_entries);
+    let summary = summarize_difference(&diff, 0.0);
+    assert_eq!(summary.max_abs_difference, 0.0);
+    assert_eq!(summary.intervals_over_threshold, 0);
+}
+
+#[test]
+fn test_backtracking_zero_at_noon() {
+    let day = &BACKTRACK_DIFF.days[171];
+    let noon = day
+        .entries
+        .iter()
+        .min_by_key(|e| (e.minutes - day.solar_noon_minutes).abs())
+        .unwrap();
+    assert_approx!(noon.difference.unwrap(), 0.0, 1e-9);
+}
+
+#[test]
+fn test_backtracking_pulls_toward_flat() {
+    for day in &BACKTRACK_DIFF.days {
+        for e in &day.entries {
+            if let (Some(r), Some(c)) = (e.reference, e.candidate) {
+                assert!(c.abs() <= r.abs() + 1e-9, "day {} min {}", day.day_of_year, e.minutes);
+                assert_approx!(e.difference.unwrap(), c - r, 1e-12);
+            }
+        }
+    }
+}
+
+#[test]
+fn test_mismatched_buffers_leave_side_empty() {
+    let wide = LookupTableConfig {
+        sunrise_buffer_minutes: 90,
+        ..*BACKTRACK_CONFIG
+    };
+    let diff = difference_table(
+        &generate_single_axis_table(&wide),
+        &generate_single_axis_table(&BACKTRACK_CONFIG),
+    );
+    let first = diff.days[0].entries[0];
+    assert!(first.candidate.is_none());
+    assert!(first.difference.is_none());
+}
+
+#[test]
+fn test_difference_lookup_nearest() {
+    let day = &BACKTRACK_DIFF.days[79];
+    let entry = day.entries[10];
+    let found = lookup_nearest(&BACKTRACK_DIFF, 80, minute_of_day(entry.minutes + 3)).unwrap();
+    assert_eq!(found.difference, entry.difference);
+}
+
+// ── Summary and windows ──
+
+#[test]
+fn test_summary_locates_largest_deviation() {
+    let summary = summarize_difference(&BACKTRACK_DIFF, 1.0);
+    assert!(summary.max_abs_difference > 10.0);
+    assert!(summary.mean_abs_difference > 0.0);
+    assert!(summary.mean_abs_difference < summary.max_abs_difference);
+    assert!(summary.intervals_over_threshold > 0);
+    let day = &BACKTRACK_DIFF.days[(summary.max_day_of_year - 1) as usize];
+    let entry = day.entries.iter().find(|e| e.minutes == summary.max_minutes).unwrap();
+    assert_approx!(entry.difference.unwrap().abs(), summary.max_abs_difference, 1e-12);
+}
+
+#[test]
+fn test_deviation_windows_morning_and_evening() {
+    let windows = deviation_windows(&BACKTRACK_DIFF, 172, 1.0);
+    assert_eq!(windows.len(), 2, "{windows:?}");
+    let noon = BACKTRACK_DIFF.days[171].solar_noon_minutes;
+    assert!(windows[0].1 < noon);
+    assert!(windows[1].0 > noon);
+    assert!(windows.iter().all(|(start, end)| start <= end));
+}
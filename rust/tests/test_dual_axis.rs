@@ -0,0 +1,341 @@
+use std::sync::LazyLock;
+
+use solar_tracker::angles::{angle_difference, declination_in_year};
+use solar_tracker::dual_axis::*;
+use solar_tracker::lookup_table::*;
+use solar_tracker::types::*;
+
+macro_rules! assert_approx {
+    ($left:expr, $right:expr, $tol:expr) => {
+        let (l, r, t) = ($left as f64, $right as f64, $tol as f64);
+        assert!(
+            (l - r).abs() < t,
+            "assert_approx failed: {} vs {} (diff {}, tol {})",
+            l,
+            r,
+            (l - r).abs(),
+            t
+        );
+    };
+}
+
+// Near-equatorial site: the sun passes within a few degrees of zenith around
+// the equinoxes, so the raw azimuth flips by ~180° across solar noon.
+static EQUATORIAL: LazyLock<DualAxisTable> = LazyLock::new(|| {
+    generate_dual_axis_table(&LookupTableConfig {
+        latitude: 1.3,
+        longitude: 103.8,
+        interval_minutes: 5,
+        ..Default::default()
+    })
+});
+
+fn angles(tilt: f64, panel_azimuth: f64) -> DualAxisAngles {
+    DualAxisAngles {
+        tilt,
+        panel_azimuth,
+    }
+}
+
+// ── Single step ──
+
+#[test]
+fn test_step_within_rate_passes_through() {
+    let cfg = AzimuthFlipConfig::default();
+    let (out, changed) =
+        condition_dual_axis_step(angles(30.0, 100.0), angles(31.0, 103.0), 5.0, &cfg);
+    assert!(!changed);
+    assert_eq!(out, angles(31.0, 103.0));
+}
+
+#[test]
+fn test_step_rate_limited_across_north() {
+    let cfg = AzimuthFlipConfig::default();
+    let (out, changed) =
+        condition_dual_axis_step(angles(30.0, 355.0), angles(30.0, 20.0), 5.0, &cfg);
+    assert!(changed);
+    assert_approx!(out.panel_azimuth, 0.0, 1e-9);
+}
+
+#[test]
+fn test_step_holds_azimuth_when_flat() {
+    let cfg = AzimuthFlipConfig::default();
+    let (out, changed) = condition_dual_axis_step(angles(2.0, 90.0), angles(1.0, 270.0), 5.0, &cfg);
+    assert!(changed);
+    assert_eq!(out, angles(1.0, 90.0));
+}
+
+#[test]
+fn test_step_negative_tilt_flip() {
+    let cfg = AzimuthFlipConfig {
+        allow_negative_tilt: true,
+        ..Default::default()
+    };
+    let (out, changed) =
+        condition_dual_axis_step(angles(10.0, 90.0), angles(10.0, 270.0), 5.0, &cfg);
+    // Both commanded axes differ from the target, so the step is adjusted
+    assert!(changed);
+    assert_eq!(out, angles(-10.0, 90.0));
+}
+
+#[test]
+fn test_step_with_noon_turns_the_sun_side() {
+    // Leaving the flat hold facing 280° with the target at 95°: the short
+    // way is through 0°, but with the sun culminating north the panel turns
+    // through 180° instead
+    let cfg = AzimuthFlipConfig::default();
+    let noon = noon_panel_azimuth(20.9, 21.0);
+    assert_eq!(noon, 180.0);
+    let (short, _) = condition_dual_axis_step(angles(6.0, 280.0), angles(6.0, 95.0), 1.0, &cfg);
+    assert_approx!(short.panel_azimuth, 281.0, 1e-9);
+    let (prev, next) = (angles(6.0, 280.0), angles(6.0, 95.0));
+    let (out, changed) = condition_dual_axis_step_with_noon(prev, next, 1.0, &cfg, noon);
+    assert!(changed);
+    assert_approx!(out.panel_azimuth, 279.0, 1e-9);
+    // With the sun south the short way is already the sun's way
+    let south = noon_panel_azimuth(20.9, 20.0);
+    let (out, _) = condition_dual_axis_step_with_noon(prev, next, 1.0, &cfg, south);
+    assert_approx!(out.panel_azimuth, 281.0, 1e-9);
+}
+
+#[test]
+fn test_step_with_noon_leaves_small_turns_alone() {
+    // A midnight sun crosses the far meridian a little at a time
+    let cfg = AzimuthFlipConfig::default();
+    let (prev, next) = (angles(80.0, 179.0), angles(80.0, 181.0));
+    let (out, _) = condition_dual_axis_step_with_noon(prev, next, 5.0, &cfg, 0.0);
+    assert_eq!(out, angles(80.0, 181.0));
+}
+
+// ── Table conditioning ──
+
+#[test]
+fn test_equatorial_raw_table_exceeds_rate() {
+    assert!(max_azimuth_rate(&EQUATORIAL) > 5.0);
+}
+
+#[test]
+fn test_condition_table_respects_rate_outside_flat_zone() {
+    let cfg = AzimuthFlipConfig::default();
+    let mut table = EQUATORIAL.clone();
+    let adjusted = condition_dual_axis_table(&mut table, &cfg);
+    assert!(adjusted > 0);
+    assert!(max_azimuth_rate(&table) <= cfg.max_azimuth_rate + 1e-9);
+}
+
+#[test]
+fn test_condition_table_negative_tilt_keeps_pointing() {
+    let cfg = AzimuthFlipConfig {
+        allow_negative_tilt: true,
+        ..Default::default()
+    };
+    let mut table = EQUATORIAL.clone();
+    condition_dual_axis_table(&mut table, &cfg);
+    assert!(table
+        .days
+        .iter()
+        .flat_map(|d| &d.entries)
+        .any(|e| e.tilt.is_some_and(|t| t < 0.0)));
+    // Flipped poses describe the same panel normal as the raw target
+    for (raw, out) in EQUATORIAL.days[79]
+        .entries
+        .iter()
+        .zip(&table.days[79].entries)
+    {
+        if let (Some(t0), Some(a0), Some(t1), Some(a1)) =
+            (raw.tilt, raw.panel_azimuth, out.tilt, out.panel_azimuth)
+        {
+            // Flipped entries are flagged, as is anything held or rate limited
+            let turn = (a0 + 180.0 - a1).rem_euclid(360.0);
+            let flip_only = t1 == -t0 && turn.min(360.0 - turn) < 1e-9;
+            if t1 != t0 {
+                assert!(out.adjusted);
+            }
+            if flip_only || !out.adjusted {
+                let n0 = [
+                    t0.to_radians().sin() * a0.to_radians().sin(),
+                    t0.to_radians().sin() * a0.to_radians().cos(),
+                ];
+                let n1 = [
+                    t1.to_radians().sin() * a1.to_radians().sin(),
+                    t1.to_radians().sin() * a1.to_radians().cos(),
+                ];
+                assert_approx!(n0[0], n1[0], 1e-9);
+                assert_approx!(n0[1], n1[1], 1e-9);
+            }
+        }
+    }
+}
+
+#[test]
+fn test_condition_table_counts_flip_only_steps() {
+    let cfg = AzimuthFlipConfig {
+        allow_negative_tilt: true,
+        ..Default::default()
+    };
+    let entry = |minutes, tilt, panel_azimuth| DualAxisEntry {
+        minutes,
+        tilt: Some(tilt),
+        panel_azimuth: Some(panel_azimuth),
+        adjusted: false,
+    };
+    let mut table = EQUATORIAL.clone();
+    for day in &mut table.days {
+        day.entries.clear();
+    }
+    // The second target is reached by flipping alone, within the rate limit
+    table.days[0].entries = vec![entry(600, 30.0, 90.0), entry(605, 30.0, 268.0)];
+    assert_eq!(condition_dual_axis_table(&mut table, &cfg), 1);
+    let flipped = table.days[0].entries[1];
+    assert!(flipped.adjusted);
+    assert_eq!((flipped.tilt, flipped.panel_azimuth), (Some(-30.0), Some(88.0)));
+}
+
+#[test]
+fn test_condition_table_keeps_azimuth_encoding() {
+    let config = LookupTableConfig {
+        azimuth_encoding: AzimuthEncoding::SouthOffset,
+        ..EQUATORIAL.config
+    };
+    let mut offset = generate_dual_axis_table(&config);
+    let mut bearing = EQUATORIAL.clone();
+    let cfg = AzimuthFlipConfig::default();
+    assert_eq!(
+        condition_dual_axis_table(&mut offset, &cfg),
+        condition_dual_axis_table(&mut bearing, &cfg)
+    );
+    for (o, b) in offset.days[79].entries.iter().zip(&bearing.days[79].entries) {
+        if let (Some(stored), Some(expected)) = (o.panel_azimuth, b.panel_azimuth) {
+            assert!(stored > -180.0 && stored <= 180.0);
+            assert_approx!(config.azimuth_encoding.decode(stored), expected, 1e-9);
+        }
+    }
+}
+
+#[test]
+fn test_condition_table_leaves_night_entries() {
+    let mut table = EQUATORIAL.clone();
+    condition_dual_axis_table(&mut table, &AzimuthFlipConfig::default());
+    for (raw, out) in EQUATORIAL.days[0]
+        .entries
+        .iter()
+        .zip(&table.days[0].entries)
+    {
+        if raw.tilt.is_none() {
+            assert!(out.tilt.is_none() && out.panel_azimuth.is_none());
+        }
+    }
+}
+
+// ── Overhead sun ──
+
+fn overhead(latitude: f64) -> Vec<i32> {
+    let config = LookupTableConfig { latitude, ..Default::default() };
+    overhead_sun_days(&config).iter().map(|d| d.day_of_year).collect()
+}
+
+#[test]
+fn test_overhead_sun_days_at_lahaina() {
+    // Published Lahaina noon dates are about May 24 and July 18
+    let config = LookupTableConfig { latitude: 20.9, ..Default::default() };
+    let days = overhead_sun_days(&config);
+    assert_eq!(days.len(), 2);
+    assert!((143..=146).contains(&days[0].day_of_year), "{days:?}");
+    assert!((198..=201).contains(&days[1].day_of_year), "{days:?}");
+    for day in &days {
+        assert!(day.noon_zenith < 0.25, "{day:?}");
+    }
+}
+
+#[test]
+fn test_overhead_sun_days_across_latitudes() {
+    // Equinoxes at the equator; November and February in the southern tropics
+    let equator = overhead(0.0);
+    assert!((79..=82).contains(&equator[0]) && (263..=267).contains(&equator[1]));
+    let south = overhead(-15.0);
+    assert!((38..=44).contains(&south[0]) && (300..=307).contains(&south[1]));
+    // The two dates close up on the June solstice at the tropic itself
+    let tropic = overhead(23.44);
+    assert!(tropic.len() == 2 && tropic[1] - tropic[0] < 7, "{tropic:?}");
+    assert!(overhead(23.5).is_empty());
+    assert!(overhead(30.0).is_empty());
+    assert!(overhead(-40.0).is_empty());
+}
+
+// A one-minute Lahaina table, raw and conditioned with the defaults, and the
+// week around each overhead day
+static LAHAINA: LazyLock<(LookupTableConfig, DualAxisTable, DualAxisTable)> = LazyLock::new(|| {
+    let config = LookupTableConfig {
+        latitude: 20.9,
+        longitude: -156.7,
+        interval_minutes: 1,
+        ..Default::default()
+    };
+    let raw = generate_dual_axis_table(&config);
+    let mut conditioned = raw.clone();
+    condition_dual_axis_table(&mut conditioned, &AzimuthFlipConfig::default());
+    (config, raw, conditioned)
+});
+
+fn near_overhead_days() -> Vec<i32> {
+    overhead_sun_days(&LAHAINA.0)
+        .iter()
+        .flat_map(|d| d.day_of_year - 3..=d.day_of_year + 3)
+        .collect()
+}
+
+#[test]
+fn test_overhead_days_hold_flat_and_limit_rate() {
+    let (_, raw, conditioned) = &*LAHAINA;
+    let limits = AzimuthFlipConfig::default();
+    for doy in near_overhead_days() {
+        let raw_day = &raw.days[doy as usize - 1];
+        let day = &conditioned.days[doy as usize - 1];
+        let mut held = 0;
+        for (w, r) in day.entries.windows(2).zip(raw_day.entries.windows(2)) {
+            let (Some(a0), Some(a1)) = (w[0].panel_azimuth, w[1].panel_azimuth) else {
+                continue;
+            };
+            let step = angle_difference(a0, a1).abs();
+            assert!(step <= limits.max_azimuth_rate + 1e-9, "day {doy} minute {}", w[1].minutes);
+            if r[1].tilt.unwrap() < limits.flat_tilt_threshold {
+                assert_eq!(a0, a1, "day {doy} minute {}", w[1].minutes);
+                held += 1;
+            }
+        }
+        // The sun passes within 5° of the zenith for a while either side of noon
+        assert!(held > 10, "day {doy}: {held}");
+    }
+}
+
+#[test]
+fn test_overhead_days_swing_the_way_the_sun_passes() {
+    // Coming out of the flat hold the panel turns a long way at the rate limit.
+    // It never reverses mid-swing, and which way it turns changes only with
+    // the side of the zenith the sun culminates on, not from day to day.
+    let (config, _, conditioned) = &*LAHAINA;
+    for doy in near_overhead_days() {
+        let day = &conditioned.days[doy as usize - 1];
+        let steps: Vec<f64> = day
+            .entries
+            .windows(2)
+            .filter_map(|w| Some(angle_difference(w[0].panel_azimuth?, w[1].panel_azimuth?)))
+            .filter(|step| step.abs() > 0.5)
+            .collect();
+        assert!(steps.len() > 45, "day {doy}: {}", steps.len());
+        let sign = steps[0].signum();
+        assert!(steps.iter().all(|s| s.signum() == sign), "day {doy} reverses");
+        let decl = declination_in_year(doy, config.year, config.declination_model);
+        let expected = if decl > config.latitude { -1.0 } else { 1.0 };
+        assert_eq!(sign, expected, "day {doy}");
+    }
+}
+
+#[cfg(feature = "json")]
+#[test]
+fn test_entry_json_reads_the_old_flag_name() {
+    let json = r#"{"minutes":600,"tilt":30.0,"panel_azimuth":90.0,"clamped":true}"#;
+    let entry: DualAxisEntry = serde_json::from_str(json).unwrap();
+    assert!(entry.adjusted);
+    assert!(serde_json::to_string(&entry).unwrap().contains("\"adjusted\":true"));
+}
@@ -0,0 +1,121 @@
+use chrono::{FixedOffset, TimeZone, Utc};
+
+use solar_tracker::angles::{equation_of_time_in_year, solar_position};
+use solar_tracker::ephemeris::*;
+
+macro_rules! assert_approx {
+    ($left:expr, $right:expr, $tol:expr) => {
+        let (l, r) = ($left as f64, $right as f64);
+        assert!(
+            (l - r).abs() <= $tol,
+            "assert_approx failed: left={}, right={}, diff={}, tol={}",
+            l, r, (l - r).abs(), $tol
+        );
+    };
+}
+
+// ── ΔT estimate ──
+
+#[test]
+fn test_delta_t_reference_epochs() {
+    // Espenak & Meeus tabulated values
+    assert_approx!(delta_t_estimate(1900.0), -2.79, 0.01);
+    assert_approx!(delta_t_estimate(1950.0), 29.07, 0.01);
+    assert_approx!(delta_t_estimate(2000.0), 63.86, 0.01);
+    assert_approx!(delta_t_estimate(2010.0), 66.70, 0.2);
+}
+
+#[test]
+fn test_delta_t_continuous_at_breaks() {
+    for year in [1600.0, 1700.0, 1800.0, 1860.0, 1900.0, 1920.0, 1941.0, 1961.0, 1986.0, 2005.0] {
+        assert_approx!(delta_t_estimate(year - 1e-6), delta_t_estimate(year), 1.0);
+    }
+    assert_approx!(delta_t_estimate(2050.0 - 1e-6), delta_t_estimate(2050.0), 2.0);
+}
+
+#[test]
+fn test_delta_t_for_uses_mid_month() {
+    let dt = Utc.with_ymd_and_hms(2026, 7, 1, 0, 0, 0).unwrap();
+    assert_eq!(delta_t_for(&dt), delta_t_estimate(2026.0 + 6.5 / 12.0));
+}
+
+#[test]
+fn test_julian_ephemeris_day() {
+    assert_approx!(julian_ephemeris_day(2_451_545.0, 86_400.0), 2_451_546.0, 1e-9);
+}
+
+// ── Ecliptic equation of time ──
+
+#[test]
+fn test_ecliptic_eot_meeus_example() {
+    // Meeus example 28.b: 1992 October 13.0 TD, E = 13m 42.7s
+    let t = (2_448_908.5 - 2_451_545.0) / 36_525.0;
+    assert_approx!(equation_of_time_ecliptic(t), 13.0 + 42.7 / 60.0, 0.01);
+}
+
+#[test]
+fn test_ecliptic_eot_for_day_matches_precise_position() {
+    let dt = Utc.with_ymd_and_hms(2026, 2, 11, 12, 0, 0).unwrap();
+    let precise = solar_position_precise(0.0, 0.0, &dt, None);
+    assert_approx!(equation_of_time_for_day(42, 2026), precise.equation_of_time, 0.05);
+}
+
+#[test]
+fn test_fourier_eot_within_a_minute_of_ecliptic() {
+    let worst = (1..=365)
+        .map(|n| (equation_of_time_in_year(n, 2026) - equation_of_time_for_day(n, 2026)).abs())
+        .fold(0.0, f64::max);
+    assert!(worst > 0.5 && worst < 1.0, "{worst}");
+}
+
+// ── Precise position ──
+
+fn spa_example() -> chrono::DateTime<FixedOffset> {
+    // Reda & Andreas (2008), NREL/TP-560-34302, table A4.1
+    let offset = FixedOffset::west_opt(7 * 3600).unwrap();
+    offset.with_ymd_and_hms(2003, 10, 17, 12, 30, 30).unwrap()
+}
+
+#[test]
+fn test_matches_spa_reference() {
+    let pos = solar_position_precise(39.742476, -105.1786, &spa_example(), Some(67.0));
+    assert_approx!(pos.declination, -9.31434, 0.005);
+    assert_approx!(pos.hour_angle, 11.105900, 0.01);
+    assert_approx!(pos.equation_of_time, 14.641503, 0.02);
+    // SPA's 50.11162° is topocentric and refracted; about 0.02° of it is
+    // refraction this model leaves out
+    assert_approx!(pos.zenith, 50.11162 + 0.02, 0.02);
+    assert_approx!(pos.azimuth, 194.34024, 0.02);
+    assert_approx!(pos.altitude + pos.zenith, 90.0, 1e-9);
+}
+
+#[test]
+fn test_delta_t_shifts_only_ephemeris() {
+    let dt = spa_example();
+    let with = solar_position_precise(39.742476, -105.1786, &dt, Some(67.0));
+    let without = solar_position_precise(39.742476, -105.1786, &dt, Some(0.0));
+    let shift = (with.declination - without.declination).abs();
+    assert!(shift > 0.0 && shift < 1e-3, "{shift}");
+    // About a minute of solar motion: tiny but not nothing
+    assert_approx!(with.hour_angle, without.hour_angle, 1e-3);
+}
+
+#[test]
+fn test_default_delta_t_is_estimate() {
+    let dt = spa_example();
+    assert_eq!(
+        solar_position_precise(39.8, -89.6, &dt, None),
+        solar_position_precise(39.8, -89.6, &dt, Some(delta_t_for(&dt)))
+    );
+}
+
+#[test]
+fn test_close_to_fast_model() {
+    let dt = Utc.with_ymd_and_hms(2026, 3, 21, 18, 0, 0).unwrap();
+    let precise = solar_position_precise(39.8, -89.6, &dt, None);
+    let fast = solar_position(39.8, -89.6, &dt);
+    assert_eq!(precise.day_of_year, fast.day_of_year);
+    // Cooper's declination is up to about a degree off near the equinoxes
+    assert_approx!(precise.zenith, fast.zenith, 1.5);
+    assert_approx!(precise.equation_of_time, fast.equation_of_time, 1.0);
+}
@@ -0,0 +1,131 @@
+use solar_tracker::event_log::*;
+use solar_tracker::types::*;
+
+fn mv(timestamp: u32, target: f32) -> Event {
+    Event {
+        timestamp,
+        kind: EventKind::Move { target },
+    }
+}
+
+// ── Encoding ──
+
+#[test]
+fn test_encode_roundtrip_all_kinds() {
+    let events = [
+        Event {
+            timestamp: 1_774_000_000,
+            kind: EventKind::ModeChange {
+                from: ControllerMode::Tracking,
+                to: ControllerMode::Stowed(StowReason::Wind),
+            },
+        },
+        mv(12, -42.5),
+        Event {
+            timestamp: 13,
+            kind: EventKind::Fault { code: 0xbeef },
+        },
+        Event {
+            timestamp: 14,
+            kind: EventKind::Stow {
+                reason: StowReason::Hail,
+            },
+        },
+    ];
+    for event in events {
+        assert_eq!(Event::decode(&event.encode()), Some(event));
+    }
+}
+
+#[test]
+fn test_encode_move_quantized_to_centidegrees() {
+    let decoded = Event::decode(&mv(1, 12.3456).encode()).unwrap();
+    match decoded.kind {
+        EventKind::Move { target } => assert!((target - 12.35).abs() < 1e-4),
+        other => panic!("unexpected {:?}", other),
+    }
+}
+
+#[test]
+fn test_decode_rejects_unknown_tag() {
+    let mut bytes = mv(1, 0.0).encode();
+    bytes[4] = 0xff;
+    assert_eq!(Event::decode(&bytes), None);
+}
+
+#[test]
+fn test_decode_rejects_invalid_mode() {
+    let mut bytes = Event {
+        timestamp: 1,
+        kind: EventKind::ModeChange {
+            from: ControllerMode::Night,
+            to: ControllerMode::Tracking,
+        },
+    }
+    .encode();
+    bytes[5] = 0x99;
+    assert_eq!(Event::decode(&bytes), None);
+}
+
+// ── Ring buffer ──
+
+#[test]
+fn test_empty_log() {
+    let log: EventLog<4> = EventLog::new();
+    assert!(log.is_empty());
+    assert_eq!(log.capacity(), 4);
+    assert_eq!(log.latest(), None);
+    assert_eq!(log.iter().count(), 0);
+}
+
+#[test]
+fn test_push_in_order() {
+    let mut log: EventLog<4> = EventLog::new();
+    for t in 0..3 {
+        log.push(mv(t, t as f32));
+    }
+    let stamps: Vec<u32> = log.iter().map(|e| e.timestamp).collect();
+    assert_eq!(stamps, vec![0, 1, 2]);
+    assert_eq!(log.latest().unwrap().timestamp, 2);
+    assert_eq!(log.dropped(), 0);
+}
+
+#[test]
+fn test_overwrites_oldest_when_full() {
+    let mut log: EventLog<4> = EventLog::new();
+    for t in 0..10 {
+        log.push(mv(t, 0.0));
+    }
+    assert_eq!(log.len(), 4);
+    let stamps: Vec<u32> = log.iter().map(|e| e.timestamp).collect();
+    assert_eq!(stamps, vec![6, 7, 8, 9]);
+    assert_eq!(log.dropped(), 6);
+}
+
+#[test]
+fn test_clear_keeps_dropped_count() {
+    let mut log: EventLog<2> = EventLog::new();
+    for t in 0..3 {
+        log.push(mv(t, 0.0));
+    }
+    log.clear();
+    assert!(log.is_empty());
+    assert_eq!(log.dropped(), 1);
+    log.push(mv(5, 0.0));
+    assert_eq!(log.latest().unwrap().timestamp, 5);
+}
+
+#[test]
+fn test_zero_capacity_log() {
+    let mut log: EventLog<0> = EventLog::new();
+    log.push(mv(1, 0.0));
+    assert!(log.is_empty());
+    assert_eq!(log.dropped(), 1);
+}
+
+#[test]
+fn test_log_usable_in_static() {
+    static LOG: std::sync::Mutex<EventLog<8>> = std::sync::Mutex::new(EventLog::new());
+    LOG.lock().unwrap().push(mv(1, 1.0));
+    assert_eq!(LOG.lock().unwrap().len(), 1);
+}
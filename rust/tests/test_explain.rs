@@ -0,0 +1,113 @@
+use chrono::{FixedOffset, TimeZone};
+
+use solar_tracker::angles::{backtrack, single_axis_tilt, solar_position};
+use solar_tracker::explain::*;
+use solar_tracker::types::ExplainStep;
+
+fn dt(month: u32, day: u32, hour: u32, minute: u32) -> chrono::DateTime<FixedOffset> {
+    let offset = FixedOffset::east_opt(-5 * 3600).unwrap();
+    offset.with_ymd_and_hms(2026, month, day, hour, minute, 0).unwrap()
+}
+
+fn output(steps: &[ExplainStep], name: &str) -> f64 {
+    steps.iter().find(|s| s.name == name).unwrap_or_else(|| panic!("no step {name}")).output
+}
+
+fn input(step: &ExplainStep, name: &str) -> f64 {
+    step.inputs.iter().find(|(k, _)| k == name).unwrap().1
+}
+
+// ── Solar position ──
+
+#[test]
+fn test_explanation_ends_at_solar_position() {
+    let t = dt(6, 21, 15, 30);
+    let steps = explain_solar_position(39.8, -89.6, &t);
+    let pos = solar_position(39.8, -89.6, &t);
+    assert_eq!(output(&steps, "day_of_year"), pos.day_of_year as f64);
+    assert_eq!(output(&steps, "declination"), pos.declination);
+    assert_eq!(output(&steps, "equation_of_time"), pos.equation_of_time);
+    assert_eq!(output(&steps, "hour_angle"), pos.hour_angle);
+    assert_eq!(output(&steps, "zenith"), pos.zenith);
+    assert_eq!(output(&steps, "altitude"), pos.altitude);
+    assert_eq!(steps.last().unwrap().name, "azimuth");
+    assert_eq!(steps.last().unwrap().output, pos.azimuth);
+}
+
+// Each step's inputs are the outputs of the steps before it, so a reader
+// can follow the chain without gaps
+#[test]
+fn test_steps_chain_outputs_to_inputs() {
+    let steps = explain_solar_position(39.8, -89.6, &dt(3, 20, 9, 0));
+    let by_name = |name: &str| steps.iter().find(|s| s.name == name).unwrap();
+    let lst = by_name("local_solar_time");
+    assert_eq!(input(lst, "t"), output(&steps, "utc_hours"));
+    assert_eq!(input(lst, "C"), output(&steps, "utc_lst_correction"));
+    assert_eq!(input(by_name("hour_angle"), "LST"), lst.output);
+    assert_eq!(input(by_name("equation_of_time"), "B"), output(&steps, "fractional_year"));
+    assert_eq!(input(by_name("zenith"), "cos θz"), output(&steps, "cos_zenith"));
+    assert_eq!(input(by_name("altitude"), "θz"), output(&steps, "zenith"));
+}
+
+// The formulas as written reproduce the outputs
+#[test]
+fn test_formulas_reproduce_outputs() {
+    let steps = explain_solar_position(39.8, -89.6, &dt(11, 3, 12, 0));
+    let by_name = |name: &str| steps.iter().find(|s| s.name == name).unwrap();
+    let correction = by_name("utc_lst_correction");
+    let expected = (4.0 * input(correction, "longitude") + input(correction, "E")) / 60.0;
+    assert!((correction.output - expected).abs() < 1e-12);
+    let hour_angle = by_name("hour_angle");
+    assert!((hour_angle.output - 15.0 * (input(hour_angle, "LST") - 12.0)).abs() < 1e-12);
+    let declination = by_name("declination");
+    let n = input(declination, "n");
+    let days = input(declination, "days_in_year");
+    let expected = 23.45 * (360.0 * (284.0 + n) / days).to_radians().sin();
+    assert!((declination.output - expected).abs() < 1e-9);
+}
+
+// ── Single axis ──
+
+#[test]
+fn test_single_axis_explanation() {
+    let t = dt(9, 22, 8, 0);
+    let pos = solar_position(39.8, -89.6, &t);
+    let ideal = single_axis_tilt(&pos, 39.8);
+    let steps = explain_single_axis(39.8, -89.6, &t, None);
+    assert_eq!(steps.last().unwrap().name, "single_axis_rotation");
+    assert_eq!(steps.last().unwrap().output, ideal);
+
+    let steps = explain_single_axis(39.8, -89.6, &t, Some(0.4));
+    let last = steps.last().unwrap();
+    assert_eq!(last.name, "backtracking");
+    assert_eq!(input(last, "R"), ideal);
+    assert_eq!(last.output, backtrack(ideal, 0.4));
+    assert!(last.output.abs() < ideal.abs());
+}
+
+// ── Formatting ──
+
+#[test]
+fn test_format_explanation() {
+    let steps = explain_solar_position(39.8, -89.6, &dt(6, 21, 12, 0));
+    let text = format_explanation(&steps);
+    assert_eq!(text.lines().count(), steps.len());
+    let first = text.lines().next().unwrap();
+    assert!(first.starts_with("day_of_year = 172.000000  [n = ordinal day"), "{first}");
+    assert!(text.contains("hour_angle = "));
+    assert!(text.contains(" °  [δ = 23.45°"));
+}
+
+#[cfg(feature = "json")]
+#[test]
+fn test_explanation_json_roundtrip() {
+    let steps = explain_single_axis(39.8, -89.6, &dt(6, 21, 12, 0), Some(0.4));
+    let json = serde_json::to_string(&steps).unwrap();
+    let parsed: Vec<ExplainStep> = serde_json::from_str(&json).unwrap();
+    assert_eq!(parsed.len(), steps.len());
+    for (p, s) in parsed.iter().zip(&steps) {
+        assert_eq!((&p.name, &p.formula, &p.unit), (&s.name, &s.formula, &s.unit));
+        assert_eq!(p.inputs.len(), s.inputs.len());
+        assert!((p.output - s.output).abs() < 1e-12);
+    }
+}
@@ -0,0 +1,113 @@
+use chrono::{DateTime, Duration, FixedOffset, NaiveDate, TimeZone, Utc};
+
+use solar_tracker::exposure::*;
+use solar_tracker::lookup_table::sunrise_sunset;
+use solar_tracker::types::*;
+
+fn utc(m: u32, d: u32, h: u32, min: u32) -> DateTime<Utc> {
+    Utc.with_ymd_and_hms(2026, m, d, h, min, 0).unwrap()
+}
+
+fn minutes(d: Duration) -> i64 {
+    d.num_minutes()
+}
+
+// ── Façades ──
+
+#[test]
+fn test_facade_window_wraps() {
+    let north = facade_exposure(39.8, -89.6, 0.0);
+    assert_eq!(north.azimuth_range, Some((270.0, 90.0)));
+    let east = facade_exposure(39.8, -89.6, 90.0);
+    assert_eq!(east.azimuth_range, Some((0.0, 180.0)));
+}
+
+#[test]
+fn test_east_facade_loses_sun_at_solar_noon() {
+    // Springfield solar noon on the June solstice is about 18:00 UTC
+    let east = facade_exposure(39.8, -89.6, 90.0);
+    let now = utc(6, 21, 14, 0);
+    assert!(sun_on(&east, &now));
+    let left = minutes(sun_on_for(&east, &now).unwrap());
+    assert!((235..=245).contains(&left), "{left}");
+}
+
+#[test]
+fn test_west_facade_waits_for_noon() {
+    let west = facade_exposure(39.8, -89.6, 270.0);
+    let now = utc(6, 21, 14, 0);
+    assert!(!sun_on(&west, &now));
+    assert_eq!(sun_on_for(&west, &now), None);
+    let next = next_sun_on(&west, &now).unwrap();
+    let wait = minutes(next - now);
+    assert!((235..=245).contains(&wait), "{wait}");
+    assert!(sun_on(&west, &next));
+}
+
+#[test]
+fn test_north_facade_seasons() {
+    // Summer sun rises north of east; winter sun never reaches a north wall
+    let north = facade_exposure(39.8, -89.6, 0.0);
+    assert!(next_sun_on(&north, &utc(6, 21, 0, 0)).is_some());
+    assert_eq!(next_sun_on(&north, &utc(12, 21, 0, 0)), None);
+}
+
+#[test]
+fn test_next_sun_on_is_now_when_already_on() {
+    let east = facade_exposure(39.8, -89.6, 90.0);
+    let now = utc(6, 21, 14, 0);
+    assert_eq!(next_sun_on(&east, &now), Some(now));
+}
+
+// ── Fields and horizon ──
+
+#[test]
+fn test_open_field_ends_at_sunset() {
+    let field = SunExposure { latitude: 51.5, longitude: -0.1, ..Default::default() };
+    let now = utc(3, 20, 12, 0);
+    let end = now + sun_on_for(&field, &now).unwrap();
+    let date = NaiveDate::from_ymd_opt(2026, 3, 20).unwrap();
+    let (_, sunset) = sunrise_sunset(51.5, -0.1, date).unwrap();
+    // Geometric horizon versus refracted sunset
+    let early = minutes(sunset - end);
+    assert!((0..=8).contains(&early), "{early}");
+}
+
+#[test]
+fn test_horizon_shortens_window() {
+    let open = SunExposure { latitude: 51.5, longitude: -0.1, ..Default::default() };
+    let hills = SunExposure { min_altitude: 15.0, ..open };
+    let now = utc(6, 21, 12, 0);
+    let open_left = sun_on_for(&open, &now).unwrap();
+    let hills_left = sun_on_for(&hills, &now).unwrap();
+    assert!(hills_left < open_left - Duration::hours(1));
+    // Early morning the sun is up but still behind the hills
+    let dawn = utc(6, 21, 5, 0);
+    assert!(sun_on(&open, &dawn));
+    assert!(!sun_on(&hills, &dawn));
+    assert!(next_sun_on(&hills, &dawn).unwrap() > dawn);
+}
+
+#[test]
+fn test_night_is_off() {
+    let field = SunExposure::default();
+    let night = utc(6, 21, 6, 0);
+    assert!(!sun_on(&field, &night));
+    assert_eq!(sun_on_for(&field, &night), None);
+}
+
+#[test]
+fn test_midnight_sun_is_capped() {
+    let field = SunExposure { latitude: 80.0, longitude: 0.0, ..Default::default() };
+    let left = sun_on_for(&field, &utc(6, 21, 12, 0)).unwrap();
+    assert_eq!(left, Duration::days(2));
+}
+
+#[test]
+fn test_time_zone_does_not_matter() {
+    let east = facade_exposure(39.8, -89.6, 90.0);
+    let now = utc(6, 21, 14, 0);
+    let cdt = now.with_timezone(&FixedOffset::west_opt(5 * 3600).unwrap());
+    assert_eq!(sun_on_for(&east, &cdt), sun_on_for(&east, &now));
+    assert_eq!(next_sun_on(&east, &cdt).unwrap(), now);
+}
@@ -0,0 +1,184 @@
+use std::sync::LazyLock;
+
+use chrono::{TimeZone, Utc};
+
+use solar_tracker::angles::*;
+use solar_tracker::fixed_mount::*;
+use solar_tracker::lookup_table::*;
+use solar_tracker::types::*;
+
+fn minute_of_day(minutes: i32) -> MinuteOfDay {
+    MinuteOfDay::new(minutes).unwrap()
+}
+
+macro_rules! assert_approx {
+    ($left:expr, $right:expr, $tol:expr) => {
+        let (l, r) = ($left as f64, $right as f64);
+        assert!(
+            (l - r).abs() <= $tol,
+            "assert_approx failed: left={}, right={}, diff={}, tol={}",
+            l, r, (l - r).abs(), $tol
+        );
+    };
+}
+
+fn config() -> LookupTableConfig {
+    LookupTableConfig { interval_minutes: 10, ..Default::default() }
+}
+
+static FIXED: LazyLock<FixedTable> =
+    LazyLock::new(|| generate_fixed_table(&config(), &FixedMountConfig::default()));
+
+// ── Table shape ──
+
+#[test]
+fn test_same_grid_as_tracker_table() {
+    let single = generate_single_axis_table(&config());
+    assert_eq!(FIXED.days.len(), single.days.len());
+    for (fixed, tracker) in FIXED.days.iter().zip(&single.days) {
+        assert_eq!(fixed.entries.len(), tracker.entries.len());
+        assert_eq!(fixed.sunrise_minutes, tracker.sunrise_minutes);
+        for (f, t) in fixed.entries.iter().zip(&tracker.entries) {
+            assert_eq!(f.minutes, t.minutes);
+            // Sunrise/sunset include refraction, so daylight starts a little
+            // before the geometric horizon the threshold is measured from
+            assert!(!f.above_threshold || t.rotation.is_some());
+        }
+    }
+}
+
+#[test]
+fn test_lighter_than_tracker_table() {
+    let single = generate_single_axis_table(&config());
+    assert!(FIXED.metadata.storage_estimate_kb < single.metadata.storage_estimate_kb);
+}
+
+// ── Angle of incidence ──
+
+#[test]
+fn test_aoi_matches_full_position() {
+    let mount = FixedMountConfig::default();
+    let surface = SurfaceOrientation { surface_tilt: mount.tilt, surface_azimuth: mount.azimuth };
+    for (doy, month, day) in [(80, 3, 21), (172, 6, 21), (355, 12, 21)] {
+        for entry in FIXED.days[doy - 1].entries.iter().filter(|e| e.aoi.is_some()) {
+            let (h, m) = minutes_to_time(entry.minutes);
+            let t = Utc.with_ymd_and_hms(2026, month, day, h as u32, m as u32, 0).unwrap();
+            let pos = solar_position(39.8, -89.6, &t);
+            let expected = angle_between(surface_normal(&surface), sun_vector(&pos));
+            assert_approx!(entry.aoi.unwrap(), expected, 0.05);
+        }
+    }
+}
+
+#[test]
+fn test_equinox_noon_faces_sun() {
+    // Tilt equal to latitude points a south-facing panel at the equinox noon sun
+    let mount = FixedMountConfig { tilt: 39.8, ..Default::default() };
+    let table = generate_fixed_table(&config(), &mount);
+    let noon = table.days[79].solar_noon_minutes;
+    let entry = lookup_fixed(&table, 80, minute_of_day(noon)).unwrap();
+    assert!(entry.above_threshold);
+    assert_approx!(entry.aoi.unwrap(), 0.0, 2.0);
+}
+
+#[test]
+fn test_sun_behind_panel_has_no_aoi() {
+    // A steep north-facing panel sees no beam on a winter day
+    let mount = FixedMountConfig { tilt: 80.0, azimuth: 0.0, min_altitude: 0.0 };
+    let table = generate_fixed_table(&config(), &mount);
+    let day = &table.days[354];
+    assert!(day.entries.iter().any(|e| e.above_threshold));
+    assert!(day.entries.iter().all(|e| e.aoi.is_none()));
+}
+
+// ── Threshold ──
+
+#[test]
+fn test_threshold_shortens_window() {
+    let (open, _) = fixed_window(&FIXED, 172).unwrap();
+    let mount = FixedMountConfig { min_altitude: 15.0, ..Default::default() };
+    let table = generate_fixed_table(&config(), &mount);
+    let (later, _) = fixed_window(&table, 172).unwrap();
+    assert!(later > open + 30, "{open} {later}");
+    for (high, low) in table.days[171].entries.iter().zip(&FIXED.days[171].entries) {
+        assert!(!high.above_threshold || low.above_threshold);
+        assert!(high.above_threshold || high.aoi.is_none());
+    }
+}
+
+#[test]
+fn test_threshold_above_noon_sun_gives_no_window() {
+    let mount = FixedMountConfig { min_altitude: 40.0, ..Default::default() };
+    let table = generate_fixed_table(&config(), &mount);
+    assert!(fixed_window(&table, 355).is_none());
+    assert!(fixed_window(&table, 172).is_some());
+    assert!(fixed_window(&table, 400).is_none());
+}
+
+// ── Lookup ──
+
+#[test]
+fn test_lookup_interpolates_aoi() {
+    let day = &FIXED.days[171];
+    let noon = day.solar_noon_minutes / 10 * 10;
+    let (a, b) = (lookup_fixed(&FIXED, 172, minute_of_day(noon - 60)), lookup_fixed(&FIXED, 172, minute_of_day(noon - 50)));
+    let mid = lookup_fixed(&FIXED, 172, minute_of_day(noon - 55)).unwrap();
+    let expected = (a.unwrap().aoi.unwrap() + b.unwrap().aoi.unwrap()) / 2.0;
+    assert_approx!(mid.aoi.unwrap(), expected, 1e-9);
+    assert_eq!(mid.minutes, noon - 55);
+    assert!(lookup_fixed(&FIXED, 0, minute_of_day(noon)).is_none());
+}
+
+// ── Inverter wake/sleep ──
+
+// Near Greenwich so the whole day fits the UTC minute grid
+static LONDON: LazyLock<(FixedTable, FixedMountConfig)> = LazyLock::new(|| {
+    let config = LookupTableConfig { latitude: 51.5, longitude: -0.1, ..config() };
+    let mount = FixedMountConfig { tilt: 35.0, ..Default::default() };
+    (generate_fixed_table(&config, &mount), mount)
+});
+
+#[test]
+fn test_clear_sky_poa_peaks_at_noon() {
+    let (table, mount) = &*LONDON;
+    let noon = table.days[171].solar_noon_minutes;
+    let peak = clear_sky_poa(table, mount, 172, minute_of_day(noon));
+    assert!((700.0..1100.0).contains(&peak), "{peak}");
+    assert!(clear_sky_poa(table, mount, 172, minute_of_day(noon - 240)) < peak);
+    assert_eq!(clear_sky_poa(table, mount, 172, minute_of_day(0)), 0.0);
+}
+
+#[test]
+fn test_zero_threshold_matches_daylight_window() {
+    let (table, mount) = &*LONDON;
+    for doy in [1, 80, 172, 355] {
+        assert_eq!(inverter_window(table, mount, doy, 0.0), fixed_window(table, doy));
+    }
+}
+
+#[test]
+fn test_threshold_narrows_window() {
+    let (table, mount) = &*LONDON;
+    let (open, close) = fixed_window(table, 172).unwrap();
+    let (wake, sleep) = inverter_window(table, mount, 172, 100.0).unwrap();
+    assert!(wake > open && sleep < close, "{open}-{close} vs {wake}-{sleep}");
+    let (winter_wake, winter_sleep) = inverter_window(table, mount, 355, 100.0).unwrap();
+    assert!(winter_sleep - winter_wake < sleep - wake);
+    assert!(inverter_window(table, mount, 172, 2000.0).is_none());
+    assert!(inverter_window(table, mount, 0, 100.0).is_none());
+}
+
+#[test]
+fn test_inverter_events() {
+    let (table, mount) = &*LONDON;
+    let (wake, last) = inverter_window(table, mount, 172, 100.0).unwrap();
+    let events: Vec<InverterEvent> = inverter_events(table, mount, 172, 100.0).collect();
+    assert_eq!(
+        events,
+        [
+            InverterEvent { minutes: wake, awake: true },
+            InverterEvent { minutes: last + 10, awake: false },
+        ]
+    );
+    assert_eq!(inverter_events(table, mount, 172, 2000.0).count(), 0);
+}
@@ -0,0 +1,157 @@
+use solar_tracker::fleet::*;
+use solar_tracker::lookup_table::*;
+use solar_tracker::types::*;
+
+macro_rules! assert_approx {
+    ($left:expr, $right:expr, $tol:expr) => {
+        let (l, r, t) = ($left as f64, $right as f64, $tol as f64);
+        assert!(
+            (l - r).abs() < t,
+            "assert_approx failed: {} vs {} (diff {}, tol {})",
+            l,
+            r,
+            (l - r).abs(),
+            t
+        );
+    };
+}
+
+fn site() -> LookupTableConfig {
+    LookupTableConfig {
+        interval_minutes: 15,
+        min_rotation: Some(-60.0),
+        max_rotation: Some(60.0),
+        ..Default::default()
+    }
+}
+
+fn rotations(table: &SingleAxisTable, doy: usize) -> Vec<f64> {
+    table.days[doy - 1]
+        .entries
+        .iter()
+        .filter_map(|e| e.rotation)
+        .collect()
+}
+
+// ── Config overrides ──
+
+#[test]
+fn test_tracker_config_without_overrides_is_site() {
+    let tracker = TrackerOverride {
+        id: "T-001".into(),
+        ..Default::default()
+    };
+    assert_eq!(tracker_config(&site(), &tracker), site());
+}
+
+#[test]
+fn test_tracker_config_overrides_limits_and_axis() {
+    let tracker = TrackerOverride {
+        id: "T-002".into(),
+        axis_azimuth: Some(175.0),
+        max_rotation: Some(45.0),
+        ..Default::default()
+    };
+    let config = tracker_config(&site(), &tracker);
+    assert_eq!(config.axis_azimuth, Some(175.0));
+    assert_eq!(config.min_rotation, Some(-60.0));
+    assert_eq!(config.max_rotation, Some(45.0));
+}
+
+#[test]
+fn test_tracker_config_adds_calibrated_azimuth_error() {
+    let tracker = TrackerOverride {
+        id: "T-003".into(),
+        calibration: Some(CalibrationOffsets {
+            axis_azimuth_error: -2.5,
+            ..Default::default()
+        }),
+        ..Default::default()
+    };
+    assert_eq!(tracker_config(&site(), &tracker).axis_azimuth, Some(177.5));
+}
+
+// ── Fleet generation ──
+
+#[test]
+fn test_fleet_tables_individualized() {
+    let trackers = vec![
+        TrackerOverride {
+            id: "T-001".into(),
+            ..Default::default()
+        },
+        TrackerOverride {
+            id: "T-002".into(),
+            max_rotation: Some(30.0),
+            ..Default::default()
+        },
+        TrackerOverride {
+            id: "T-003".into(),
+            calibration: Some(CalibrationOffsets {
+                zero_offset: 1.5,
+                ..Default::default()
+            }),
+            ..Default::default()
+        },
+    ];
+    let fleet = generate_fleet_tables(&site(), &trackers, generate_single_axis_table);
+    assert_eq!(fleet.len(), 3);
+    assert_eq!(fleet[1].id, "T-002");
+
+    let base = generate_single_axis_table(&site());
+    assert_eq!(fleet[0].table.days, base.days);
+    assert!(rotations(&fleet[1].table, 172).iter().all(|&r| r <= 30.0));
+    for (shifted, nominal) in rotations(&fleet[2].table, 172)
+        .iter()
+        .zip(rotations(&base, 172))
+    {
+        assert_approx!(*shifted, nominal + 1.5, 1e-9);
+    }
+    assert_eq!(fleet[2].calibration.zero_offset, 1.5);
+}
+
+#[test]
+fn test_as_built_axis_azimuth_skews_schedule() {
+    let tracker = TrackerOverride {
+        id: "T-004".into(),
+        axis_azimuth: Some(190.0),
+        ..Default::default()
+    };
+    let config = LookupTableConfig {
+        min_rotation: None,
+        max_rotation: None,
+        ..site()
+    };
+    let fleet = generate_fleet_tables(&config, &[tracker], generate_single_axis_table);
+    let exact_ns = generate_single_axis_table(&LookupTableConfig {
+        axis_azimuth: Some(180.0),
+        ..config
+    });
+    // Rotating the axis toward the west moves the flat-panel crossing
+    // away from solar noon
+    let crossing = |t: &SingleAxisTable| {
+        t.days[79]
+            .entries
+            .iter()
+            .find(|e| e.rotation.is_some_and(|r| r > 0.0))
+            .unwrap()
+            .minutes
+    };
+    assert_ne!(crossing(&fleet[0].table), crossing(&exact_ns));
+}
+
+#[test]
+fn test_apply_calibration_scale() {
+    let mut table = generate_single_axis_table(&site());
+    let nominal = rotations(&table, 80);
+    apply_calibration(
+        &mut table,
+        &CalibrationOffsets {
+            scale: 1.02,
+            ..Default::default()
+        },
+    );
+    for (scaled, r) in rotations(&table, 80).iter().zip(nominal) {
+        assert_approx!(*scaled, r * 1.02, 1e-9);
+    }
+}
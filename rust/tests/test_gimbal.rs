@@ -0,0 +1,132 @@
+use chrono::{FixedOffset, TimeZone};
+
+use solar_tracker::angles::*;
+use solar_tracker::gimbal::*;
+use solar_tracker::types::*;
+
+macro_rules! assert_approx {
+    ($left:expr, $right:expr, $tol:expr) => {
+        let (l, r, t) = ($left as f64, $right as f64, $tol as f64);
+        assert!(
+            (l - r).abs() < t,
+            "assert_approx failed: {} vs {} (diff {}, tol {})",
+            l,
+            r,
+            (l - r).abs(),
+            t
+        );
+    };
+}
+
+const AZ_EL: GimbalAxes = GimbalAxes {
+    outer: [0.0, 0.0, 1.0],
+    inner: [1.0, 0.0, 0.0],
+};
+
+// Roll about a horizontal axis pointing south, then tilt about east
+const TILT_ROLL: GimbalAxes = GimbalAxes {
+    outer: [0.0, -1.0, 0.0],
+    inner: [1.0, 0.0, 0.0],
+};
+
+fn direction(altitude: f64, azimuth: f64) -> [f64; 3] {
+    let (sin_alt, cos_alt) = deg_to_rad(altitude).sin_cos();
+    let (sin_az, cos_az) = deg_to_rad(azimuth).sin_cos();
+    [cos_alt * sin_az, cos_alt * cos_az, sin_alt]
+}
+
+// ── Decomposition ──
+
+#[test]
+fn test_decompose_round_trip() {
+    for axes in [AZ_EL, TILT_ROLL] {
+        for alt in [5.0, 30.0, 60.0, 85.0] {
+            for az in [0.0, 75.0, 180.0, 290.0] {
+                let target = direction(alt, az);
+                let s = decompose_gimbal(&axes, target).unwrap();
+                let pointed = gimbal_pointing(&axes, s.outer_angle, s.inner_angle).unwrap();
+                assert_approx!(angle_between(pointed, target), 0.0, 1e-5);
+            }
+        }
+    }
+}
+
+#[test]
+fn test_decompose_matches_tilt_roll_mount() {
+    let offset = FixedOffset::east_opt(-6 * 3600).unwrap();
+    for hour in 7..18 {
+        let t = offset.with_ymd_and_hms(2026, 6, 21, hour, 0, 0).unwrap();
+        let pos = solar_position(39.8, -89.6, &t);
+        let m = mount_angles(&pos, 39.8, DualAxisMount::TiltRoll);
+        let s = decompose_gimbal(&TILT_ROLL, sun_vector(&pos)).unwrap();
+        assert_approx!(s.outer_angle, m.primary, 1e-9);
+        assert_approx!(s.inner_angle, m.secondary, 1e-9);
+    }
+}
+
+#[test]
+fn test_singularity_margin_az_el_is_zenith_angle() {
+    let s = decompose_gimbal(&AZ_EL, direction(88.5, 120.0)).unwrap();
+    assert_approx!(s.singularity_margin, 1.5, 1e-9);
+}
+
+#[test]
+fn test_non_orthogonal_axes_rejected() {
+    let axes = GimbalAxes {
+        outer: [0.0, 0.0, 1.0],
+        inner: [1.0, 0.0, 0.5],
+    };
+    assert!(decompose_gimbal(&axes, [0.0, 0.0, 1.0]).is_none());
+    assert!(decompose_gimbal(&AZ_EL, [0.0, 0.0, 0.0]).is_none());
+}
+
+#[test]
+fn test_alternate_solution_same_pointing() {
+    let target = direction(40.0, 200.0);
+    let s = decompose_gimbal(&AZ_EL, target).unwrap();
+    let alt = alternate_solution(&s);
+    let pointed = gimbal_pointing(&AZ_EL, alt.outer_angle, alt.inner_angle).unwrap();
+    assert_approx!(angle_between(pointed, target), 0.0, 1e-6);
+    assert!(alt.inner_angle.abs() > 90.0);
+}
+
+// ── Avoidance planning ──
+
+#[test]
+fn test_plan_without_previous_is_nominal() {
+    let cmd = plan_gimbal_move(
+        &AZ_EL,
+        direction(89.5, 10.0),
+        None,
+        &GimbalConfig::default(),
+    );
+    assert_eq!(cmd.unwrap().avoidance, GimbalAvoidance::None);
+}
+
+#[test]
+fn test_plan_holds_outer_near_lock() {
+    let cfg = GimbalConfig::default();
+    let previous = decompose_gimbal(&AZ_EL, direction(85.0, 90.0)).unwrap();
+    let target = direction(89.0, 270.0);
+    let cmd = plan_gimbal_move(&AZ_EL, target, Some(&previous), &cfg).unwrap();
+    assert_eq!(cmd.avoidance, GimbalAvoidance::HoldOuter);
+    assert_eq!(cmd.solution.outer_angle, previous.outer_angle);
+    let pointed = gimbal_pointing(&AZ_EL, cmd.solution.outer_angle, cmd.solution.inner_angle);
+    assert!(angle_between(pointed.unwrap(), target) < 1e-6);
+}
+
+#[test]
+fn test_plan_uses_alternate_when_inner_range_allows() {
+    let cfg = GimbalConfig {
+        max_inner_angle: 180.0,
+        ..Default::default()
+    };
+    let previous = decompose_gimbal(&AZ_EL, direction(60.0, 90.0)).unwrap();
+    let target = direction(60.0, 260.0);
+    let cmd = plan_gimbal_move(&AZ_EL, target, Some(&previous), &cfg).unwrap();
+    assert_eq!(cmd.avoidance, GimbalAvoidance::UseAlternate);
+    assert!((cmd.solution.outer_angle - previous.outer_angle).abs() < 20.0);
+
+    let limited = plan_gimbal_move(&AZ_EL, target, Some(&previous), &GimbalConfig::default());
+    assert_eq!(limited.unwrap().avoidance, GimbalAvoidance::None);
+}
@@ -0,0 +1,175 @@
+use std::sync::LazyLock;
+
+use chrono::{DateTime, NaiveDate};
+
+use solar_tracker::angles::{single_axis_tilt, solar_position};
+use solar_tracker::golden::*;
+use solar_tracker::types::*;
+
+static DATASET: LazyLock<GoldenDataset> =
+    LazyLock::new(|| golden_dataset(&GoldenConfig::default()));
+
+// ── Cases ──
+
+#[test]
+fn test_case_count_covers_grid() {
+    let config = GoldenConfig::default();
+    let expected = config.latitudes.len()
+        * config.longitudes.len()
+        * config.dates.len()
+        * config.utc_hours.len();
+    assert_eq!(DATASET.cases.len(), expected);
+}
+
+#[test]
+fn test_cases_match_library() {
+    for case in DATASET.cases.iter().step_by(17) {
+        let dt = DateTime::parse_from_rfc3339(&case.timestamp).unwrap();
+        let pos = solar_position(case.latitude, case.longitude, &dt);
+        assert!((case.position.zenith - pos.zenith).abs() <= 1e-9);
+        assert!((case.position.azimuth - pos.azimuth).abs() <= 1e-9);
+        let tilt = single_axis_tilt(&pos, case.latitude);
+        assert!((case.single_axis_tilt - tilt).abs() <= 1e-9);
+    }
+}
+
+#[test]
+fn test_values_are_rounded() {
+    let scale = 10f64.powi(GOLDEN_DECIMALS);
+    for case in &DATASET.cases {
+        let z = case.position.zenith * scale;
+        assert!((z - z.round()).abs() < 1e-3, "{}", case.position.zenith);
+    }
+}
+
+#[test]
+fn test_invalid_hours_skipped() {
+    let config = GoldenConfig {
+        latitudes: vec![39.8],
+        longitudes: vec![-89.6],
+        dates: vec![NaiveDate::from_ymd_opt(2026, 3, 21).unwrap()],
+        utc_hours: vec![12, 25],
+        table_interval_minutes: 60,
+    };
+    let dataset = golden_dataset(&config);
+    assert_eq!(dataset.cases.len(), 1);
+    assert_eq!(dataset.cases[0].timestamp, "2026-03-21T12:00:00Z");
+}
+
+// ── Table rows ──
+
+#[test]
+fn test_table_rows_for_each_date() {
+    let rows: Vec<&GoldenTableRow> = DATASET
+        .table_rows
+        .iter()
+        .filter(|r| r.latitude == 39.8 && r.longitude == -89.6)
+        .collect();
+    let days: std::collections::BTreeSet<i32> = rows.iter().map(|r| r.day_of_year).collect();
+    assert_eq!(
+        days.into_iter().collect::<Vec<_>>(),
+        vec![1, 80, 172, 265, 355]
+    );
+    assert!(rows.iter().all(|r| r.minutes % 60 == 0));
+    assert!(rows
+        .iter()
+        .any(|r| r.rotation.is_some() && r.tilt.is_some()));
+}
+
+// ── Comparison ──
+
+fn output<'a>(report: &'a GoldenChangeReport, name: &str) -> &'a GoldenOutputChange {
+    report.outputs.iter().find(|o| o.output == name).unwrap()
+}
+
+#[test]
+fn test_identical_datasets_unchanged() {
+    let report = compare_golden(&DATASET, &DATASET, 1e-6);
+    assert_eq!(report.outputs.len(), 12);
+    assert_eq!(report.unmatched_old, 0);
+    assert_eq!(report.unmatched_new, 0);
+    for change in &report.outputs {
+        assert!(change.compared > 0, "{}", change.output);
+        assert_eq!(change.changed, 0);
+        assert_eq!(change.max_delta, 0.0);
+        assert_eq!(change.max_delta_at, None);
+    }
+    let rotation = output(&report, "single_axis_table.rotation");
+    let daylight = DATASET.table_rows.iter().filter(|r| r.rotation.is_some()).count();
+    assert_eq!(rotation.compared, daylight);
+}
+
+#[test]
+fn test_perturbed_zenith_located() {
+    let mut new = DATASET.clone();
+    new.cases[5].position.zenith += 0.02;
+    new.cases[9].position.zenith -= 0.0001;
+    let report = compare_golden(&DATASET, &new, 0.001);
+    let zenith = output(&report, "solar_position.zenith");
+    assert_eq!(zenith.changed, 1);
+    assert!((zenith.max_delta - 0.02).abs() < 1e-9);
+    let case = &DATASET.cases[5];
+    let at = format!("{},{} {}", case.latitude, case.longitude, case.timestamp);
+    assert_eq!(zenith.max_delta_at, Some(at));
+    assert_eq!(output(&report, "solar_position.azimuth").max_delta, 0.0);
+}
+
+#[test]
+fn test_azimuth_delta_wraps() {
+    let mut old = DATASET.clone();
+    let mut new = DATASET.clone();
+    old.cases[0].position.azimuth = 359.9;
+    new.cases[0].position.azimuth = 0.1;
+    let report = compare_golden(&old, &new, 0.001);
+    let azimuth = output(&report, "solar_position.azimuth");
+    assert!((azimuth.max_delta - 0.2).abs() < 1e-9);
+    assert_eq!(azimuth.changed, 1);
+}
+
+#[test]
+fn test_availability_change_counted() {
+    let mut new = DATASET.clone();
+    let row = new.table_rows.iter_mut().find(|r| r.rotation.is_none()).unwrap();
+    row.rotation = Some(10.0);
+    let report = compare_golden(&DATASET, &new, 0.001);
+    let rotation = output(&report, "single_axis_table.rotation");
+    assert_eq!(rotation.availability_changed, 1);
+    assert_eq!(rotation.changed, 0);
+}
+
+#[test]
+fn test_unmatched_cases_counted() {
+    let mut new = DATASET.clone();
+    new.cases.truncate(new.cases.len() - 3);
+    new.table_rows.pop();
+    let report = compare_golden(&DATASET, &new, 0.001);
+    assert_eq!(report.unmatched_old, 4);
+    assert_eq!(report.unmatched_new, 0);
+    let reversed = compare_golden(&new, &DATASET, 0.001);
+    assert_eq!(reversed.unmatched_new, 4);
+}
+
+// ── JSON ──
+
+#[cfg(feature = "json")]
+#[test]
+fn test_json_is_deterministic_and_roundtrips() {
+    let json = golden_dataset_json(&DATASET);
+    assert_eq!(
+        json,
+        golden_dataset_json(&golden_dataset(&GoldenConfig::default()))
+    );
+    assert!(json.ends_with("}\n"));
+    assert!(json.starts_with("{\"cases\":[{\"latitude\":-33.9,"));
+    let parsed: GoldenDataset = serde_json::from_str(&json).unwrap();
+    assert_eq!(parsed, *DATASET);
+}
+
+#[cfg(feature = "json")]
+#[test]
+fn test_compare_loaded_dataset() {
+    let loaded = golden_dataset_from_json(&golden_dataset_json(&DATASET)).unwrap();
+    let report = compare_golden(&DATASET, &loaded, 0.0);
+    assert!(report.outputs.iter().all(|o| o.changed == 0));
+    assert!(golden_dataset_from_json("{").is_err());
+}
@@ -0,0 +1,146 @@
+use std::sync::LazyLock;
+
+use chrono::{TimeZone, Utc};
+
+use solar_tracker::angles::solar_position;
+use solar_tracker::horizon::*;
+use solar_tracker::lookup_table::*;
+use solar_tracker::stow::fill_single_axis_stow;
+use solar_tracker::types::*;
+
+fn minute_of_day(minutes: i32) -> MinuteOfDay {
+    MinuteOfDay::new(minutes).unwrap()
+}
+
+macro_rules! assert_approx {
+    ($left:expr, $right:expr, $tol:expr) => {
+        let (l, r) = ($left as f64, $right as f64);
+        assert!(
+            (l - r).abs() <= $tol,
+            "assert_approx failed: left={}, right={}, diff={}, tol={}",
+            l, r, (l - r).abs(), $tol
+        );
+    };
+}
+
+// London, so the whole day fits the UTC grid
+static CONFIG: LazyLock<LookupTableConfig> = LazyLock::new(|| LookupTableConfig {
+    interval_minutes: 10,
+    latitude: 51.5,
+    longitude: -0.1,
+    ..Default::default()
+});
+
+// A ridge to the east hides the low morning sun
+static RIDGE: LazyLock<HorizonProfile> = LazyLock::new(|| HorizonProfile {
+    points: vec![(40.0, 0.0), (60.0, 25.0), (120.0, 25.0), (140.0, 0.0)],
+});
+
+// ── Profile ──
+
+#[test]
+fn test_empty_profile_is_flat() {
+    let flat = HorizonProfile::default();
+    assert_eq!(horizon_elevation(&flat, 0.0), 0.0);
+    assert_eq!(horizon_elevation(&flat, 237.0), 0.0);
+}
+
+#[test]
+fn test_single_point_is_constant() {
+    let wall = HorizonProfile { points: vec![(90.0, 12.0)] };
+    assert_eq!(horizon_elevation(&wall, 90.0), 12.0);
+    assert_eq!(horizon_elevation(&wall, 300.0), 12.0);
+}
+
+#[test]
+fn test_interpolates_between_points() {
+    assert_approx!(horizon_elevation(&RIDGE, 50.0), 12.5, 1e-12);
+    assert_eq!(horizon_elevation(&RIDGE, 60.0), 25.0);
+    assert_eq!(horizon_elevation(&RIDGE, 90.0), 25.0);
+    // From 140° round through north to 40° stays flat
+    assert_eq!(horizon_elevation(&RIDGE, 270.0), 0.0);
+}
+
+#[test]
+fn test_interpolation_wraps_through_north() {
+    let profile = HorizonProfile { points: vec![(10.0, 30.0), (180.0, 0.0), (350.0, 10.0)] };
+    assert_approx!(horizon_elevation(&profile, 0.0), 20.0, 1e-12);
+    assert_approx!(horizon_elevation(&profile, 360.0), 20.0, 1e-12);
+    assert_approx!(horizon_elevation(&profile, -5.0), 15.0, 1e-12);
+}
+
+#[test]
+fn test_sun_behind_horizon() {
+    let morning = Utc.with_ymd_and_hms(2026, 6, 21, 5, 0, 0).unwrap();
+    let pos = solar_position(51.5, -0.1, &morning);
+    assert!(pos.altitude > 0.0 && pos.altitude < 25.0);
+    assert!(sun_behind_horizon(&RIDGE, &pos));
+    assert!(!sun_behind_horizon(&HorizonProfile::default(), &pos));
+    let noon = Utc.with_ymd_and_hms(2026, 6, 21, 12, 0, 0).unwrap();
+    assert!(!sun_behind_horizon(&RIDGE, &solar_position(51.5, -0.1, &noon)));
+}
+
+// ── Tables ──
+
+#[test]
+fn test_horizon_table_blocks_mornings_only() {
+    let table = generate_horizon_table(&CONFIG, &RIDGE);
+    let day = &table.days[171];
+    let blocked: Vec<i32> = day.entries.iter().filter(|e| e.blocked).map(|e| e.minutes).collect();
+    assert!(!blocked.is_empty());
+    assert!(blocked.iter().all(|&m| m < 12 * 60), "{blocked:?}");
+    assert!(lookup_horizon(&table, 172, minute_of_day(4 * 60 + 30)).unwrap().blocked);
+    assert!(!lookup_horizon(&table, 172, minute_of_day(12 * 60)).unwrap().blocked);
+}
+
+#[test]
+fn test_flat_profile_blocks_nothing() {
+    let table = generate_horizon_table(&CONFIG, &HorizonProfile::default());
+    assert!(table.days.iter().flat_map(|d| &d.entries).all(|e| !e.blocked));
+}
+
+#[test]
+fn test_horizon_table_shares_grid() {
+    let horizon = generate_horizon_table(&CONFIG, &RIDGE);
+    let tracker = generate_single_axis_table(&CONFIG);
+    for (h, t) in horizon.days.iter().zip(&tracker.days) {
+        let h_minutes: Vec<i32> = h.entries.iter().map(|e| e.minutes).collect();
+        let t_minutes: Vec<i32> = t.entries.iter().map(|e| e.minutes).collect();
+        assert_eq!(h_minutes, t_minutes);
+    }
+}
+
+// ── Parking ──
+
+#[test]
+fn test_single_axis_parks_behind_ridge() {
+    let original = generate_single_axis_table(&CONFIG);
+    let mut parked = original.clone();
+    park_single_axis_behind_horizon(&mut parked, &RIDGE);
+    let horizon = generate_horizon_table(&CONFIG, &RIDGE);
+    let rows = original.days.iter().zip(&parked.days).zip(&horizon.days);
+    for ((before, after), blocked) in rows {
+        let entries = before.entries.iter().zip(&after.entries).zip(&blocked.entries);
+        for ((b, a), h) in entries {
+            if h.blocked {
+                assert_eq!(a.rotation, None);
+            } else {
+                assert_eq!(a, b);
+            }
+        }
+    }
+    // Parked entries take the night stow angle like any other
+    fill_single_axis_stow(&mut parked, 0.0);
+    assert_eq!(lookup_single_axis(&parked, 172, minute_of_day(4 * 60 + 30)).unwrap().rotation, Some(0.0));
+}
+
+#[test]
+fn test_dual_axis_parks_behind_ridge() {
+    let mut table = generate_dual_axis_table(&CONFIG);
+    let tracking = table.days[171].entries.iter().filter(|e| e.tilt.is_some()).count();
+    park_dual_axis_behind_horizon(&mut table, &RIDGE);
+    let after = table.days[171].entries.iter().filter(|e| e.tilt.is_some()).count();
+    assert!(after < tracking);
+    let entry = table.days[171].entries.iter().find(|e| e.minutes == 4 * 60 + 30).unwrap();
+    assert_eq!((entry.tilt, entry.panel_azimuth), (None, None));
+}
@@ -0,0 +1,141 @@
+use solar_tracker::interp::*;
+
+macro_rules! assert_approx {
+    ($left:expr, $right:expr, $tol:expr) => {
+        let (l, r) = ($left as f64, $right as f64);
+        assert!(
+            (l - r).abs() <= $tol,
+            "assert_approx failed: left={}, right={}, diff={}, tol={}",
+            l, r, (l - r).abs(), $tol
+        );
+    };
+}
+
+// A downstream record type, to exercise the trait outside the crate
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Sample {
+    minutes: i32,
+    heading: f64,
+}
+
+impl HasMinutes for Sample {
+    fn minutes(&self) -> i32 {
+        self.minutes
+    }
+
+    fn set_minutes(&mut self, minutes: i32) {
+        self.minutes = minutes;
+    }
+}
+
+fn samples() -> Vec<Sample> {
+    [350.0, 10.0, 30.0]
+        .iter()
+        .enumerate()
+        .map(|(i, &heading)| Sample {
+            minutes: 600 + 10 * i as i32,
+            heading,
+        })
+        .collect()
+}
+
+// ── Interpolate angle wraparound ──
+
+#[test]
+fn test_interpolate_angle_normal() {
+    assert_approx!(interpolate_angle(Some(0.0), Some(90.0), 0.5).unwrap(), 45.0, 0.01);
+    assert_approx!(interpolate_angle(Some(0.0), Some(90.0), 0.0).unwrap(), 0.0, 0.01);
+    assert_approx!(interpolate_angle(Some(0.0), Some(90.0), 1.0).unwrap(), 90.0, 0.01);
+}
+
+#[test]
+fn test_interpolate_angle_wraparound_350_to_10() {
+    let result = interpolate_angle(Some(350.0), Some(10.0), 0.5);
+    assert_approx!(result.unwrap(), 0.0, 0.01);
+}
+
+#[test]
+fn test_interpolate_angle_wraparound_10_to_350() {
+    let result = interpolate_angle(Some(10.0), Some(350.0), 0.5);
+    assert_approx!(result.unwrap(), 0.0, 0.01);
+}
+
+#[test]
+fn test_interpolate_angle_none_input() {
+    assert!(interpolate_angle(None, Some(10.0), 0.5).is_none());
+    assert!(interpolate_angle(Some(10.0), None, 0.5).is_none());
+}
+
+// ── Linear interpolation ──
+
+#[test]
+fn test_interpolate_linear() {
+    assert_approx!(interpolate_linear(Some(-10.0), Some(30.0), 0.25).unwrap(), 0.0, 1e-12);
+    // No wraparound: -170 to 170 goes through zero
+    assert_approx!(interpolate_linear(Some(-170.0), Some(170.0), 0.5).unwrap(), 0.0, 1e-12);
+    assert!(interpolate_linear(Some(1.0), None, 0.5).is_none());
+}
+
+// ── Bracketing entries ──
+
+#[test]
+fn test_find_bracketing_between_entries() {
+    let entries = samples();
+    let (before, after, fraction) = find_bracketing_entries(&entries, 10, 605).unwrap();
+    assert_eq!(before.minutes, 600);
+    assert_eq!(after.unwrap().minutes, 610);
+    assert_approx!(fraction, 0.5, 1e-12);
+    let heading = interpolate_angle(Some(before.heading), Some(after.unwrap().heading), fraction);
+    assert_approx!(heading.unwrap(), 0.0, 1e-9);
+}
+
+#[test]
+fn test_find_bracketing_exact_and_last() {
+    let entries = samples();
+    let (before, after, fraction) = find_bracketing_entries(&entries, 10, 610).unwrap();
+    assert_eq!(before.minutes, 610);
+    assert!(after.is_none());
+    assert_eq!(fraction, 0.0);
+    let (last, after, _) = find_bracketing_entries(&entries, 10, 620).unwrap();
+    assert_eq!(last.minutes, 620);
+    assert!(after.is_none());
+}
+
+#[test]
+fn test_find_bracketing_out_of_range() {
+    let entries = samples();
+    assert!(find_bracketing_entries(&entries, 10, 599).is_none());
+    assert!(find_bracketing_entries(&entries, 10, 621).is_none());
+    assert!(find_bracketing_entries::<Sample>(&[], 10, 600).is_none());
+}
+
+#[test]
+fn test_find_bracketing_bad_interval() {
+    let entries = samples();
+    assert!(find_bracketing_entries(&entries, 0, 605).is_none());
+    assert!(find_bracketing_entries(&entries, -10, 605).is_none());
+}
+
+#[test]
+fn test_find_bracketing_irregular_entries() {
+    let at = |minutes: &[i32]| -> Vec<Sample> {
+        minutes.iter().map(|&minutes| Sample { minutes, heading: 0.0 }).collect()
+    };
+    // Duplicates and gaps that break the index arithmetic give None rather
+    // than a fraction outside [0, 1]
+    assert!(find_bracketing_entries(&at(&[600, 600, 600, 630]), 10, 615).is_none());
+    assert!(find_bracketing_entries(&at(&[600, 640, 650]), 10, 645).is_none());
+    assert!(find_bracketing_entries(&at(&[600, 650, 620]), 10, 612).is_none());
+    // Spans wider than i32 do not overflow
+    let wide = at(&[-1_000_000_000, 2_000_000_000]);
+    let (_, after, fraction) = find_bracketing_entries(&wide, 2_000_000_000, 500_000_000).unwrap();
+    assert!(after.is_some());
+    assert_approx!(fraction, 0.5, 1e-9);
+}
+
+#[test]
+fn test_set_minutes() {
+    let mut s = samples()[0];
+    s.set_minutes(605);
+    assert_eq!(s.minutes(), 605);
+}
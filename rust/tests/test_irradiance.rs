@@ -0,0 +1,222 @@
+use solar_tracker::irradiance::*;
+use solar_tracker::types::*;
+
+macro_rules! assert_approx {
+    ($left:expr, $right:expr, $tol:expr) => {
+        let (l, r) = ($left as f64, $right as f64);
+        assert!(
+            (l - r).abs() <= $tol,
+            "assert_approx failed: left={}, right={}, diff={}, tol={}",
+            l, r, (l - r).abs(), $tol
+        );
+    };
+}
+
+fn pos_at_zenith(zenith: f64) -> SolarPosition {
+    SolarPosition {
+        day_of_year: 172,
+        declination: 23.45,
+        equation_of_time: -1.5,
+        local_solar_time: 12.0,
+        hour_angle: 0.0,
+        zenith,
+        altitude: 90.0 - zenith,
+        azimuth: 180.0,
+    }
+}
+
+// ── Extraterrestrial irradiance ──
+
+#[test]
+fn test_extraterrestrial_irradiance_range() {
+    // About ±3.3% around the solar constant over the year
+    assert_approx!(extraterrestrial_irradiance(3), 1407.0, 3.0);
+    assert_approx!(extraterrestrial_irradiance(185), 1317.0, 3.0);
+    for n in 1..=365 {
+        let g0 = extraterrestrial_irradiance(n);
+        assert!(g0 > SOLAR_CONSTANT * 0.96 && g0 < SOLAR_CONSTANT * 1.04, "day {n}: {g0}");
+    }
+}
+
+#[test]
+fn test_extraterrestrial_horizontal_irradiance() {
+    let overhead = pos_at_zenith(0.0);
+    assert_approx!(
+        extraterrestrial_horizontal_irradiance(&overhead),
+        extraterrestrial_irradiance(172),
+        1e-9
+    );
+    let g0n = extraterrestrial_irradiance(172);
+    assert_approx!(extraterrestrial_horizontal_irradiance(&pos_at_zenith(60.0)), g0n / 2.0, 1e-9);
+    assert_eq!(extraterrestrial_horizontal_irradiance(&pos_at_zenith(95.0)), 0.0);
+}
+
+#[test]
+fn test_daily_extraterrestrial_irradiation() {
+    // Duffie & Beckman Example 1.10.1: 43°N on 15 April, 33.8 MJ/m²
+    let h0 = daily_extraterrestrial_irradiation(43.0, 105);
+    assert_approx!(h0 * 3600.0 / 1e6, 33.8, 0.3);
+    // Equator at equinox: 24/π·G0n
+    assert_approx!(
+        daily_extraterrestrial_irradiation(0.0, 80),
+        24.0 / std::f64::consts::PI * extraterrestrial_irradiance(80),
+        20.0
+    );
+}
+
+#[test]
+fn test_daily_extraterrestrial_irradiation_polar() {
+    assert_eq!(daily_extraterrestrial_irradiation(80.0, 355), 0.0);
+    // Midnight sun at the pole exceeds the equator at the June solstice
+    let pole = daily_extraterrestrial_irradiation(89.9, 172);
+    assert!(pole > daily_extraterrestrial_irradiation(0.0, 172));
+}
+
+// ── Erbs decomposition ──
+
+#[test]
+fn test_erbs_diffuse_fraction_regions() {
+    assert_approx!(erbs_diffuse_fraction(0.0), 1.0, 1e-12);
+    assert_approx!(erbs_diffuse_fraction(0.9), 0.165, 1e-12);
+    // Continuous at the breakpoints
+    assert_approx!(erbs_diffuse_fraction(0.22), erbs_diffuse_fraction(0.2201), 0.01);
+    assert_approx!(erbs_diffuse_fraction(0.8), 0.165, 0.01);
+    // Decreasing through the polynomial region (the published fit turns up
+    // by ~0.001 in its last hundredth)
+    let mut prev = erbs_diffuse_fraction(0.22);
+    for i in 23..=79 {
+        let kd = erbs_diffuse_fraction(i as f64 / 100.0);
+        assert!(kd <= prev + 1e-9, "kt={}", i as f64 / 100.0);
+        prev = kd;
+    }
+}
+
+#[test]
+fn test_decompose_ghi_clear_and_overcast() {
+    let pos = pos_at_zenith(30.0);
+    let g0h = extraterrestrial_horizontal_irradiance(&pos);
+    let clear = decompose_ghi(0.75 * g0h, &pos).unwrap();
+    assert_approx!(clear.clearness_index, 0.75, 1e-9);
+    assert!(clear.diffuse_fraction < 0.3);
+    assert!(clear.dni > 700.0);
+    // Components close back to GHI
+    let cos_z = 30f64.to_radians().cos();
+    assert_approx!(clear.dni * cos_z + clear.dhi, 0.75 * g0h, 1e-9);
+
+    let overcast = decompose_ghi(0.1 * g0h, &pos).unwrap();
+    assert!(overcast.diffuse_fraction > 0.95);
+    assert!(overcast.dni < 10.0);
+}
+
+#[test]
+fn test_decompose_ghi_limits() {
+    assert!(decompose_ghi(100.0, &pos_at_zenith(95.0)).is_none());
+    let low = decompose_ghi(20.0, &pos_at_zenith(89.5)).unwrap();
+    assert!(low.dni.is_finite() && low.dni < 300.0);
+    let noisy = decompose_ghi(-5.0, &pos_at_zenith(40.0)).unwrap();
+    assert_eq!(noisy.dni, 0.0);
+    assert_eq!(noisy.dhi, 0.0);
+}
+
+// ── Clear-sky GHI ──
+
+#[test]
+fn test_clear_sky_ghi_overhead() {
+    assert_approx!(clear_sky_ghi(0.0), 1037.2, 1.0);
+}
+
+#[test]
+fn test_clear_sky_ghi_zero_below_horizon() {
+    assert_eq!(clear_sky_ghi(90.0), 0.0);
+    assert_eq!(clear_sky_ghi(120.0), 0.0);
+}
+
+#[test]
+fn test_clear_sky_ghi_decreases_with_zenith() {
+    let mut prev = clear_sky_ghi(0.0);
+    for z in (5..90).step_by(5) {
+        let ghi = clear_sky_ghi(z as f64);
+        assert!(ghi < prev, "zenith={}: {} >= {}", z, ghi, prev);
+        prev = ghi;
+    }
+}
+
+// ── Clearness index ──
+
+#[test]
+fn test_clearness_index_ratio() {
+    assert_approx!(clearness_index(500.0, 1000.0).unwrap(), 0.5, 1e-12);
+}
+
+#[test]
+fn test_clearness_index_none_without_clear_sky() {
+    assert_eq!(clearness_index(10.0, 0.0), None);
+}
+
+#[test]
+fn test_clearness_index_clamped() {
+    assert_eq!(clearness_index(-5.0, 800.0), Some(0.0));
+    assert_eq!(clearness_index(5000.0, 800.0), Some(1.5));
+}
+
+// ── Monitor ──
+
+#[test]
+fn test_monitor_starts_sun_pointing() {
+    let monitor = ClearnessMonitor::default();
+    assert_eq!(monitor.mode(), TrackingMode::SunPointing);
+}
+
+#[test]
+fn test_monitor_switches_to_diffuse_under_overcast() {
+    let mut monitor = ClearnessMonitor::default();
+    let pos = pos_at_zenith(30.0);
+    let cs = clear_sky_ghi(30.0);
+    let mut last = None;
+    for _ in 0..20 {
+        last = Some(monitor.update(0.15 * cs, &pos));
+    }
+    let sample = last.unwrap();
+    assert_eq!(sample.mode, TrackingMode::DiffuseOptimal);
+    assert_approx!(sample.smoothed_index.unwrap(), 0.15, 1e-6);
+}
+
+#[test]
+fn test_monitor_hysteresis_holds_between_thresholds() {
+    let mut monitor = ClearnessMonitor::default();
+    let pos = pos_at_zenith(30.0);
+    let cs = clear_sky_ghi(30.0);
+    for _ in 0..20 {
+        monitor.update(0.1 * cs, &pos);
+    }
+    assert_eq!(monitor.mode(), TrackingMode::DiffuseOptimal);
+    for _ in 0..50 {
+        monitor.update(0.4 * cs, &pos);
+    }
+    assert_eq!(monitor.mode(), TrackingMode::DiffuseOptimal);
+    for _ in 0..50 {
+        monitor.update(0.9 * cs, &pos);
+    }
+    assert_eq!(monitor.mode(), TrackingMode::SunPointing);
+}
+
+#[test]
+fn test_monitor_single_cloud_does_not_flip_mode() {
+    let mut monitor = ClearnessMonitor::default();
+    let pos = pos_at_zenith(30.0);
+    let cs = clear_sky_ghi(30.0);
+    for _ in 0..10 {
+        monitor.update(cs, &pos);
+    }
+    let sample = monitor.update(0.05 * cs, &pos);
+    assert_eq!(sample.mode, TrackingMode::SunPointing);
+}
+
+#[test]
+fn test_monitor_ignores_low_sun() {
+    let mut monitor = ClearnessMonitor::default();
+    let sample = monitor.update(0.0, &pos_at_zenith(89.5));
+    assert_eq!(sample.clearness_index, None);
+    assert_eq!(sample.smoothed_index, None);
+    assert_eq!(sample.mode, TrackingMode::SunPointing);
+}
@@ -0,0 +1,134 @@
+use std::sync::LazyLock;
+
+use solar_tracker::angles::surface_orientation;
+use solar_tracker::latitude_bands::*;
+use solar_tracker::lookup_table::*;
+use solar_tracker::types::*;
+
+fn minute_of_day(minutes: i32) -> MinuteOfDay {
+    MinuteOfDay::new(minutes).unwrap()
+}
+
+fn base_config() -> LookupTableConfig {
+    LookupTableConfig {
+        interval_minutes: 10,
+        ..Default::default()
+    }
+}
+
+static SA_LIBRARY: LazyLock<LatitudeBandLibrary<SingleAxisEntry>> = LazyLock::new(|| {
+    generate_latitude_band_library(&base_config(), 38.0, 42.0, 1.0, generate_single_axis_table)
+        .unwrap()
+});
+
+static SA_SITE: LazyLock<SingleAxisTable> =
+    LazyLock::new(|| generate_single_axis_table(&base_config()));
+
+#[test]
+fn test_library_band_count_and_longitude() {
+    assert_eq!(SA_LIBRARY.tables.len(), 5);
+    assert_eq!(SA_LIBRARY.tables[0].config.latitude, 38.0);
+    assert_eq!(SA_LIBRARY.tables[4].config.latitude, 42.0);
+    assert!(SA_LIBRARY.tables.iter().all(|t| t.config.longitude == 0.0));
+}
+
+#[test]
+fn test_band_key_shift_and_wrap() {
+    assert_eq!(band_key(0.0, 100, minute_of_day(600), 365), (100, minute_of_day(600)));
+    assert_eq!(band_key(-90.0, 100, minute_of_day(1000), 365), (100, minute_of_day(640)));
+    assert_eq!(band_key(-90.0, 100, minute_of_day(100), 365), (99, minute_of_day(1180)));
+    assert_eq!(band_key(90.0, 100, minute_of_day(1400), 365), (101, minute_of_day(320)));
+    assert_eq!(band_key(90.0, 365, minute_of_day(1400), 365), (365, minute_of_day(320)));
+}
+
+#[test]
+fn test_band_lookup_matches_site_table() {
+    let mut max_err: f64 = 0.0;
+    for doy in [20, 80, 172, 264, 355] {
+        for minutes in (900..1500).step_by(7) {
+            let minutes = minutes % 1440;
+            let site = lookup_single_axis(&SA_SITE, doy, minute_of_day(minutes));
+            let band = lookup_single_axis_band(&SA_LIBRARY, 39.8, -89.6, doy, minute_of_day(minutes));
+            if let (Some(site), Some(band)) = (site, band) {
+                if let (Some(a), Some(b)) = (site.rotation, band.rotation) {
+                    max_err = max_err.max((a - b).abs());
+                }
+            }
+        }
+    }
+    assert!(max_err < 0.5, "max error {}", max_err);
+}
+
+#[test]
+fn test_band_lookup_outside_library_is_none() {
+    assert!(lookup_single_axis_band(&SA_LIBRARY, 50.0, -89.6, 80, minute_of_day(1080)).is_none());
+    assert!(lookup_single_axis_band(&SA_LIBRARY, 30.0, -89.6, 80, minute_of_day(1080)).is_none());
+}
+
+#[test]
+fn test_band_lookup_on_band_uses_single_table() {
+    let table = &SA_LIBRARY.tables[2];
+    let direct = lookup_single_axis(table, 80, minute_of_day(720)).unwrap();
+    let band = lookup_single_axis_band(&SA_LIBRARY, 40.0, 0.0, 80, minute_of_day(720)).unwrap();
+    assert_eq!(direct, band);
+}
+
+#[test]
+fn test_band_lookup_surface_follows_rotation() {
+    let config = LookupTableConfig {
+        include_surface_orientation: true,
+        ..base_config()
+    };
+    let library =
+        generate_latitude_band_library(&config, 39.0, 41.0, 1.0, generate_single_axis_table)
+            .unwrap();
+    for minutes in (900..1440).step_by(7) {
+        let Some(entry) = lookup_single_axis_band(&library, 39.8, -89.6, 172, minute_of_day(minutes)) else {
+            continue;
+        };
+        let (Some(rotation), Some(surface)) = (entry.rotation, entry.surface) else {
+            continue;
+        };
+        let expected = surface_orientation(rotation, 0.0, 180.0);
+        assert!((surface.surface_tilt - expected.surface_tilt).abs() < 1e-9, "{minutes}");
+        let azimuth_error = (surface.surface_azimuth - expected.surface_azimuth).abs();
+        assert!(azimuth_error < 1e-9, "{minutes}");
+    }
+}
+
+#[test]
+fn test_dual_axis_band_lookup_matches_site_table() {
+    let library =
+        generate_latitude_band_library(&base_config(), 39.0, 41.0, 1.0, generate_dual_axis_table)
+            .unwrap();
+    let site = generate_dual_axis_table(&base_config());
+    let mut max_err: f64 = 0.0;
+    for minutes in (900..1440).step_by(11) {
+        let a = lookup_dual_axis(&site, 172, minute_of_day(minutes));
+        let b = lookup_dual_axis_band(&library, 39.8, -89.6, 172, minute_of_day(minutes));
+        if let (Some(a), Some(b)) = (a, b) {
+            if let (Some(ta), Some(tb)) = (a.tilt, b.tilt) {
+                max_err = max_err.max((ta - tb).abs());
+            }
+        }
+    }
+    assert!(max_err < 0.5, "max error {}", max_err);
+}
+
+#[test]
+fn test_library_rejects_unusable_steps_and_ranges() {
+    let build = |min: f64, max: f64, step: f64| {
+        generate_latitude_band_library(&base_config(), min, max, step, generate_single_axis_table)
+            .map(|library| library.tables.len())
+    };
+    for step in [0.0, -1.0, f64::NAN, f64::INFINITY] {
+        assert!(matches!(build(38.0, 42.0, step), Err(LatitudeBandError::InvalidStep(_))), "{step}");
+    }
+    for (min, max) in [(42.0, 38.0), (f64::NAN, 42.0), (38.0, f64::INFINITY)] {
+        assert!(
+            matches!(build(min, max, 1.0), Err(LatitudeBandError::InvalidRange { .. })),
+            "{min}..{max}"
+        );
+    }
+    assert_eq!(build(40.0, 40.0, 1.0), Ok(1));
+}
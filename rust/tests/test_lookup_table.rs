@@ -1,9 +1,17 @@
 use std::sync::LazyLock;
 
-use solar_tracker::angles::day_of_year;
+use chrono::{DateTime, NaiveDate, TimeZone, Timelike, Utc};
+
+use solar_tracker::angles::{
+    day_of_year, day_of_year_from_date, solar_position_with_model, surface_orientation,
+};
 use solar_tracker::lookup_table::*;
 use solar_tracker::types::*;
 
+fn minute_of_day(minutes: i32) -> MinuteOfDay {
+    MinuteOfDay::new(minutes).unwrap()
+}
+
 macro_rules! assert_approx {
     ($left:expr, $right:expr, $tol:expr) => {
         let (l, r) = ($left as f64, $right as f64);
@@ -45,6 +53,73 @@ fn test_known_time_conversions() {
     assert_eq!(minutes_to_time(390), (6, 30));
 }
 
+// ── Minute of day ──
+
+#[test]
+fn test_minute_of_day_checked_construction() {
+    assert_eq!(MinuteOfDay::new(0), Some(MinuteOfDay::MIDNIGHT));
+    assert_eq!(MinuteOfDay::new(1439).map(i32::from), Some(1439));
+    for bad in [-1, 1440, i32::MIN, i32::MAX] {
+        assert!(MinuteOfDay::new(bad).is_none(), "{bad}");
+        assert_eq!(MinuteOfDay::try_from(bad), Err(MinuteOutOfRange(bad)));
+    }
+    assert_eq!(MinuteOfDay::from_hm(12, 0), Some(MinuteOfDay::NOON));
+    assert!(MinuteOfDay::from_hm(24, 0).is_none());
+    assert!(MinuteOfDay::from_hm(6, 60).is_none());
+    assert_eq!(MinuteOutOfRange(1440).to_string(), "minute 1440 outside 0..1440");
+}
+
+#[test]
+fn test_minute_of_day_arithmetic_wraps() {
+    let late = MinuteOfDay::from_hm(23, 0).unwrap();
+    let early = MinuteOfDay::from_hm(1, 0).unwrap();
+    assert_eq!(late.wrapping_add(120), early);
+    assert_eq!(early.wrapping_add(-120), late);
+    assert_eq!(late.wrapping_add(i32::MAX), MinuteOfDay::wrapping(1380 + i32::MAX % 1440));
+    assert_eq!(late.checked_add(59).map(i32::from), Some(1439));
+    assert!(late.checked_add(60).is_none());
+    assert!(early.checked_add(-61).is_none());
+    assert!(late.checked_add(i32::MAX).is_none());
+    assert_eq!(late.minutes_until(early), 120);
+    assert_eq!(early.minutes_until(late), 1320);
+    assert_eq!(late.minutes_until(late), 0);
+    assert_eq!(MinuteOfDay::wrapping(1440), MinuteOfDay::MIDNIGHT);
+    assert_eq!(MinuteOfDay::wrapping(-1440 * 3 - 1).get(), 1439);
+}
+
+#[test]
+fn test_minute_of_day_matches_time_helpers() {
+    for m in [0, 1, 59, 60, 390, 719, 720, 1439] {
+        let minute = MinuteOfDay::new(m).unwrap();
+        let (hour, min) = minutes_to_time(m);
+        assert_eq!((minute.hour() as i32, minute.minute() as i32), (hour, min));
+        assert_eq!(minute.to_string(), format!("{hour:02}:{min:02}"));
+    }
+}
+
+#[test]
+fn test_minute_of_day_naive_time_conversions() {
+    let time = chrono::NaiveTime::from_hms_opt(18, 7, 59).unwrap();
+    let minute = MinuteOfDay::from(time);
+    assert_eq!(minute.get(), 18 * 60 + 7);
+    // Seconds are dropped
+    let whole = chrono::NaiveTime::from_hms_opt(18, 7, 0).unwrap();
+    assert_eq!(chrono::NaiveTime::from(minute), whole);
+    let key = MinuteOfDay::from_hm(18, 0).unwrap();
+    let typed = lookup_single_axis(&SA_TABLE_15, 172, minute_of_day(key.into()));
+    assert_eq!(typed, lookup_single_axis(&SA_TABLE_15, 172, minute_of_day(1080)));
+}
+
+#[cfg(feature = "json")]
+#[test]
+fn test_minute_of_day_json_is_validated() {
+    let minute: MinuteOfDay = serde_json::from_str("1080").unwrap();
+    assert_eq!(minute, MinuteOfDay::from_hm(18, 0).unwrap());
+    assert_eq!(serde_json::to_string(&minute).unwrap(), "1080");
+    assert!(serde_json::from_str::<MinuteOfDay>("1440").is_err());
+    assert!(serde_json::from_str::<MinuteOfDay>("-5").is_err());
+}
+
 // ── Intervals per day ──
 
 #[test]
@@ -76,6 +151,24 @@ fn test_doy_roundtrip() {
     }
 }
 
+#[test]
+fn test_date_from_day_of_year() {
+    let date = date_from_day_of_year(2024, 60).unwrap();
+    assert_eq!(date, NaiveDate::from_ymd_opt(2024, 2, 29).unwrap());
+    assert_eq!(day_of_year_from_date(date), 60);
+    assert!(date_from_day_of_year(2026, 366).is_none());
+    assert!(date_from_day_of_year(2026, 0).is_none());
+    assert!(date_from_day_of_year(2026, -5).is_none());
+}
+
+#[test]
+fn test_day_of_year_from_date_matches_triple() {
+    for (m, d) in [(1, 1), (3, 21), (6, 21), (12, 31)] {
+        let date = NaiveDate::from_ymd_opt(2026, m, d).unwrap();
+        assert_eq!(day_of_year_from_date(date), day_of_year(2026, m, d));
+    }
+}
+
 #[test]
 fn test_doy_boundary_days() {
     assert_eq!(doy_to_month_day(2026, 1), (1, 1));
@@ -125,6 +218,132 @@ fn test_polar_night() {
     assert_eq!(ss.sunrise, ss.sunset);
 }
 
+// ── Precise sunrise/sunset ──
+
+fn utc(date: (i32, u32, u32), time: (u32, u32)) -> DateTime<Utc> {
+    Utc.with_ymd_and_hms(date.0, date.1, date.2, time.0, time.1, 0).unwrap()
+}
+
+#[test]
+fn test_sunrise_sunset_springfield_solstice() {
+    // NOAA: 05:29 and 20:31 CDT
+    let date = NaiveDate::from_ymd_opt(2026, 6, 21).unwrap();
+    let (rise, set) = sunrise_sunset(39.8, -89.6, date).unwrap();
+    let rise_err = (rise - utc((2026, 6, 21), (10, 29))).num_seconds();
+    let set_err = (set - utc((2026, 6, 22), (1, 31))).num_seconds();
+    assert!(rise_err.abs() <= 120, "sunrise {rise}");
+    assert!(set_err.abs() <= 120, "sunset {set}");
+}
+
+#[test]
+fn test_sunrise_sunset_equator_longer_than_12h() {
+    let date = NaiveDate::from_ymd_opt(2026, 3, 20).unwrap();
+    let (rise, set) = sunrise_sunset(0.0, 0.0, date).unwrap();
+    let daylight = (set - rise).num_minutes();
+    assert!((725..=730).contains(&daylight), "daylight {daylight} min");
+}
+
+#[test]
+fn test_sunrise_sunset_with_model() {
+    let date = NaiveDate::from_ymd_opt(2026, 6, 21).unwrap();
+    let model = |m| sunrise_sunset_with_model(39.8, -89.6, date, m);
+    let (fast_rise, fast_set) = model(PositionModel::Fast).unwrap();
+    let (rise, set) = model(PositionModel::Standard).unwrap();
+    // The refracted horizon adds several minutes at each end
+    assert!((fast_rise - rise).num_minutes() >= 3);
+    assert!((set - fast_set).num_minutes() >= 3);
+    let rise_err = (rise - utc((2026, 6, 21), (10, 29))).num_seconds();
+    assert!(rise_err.abs() <= 90, "sunrise {rise}");
+    assert_eq!(model(PositionModel::Precise), Some((rise, set)));
+    assert!(sunrise_sunset_with_model(80.0, 15.0, date, PositionModel::Fast).is_none());
+}
+
+#[test]
+fn test_sunrise_sunset_polar() {
+    let summer = NaiveDate::from_ymd_opt(2026, 6, 21).unwrap();
+    let winter = NaiveDate::from_ymd_opt(2026, 12, 21).unwrap();
+    assert!(sunrise_sunset(80.0, 15.0, summer).is_none());
+    assert!(sunrise_sunset(80.0, 15.0, winter).is_none());
+}
+
+#[test]
+fn test_precise_sunrise_earlier_than_estimate() {
+    for doy in [1, 80, 172, 266] {
+        let estimate = estimate_sunrise_sunset(39.8, doy);
+        let precise = precise_sunrise_sunset(39.8, doy);
+        assert!(precise.sunrise < estimate.sunrise);
+        assert!(precise.sunset > estimate.sunset);
+        assert!(estimate.sunrise - precise.sunrise <= 6);
+    }
+}
+
+#[test]
+fn test_precise_polar_day_inside_arctic_circle() {
+    // At 66.3° the geometric horizon still has a midsummer crossing, but
+    // with refraction and the solar disk the sun never sets
+    let estimate = estimate_sunrise_sunset(66.3, 172);
+    assert!(estimate.sunrise > 0 && estimate.sunset < 1440);
+    assert_eq!(precise_sunrise_sunset(66.3, 172), SunriseSunset { sunrise: 0, sunset: 1440 });
+    let table = generate_single_axis_table(&LookupTableConfig {
+        latitude: 66.3,
+        interval_minutes: 60,
+        precise_sunrise_sunset: true,
+        ..Default::default()
+    });
+    assert_eq!(table.days[171].sunrise_minutes, 0);
+    assert_eq!(table.days[171].sunset_minutes, 1440);
+    // Far enough north both agree the sun stays down at midwinter
+    assert_eq!(precise_sunrise_sunset(70.0, 355), SunriseSunset { sunrise: 720, sunset: 720 });
+}
+
+#[test]
+fn test_precise_sunrise_in_table_generation() {
+    let config = LookupTableConfig {
+        interval_minutes: 15,
+        precise_sunrise_sunset: true,
+        ..Default::default()
+    };
+    let table = generate_single_axis_table(&config);
+    assert_eq!(table.days[171].sunrise_minutes, precise_sunrise_sunset(39.8, 172).sunrise);
+    assert!(table.days[171].sunrise_minutes < SA_TABLE_15.days[171].sunrise_minutes);
+}
+
+// ── Solar noon ──
+
+#[test]
+fn test_solar_noon_springfield_solstice() {
+    // NOAA: 13:00 CDT
+    let date = NaiveDate::from_ymd_opt(2026, 6, 21).unwrap();
+    let err = (solar_noon(-89.6, date) - utc((2026, 6, 21), (18, 0))).num_seconds();
+    assert!(err.abs() <= 120, "solar noon off by {err} s");
+}
+
+#[test]
+fn test_solar_noon_midway_between_sunrise_and_sunset() {
+    let date = NaiveDate::from_ymd_opt(2026, 11, 3).unwrap();
+    let (rise, set) = sunrise_sunset(39.8, -89.6, date).unwrap();
+    let noon = solar_noon(-89.6, date);
+    assert!(((noon - rise) - (set - noon)).num_seconds().abs() <= 1);
+}
+
+#[test]
+fn test_day_data_solar_noon_minutes() {
+    for doy in [1, 45, 172, 310] {
+        let day = &SA_TABLE_15.days[doy - 1];
+        let noon = solar_noon(-89.6, NaiveDate::from_yo_opt(2026, doy as u32).unwrap());
+        let expected = (noon.time().num_seconds_from_midnight() as f64 / 60.0).round() as i32;
+        assert!((day.solar_noon_minutes - expected).abs() <= 1);
+        // The tracker passes through flat at solar noon
+        let flat = day
+            .entries
+            .iter()
+            .filter(|e| e.rotation.is_some())
+            .min_by(|a, b| a.rotation.unwrap().abs().total_cmp(&b.rotation.unwrap().abs()))
+            .unwrap();
+        assert!((flat.minutes - day.solar_noon_minutes).abs() <= 8);
+    }
+}
+
 // ── Single axis one day ──
 
 static SA_TABLE_15: LazyLock<SingleAxisTable> = LazyLock::new(|| {
@@ -257,12 +476,515 @@ fn test_full_year_entry_structure_consistent() {
     }
 }
 
+// ── Backtracking table ──
+
+static SA_TABLE_BT: LazyLock<SingleAxisTable> = LazyLock::new(|| {
+    let config = LookupTableConfig {
+        interval_minutes: 15,
+        gcr: Some(0.4),
+        ..Default::default()
+    };
+    generate_single_axis_table(&config)
+});
+
+#[test]
+fn test_backtracked_table_same_structure() {
+    for (bt, plain) in SA_TABLE_BT.days.iter().zip(SA_TABLE_15.days.iter()) {
+        assert_eq!(bt.entries.len(), plain.entries.len());
+    }
+}
+
+#[test]
+fn test_backtracked_table_never_exceeds_true_tracking() {
+    for (bt, plain) in SA_TABLE_BT.days.iter().zip(SA_TABLE_15.days.iter()) {
+        for (e_bt, e_plain) in bt.entries.iter().zip(plain.entries.iter()) {
+            if let (Some(r_bt), Some(r_plain)) = (e_bt.rotation, e_plain.rotation) {
+                assert!(
+                    r_bt.abs() <= r_plain.abs() + 1e-9,
+                    "day {} minute {}: {} vs {}",
+                    bt.day_of_year, e_bt.minutes, r_bt, r_plain
+                );
+            }
+        }
+    }
+}
+
+#[test]
+fn test_backtracked_table_flattens_near_sunrise() {
+    let day = &SA_TABLE_BT.days[171];
+    let first = day.entries.iter().find_map(|e| e.rotation).unwrap();
+    assert!(first.abs() < 30.0, "first rotation {}", first);
+}
+
+// ── Terrain slope in tables ──
+
+#[test]
+fn test_terrain_flat_table_close_to_default() {
+    let config = LookupTableConfig {
+        interval_minutes: 15,
+        terrain: Some(TerrainSlope { slope: 0.0, aspect: 180.0 }),
+        ..Default::default()
+    };
+    let table = generate_single_axis_table(&config);
+    let noon = table.days[79].entries.iter().filter_map(|e| e.rotation).map(f64::abs);
+    assert!(noon.fold(f64::MAX, f64::min) < 4.0);
+}
+
+#[test]
+fn test_terrain_west_slope_shifts_backtracking() {
+    let base = LookupTableConfig {
+        interval_minutes: 15,
+        gcr: Some(0.4),
+        ..Default::default()
+    };
+    let sloped = LookupTableConfig {
+        terrain: Some(TerrainSlope { slope: 8.0, aspect: 270.0 }),
+        ..base
+    };
+    let (flat, hill) = (generate_single_axis_table(&base), generate_single_axis_table(&sloped));
+    // Ground falling to the west puts each eastern neighbour higher, so
+    // morning rows back off further and afternoon rows can follow the sun
+    // longer
+    let rotations = |t: &SingleAxisTable| -> Vec<f64> {
+        t.days[79].entries.iter().filter_map(|e| e.rotation).collect()
+    };
+    let first = |t: &SingleAxisTable| rotations(t)[0];
+    let last = |t: &SingleAxisTable| *rotations(t).last().unwrap();
+    assert!(first(&hill) > first(&flat));
+    assert!(last(&hill) > last(&flat));
+}
+
+#[test]
+fn test_terrain_south_slope_tilts_surface() {
+    let config = LookupTableConfig {
+        interval_minutes: 15,
+        include_surface_orientation: true,
+        terrain: Some(TerrainSlope { slope: 5.0, aspect: 180.0 }),
+        ..Default::default()
+    };
+    let table = generate_single_axis_table(&config);
+    let noon = table.days[79]
+        .entries
+        .iter()
+        .filter(|e| e.rotation.is_some())
+        .min_by(|a, b| a.rotation.unwrap().abs().total_cmp(&b.rotation.unwrap().abs()))
+        .unwrap();
+    assert_approx!(noon.surface.unwrap().surface_tilt, 5.0, 2.0);
+}
+
+// ── Rotation limits in tables ──
+
+static SA_TABLE_LIMITED: LazyLock<SingleAxisTable> = LazyLock::new(|| {
+    let config = LookupTableConfig {
+        interval_minutes: 15,
+        min_rotation: Some(-45.0),
+        max_rotation: Some(45.0),
+        ..Default::default()
+    };
+    generate_single_axis_table(&config)
+});
+
+#[test]
+fn test_limited_table_within_limits() {
+    for day in &SA_TABLE_LIMITED.days {
+        for entry in &day.entries {
+            if let Some(r) = entry.rotation {
+                assert!((-45.0..=45.0).contains(&r), "rotation {}", r);
+            }
+        }
+    }
+}
+
+#[test]
+fn test_limited_table_flags_clamped_entries() {
+    let day = &SA_TABLE_LIMITED.days[171];
+    let clamped: Vec<_> = day.entries.iter().filter(|e| e.clamped).collect();
+    assert!(!clamped.is_empty());
+    for e in clamped {
+        assert_approx!(e.rotation.unwrap().abs(), 45.0, 1e-9);
+    }
+}
+
+#[test]
+fn test_unlimited_table_has_no_clamped_entries() {
+    assert!(SA_TABLE_15.days.iter().all(|d| d.entries.iter().all(|e| !e.clamped)));
+}
+
+#[test]
+fn test_lookup_propagates_clamped_flag() {
+    let day = &SA_TABLE_LIMITED.days[171];
+    let entry = day.entries.iter().find(|e| e.clamped).unwrap();
+    let result = lookup_single_axis(&SA_TABLE_LIMITED, 172, minute_of_day(entry.minutes + 5)).unwrap();
+    assert!(result.clamped);
+}
+
+#[test]
+fn test_limited_dual_axis_tilt() {
+    let config = LookupTableConfig {
+        interval_minutes: 30,
+        max_rotation: Some(60.0),
+        ..Default::default()
+    };
+    let table = generate_dual_axis_table(&config);
+    let day = &table.days[354];
+    assert!(day.entries.iter().any(|e| e.adjusted));
+    for e in &day.entries {
+        if let Some(t) = e.tilt {
+            assert!(t <= 60.0);
+        }
+    }
+}
+
+// ── Polar axis table ──
+
+static POLAR_TABLE_15: LazyLock<SingleAxisTable> = LazyLock::new(|| {
+    let config = LookupTableConfig {
+        interval_minutes: 15,
+        ..Default::default()
+    };
+    generate_polar_axis_table(&config)
+});
+
+#[test]
+fn test_polar_table_same_structure_as_single_axis() {
+    assert_eq!(POLAR_TABLE_15.days.len(), SA_TABLE_15.days.len());
+    assert_eq!(POLAR_TABLE_15.days[79].entries.len(), SA_TABLE_15.days[79].entries.len());
+}
+
+#[test]
+fn test_polar_table_rotation_linear_in_time() {
+    let day = &POLAR_TABLE_15.days[171];
+    let rotations: Vec<f64> = day.entries.iter().filter_map(|e| e.rotation).collect();
+    for pair in rotations.windows(2) {
+        assert_approx!(pair[1] - pair[0], 3.75, 1e-6);
+    }
+}
+
+#[test]
+fn test_polar_table_lookup_interpolates() {
+    let day = &POLAR_TABLE_15.days[79];
+    let noon = day.entries.iter().find(|e| e.rotation.is_some_and(|r| r.abs() < 4.0)).unwrap();
+    let result = lookup_single_axis(&POLAR_TABLE_15, 80, minute_of_day(noon.minutes + 5)).unwrap();
+    assert_approx!(result.rotation.unwrap(), noon.rotation.unwrap() + 1.25, 1e-6);
+}
+
+// ── Surface orientation in tables ──
+
+static SA_TABLE_SURFACE: LazyLock<SingleAxisTable> = LazyLock::new(|| {
+    let config = LookupTableConfig {
+        interval_minutes: 15,
+        include_surface_orientation: true,
+        ..Default::default()
+    };
+    generate_single_axis_table(&config)
+});
+
+#[test]
+fn test_surface_orientation_absent_by_default() {
+    assert!(SA_TABLE_15.days[79].entries.iter().all(|e| e.surface.is_none()));
+}
+
+#[test]
+fn test_surface_orientation_present_for_daylight_entries() {
+    for e in &SA_TABLE_SURFACE.days[79].entries {
+        assert_eq!(e.rotation.is_some(), e.surface.is_some());
+        if let (Some(r), Some(s)) = (e.rotation, e.surface) {
+            assert_approx!(s.surface_tilt, r.abs(), 1e-9);
+        }
+    }
+}
+
+#[test]
+fn test_surface_orientation_increases_storage_estimate() {
+    let (with_surface, without) = (&SA_TABLE_SURFACE.metadata, &SA_TABLE_15.metadata);
+    assert!(with_surface.storage_estimate_kb > without.storage_estimate_kb);
+}
+
+#[test]
+fn test_lookup_interpolates_surface_orientation() {
+    let day = &SA_TABLE_SURFACE.days[79];
+    let e = day.entries.iter().find(|e| e.rotation.is_some_and(|r| r < -20.0)).unwrap();
+    let result = lookup_single_axis(&SA_TABLE_SURFACE, 80, minute_of_day(e.minutes + 7)).unwrap();
+    let s = result.surface.unwrap();
+    assert_approx!(s.surface_azimuth, 90.0, 1e-6);
+    assert_approx!(s.surface_tilt, result.rotation.unwrap().abs(), 1e-6);
+}
+
+// The surface follows the interpolated rotation even across solar noon,
+// where the two entries face opposite sides of the axis
+fn assert_surface_matches_rotation(table: &SingleAxisTable, axis_tilt: f64, axis_azimuth: f64) {
+    let day = &table.days[171];
+    let before = day.entries.iter().rev().find(|e| e.rotation.is_some_and(|r| r < 0.0));
+    let before = before.unwrap().minutes;
+    for minutes in before..before + 15 {
+        let entry = lookup_single_axis(table, 172, minute_of_day(minutes)).unwrap();
+        let (rotation, surface) = (entry.rotation.unwrap(), entry.surface.unwrap());
+        let expected = surface_orientation(rotation, axis_tilt, axis_azimuth);
+        assert_approx!(surface.surface_tilt, expected.surface_tilt, 1e-9);
+        assert_approx!(surface.surface_azimuth, expected.surface_azimuth, 1e-9);
+    }
+}
+
+#[test]
+fn test_lookup_surface_follows_rotation_across_noon() {
+    assert_surface_matches_rotation(&SA_TABLE_SURFACE, 0.0, 180.0);
+    let config = LookupTableConfig {
+        interval_minutes: 15,
+        include_surface_orientation: true,
+        axis_tilt: Some(10.0),
+        axis_azimuth: Some(170.0),
+        ..Default::default()
+    };
+    assert_surface_matches_rotation(&generate_single_axis_table(&config), 10.0, 170.0);
+    let polar = generate_polar_axis_table(&LookupTableConfig { axis_tilt: None, ..config });
+    assert_surface_matches_rotation(&polar, 39.8, 180.0);
+}
+
+#[test]
+fn test_polar_table_surface_tilt_at_noon_equals_latitude() {
+    let config = LookupTableConfig {
+        interval_minutes: 15,
+        include_surface_orientation: true,
+        ..Default::default()
+    };
+    let table = generate_polar_axis_table(&config);
+    let noon = table.days[79]
+        .entries
+        .iter()
+        .filter(|e| e.rotation.is_some())
+        .min_by(|a, b| a.rotation.unwrap().abs().total_cmp(&b.rotation.unwrap().abs()))
+        .unwrap();
+    assert_approx!(noon.surface.unwrap().surface_tilt, 39.8, 1.0);
+}
+
+// ── East-west axis table ──
+
+static EW_TABLE_15: LazyLock<SingleAxisTable> = LazyLock::new(|| {
+    let config = LookupTableConfig {
+        interval_minutes: 15,
+        include_surface_orientation: true,
+        ..Default::default()
+    };
+    generate_ew_axis_table(&config)
+});
+
+fn ew_noon_entry(doy: usize) -> SingleAxisEntry {
+    // Solar noon is where the north-south tracker sits flat
+    let noon_minutes = SA_TABLE_15.days[doy - 1]
+        .entries
+        .iter()
+        .filter(|e| e.rotation.is_some())
+        .min_by(|a, b| a.rotation.unwrap().abs().total_cmp(&b.rotation.unwrap().abs()))
+        .unwrap()
+        .minutes;
+    *EW_TABLE_15.days[doy - 1]
+        .entries
+        .iter()
+        .find(|e| e.minutes == noon_minutes)
+        .unwrap()
+}
+
+#[test]
+fn test_ew_table_noon_tilt_tracks_season() {
+    let winter = ew_noon_entry(355).rotation.unwrap();
+    let summer = ew_noon_entry(172).rotation.unwrap();
+    assert!(winter > summer);
+    assert_approx!(ew_noon_entry(80).rotation.unwrap(), 39.8, 2.0);
+}
+
+#[test]
+fn test_ew_table_surface_faces_south_at_noon() {
+    let noon = ew_noon_entry(80);
+    let s = noon.surface.unwrap();
+    assert_approx!(s.surface_azimuth, 180.0, 1e-6);
+    assert_approx!(s.surface_tilt, noon.rotation.unwrap(), 1e-6);
+}
+
+// ── Mount table ──
+
+static TILT_ROLL_TABLE: LazyLock<MountTable> = LazyLock::new(|| {
+    let config = LookupTableConfig {
+        interval_minutes: 15,
+        dual_axis_mount: DualAxisMount::TiltRoll,
+        ..Default::default()
+    };
+    generate_mount_table(&config)
+});
+
+#[test]
+fn test_mount_table_default_is_azimuth_elevation() {
+    let config = LookupTableConfig {
+        interval_minutes: 15,
+        ..Default::default()
+    };
+    let mount = generate_mount_table(&config);
+    let dual = generate_dual_axis_table(&config);
+    for (m, d) in mount.days[171].entries.iter().zip(&dual.days[171].entries) {
+        assert_eq!(m.primary, d.panel_azimuth);
+        assert_eq!(m.secondary, d.tilt);
+    }
+}
+
+#[test]
+fn test_mount_table_tilt_roll_roll_crosses_zero() {
+    let day = &TILT_ROLL_TABLE.days[79];
+    let rolls: Vec<f64> = day.entries.iter().filter_map(|e| e.primary).collect();
+    assert!(rolls.first().unwrap() < &-60.0);
+    assert!(rolls.last().unwrap() > &60.0);
+    assert!(rolls.windows(2).all(|w| w[1] > w[0]));
+}
+
+#[test]
+fn test_mount_lookup_interpolates_linearly() {
+    let day = &TILT_ROLL_TABLE.days[79];
+    let e = day.entries.iter().find(|e| e.primary.is_some_and(|r| r < -20.0)).unwrap();
+    let next = day.entries.iter().find(|n| n.minutes == e.minutes + 15).unwrap();
+    let result = lookup_mount(&TILT_ROLL_TABLE, 80, minute_of_day(e.minutes + 5)).unwrap();
+    let expected = e.primary.unwrap() + (next.primary.unwrap() - e.primary.unwrap()) / 3.0;
+    assert_approx!(result.primary.unwrap(), expected, 1e-9);
+    assert!(result.primary.unwrap() < 0.0);
+}
+
+// ── Longitude shift ──
+
+#[test]
+fn test_shift_updates_longitude_and_minutes() {
+    let shifted = shift_single_axis_table(&SA_TABLE_15, -87.6);
+    assert_eq!(shifted.config.longitude, -87.6);
+    let orig = &SA_TABLE_15.days[79].entries;
+    let new = &shifted.days[79].entries;
+    assert_eq!(new[0].minutes, orig[0].minutes - 8);
+    assert_eq!(new[0].rotation, orig[0].rotation);
+    assert_eq!(shifted.days[79].solar_noon_minutes, SA_TABLE_15.days[79].solar_noon_minutes - 8);
+}
+
+#[test]
+fn test_shift_matches_direct_computation() {
+    use chrono::{Duration, NaiveDate};
+    use solar_tracker::angles::{single_axis_tilt, solar_position};
+
+    let shifted = shift_single_axis_table(&SA_TABLE_15, -87.6);
+    let mut max_err: f64 = 0.0;
+    for doy in [1, 80, 172, 264, 355] {
+        let date = NaiveDate::from_yo_opt(2026, doy as u32).unwrap();
+        for e in &shifted.days[doy - 1].entries {
+            let Some(rotation) = e.rotation else { continue };
+            let midnight = date.and_hms_opt(0, 0, 0).unwrap().and_utc();
+            let t = midnight + Duration::minutes(e.minutes as i64);
+            let pos = solar_position(39.8, -87.6, &t);
+            if pos.hour_angle.abs() < 80.0 {
+                max_err = max_err.max((single_axis_tilt(&pos, 39.8) - rotation).abs());
+            }
+        }
+    }
+    assert!(max_err < 0.2, "max error {}", max_err);
+}
+
+#[test]
+fn test_shift_drops_entries_past_midnight() {
+    let shifted = shift_dual_axis_table(&DA_TABLE_15, -110.0);
+    for day in &shifted.days {
+        assert!(day.entries.iter().all(|e| (0..1440).contains(&e.minutes)));
+    }
+    assert!(shifted.metadata.total_entries < DA_TABLE_15.metadata.total_entries);
+}
+
+#[test]
+fn test_shift_to_same_longitude_is_identity() {
+    let shifted = shift_single_axis_table(&SA_TABLE_15, SA_TABLE_15.config.longitude);
+    assert_eq!(shifted.days, SA_TABLE_15.days);
+}
+
+// ── Declination model in tables ──
+
+#[test]
+fn test_table_honors_declination_model() {
+    let config = LookupTableConfig {
+        interval_minutes: 60,
+        declination_model: DeclinationModel::Spencer,
+        ..Default::default()
+    };
+    let table = generate_dual_axis_table(&config);
+    let entry = table.days[265].entries.iter().find(|e| e.minutes == 1080).unwrap();
+    let t = Utc.with_ymd_and_hms(2026, 9, 23, 18, 0, 0).unwrap();
+    let pos = solar_position_with_model(39.8, -89.6, &t, DeclinationModel::Spencer);
+    assert_approx!(entry.tilt.unwrap(), pos.zenith, 0.01);
+}
+
+#[test]
+fn test_table_sunrise_follows_declination_model() {
+    let spencer = generate_single_axis_table(&LookupTableConfig {
+        interval_minutes: 60,
+        declination_model: DeclinationModel::Spencer,
+        ..Default::default()
+    });
+    let cooper = generate_single_axis_table(&LookupTableConfig {
+        interval_minutes: 60,
+        ..Default::default()
+    });
+    // Cooper runs about a degree south at the September equinox
+    assert!(spencer.days[265].sunrise_minutes < cooper.days[265].sunrise_minutes);
+    assert!(spencer.days[265].sunset_minutes > cooper.days[265].sunset_minutes);
+}
+
+#[test]
+fn test_table_honors_equation_of_time_model() {
+    let config = LookupTableConfig {
+        interval_minutes: 60,
+        ..Default::default()
+    };
+    let ecliptic = generate_single_axis_table(&LookupTableConfig {
+        equation_of_time_model: EquationOfTimeModel::Ecliptic,
+        ..config
+    });
+    let fourier = generate_single_axis_table(&config);
+    // Day 12, where the Fourier series is furthest off: about 47 seconds of
+    // sun motion
+    let e = ecliptic.days[11].entries.iter().find(|e| e.minutes == 1080).unwrap();
+    let f = fourier.days[11].entries.iter().find(|e| e.minutes == 1080).unwrap();
+    let diff = (e.rotation.unwrap() - f.rotation.unwrap()).abs();
+    assert!(diff > 0.05 && diff < 0.5, "{diff}");
+}
+
+#[test]
+fn test_config_with_position_model() {
+    let config = LookupTableConfig::default().with_position_model(PositionModel::Standard);
+    assert_eq!(config.declination_model, DeclinationModel::Spencer);
+    assert_eq!(config.equation_of_time_model, EquationOfTimeModel::Ecliptic);
+    assert!(config.precise_sunrise_sunset);
+    let fast = config.with_position_model(PositionModel::Fast);
+    assert_eq!(fast, LookupTableConfig::default());
+    // Per-day paths have nothing finer than the Standard formulas
+    assert_eq!(config.with_position_model(PositionModel::Precise), config);
+}
+
+// The table's sunrise agrees with `sunrise_sunset_with_model` for the same
+// model, to the table's whole-minute rounding
+#[test]
+fn test_table_sunrise_matches_position_model() {
+    let config = LookupTableConfig {
+        interval_minutes: 60,
+        ..Default::default()
+    };
+    for model in [PositionModel::Fast, PositionModel::Standard] {
+        let table = generate_single_axis_table(&config.with_position_model(model));
+        for doy in [15, 172, 300] {
+            let date = NaiveDate::from_yo_opt(2026, doy as u32).unwrap();
+            let (rise, set) = sunrise_sunset_with_model(39.8, -89.6, date, model).unwrap();
+            let day = &table.days[doy - 1];
+            let half_day = (set - rise).num_seconds() as f64 / 120.0;
+            let table_half_day = (day.sunset_minutes - day.sunrise_minutes) as f64 / 2.0;
+            assert!((half_day - table_half_day).abs() <= 1.5, "{model:?} {doy}");
+        }
+    }
+}
+
 // ── Lookup single axis ──
 
 #[test]
 fn test_lookup_single_axis_exact_boundary() {
     // Solar noon in UTC for Springfield
-    let result = lookup_single_axis(&SA_TABLE_15, 80, 1080);
+    let result = lookup_single_axis(&SA_TABLE_15, 80, minute_of_day(1080));
     assert!(result.is_some());
     let r = result.unwrap();
     assert_eq!(r.minutes, 1080);
@@ -271,9 +993,9 @@ fn test_lookup_single_axis_exact_boundary() {
 
 #[test]
 fn test_lookup_single_axis_interpolated() {
-    let result = lookup_single_axis(&SA_TABLE_15, 80, 1087);
-    let at_1080 = lookup_single_axis(&SA_TABLE_15, 80, 1080);
-    let at_1095 = lookup_single_axis(&SA_TABLE_15, 80, 1095);
+    let result = lookup_single_axis(&SA_TABLE_15, 80, minute_of_day(1087));
+    let at_1080 = lookup_single_axis(&SA_TABLE_15, 80, minute_of_day(1080));
+    let at_1095 = lookup_single_axis(&SA_TABLE_15, 80, minute_of_day(1095));
     assert!(result.is_some());
     let r = result.unwrap();
     assert_eq!(r.minutes, 1087);
@@ -294,7 +1016,7 @@ fn test_lookup_single_axis_interpolated() {
 
 #[test]
 fn test_lookup_dual_axis_exact_boundary() {
-    let result = lookup_dual_axis(&DA_TABLE_15, 80, 1080);
+    let result = lookup_dual_axis(&DA_TABLE_15, 80, minute_of_day(1080));
     assert!(result.is_some());
     let r = result.unwrap();
     assert!(r.tilt.is_some());
@@ -303,19 +1025,271 @@ fn test_lookup_dual_axis_exact_boundary() {
 
 #[test]
 fn test_lookup_dual_axis_interpolated() {
-    let result = lookup_dual_axis(&DA_TABLE_15, 80, 1087);
+    let result = lookup_dual_axis(&DA_TABLE_15, 80, minute_of_day(1087));
     assert!(result.is_some());
     let r = result.unwrap();
     assert!(r.tilt.is_some());
     assert!(r.panel_azimuth.is_some());
 }
 
+// ── Azimuth encoding ──
+
+#[test]
+fn test_azimuth_encoding_round_trip() {
+    let encodings =
+        [AzimuthEncoding::Bearing, AzimuthEncoding::SouthOffset, AzimuthEncoding::NorthOffset];
+    for encoding in encodings {
+        for bearing in [0.0, 0.5, 90.0, 179.5, 180.0, 270.0, 359.5] {
+            let stored = encoding.encode(bearing);
+            assert!(stored > -180.0 && stored < 360.0, "{encoding:?} {bearing}");
+            assert_approx!(encoding.decode(stored), bearing, 1e-9);
+        }
+    }
+    // A panel facing due west, its bearing due east
+    assert_eq!(AzimuthEncoding::SouthOffset.encode(90.0), 90.0);
+    assert_eq!(AzimuthEncoding::NorthOffset.encode(90.0), -90.0);
+}
+
+#[test]
+fn test_south_offset_table_never_wraps() {
+    let config = LookupTableConfig { interval_minutes: 15, ..Default::default() };
+    let offset = generate_dual_axis_table(&LookupTableConfig {
+        azimuth_encoding: AzimuthEncoding::SouthOffset,
+        ..config
+    });
+    for (o, b) in offset.days[171].entries.iter().zip(&DA_TABLE_15.days[171].entries) {
+        assert_eq!(o.tilt, b.tilt);
+        let (Some(stored), Some(bearing)) = (o.panel_azimuth, b.panel_azimuth) else {
+            assert_eq!(o.panel_azimuth, b.panel_azimuth);
+            continue;
+        };
+        assert_approx!(AzimuthEncoding::SouthOffset.decode(stored), bearing, 1e-9);
+    }
+    // Facing east in the morning and west in the evening, through zero at
+    // noon rather than wrapping 359° → 0°
+    let stored: Vec<f64> =
+        offset.days[171].entries.iter().filter_map(|e| e.panel_azimuth).collect();
+    assert!(stored[0] < -90.0 && *stored.last().unwrap() > 90.0);
+    assert!(stored.windows(2).all(|w| w[1] > w[0]));
+    let bearings: Vec<f64> =
+        DA_TABLE_15.days[171].entries.iter().filter_map(|e| e.panel_azimuth).collect();
+    assert!(bearings.windows(2).any(|w| (w[1] - w[0]).abs() > 180.0));
+}
+
+#[test]
+fn test_offset_lookup_interpolates_in_encoding() {
+    let table = generate_dual_axis_table(&LookupTableConfig {
+        interval_minutes: 15,
+        azimuth_encoding: AzimuthEncoding::SouthOffset,
+        ..Default::default()
+    });
+    let noon = table.days[171].solar_noon_minutes;
+    for minutes in noon - 20..noon + 20 {
+        let stored = lookup_dual_axis(&table, 172, minute_of_day(minutes)).unwrap().panel_azimuth.unwrap();
+        let bearing = lookup_dual_axis(&DA_TABLE_15, 172, minute_of_day(minutes)).unwrap().panel_azimuth.unwrap();
+        assert!(stored.abs() < 90.0, "{minutes}: {stored}");
+        assert_approx!(AzimuthEncoding::SouthOffset.decode(stored), bearing, 1e-9);
+    }
+}
+
+// ── Time base ──
+
+// Springfield on standard time all year: UTC−6
+const CST: TimeBase = TimeBase::FixedOffset { offset_minutes: -360 };
+
+fn clock(month: u32, day: u32, hour: u32, minute: u32) -> chrono::NaiveDateTime {
+    NaiveDate::from_ymd_opt(2026, month, day).unwrap().and_hms_opt(hour, minute, 0).unwrap()
+}
+
+#[test]
+fn test_utc_time_base_keys_are_the_clock() {
+    let config = LookupTableConfig::default();
+    assert_eq!(table_keys_at_clock(&config, clock(6, 21, 18, 0)), Some((172, minute_of_day(1080))));
+    assert_eq!(clock_at_table_keys(&config, 172, minute_of_day(1080)), Some(clock(6, 21, 18, 0)));
+}
+
+#[test]
+fn test_fixed_offset_time_base_keys() {
+    let config = LookupTableConfig { time_base: CST, ..Default::default() };
+    assert_eq!(table_keys_at_clock(&config, clock(6, 21, 12, 0)), Some((172, minute_of_day(1080))));
+    // Evening local time is already the next UTC day
+    assert_eq!(table_keys_at_clock(&config, clock(6, 21, 20, 30)), Some((173, minute_of_day(150))));
+    for (doy, minutes) in [(1, 0), (80, 725), (172, 150), (365, 1439)] {
+        let local = clock_at_table_keys(&config, doy, minute_of_day(minutes)).unwrap();
+        assert_eq!(table_keys_at_clock(&config, local), Some((doy, minute_of_day(minutes))));
+    }
+    assert!(clock_at_table_keys(&config, 366, minute_of_day(0)).is_none());
+}
+
+#[test]
+fn test_lookup_at_clock_matches_utc_keys() {
+    let table = generate_single_axis_table(&LookupTableConfig {
+        interval_minutes: 15,
+        time_base: CST,
+        ..Default::default()
+    });
+    for (hour, minute) in [(9, 0), (12, 7), (16, 45)] {
+        let local = clock(3, 21, hour, minute);
+        let utc = (hour * 60 + minute) as i32 + 360;
+        assert_eq!(lookup_single_axis_at_clock(&table, local), lookup_single_axis(&table, 80, minute_of_day(utc)));
+        assert_eq!(lookup_single_axis_at_clock(&table, local).unwrap().minutes, utc);
+    }
+    let dual = generate_dual_axis_table(&LookupTableConfig {
+        interval_minutes: 15,
+        time_base: CST,
+        ..Default::default()
+    });
+    let at_clock = lookup_dual_axis_at_clock(&dual, clock(3, 21, 12, 0));
+    assert_eq!(at_clock, lookup_dual_axis(&dual, 80, minute_of_day(1080)));
+}
+
+// The time base changes how clocks map to keys, never the entries
+#[test]
+fn test_time_base_leaves_entries_unchanged() {
+    let utc = generate_single_axis_table(&LookupTableConfig {
+        interval_minutes: 60,
+        ..Default::default()
+    });
+    let cst = generate_single_axis_table(&LookupTableConfig {
+        interval_minutes: 60,
+        time_base: CST,
+        ..Default::default()
+    });
+    assert_eq!(utc.days, cst.days);
+}
+
+#[cfg(feature = "tz")]
+#[test]
+fn test_zone_time_base_follows_dst() {
+    let config = LookupTableConfig {
+        time_base: TimeBase::Zone(chrono_tz::America::Chicago),
+        ..Default::default()
+    };
+    // 1 pm CDT in June and noon CST in January are both 18:00 UTC
+    assert_eq!(table_keys_at_clock(&config, clock(6, 21, 13, 0)), Some((172, minute_of_day(1080))));
+    assert_eq!(table_keys_at_clock(&config, clock(1, 15, 12, 0)), Some((15, minute_of_day(1080))));
+    assert_eq!(clock_at_table_keys(&config, 172, minute_of_day(1080)), Some(clock(6, 21, 13, 0)));
+    // The skipped spring-forward hour has no instant; the repeated fall-back
+    // hour resolves to its first (daylight-time) pass
+    assert!(table_keys_at_clock(&config, clock(3, 8, 2, 30)).is_none());
+    assert_eq!(table_keys_at_clock(&config, clock(11, 1, 1, 30)), Some((305, minute_of_day(390))));
+}
+
+// ── Hour-angle keys ──
+
+fn hour_angle_config(longitude: f64) -> LookupTableConfig {
+    LookupTableConfig {
+        longitude,
+        interval_minutes: 15,
+        table_key: TableKey::HourAngle,
+        ..Default::default()
+    }
+}
+
+static HA_TABLE: LazyLock<SingleAxisTable> =
+    LazyLock::new(|| generate_single_axis_table(&hour_angle_config(-89.6501)));
+
+#[test]
+fn test_hour_angle_table_is_symmetric_about_noon() {
+    for day in &HA_TABLE.days {
+        assert_eq!(day.solar_noon_minutes, 720);
+        let rotation_at = |m: i32| day.entries.iter().find(|e| e.minutes == m)?.rotation;
+        for offset in [60, 180, 300] {
+            if let (Some(am), Some(pm)) = (rotation_at(720 - offset), rotation_at(720 + offset)) {
+                assert_approx!(am, -pm, 1e-9);
+            }
+        }
+    }
+}
+
+// Nearby longitudes share one hour-angle table
+#[test]
+fn test_hour_angle_table_ignores_longitude() {
+    let east = generate_single_axis_table(&hour_angle_config(-85.0));
+    assert_eq!(east.days, HA_TABLE.days);
+    let shifted = shift_single_axis_table(&HA_TABLE, -85.0);
+    assert_eq!(shifted.days, HA_TABLE.days);
+    assert_eq!(shifted.config.longitude, -85.0);
+}
+
+#[test]
+fn test_hour_angle_lookup_matches_utc_table() {
+    let utc_table = generate_single_axis_table(&LookupTableConfig {
+        interval_minutes: 1,
+        ..Default::default()
+    });
+    for (hour, minute) in [(14, 0), (18, 7), (22, 30)] {
+        let at = clock(3, 21, hour, minute);
+        let (doy, minutes) = table_keys_at_clock(&HA_TABLE.config, at).unwrap();
+        assert_eq!(doy, 80);
+        let by_angle = lookup_single_axis_at_clock(&HA_TABLE, at).unwrap();
+        let utc_minute = MinuteOfDay::from_hm(hour, minute).unwrap();
+        let by_utc = lookup_single_axis(&utc_table, 80, utc_minute).unwrap();
+        assert_eq!(by_angle.minutes, minutes.get());
+        // Interpolated between 15-minute samples, against a 1-minute table
+        assert_approx!(by_angle.rotation.unwrap(), by_utc.rotation.unwrap(), 0.5);
+    }
+}
+
+#[test]
+fn test_hour_angle_keys_round_trip_clock() {
+    let config = LookupTableConfig { time_base: CST, ..hour_angle_config(-89.6501) };
+    for (doy, minutes) in [(15, 480), (80, 720), (172, 1000), (300, 615)] {
+        let local = clock_at_table_keys(&config, doy, minute_of_day(minutes)).unwrap();
+        assert_eq!(table_keys_at_clock(&config, local), Some((doy, minute_of_day(minutes))));
+    }
+    // Solar noon in Springfield falls near 12:00 CST
+    let noon = clock_at_table_keys(&config, 80, minute_of_day(720)).unwrap();
+    assert_eq!(noon.date(), NaiveDate::from_ymd_opt(2026, 3, 21).unwrap());
+    assert!((11..=12).contains(&noon.hour()), "{noon}");
+}
+
+#[test]
+fn test_hour_angle_keys_for_other_longitude() {
+    let utc = clock(6, 21, 18, 0);
+    let (_, springfield) = hour_angle_keys(&HA_TABLE.config, -89.6501, utc).unwrap();
+    let (_, east) = hour_angle_keys(&HA_TABLE.config, -74.0, utc).unwrap();
+    // 15.65° of longitude is about 63 minutes of solar time
+    assert!((62..=64).contains(&springfield.minutes_until(east)), "{springfield} {east}");
+}
+
 // ── Lookup outside range ──
 
 #[test]
 fn test_nighttime_returns_none() {
-    assert!(lookup_single_axis(&SA_TABLE_15, 80, 0).is_none());
-    assert!(lookup_single_axis(&SA_TABLE_15, 80, 120).is_none());
+    assert!(lookup_single_axis(&SA_TABLE_15, 80, minute_of_day(0)).is_none());
+    assert!(lookup_single_axis(&SA_TABLE_15, 80, minute_of_day(120)).is_none());
+}
+
+#[test]
+fn test_hostile_day_of_year_returns_none() {
+    for doy in [i32::MIN, -1, 0, 366, 367, i32::MAX] {
+        assert!(lookup_single_axis(&SA_TABLE_30, doy, minute_of_day(720)).is_none(), "doy {doy}");
+        assert!(lookup_dual_axis(&DA_TABLE_15, doy, minute_of_day(720)).is_none(), "doy {doy}");
+        assert!(lookup_nearest(&SA_TABLE_30, doy, minute_of_day(720)).is_none(), "doy {doy}");
+    }
+}
+
+#[test]
+fn test_hostile_minutes_are_rejected() {
+    // Out-of-range minutes never reach a lookup: the key type refuses them
+    for minutes in [i32::MIN, -1, 1440, i32::MAX] {
+        assert!(MinuteOfDay::new(minutes).is_none(), "minutes {minutes}");
+        assert!(MinuteOfDay::try_from(minutes).is_err(), "minutes {minutes}");
+    }
+}
+
+#[test]
+fn test_tampered_table_returns_none() {
+    // A deserialized table can carry any interval and any entry order
+    let mut table = SA_TABLE_30.clone();
+    table.config.interval_minutes = 0;
+    assert!(lookup_single_axis(&table, 172, minute_of_day(720)).is_none());
+    table.config.interval_minutes = 30;
+    table.days[171].entries.reverse();
+    assert!(lookup_single_axis(&table, 172, minute_of_day(725)).is_none());
+    table.days.truncate(10);
+    assert!(lookup_single_axis(&table, 172, minute_of_day(720)).is_none());
 }
 
 // ── Compact export ──
@@ -352,29 +1326,110 @@ fn test_dual_axis_compact() {
     assert!(sample.1.is_some());
 }
 
-// ── Interpolate angle wraparound ──
+// ── Step-valued tables ──
+
+fn mode_table() -> StepTable<ControllerMode> {
+    step_table(&SA_TABLE_15, |e| match e.rotation {
+        Some(r) if r.abs() < 30.0 => ControllerMode::Tracking,
+        Some(_) => ControllerMode::Backtracking,
+        None => ControllerMode::Night,
+    })
+}
 
 #[test]
-fn test_interpolate_angle_normal() {
-    assert_approx!(interpolate_angle(Some(0.0), Some(90.0), 0.5).unwrap(), 45.0, 0.01);
-    assert_approx!(interpolate_angle(Some(0.0), Some(90.0), 0.0).unwrap(), 0.0, 0.01);
-    assert_approx!(interpolate_angle(Some(0.0), Some(90.0), 1.0).unwrap(), 90.0, 0.01);
+fn test_step_table_preserves_structure() {
+    let modes = mode_table();
+    assert_eq!(modes.days.len(), SA_TABLE_15.days.len());
+    for (day, src) in modes.days.iter().zip(&SA_TABLE_15.days) {
+        assert_eq!(day.entries.len(), src.entries.len());
+        assert_eq!(day.sunrise_minutes, src.sunrise_minutes);
+        for (e, s) in day.entries.iter().zip(&src.entries) {
+            assert_eq!(e.minutes, s.minutes);
+        }
+    }
+}
+
+#[test]
+fn test_lookup_nearest_does_not_interpolate() {
+    let modes = mode_table();
+    let day = &modes.days[0];
+    let idx = day.entries.windows(2).position(|w| w[0].value != w[1].value).unwrap();
+    let (a, b) = (&day.entries[idx], &day.entries[idx + 1]);
+    let doy = day.day_of_year;
+    assert_eq!(lookup_nearest(&modes, doy, minute_of_day(a.minutes + 5)).unwrap().value, a.value);
+    assert_eq!(lookup_nearest(&modes, doy, minute_of_day(b.minutes - 5)).unwrap().value, b.value);
+    // Midway ties go to the earlier entry
+    let mid = lookup_nearest(&modes, doy, minute_of_day((a.minutes + b.minutes) / 2)).unwrap();
+    assert_eq!(mid.value, a.value);
+    assert_eq!(mid.minutes, (a.minutes + b.minutes) / 2);
 }
 
 #[test]
-fn test_interpolate_angle_wraparound_350_to_10() {
-    let result = interpolate_angle(Some(350.0), Some(10.0), 0.5);
-    assert_approx!(result.unwrap(), 0.0, 0.01);
+fn test_lookup_nearest_outside_range() {
+    let modes = mode_table();
+    let day = &modes.days[0];
+    let first = day.entries[0].minutes;
+    assert!(lookup_nearest(&modes, day.day_of_year, minute_of_day(first - 60)).is_none());
 }
 
 #[test]
-fn test_interpolate_angle_wraparound_10_to_350() {
-    let result = interpolate_angle(Some(10.0), Some(350.0), 0.5);
-    assert_approx!(result.unwrap(), 0.0, 0.01);
+fn test_generic_compact_export() {
+    let modes = mode_table();
+    let compact = table_to_compact(&modes, |e| e.value);
+    assert_eq!(compact.len(), modes.days.len());
+    assert_eq!(compact[0][0], modes.days[0].entries[0].value);
 }
 
+#[cfg(feature = "json")]
 #[test]
-fn test_interpolate_angle_none_input() {
-    assert!(interpolate_angle(None, Some(10.0), 0.5).is_none());
-    assert!(interpolate_angle(Some(10.0), None, 0.5).is_none());
+fn test_step_table_json_roundtrip() {
+    let modes = mode_table();
+    let json = serde_json::to_string(&modes).unwrap();
+    assert!(json.contains("\"Tracking\""));
+    let parsed: StepTable<ControllerMode> = serde_json::from_str(&json).unwrap();
+    assert_eq!(parsed.days[0].entries, modes.days[0].entries);
+}
+
+// Configs saved before surface orientation existed load without it
+#[cfg(feature = "json")]
+#[test]
+fn test_config_json_defaults_surface_orientation() {
+    let mut value = serde_json::to_value(LookupTableConfig::default()).unwrap();
+    value.as_object_mut().unwrap().remove("include_surface_orientation");
+    let config: LookupTableConfig = serde_json::from_value(value).unwrap();
+    assert!(!config.include_surface_orientation);
+}
+
+#[cfg(feature = "json")]
+#[test]
+fn test_config_json_defaults_dual_axis_mount() {
+    let mut value = serde_json::to_value(LookupTableConfig::default()).unwrap();
+    value.as_object_mut().unwrap().remove("dual_axis_mount");
+    let config: LookupTableConfig = serde_json::from_value(value).unwrap();
+    assert_eq!(config.dual_axis_mount, DualAxisMount::AzimuthElevation);
+}
+
+#[cfg(feature = "json")]
+#[test]
+fn test_config_json_defaults_precise_sunrise_sunset() {
+    let mut value = serde_json::to_value(LookupTableConfig::default()).unwrap();
+    value.as_object_mut().unwrap().remove("precise_sunrise_sunset");
+    let config: LookupTableConfig = serde_json::from_value(value).unwrap();
+    assert!(!config.precise_sunrise_sunset);
+}
+
+// ── Deterministic math ──
+
+// With `deterministic` the table is built from IEEE-only trig, so its bits
+// are fixed on every target.
+#[cfg(feature = "deterministic")]
+#[test]
+fn test_deterministic_table_fingerprint() {
+    let fingerprint = SA_TABLE_15
+        .days
+        .iter()
+        .flat_map(|d| &d.entries)
+        .filter_map(|e| e.rotation)
+        .fold(0u64, |acc, r| acc.rotate_left(5) ^ r.to_bits());
+    assert_eq!(fingerprint, 2235041418795722275);
 }
@@ -0,0 +1,66 @@
+use std::sync::LazyLock;
+
+use solar_tracker::lookup_table::*;
+use solar_tracker::magnetic::*;
+use solar_tracker::types::*;
+
+macro_rules! assert_approx {
+    ($left:expr, $right:expr, $tol:expr) => {
+        let (l, r, t) = ($left as f64, $right as f64, $tol as f64);
+        assert!(
+            (l - r).abs() < t,
+            "assert_approx failed: {} vs {} (diff {}, tol {})",
+            l,
+            r,
+            (l - r).abs(),
+            t
+        );
+    };
+}
+
+static TRUE_TABLE: LazyLock<DualAxisTable> =
+    LazyLock::new(|| generate_dual_axis_table(&LookupTableConfig::default()));
+
+// ── Bearing conversion ──
+
+#[test]
+fn test_magnetic_bearing_east_declination() {
+    assert_approx!(magnetic_bearing(180.0, 10.0), 170.0, 1e-12);
+    assert_approx!(magnetic_bearing(5.0, 10.0), 355.0, 1e-12);
+}
+
+#[test]
+fn test_magnetic_bearing_west_declination() {
+    assert_approx!(magnetic_bearing(355.0, -10.0), 5.0, 1e-12);
+}
+
+#[test]
+fn test_true_bearing_round_trip() {
+    for az in [0.0, 45.0, 179.0, 359.5] {
+        assert_approx!(true_bearing(magnetic_bearing(az, -7.3), -7.3), az, 1e-9);
+    }
+}
+
+// ── Table generation ──
+
+#[test]
+fn test_table_panel_azimuth_in_magnetic_bearings() {
+    let table = generate_dual_axis_table(&LookupTableConfig {
+        magnetic_declination: Some(-3.0),
+        ..Default::default()
+    });
+    for (t, m) in TRUE_TABLE.days[100]
+        .entries
+        .iter()
+        .zip(&table.days[100].entries)
+    {
+        assert_eq!(t.tilt, m.tilt);
+        match (t.panel_azimuth, m.panel_azimuth) {
+            (Some(t), Some(m)) => {
+                assert_approx!(true_bearing(m, -3.0), t, 1e-9);
+            }
+            (None, None) => {}
+            other => panic!("daylight mismatch: {other:?}"),
+        }
+    }
+}
@@ -0,0 +1,184 @@
+// Counts heap allocations made by the current thread, so lookups can be held
+// to the no-allocation guarantee firmware relies on. Each test file is its
+// own binary, so the counting allocator only applies here.
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::cell::Cell;
+use std::sync::LazyLock;
+
+use solar_tracker::angles::{circular_mean, circular_mean_weighted};
+use solar_tracker::fixed_mount::*;
+use solar_tracker::interp::find_bracketing_entries;
+use solar_tracker::latitude_bands::*;
+use solar_tracker::lookup_table::*;
+#[cfg(feature = "heapless")]
+use solar_tracker::program::*;
+use solar_tracker::shading::*;
+use solar_tracker::types::*;
+
+struct CountingAllocator;
+
+thread_local! {
+    static ALLOCATIONS: Cell<usize> = const { Cell::new(0) };
+}
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.with(|count| count.set(count.get() + 1));
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        ALLOCATIONS.with(|count| count.set(count.get() + 1));
+        System.realloc(ptr, layout, new_size)
+    }
+}
+
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+fn allocations<R>(f: impl FnOnce() -> R) -> usize {
+    let before = ALLOCATIONS.with(Cell::get);
+    std::hint::black_box(f());
+    ALLOCATIONS.with(Cell::get) - before
+}
+
+static SINGLE: LazyLock<SingleAxisTable> =
+    LazyLock::new(|| generate_single_axis_table(&LookupTableConfig::default()));
+static DUAL: LazyLock<DualAxisTable> =
+    LazyLock::new(|| generate_dual_axis_table(&LookupTableConfig::default()));
+static MOUNT: LazyLock<MountTable> =
+    LazyLock::new(|| generate_mount_table(&LookupTableConfig::default()));
+static SHADE: LazyLock<ShadeTable> = LazyLock::new(|| {
+    generate_shade_table(&LookupTableConfig::default(), &ShadeConfig::default())
+});
+static FIXED: LazyLock<FixedTable> = LazyLock::new(|| {
+    generate_fixed_table(&LookupTableConfig::default(), &FixedMountConfig::default())
+});
+static BANDS: LazyLock<LatitudeBandLibrary<SingleAxisEntry>> = LazyLock::new(|| {
+    let base = LookupTableConfig::default();
+    generate_latitude_band_library(&base, 38.0, 42.0, 1.0, generate_single_axis_table)
+        .unwrap()
+});
+
+// Every interval of a few days, plus days the table does not hold
+fn queries() -> impl Iterator<Item = (i32, MinuteOfDay)> {
+    [1, 80, 172, 266, 355, 0, 400]
+        .into_iter()
+        .flat_map(|doy| (0..1440).step_by(7).filter_map(MinuteOfDay::new).map(move |minutes| (doy, minutes)))
+}
+
+// ── Table lookups ──
+
+#[test]
+fn test_lookups_do_not_allocate() {
+    let (single, dual, mount) = (&*SINGLE, &*DUAL, &*MOUNT);
+    let count = allocations(|| {
+        for (doy, minutes) in queries() {
+            std::hint::black_box(lookup_single_axis(single, doy, minutes));
+            std::hint::black_box(lookup_dual_axis(dual, doy, minutes));
+            std::hint::black_box(lookup_mount(mount, doy, minutes));
+            std::hint::black_box(lookup_nearest(single, doy, minutes));
+        }
+    });
+    assert_eq!(count, 0);
+}
+
+#[test]
+fn test_fixed_lookup_and_window_do_not_allocate() {
+    let fixed = &*FIXED;
+    let count = allocations(|| {
+        for (doy, minutes) in queries() {
+            std::hint::black_box(lookup_fixed(fixed, doy, minutes));
+        }
+        for doy in 0..=367 {
+            std::hint::black_box(fixed_window(fixed, doy));
+        }
+    });
+    assert_eq!(count, 0);
+}
+
+#[test]
+fn test_band_lookup_does_not_allocate() {
+    let bands = &*BANDS;
+    let count = allocations(|| {
+        for (doy, minutes) in queries() {
+            std::hint::black_box(lookup_single_axis_band(bands, 39.8, -89.6, doy, minutes));
+        }
+    });
+    assert_eq!(count, 0);
+}
+
+#[test]
+fn test_bracketing_does_not_allocate() {
+    let entries = &SINGLE.days[171].entries;
+    let count = allocations(|| {
+        for minutes in 0..1440 {
+            std::hint::black_box(find_bracketing_entries(entries, 5, minutes));
+        }
+    });
+    assert_eq!(count, 0);
+}
+
+#[test]
+fn test_circular_means_do_not_allocate() {
+    let angles = [350.0, 10.0, 20.0, 340.0];
+    let samples = [(350.0, 1.0), (10.0, 2.0)];
+    let count = allocations(|| {
+        std::hint::black_box(circular_mean(&angles));
+        std::hint::black_box(circular_mean_weighted(&samples));
+    });
+    assert_eq!(count, 0);
+}
+
+// ── Shade screens ──
+
+#[test]
+fn test_shade_lookup_and_events_do_not_allocate() {
+    let shade = &*SHADE;
+    let count = allocations(|| {
+        for (doy, minutes) in queries() {
+            std::hint::black_box(lookup_shade(shade, doy, minutes));
+        }
+        for doy in 0..=367 {
+            std::hint::black_box(shade_events(shade, doy).count());
+        }
+    });
+    assert_eq!(count, 0);
+}
+
+#[cfg(feature = "heapless")]
+#[test]
+fn test_programs_do_not_allocate() {
+    let (single, shade, fixed) = (&*SINGLE, &*SHADE, &*FIXED);
+    let mount = FixedMountConfig::default();
+    let count = allocations(|| {
+        for doy in [0, 1, 172, 355, 400] {
+            std::hint::black_box(daily_program::<256>(single, doy, 0.5).ok());
+            std::hint::black_box(shade_program::<8>(shade, doy).ok());
+        }
+        for doy in 0..=367 {
+            std::hint::black_box(inverter_program::<2>(fixed, &mount, doy, 50.0).ok());
+        }
+    });
+    assert_eq!(count, 0);
+}
+
+#[test]
+fn test_shade_events_match_schedule() {
+    for doy in [1, 172, 355] {
+        let events: Vec<ShadeEvent> = shade_events(&SHADE, doy).collect();
+        assert_eq!(events, shade_schedule(&SHADE, doy));
+    }
+}
+
+// ── Counter sanity ──
+
+#[test]
+fn test_counter_sees_allocations() {
+    assert!(allocations(|| vec![0u8; 64]) >= 1);
+}
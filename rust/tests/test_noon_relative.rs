@@ -0,0 +1,122 @@
+use std::sync::LazyLock;
+
+use solar_tracker::lookup_table::{generate_single_axis_table, lookup_single_axis};
+use solar_tracker::noon_relative::*;
+use solar_tracker::types::*;
+
+fn minute_of_day(minutes: i32) -> MinuteOfDay {
+    MinuteOfDay::new(minutes).unwrap()
+}
+
+macro_rules! assert_approx {
+    ($left:expr, $right:expr, $tol:expr) => {
+        let (l, r) = ($left as f64, $right as f64);
+        assert!(
+            (l - r).abs() <= $tol,
+            "assert_approx failed: left={}, right={}, diff={}, tol={}",
+            l, r, (l - r).abs(), $tol
+        );
+    };
+}
+
+fn config() -> LookupTableConfig {
+    LookupTableConfig { interval_minutes: 10, ..Default::default() }
+}
+
+static TABLE: LazyLock<NoonRelativeTable> =
+    LazyLock::new(|| generate_noon_relative_table(&config()));
+
+static UTC_TABLE: LazyLock<SingleAxisTable> =
+    LazyLock::new(|| generate_single_axis_table(&config()));
+
+// Largest rotation change between the same key on consecutive days.
+fn adjacent_day_spread<'a>(days: impl Iterator<Item = &'a [SingleAxisEntry]>) -> f64 {
+    let days: Vec<&[SingleAxisEntry]> = days.collect();
+    let mut worst = 0.0_f64;
+    for pair in days.windows(2) {
+        for a in pair[0] {
+            let b = pair[1].iter().find(|b| b.minutes == a.minutes);
+            if let (Some(ra), Some(rb)) = (a.rotation, b.and_then(|b| b.rotation)) {
+                worst = worst.max((ra - rb).abs());
+            }
+        }
+    }
+    worst
+}
+
+// ── Layout ──
+
+#[test]
+fn test_entries_centered_on_noon() {
+    assert_eq!(TABLE.config.table_key, TableKey::HourAngle);
+    for day in &TABLE.days {
+        assert!(day.entries.iter().all(|e| (-720..720).contains(&e.minutes)));
+        let noon = day.entries.iter().find(|e| e.minutes == 0).unwrap();
+        assert_approx!(noon.rotation.unwrap(), 0.0, 1e-9);
+        for offset in [-300, -120, 120, 300] {
+            assert!(day.entries.iter().any(|e| e.minutes == offset), "{offset}");
+        }
+    }
+}
+
+#[test]
+fn test_solar_noon_matches_utc_table() {
+    for (day, utc) in TABLE.days.iter().zip(&UTC_TABLE.days) {
+        assert_approx!(day.solar_noon_minutes, utc.solar_noon_minutes, 0.5);
+    }
+}
+
+// The point of the layout: days differ only by the declination
+#[test]
+fn test_adjacent_days_nearly_identical() {
+    let relative = adjacent_day_spread(TABLE.days.iter().map(|d| d.entries.as_slice()));
+    let utc = adjacent_day_spread(UTC_TABLE.days.iter().map(|d| d.entries.as_slice()));
+    assert!(relative < 0.5, "{relative}");
+    assert!(relative < utc / 2.0, "{relative} vs {utc}");
+}
+
+// ── Lookup ──
+
+#[test]
+fn test_lookup_by_offset() {
+    let entry = lookup_noon_relative(&TABLE, 172, -125).unwrap();
+    assert_eq!(entry.minutes, -125);
+    let before = lookup_noon_relative(&TABLE, 172, -130).unwrap().rotation.unwrap();
+    let after = lookup_noon_relative(&TABLE, 172, -120).unwrap().rotation.unwrap();
+    assert_approx!(entry.rotation.unwrap(), (before + after) / 2.0, 1e-9);
+    assert!(lookup_noon_relative(&TABLE, 172, -800).is_none());
+    assert!(lookup_noon_relative(&TABLE, 0, 0).is_none());
+    assert!(lookup_noon_relative(&TABLE, 366, 0).is_none());
+}
+
+#[test]
+fn test_lookup_at_utc_matches_utc_table() {
+    for doy in [15, 80, 172, 300] {
+        for minutes in [900, 1000, 1085, 1200, 1320] {
+            let relative = lookup_noon_relative_at_utc(&TABLE, doy, minute_of_day(minutes)).unwrap();
+            let utc = lookup_single_axis(&UTC_TABLE, doy, minute_of_day(minutes)).unwrap();
+            assert_eq!(relative.minutes, minutes);
+            match (relative.rotation, utc.rotation) {
+                // Different sample grids, and the offset rounds to the minute
+                (Some(a), Some(b)) => {
+                    assert_approx!(a, b, 0.5);
+                }
+                (a, b) => assert_eq!(a.is_some(), b.is_some(), "{doy} {minutes}"),
+            }
+        }
+    }
+}
+
+#[test]
+fn test_table_independent_of_longitude() {
+    let east = generate_noon_relative_table(&LookupTableConfig {
+        longitude: -80.0,
+        ..config()
+    });
+    for (a, b) in east.days.iter().zip(&TABLE.days) {
+        assert_eq!(a.entries, b.entries);
+        // Four minutes earlier in UTC per degree east
+        let shift = 4.0 * (-80.0 - config().longitude);
+        assert_approx!(b.solar_noon_minutes - a.solar_noon_minutes, shift, 1e-9);
+    }
+}
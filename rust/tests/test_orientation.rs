@@ -0,0 +1,159 @@
+use std::sync::LazyLock;
+
+use solar_tracker::orientation::*;
+use solar_tracker::simulation::simulate_yield;
+use solar_tracker::types::*;
+
+macro_rules! assert_approx {
+    ($left:expr, $right:expr, $tol:expr) => {
+        let (l, r) = ($left as f64, $right as f64);
+        assert!(
+            (l - r).abs() <= $tol,
+            "assert_approx failed: left={}, right={}, diff={}, tol={}",
+            l, r, (l - r).abs(), $tol
+        );
+    };
+}
+
+fn config() -> SimulationConfig {
+    SimulationConfig { step_minutes: 60, ..Default::default() }
+}
+
+static SPRINGFIELD: LazyLock<FixedOrientation> =
+    LazyLock::new(|| optimize_fixed_orientation(&config(), &OrientationSearch::default()));
+
+// ── Unweighted optimum ──
+
+#[test]
+fn test_springfield_faces_south_near_latitude() {
+    let best = *SPRINGFIELD;
+    assert_approx!(best.azimuth, 180.0, 2.0);
+    assert!((25.0..=45.0).contains(&best.tilt), "tilt {}", best.tilt);
+}
+
+#[test]
+fn test_score_matches_simulation() {
+    let best = *SPRINGFIELD;
+    let fixed = SimulationConfig {
+        strategy: TrackingStrategy::Fixed { tilt: best.tilt, azimuth: best.azimuth },
+        ..config()
+    };
+    assert_approx!(best.weighted_kwh_per_m2, simulate_yield(&fixed).annual_kwh_per_m2, 1e-6);
+}
+
+#[test]
+fn test_optimum_beats_neighbours() {
+    let best = *SPRINGFIELD;
+    for (tilt, azimuth) in [(best.tilt + 5.0, best.azimuth), (best.tilt, best.azimuth + 10.0)] {
+        let fixed = SimulationConfig {
+            strategy: TrackingStrategy::Fixed { tilt, azimuth },
+            ..config()
+        };
+        assert!(simulate_yield(&fixed).annual_kwh_per_m2 < best.weighted_kwh_per_m2);
+    }
+}
+
+#[test]
+fn test_southern_hemisphere_faces_north() {
+    let sydney = SimulationConfig { latitude: -33.9, longitude: 151.2, ..config() };
+    let best = optimize_fixed_orientation(&sydney, &OrientationSearch::default());
+    let from_north = best.azimuth.min(360.0 - best.azimuth);
+    assert!(from_north <= 2.0, "azimuth {}", best.azimuth);
+}
+
+// ── Constraints and weighting ──
+
+#[test]
+fn test_search_box_is_respected() {
+    let search = OrientationSearch {
+        max_tilt: 15.0,
+        min_azimuth: 90.0,
+        max_azimuth: 170.0,
+        ..Default::default()
+    };
+    let best = optimize_fixed_orientation(&config(), &search);
+    assert_approx!(best.tilt, 15.0, 1e-9);
+    assert_approx!(best.azimuth, 170.0, 1e-9);
+}
+
+#[test]
+fn test_foggy_mornings_turn_the_array_west() {
+    let mut weights = [1.0; 24];
+    weights[..11].fill(0.2);
+    let search = OrientationSearch { hourly_weights: Some(weights), ..Default::default() };
+    let best = optimize_fixed_orientation(&config(), &search);
+    assert!(best.azimuth > SPRINGFIELD.azimuth + 10.0, "azimuth {}", best.azimuth);
+    assert!(best.weighted_kwh_per_m2 < SPRINGFIELD.weighted_kwh_per_m2);
+}
+
+#[test]
+fn test_supplied_irradiance() {
+    // Pure isotropic diffuse favours lying flat
+    let overcast = SimulationConfig { sky_model: SkyDiffuseModel::Isotropic, ..config() };
+    let best = optimize_fixed_orientation_with(&overcast, &Default::default(), |_, _, _| {
+        Some(IrradianceComponents { ghi: 200.0, dni: 0.0, dhi: 200.0 })
+    });
+    assert_approx!(best.tilt, 0.0, 1e-9);
+}
+
+// ── Seasonal adjustment schedule ──
+
+static QUARTERLY: LazyLock<Vec<TiltAdjustment>> =
+    LazyLock::new(|| seasonal_tilt_schedule(&config(), AdjustmentFrequency::Quarterly));
+
+#[test]
+fn test_schedule_lengths_and_dates() {
+    for frequency in
+        [AdjustmentFrequency::Twice, AdjustmentFrequency::Quarterly, AdjustmentFrequency::Monthly]
+    {
+        let schedule = seasonal_tilt_schedule(&config(), frequency);
+        assert_eq!(schedule.len(), frequency.per_year());
+        assert!(schedule.windows(2).all(|w| w[0].day_of_year < w[1].day_of_year));
+    }
+    let twice = seasonal_tilt_schedule(&config(), AdjustmentFrequency::Twice);
+    assert_eq!((twice[0].month, twice[0].day), (3, 20));
+    assert_eq!((twice[1].month, twice[1].day), (9, 22));
+    assert_eq!(twice[1].day_of_year, 265);
+}
+
+#[test]
+fn test_twice_yearly_summer_flatter_than_winter() {
+    let twice = seasonal_tilt_schedule(&config(), AdjustmentFrequency::Twice);
+    let (summer, winter) = (twice[0], twice[1]);
+    assert!(summer.tilt + 20.0 < winter.tilt, "summer {} winter {}", summer.tilt, winter.tilt);
+    assert!(summer.tilt < SPRINGFIELD.tilt && SPRINGFIELD.tilt < winter.tilt);
+    assert_eq!(summer.azimuth, 180.0);
+}
+
+#[test]
+fn test_quarterly_peaks_in_winter() {
+    let tilts: Vec<f64> = QUARTERLY.iter().map(|a| a.tilt).collect();
+    // Feb, May, Aug, Nov settings: summer (May) flattest, winter (Nov) steepest
+    assert!(tilts[1] < tilts[0] && tilts[1] < tilts[2], "{tilts:?}");
+    assert!(tilts[3] > tilts[0] && tilts[3] > tilts[2], "{tilts:?}");
+}
+
+#[test]
+fn test_adjusting_beats_fixed() {
+    let total: f64 = QUARTERLY.iter().map(|a| a.kwh_per_m2).sum();
+    let annual = SPRINGFIELD.weighted_kwh_per_m2;
+    assert!(total > annual, "adjusted {total} fixed {annual}");
+    assert!(total < annual * 1.1);
+}
+
+#[test]
+fn test_southern_schedule_faces_north() {
+    let sydney = SimulationConfig { latitude: -33.9, longitude: 151.2, ..config() };
+    let twice = seasonal_tilt_schedule(&sydney, AdjustmentFrequency::Twice);
+    assert!(twice.iter().all(|a| a.azimuth == 0.0));
+    // From the March equinox the southern sun is low, so that setting is steeper
+    assert!(twice[0].tilt > twice[1].tilt + 20.0);
+}
+
+#[test]
+fn test_equatorial_summer_setting_stays_flat() {
+    let quito = SimulationConfig { latitude: 5.0, longitude: -78.5, ..config() };
+    let quarterly = seasonal_tilt_schedule(&quito, AdjustmentFrequency::Quarterly);
+    assert!(quarterly.iter().all(|a| (0.0..=90.0).contains(&a.tilt)));
+    assert_approx!(quarterly[1].tilt, 0.0, 1e-9);
+}
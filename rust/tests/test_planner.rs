@@ -0,0 +1,160 @@
+use std::sync::LazyLock;
+
+use solar_tracker::lookup_table::*;
+use solar_tracker::planner::*;
+use solar_tracker::types::*;
+
+static BASE: LazyLock<LookupTableConfig> = LazyLock::new(|| LookupTableConfig {
+    max_rotation: Some(60.0),
+    min_rotation: Some(-60.0),
+    ..Default::default()
+});
+
+static LAYOUTS: LazyLock<Vec<TableLayout>> =
+    LazyLock::new(|| sweep_table_layouts(&BASE, &PlannerConfig::default()));
+
+fn layout(interval_minutes: i32, format: StorageFormat) -> TableLayout {
+    *LAYOUTS
+        .iter()
+        .find(|l| l.interval_minutes == interval_minutes && l.format == format)
+        .unwrap()
+}
+
+// ── Storage formats ──
+
+#[test]
+fn test_quantize() {
+    assert_eq!(StorageFormat::Centidegrees16.quantize(12.3456), 12.35);
+    assert_eq!(StorageFormat::HalfDegree8.quantize(12.3456), 12.5);
+    assert_eq!(StorageFormat::HalfDegree8.quantize(-70.0), -64.0);
+    assert_eq!(StorageFormat::Float32.bytes(), 4);
+}
+
+// ── Cosine loss ──
+
+#[test]
+fn test_cosine_loss_zero_for_reference() {
+    let table = generate_single_axis_table(&LookupTableConfig {
+        interval_minutes: 1,
+        ..*BASE
+    });
+    assert!(cosine_loss(&table, &table, StorageFormat::Float32, 30) < 1e-12);
+}
+
+#[test]
+fn test_loss_grows_with_interval_and_coarser_format() {
+    assert!(
+        layout(60, StorageFormat::Float32).energy_loss
+            > layout(5, StorageFormat::Float32).energy_loss
+    );
+    assert!(
+        layout(5, StorageFormat::HalfDegree8).energy_loss
+            > layout(5, StorageFormat::Centidegrees16).energy_loss
+    );
+}
+
+#[test]
+fn test_storage_scales_with_format() {
+    let f32_bytes = layout(10, StorageFormat::Float32).storage_bytes;
+    assert_eq!(
+        layout(10, StorageFormat::Centidegrees16).storage_bytes * 2,
+        f32_bytes
+    );
+    assert_eq!(
+        layout(10, StorageFormat::HalfDegree8).storage_bytes * 4,
+        f32_bytes
+    );
+}
+
+// ── Recommendation ──
+
+#[test]
+fn test_recommendation_meets_constraints_and_is_smallest() {
+    let planner = PlannerConfig::default();
+    let best = recommend_table_layout(&BASE, &planner).unwrap();
+    assert!(best.energy_loss <= planner.max_energy_loss);
+    assert!(best.storage_bytes <= planner.flash_budget_bytes);
+    for l in LAYOUTS.iter() {
+        if l.energy_loss <= planner.max_energy_loss {
+            assert!(l.storage_bytes >= best.storage_bytes);
+        }
+    }
+}
+
+#[test]
+fn test_recommendation_none_when_budget_too_small() {
+    let planner = PlannerConfig {
+        flash_budget_bytes: 512,
+        ..Default::default()
+    };
+    assert_eq!(recommend_table_layout(&BASE, &planner), None);
+}
+
+// ── Monthly budget ──
+
+static HOURLY: LazyLock<SingleAxisTable> = LazyLock::new(|| {
+    generate_single_axis_table(&LookupTableConfig {
+        interval_minutes: 60,
+        ..*BASE
+    })
+});
+
+#[test]
+fn test_monthly_budget_adds_up_to_table() {
+    let months = monthly_storage_budget(&HOURLY, StorageFormat::Centidegrees16);
+    assert_eq!(months.iter().map(|m| m.month).collect::<Vec<_>>(), (1..=12).collect::<Vec<_>>());
+    assert_eq!(months.iter().map(|m| m.days).sum::<usize>(), HOURLY.days.len());
+    assert_eq!(months[1].days, 28);
+    let entries: usize = months.iter().map(|m| m.entries).sum();
+    assert_eq!(entries, HOURLY.metadata.total_entries);
+    for (m, stats) in months.iter().zip(&HOURLY.metadata.months) {
+        assert_eq!((m.month, m.days, m.entries), (stats.month, stats.days, stats.entries));
+    }
+    for m in &months {
+        assert_eq!(m.storage_bytes, m.entries * 2);
+    }
+    // Summer days are longer at Springfield, so June outweighs December
+    assert!(months[5].entries > months[11].entries);
+}
+
+#[test]
+fn test_monthly_budget_error_grows_with_interval_and_format() {
+    let minute = generate_single_axis_table(&LookupTableConfig {
+        interval_minutes: 1,
+        ..*BASE
+    });
+    for m in monthly_storage_budget(&minute, StorageFormat::Float32) {
+        assert!(m.max_interpolation_error < 1e-5, "{m:?}");
+        assert_eq!(m.uncovered_minutes, 0, "{m:?}");
+    }
+    let fine = monthly_storage_budget(&HOURLY, StorageFormat::Centidegrees16);
+    let coarse = monthly_storage_budget(&HOURLY, StorageFormat::HalfDegree8);
+    for (f, c) in fine.iter().zip(&coarse) {
+        assert!(f.max_interpolation_error > 0.1, "{f:?}");
+        // Rounding to half degrees moves a value by at most 0.25°, either way
+        assert!(c.max_interpolation_error >= f.max_interpolation_error - 0.25, "{c:?}");
+        assert!(c.max_interpolation_error <= f.max_interpolation_error + 0.26, "{c:?}");
+        assert_eq!(c.storage_bytes * 2, f.storage_bytes);
+    }
+}
+
+#[test]
+fn test_monthly_budget_counts_uncovered_edges() {
+    // An hourly table has no rotation between sunrise and its first daylight
+    // entry, nor after its last; those minutes are counted, not skipped
+    for m in monthly_storage_budget(&HOURLY, StorageFormat::Float32) {
+        assert!(m.uncovered_minutes > 0, "{m:?}");
+        assert!(m.uncovered_minutes <= m.days * 2 * 60, "{m:?}");
+    }
+}
+
+#[test]
+fn test_monthly_budget_without_metadata_months() {
+    // Tables serialized before per-month statistics existed
+    let mut old = HOURLY.clone();
+    old.metadata.months.clear();
+    assert_eq!(
+        monthly_storage_budget(&old, StorageFormat::Float32),
+        monthly_storage_budget(&HOURLY, StorageFormat::Float32)
+    );
+}
@@ -0,0 +1,78 @@
+use solar_tracker::portable_math;
+
+fn close(a: f64, b: f64) -> bool {
+    (a - b).abs() <= 4.0 * f64::EPSILON * b.abs().max(1.0)
+}
+
+fn grid() -> impl Iterator<Item = f64> {
+    (-4000..=4000).map(|i| i as f64 * 0.0123)
+}
+
+// ── Agreement with libm ──
+
+#[test]
+fn test_sin_cos_tan_match_std() {
+    for x in grid() {
+        let (s, c) = portable_math::sin_cos(x);
+        assert!(close(s, x.sin()), "sin({x}) = {s} vs {}", x.sin());
+        assert!(close(c, x.cos()), "cos({x}) = {c} vs {}", x.cos());
+        if x.cos().abs() > 1e-3 {
+            let t = portable_math::tan(x);
+            assert!((t - x.tan()).abs() <= 1e-12 * x.tan().abs().max(1.0), "tan({x})");
+        }
+    }
+}
+
+#[test]
+fn test_atan_atan2_match_std() {
+    for x in grid() {
+        assert!(close(portable_math::atan(x), x.atan()), "atan({x})");
+        for y in [-3.0, -0.5, 0.0, 0.25, 7.0] {
+            let a = portable_math::atan2(y, x);
+            assert!(close(a, y.atan2(x)), "atan2({y}, {x}) = {a} vs {}", y.atan2(x));
+        }
+    }
+}
+
+#[test]
+fn test_asin_acos_match_std() {
+    for i in -1000..=1000 {
+        let x = i as f64 / 1000.0;
+        assert!((portable_math::asin(x) - x.asin()).abs() <= 1e-15, "asin({x})");
+        assert!((portable_math::acos(x) - x.acos()).abs() <= 1e-15, "acos({x})");
+    }
+}
+
+// ── Special values ──
+
+#[test]
+fn test_special_values() {
+    use std::f64::consts::{FRAC_PI_2, PI};
+    assert!(portable_math::sin(f64::INFINITY).is_nan());
+    assert!(portable_math::acos(1.5).is_nan());
+    assert_eq!(portable_math::atan2(0.0, -1.0), PI);
+    assert_eq!(portable_math::atan2(-0.0, -1.0), -PI);
+    assert_eq!(portable_math::atan2(1.0, 0.0), FRAC_PI_2);
+    assert_eq!(portable_math::atan(f64::NEG_INFINITY), -FRAC_PI_2);
+    assert_eq!(portable_math::acos(1.0), 0.0);
+    assert_eq!(portable_math::sin(0.0), 0.0);
+    assert_eq!(portable_math::cos(0.0), 1.0);
+    assert_eq!(portable_math::hypot(3.0, 4.0), 5.0);
+}
+
+// ── Determinism ──
+
+// Bit patterns recorded from this implementation; any target that builds
+// with only IEEE arithmetic must reproduce them exactly.
+#[test]
+fn test_reference_bits() {
+    let cases: [(f64, u64); 4] = [
+        (portable_math::sin(1.0), 0x3FEA_ED54_8F09_0CEE),
+        (portable_math::cos(2.5), 0xBFE9_A2F7_EF85_8B7D),
+        (portable_math::atan2(0.3, -0.7), 0x4005_E4C3_6CA0_118A),
+        (portable_math::acos(0.123), 0x3FF7_28E5_0971_DD76),
+    ];
+    for (value, bits) in cases {
+        assert_eq!(value.to_bits(), bits, "{value}: {:#018X}", value.to_bits());
+    }
+}
@@ -0,0 +1,109 @@
+#![cfg(feature = "heapless")]
+
+use std::sync::LazyLock;
+
+use solar_tracker::fixed_mount::{generate_fixed_table, inverter_events};
+use solar_tracker::lookup_table::generate_single_axis_table;
+use solar_tracker::program::*;
+use solar_tracker::shading::{generate_shade_table, shade_schedule};
+use solar_tracker::types::*;
+
+static TABLE: LazyLock<SingleAxisTable> =
+    LazyLock::new(|| generate_single_axis_table(&LookupTableConfig::default()));
+
+// ── FixedSchedule ──
+
+#[test]
+fn test_push_until_full() {
+    let mut schedule: FixedSchedule<i32, 3> = FixedSchedule::new();
+    assert!(schedule.is_empty());
+    assert_eq!(schedule.capacity(), 3);
+    for i in 0..3 {
+        assert_eq!(schedule.push(i), Ok(()));
+    }
+    assert_eq!(schedule.push(3), Err(3));
+    assert_eq!(schedule.len(), 3);
+    assert_eq!(schedule.iter().copied().collect::<Vec<_>>(), [0, 1, 2]);
+    assert_eq!((schedule.first(), schedule.last()), (Some(&0), Some(&2)));
+    assert_eq!(schedule.get(3), None);
+    schedule.clear();
+    assert!(schedule.is_empty() && schedule.last().is_none());
+}
+
+#[test]
+fn test_try_from_iter_reports_required() {
+    let err = FixedSchedule::<i32, 4>::try_from_iter(0..10).unwrap_err();
+    assert_eq!(err, ScheduleFull { capacity: 4, required: 10 });
+    assert_eq!(err.to_string(), "schedule needs 10 slots but holds 4");
+    assert_eq!(FixedSchedule::<i32, 4>::try_from_iter(0..4).unwrap().len(), 4);
+}
+
+#[test]
+fn test_lives_in_a_static() {
+    static EMPTY: DailyProgram<64> = DailyProgram::new();
+    assert!(EMPTY.is_empty());
+    assert_eq!(EMPTY.capacity(), 64);
+}
+
+// ── Daily program ──
+
+#[test]
+fn test_daily_program_follows_table() {
+    let program = daily_program::<256>(&TABLE, 172, 0.0).unwrap();
+    let daylight = TABLE.days[171].entries.iter().filter(|e| e.rotation.is_some());
+    assert_eq!(program.len(), daylight.clone().count());
+    for (step, entry) in program.iter().zip(daylight) {
+        assert_eq!(step.minutes, entry.minutes);
+        assert_eq!(step.rotation, entry.rotation.unwrap() as f32);
+    }
+}
+
+#[test]
+fn test_deadband_thins_program() {
+    let full = daily_program::<256>(&TABLE, 172, 0.0).unwrap();
+    let thinned = daily_program::<256>(&TABLE, 172, 2.0).unwrap();
+    assert!(thinned.len() < full.len() / 2, "{} vs {}", thinned.len(), full.len());
+    assert_eq!(thinned.first(), full.first());
+    let steps: Vec<_> = thinned.iter().collect();
+    for pair in steps.windows(2) {
+        assert!((pair[1].rotation - pair[0].rotation).abs() > 2.0);
+    }
+}
+
+#[test]
+fn test_daily_program_capacity_and_unknown_day() {
+    let err = daily_program::<8>(&TABLE, 172, 0.0).unwrap_err();
+    assert_eq!(err.capacity, 8);
+    assert!(err.required > 100);
+    assert!(daily_program::<8>(&TABLE, 0, 0.0).unwrap().is_empty());
+    assert!(daily_program::<8>(&TABLE, 400, 0.0).unwrap().is_empty());
+}
+
+// ── Shade program ──
+
+#[test]
+fn test_shade_program_matches_schedule() {
+    let shade = generate_shade_table(&LookupTableConfig::default(), &ShadeConfig::default());
+    for doy in [1, 172, 355] {
+        let program = shade_program::<8>(&shade, doy).unwrap();
+        assert_eq!(program.iter().copied().collect::<Vec<_>>(), shade_schedule(&shade, doy));
+    }
+}
+
+// ── Inverter program ──
+
+#[test]
+fn test_inverter_program_matches_events() {
+    let mount = FixedMountConfig::default();
+    let table = generate_fixed_table(&LookupTableConfig::default(), &mount);
+    for doy in [1, 172, 355] {
+        let program = inverter_program::<2>(&table, &mount, doy, 50.0).unwrap();
+        let events: Vec<_> = inverter_events(&table, &mount, doy, 50.0).collect();
+        assert_eq!(program.iter().copied().collect::<Vec<_>>(), events);
+        assert_eq!(program.len(), 2);
+    }
+    let none = inverter_program::<2>(&table, &mount, 172, 5000.0).unwrap();
+    assert!(none.is_empty());
+    let full = inverter_program::<1>(&table, &mount, 172, 50.0);
+    assert_eq!(full.unwrap_err(), ScheduleFull { capacity: 1, required: 2 });
+}
@@ -0,0 +1,94 @@
+use std::sync::LazyLock;
+
+use solar_tracker::diagnostics::diagnostic_bundle;
+use solar_tracker::difference::difference_table;
+use solar_tracker::event_log::EventLog;
+use solar_tracker::lookup_table::*;
+use solar_tracker::provenance::*;
+use solar_tracker::simulation::simulate_yield;
+use solar_tracker::types::*;
+
+fn project() -> ProjectMetadata {
+    ProjectMetadata {
+        site_name: Some("Springfield North".into()),
+        project_id: Some("SPR-0042".into()),
+        operator: None,
+        license: Some("CC-BY-4.0".into()),
+    }
+}
+
+static STAMPED: LazyLock<SingleAxisTable> = LazyLock::new(|| {
+    let config = LookupTableConfig { interval_minutes: 60, ..Default::default() };
+    stamp(generate_single_axis_table(&config), &project())
+});
+
+// ── Stamping ──
+
+#[test]
+fn test_generated_artifacts_start_unstamped() {
+    let config = LookupTableConfig { interval_minutes: 60, ..Default::default() };
+    assert!(generate_single_axis_table(&config).project().is_none());
+    let report = simulate_yield(&SimulationConfig { step_minutes: 120, ..Default::default() });
+    assert!(report.project().is_none());
+}
+
+#[test]
+fn test_stamp_and_clear() {
+    assert_eq!(STAMPED.project(), Some(&project()));
+    let mut table = STAMPED.clone();
+    table.set_project(None);
+    assert!(table.project().is_none());
+    let report = simulate_yield(&SimulationConfig { step_minutes: 120, ..Default::default() });
+    assert_eq!(stamp(report, &project()).project, Some(project()));
+}
+
+// ── Carried into derived artifacts ──
+
+#[test]
+fn test_derived_tables_inherit_stamp() {
+    assert_eq!(shift_single_axis_table(&STAMPED, -87.6).project(), Some(&project()));
+    assert_eq!(step_table(&STAMPED, |e| e.rotation).project(), Some(&project()));
+    assert_eq!(difference_table(&STAMPED, &STAMPED).project(), Some(&project()));
+}
+
+#[test]
+fn test_diagnostic_bundle_inherits_stamp() {
+    let bundle = diagnostic_bundle(
+        &*STAMPED,
+        &EventLog::<4>::new(),
+        ControllerMode::Tracking,
+        CalibrationOffsets::default(),
+        None,
+    );
+    assert_eq!(bundle.project(), Some(&project()));
+}
+
+// ── Report fields ──
+
+#[test]
+fn test_project_fields_skip_unset() {
+    assert_eq!(
+        project_fields(&project()),
+        [("Site", "Springfield North"), ("Project", "SPR-0042"), ("License", "CC-BY-4.0")]
+    );
+    assert!(project_fields(&ProjectMetadata::default()).is_empty());
+}
+
+// ── Serialization ──
+
+#[cfg(feature = "json")]
+#[test]
+fn test_stamp_survives_json() {
+    let json = serde_json::to_string(&*STAMPED).unwrap();
+    let back: SingleAxisTable = serde_json::from_str(&json).unwrap();
+    assert_eq!(back.project(), Some(&project()));
+}
+
+#[cfg(feature = "json")]
+#[test]
+fn test_unstamped_json_still_loads() {
+    let json = r#"{"generated_at": "2026-01-01T00:00:00Z",
+                   "total_entries": 0, "storage_estimate_kb": 0.0}"#;
+    let metadata: TableMetadata = serde_json::from_str(json).unwrap();
+    assert_eq!(metadata.project, None);
+}
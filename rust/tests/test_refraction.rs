@@ -0,0 +1,106 @@
+use chrono::{TimeZone, Utc};
+
+use solar_tracker::angles::solar_position;
+use solar_tracker::lookup_table::SUNRISE_ALTITUDE;
+use solar_tracker::refraction::*;
+use solar_tracker::types::Atmosphere;
+
+macro_rules! assert_approx {
+    ($left:expr, $right:expr, $tol:expr) => {
+        let (l, r) = ($left as f64, $right as f64);
+        assert!(
+            (l - r).abs() <= $tol,
+            "assert_approx failed: left={}, right={}, diff={}, tol={}",
+            l, r, (l - r).abs(), $tol
+        );
+    };
+}
+
+// ── Atmosphere ──
+
+#[test]
+fn test_default_atmosphere_is_reference() {
+    let standard = Atmosphere::default();
+    assert_eq!(standard.pressure_hpa, 1010.0);
+    assert_eq!(standard.temperature_c, 10.0);
+    assert_approx!(standard.density_factor(), 1.0, 1e-12);
+}
+
+#[test]
+fn test_pressure_at_elevation() {
+    assert_approx!(Atmosphere::at_elevation(0.0, 15.0).pressure_hpa, 1013.25, 1e-9);
+    // Standard atmosphere: about 899 hPa at 1000 m, 701 hPa at 3000 m
+    assert_approx!(Atmosphere::at_elevation(1000.0, 15.0).pressure_hpa, 898.7, 0.5);
+    assert_approx!(Atmosphere::at_elevation(3000.0, 15.0).pressure_hpa, 701.1, 0.5);
+}
+
+// ── Refraction ──
+
+#[test]
+fn test_refraction_reference_values() {
+    let standard = Atmosphere::default();
+    // Sæmundsson: about 29' at the true horizon, 5.4' at 10°, 1' at 45°
+    assert_approx!(atmospheric_refraction(0.0, &standard) * 60.0, 29.0, 0.1);
+    assert_approx!(atmospheric_refraction(10.0, &standard) * 60.0, 5.41, 0.02);
+    assert_approx!(atmospheric_refraction(45.0, &standard) * 60.0, 1.01, 0.01);
+    assert!(atmospheric_refraction(90.0, &standard) < 1e-4);
+}
+
+#[test]
+fn test_refraction_decreases_with_altitude() {
+    let standard = Atmosphere::default();
+    let mut previous = f64::INFINITY;
+    for tenth in -8..=900 {
+        let r = atmospheric_refraction(tenth as f64 / 10.0, &standard);
+        assert!((0.0..=previous).contains(&r), "{tenth}");
+        previous = r;
+    }
+}
+
+#[test]
+fn test_no_refraction_below_horizon_cutoff() {
+    let standard = Atmosphere::default();
+    assert_eq!(atmospheric_refraction(-1.0, &standard), 0.0);
+    assert_eq!(atmospheric_refraction(-30.0, &standard), 0.0);
+    assert_eq!(apparent_altitude(-30.0, &standard), -30.0);
+}
+
+// Thin mountain air bends less; cold dense air bends more
+#[test]
+fn test_refraction_scales_with_density() {
+    let standard = Atmosphere::default();
+    let mountain = Atmosphere::at_elevation(3000.0, 0.0);
+    let arctic = Atmosphere { pressure_hpa: 1010.0, temperature_c: -30.0 };
+    let base = atmospheric_refraction(2.0, &standard);
+    assert_approx!(atmospheric_refraction(2.0, &mountain), base * mountain.density_factor(), 1e-12);
+    assert!(mountain.density_factor() < 0.75);
+    assert_approx!(atmospheric_refraction(2.0, &arctic), base * 283.0 / 243.0, 1e-12);
+}
+
+// ── Horizon ──
+
+#[test]
+fn test_sunrise_altitude() {
+    assert_approx!(sunrise_altitude(&Atmosphere::default()), SUNRISE_ALTITUDE, 1e-3);
+    let mountain = Atmosphere::at_elevation(4000.0, -5.0);
+    let raised = sunrise_altitude(&mountain);
+    assert!(raised > SUNRISE_ALTITUDE && raised < -0.6, "{raised}");
+}
+
+// ── Position ──
+
+#[test]
+fn test_refracted_position() {
+    let standard = Atmosphere::default();
+    // Springfield shortly after sunrise on the summer solstice
+    let t = Utc.with_ymd_and_hms(2026, 6, 21, 10, 45, 0).unwrap();
+    let geometric = solar_position(39.8, -89.6, &t);
+    let seen = solar_position_refracted(39.8, -89.6, &t, &standard);
+    assert!(geometric.altitude > 0.0 && geometric.altitude < 5.0, "{}", geometric.altitude);
+    assert!(seen.altitude - geometric.altitude > 0.1);
+    assert_approx!(seen.zenith + seen.altitude, 90.0, 1e-12);
+    assert_eq!(seen.azimuth, geometric.azimuth);
+    assert_eq!(seen.hour_angle, geometric.hour_angle);
+    let thin = Atmosphere::at_elevation(3000.0, 0.0);
+    assert!(solar_position_refracted(39.8, -89.6, &t, &thin).altitude < seen.altitude);
+}
@@ -0,0 +1,262 @@
+use std::sync::LazyLock;
+
+use solar_tracker::commissioning::*;
+use solar_tracker::lookup_table::*;
+use solar_tracker::provenance::stamp;
+use solar_tracker::report::*;
+use solar_tracker::types::*;
+
+static TABLE: LazyLock<SingleAxisTable> = LazyLock::new(|| {
+    let config = LookupTableConfig { interval_minutes: 60, ..Default::default() };
+    generate_single_axis_table(&config)
+});
+
+fn readings(east: f64, flat: f64, west: f64) -> Vec<(CommissioningTarget, f64)> {
+    vec![
+        (CommissioningTarget::EastLimit, east),
+        (CommissioningTarget::Flat, flat),
+        (CommissioningTarget::WestLimit, west),
+    ]
+}
+
+fn data_rows(csv: &str) -> Vec<&str> {
+    csv.lines().filter(|l| !l.starts_with('#')).skip(1).collect()
+}
+
+// ── Numbers ──
+
+#[test]
+fn test_number_decimal_separator() {
+    assert_eq!(format_number(-12.345, 2, &ReportLocale::US), "-12.35");
+    assert_eq!(format_number(-12.345, 2, &ReportLocale::EUROPEAN), "-12,35");
+    assert_eq!(format_number(1234.5, 1, &ReportLocale::EUROPEAN), "1234,5");
+}
+
+#[test]
+fn test_number_without_decimals_has_no_separator() {
+    assert_eq!(format_number(45.4, 0, &ReportLocale::EUROPEAN), "45");
+}
+
+// ── Times ──
+
+#[test]
+fn test_time_24_hour() {
+    let locale = ReportLocale::EUROPEAN;
+    assert_eq!(format_time(0, &locale), "00:00");
+    assert_eq!(format_time(7 * 60 + 5, &locale), "07:05");
+    assert_eq!(format_time(23 * 60 + 59, &locale), "23:59");
+}
+
+#[test]
+fn test_time_12_hour() {
+    let locale = ReportLocale::US;
+    assert_eq!(format_time(0, &locale), "12:00 AM");
+    assert_eq!(format_time(7 * 60 + 5, &locale), "7:05 AM");
+    assert_eq!(format_time(12 * 60, &locale), "12:00 PM");
+    assert_eq!(format_time(13 * 60 + 30, &locale), "1:30 PM");
+}
+
+#[test]
+fn test_time_wraps_into_day() {
+    let locale = ReportLocale::EUROPEAN;
+    assert_eq!(format_time(1440 + 90, &locale), "01:30");
+    assert_eq!(format_time(-30, &locale), "23:30");
+}
+
+#[test]
+fn test_mixed_locale() {
+    let locale = ReportLocale { clock: ClockFormat::H12, ..ReportLocale::EUROPEAN };
+    assert_eq!(format_time(18 * 60, &locale), "6:00 PM");
+    assert_eq!(format_number(0.5, 1, &locale), "0,5");
+}
+
+// ── Schedule CSV ──
+
+#[test]
+fn test_schedule_csv_rows_match_table() {
+    let csv = schedule_csv(&TABLE, 172, &ReportLocale::US);
+    assert_eq!(csv.lines().next(), Some("time,rotation,clamped"));
+    let daylight = TABLE.days[171].entries.iter().filter(|e| e.rotation.is_some()).count();
+    let rows = data_rows(&csv);
+    assert_eq!(rows.len(), daylight);
+    assert!(rows.iter().all(|r| r.split(',').count() == 3));
+    assert!(rows.iter().any(|r| r.starts_with("12:00 PM,")));
+}
+
+#[test]
+fn test_schedule_csv_european_fields() {
+    let csv = schedule_csv(&TABLE, 172, &ReportLocale::EUROPEAN);
+    assert_eq!(csv.lines().next(), Some("time;rotation;clamped"));
+    for row in data_rows(&csv) {
+        let fields: Vec<&str> = row.split(';').collect();
+        assert_eq!(fields.len(), 3);
+        assert!(!fields[1].contains('.'));
+        assert!(fields[1].contains(','));
+    }
+    assert!(csv.contains("\n12:00;"));
+}
+
+#[test]
+fn test_schedule_csv_missing_day_is_header_only() {
+    let csv = schedule_csv(&TABLE, 400, &ReportLocale::US);
+    assert_eq!(csv, "time,rotation,clamped\n");
+}
+
+#[test]
+fn test_schedule_csv_project_header() {
+    let project = ProjectMetadata {
+        site_name: Some("Lyon Est".into()),
+        project_id: Some("LY-7".into()),
+        ..Default::default()
+    };
+    let table = stamp(TABLE.clone(), &project);
+    let csv = schedule_csv(&table, 172, &ReportLocale::EUROPEAN);
+    assert!(csv.starts_with("# Site: Lyon Est\n# Project: LY-7\ntime;"));
+}
+
+#[test]
+fn test_schedule_csv_reads_table_time_base() {
+    let config = LookupTableConfig {
+        interval_minutes: 60,
+        time_base: TimeBase::FixedOffset { offset_minutes: -360 },
+        ..Default::default()
+    };
+    let csv = schedule_csv(&generate_single_axis_table(&config), 172, &ReportLocale::EUROPEAN);
+    let utc = schedule_csv(&TABLE, 172, &ReportLocale::EUROPEAN);
+    // 18:00 UTC is noon on a clock six hours behind
+    assert!(utc.contains("\n18:00;"));
+    let noon_row = utc.lines().find(|r| r.starts_with("18:00;")).unwrap();
+    assert!(csv.contains(&format!("\n12:00;{}", &noon_row[6..])), "{csv}");
+    assert!(!csv.contains("\n18:00;"));
+}
+
+#[test]
+fn test_schedule_csv_hour_angle_keys_in_clock_time() {
+    let config = LookupTableConfig {
+        interval_minutes: 60,
+        table_key: TableKey::HourAngle,
+        ..Default::default()
+    };
+    let table = generate_single_axis_table(&config);
+    let csv = schedule_csv(&table, 45, &ReportLocale::EUROPEAN);
+    let times: Vec<&str> = data_rows(&csv).iter().map(|r| &r[..5]).collect();
+    // In mid-February the sun is 14 minutes slow, so at -89.6° the solar
+    // noon key reads a little after 18:10 UTC rather than on the hour
+    assert!(times.iter().any(|t| t.starts_with("18:1")), "{times:?}");
+    assert!(!times.contains(&"12:00"), "{times:?}");
+}
+
+#[test]
+fn test_project_header_strips_line_breaks() {
+    let project = ProjectMetadata {
+        site_name: Some("Springfield\n12:00,99.00,false\r\nEast".into()),
+        ..Default::default()
+    };
+    let table = stamp(TABLE.clone(), &project);
+    let csv = schedule_csv(&table, 172, &ReportLocale::US);
+    assert!(csv.starts_with("# Site: Springfield 12:00,99.00,false  East\ntime,"), "{csv}");
+    let daylight = TABLE.days[171].entries.iter().filter(|e| e.rotation.is_some()).count();
+    assert_eq!(data_rows(&csv).len(), daylight);
+}
+
+// ── Commissioning CSV ──
+
+#[test]
+fn test_commissioning_csv_pass() {
+    let steps = commissioning_sequence(-45.0, 45.0);
+    let readings = readings(-44.6, 0.2, 45.1);
+    let report = verify_commissioning(&steps, &readings, 1.0);
+    let csv = commissioning_csv(&steps, &readings, &report, &ReportLocale::EUROPEAN, None);
+    let lines: Vec<&str> = csv.lines().collect();
+    assert_eq!(
+        lines,
+        vec![
+            "target;expected;measured;deviation;status",
+            "east_limit;-45,00;-44,60;0,40;ok",
+            "flat;0,00;0,20;0,20;ok",
+            "west_limit;45,00;45,10;0,10;ok",
+            "# Result: PASS",
+        ]
+    );
+}
+
+#[test]
+fn test_commissioning_csv_marks_failing_steps() {
+    let steps = commissioning_sequence(-45.0, 45.0);
+    let readings = vec![
+        (CommissioningTarget::EastLimit, -40.0),
+        (CommissioningTarget::WestLimit, 45.0),
+    ];
+    let report = verify_commissioning(&steps, &readings, 1.0);
+    let csv = commissioning_csv(&steps, &readings, &report, &ReportLocale::US, None);
+    let rows = data_rows(&csv);
+    assert_eq!(rows[0], "east_limit,-45.00,-40.00,5.00,out_of_tolerance");
+    assert_eq!(rows[1], "flat,0.00,,,missing");
+    assert!(csv.ends_with("# Result: FAIL\n"));
+}
+
+#[test]
+fn test_commissioning_csv_lists_structural_issues() {
+    let steps = commissioning_sequence(-45.0, 45.0);
+    let readings = readings(45.0, 0.0, -45.0);
+    let report = verify_commissioning(&steps, &readings, 1.0);
+    let csv = commissioning_csv(&steps, &readings, &report, &ReportLocale::US, None);
+    assert!(csv.contains("# Issue: rotation sign inverted\n"));
+    assert!(data_rows(&csv).iter().all(|r| r.ends_with(",unchecked")));
+    assert!(csv.ends_with("# Result: FAIL\n"));
+}
+
+// ── Sweep CSV ──
+
+#[test]
+fn test_sweep_csv_leaves_unused_parameters_empty() {
+    let rows = [
+        SweepRow {
+            strategy: SweepStrategy::Backtracking,
+            tilt: Some(0.0),
+            azimuth: Some(180.0),
+            gcr: Some(0.4),
+            annual_kwh_per_m2: 2345.67,
+        },
+        SweepRow {
+            strategy: SweepStrategy::DualAxis,
+            tilt: None,
+            azimuth: None,
+            gcr: None,
+            annual_kwh_per_m2: 2789.01,
+        },
+    ];
+    let csv = sweep_csv(&rows, &ReportLocale::EUROPEAN);
+    let lines: Vec<&str> = csv.lines().collect();
+    assert_eq!(
+        lines,
+        vec![
+            "strategy;tilt;azimuth;gcr;annual_kwh_per_m2",
+            "backtracking;0,00;180,00;0,40;2345,7",
+            "dual_axis;;;;2789,0",
+        ]
+    );
+}
+
+// ── Monthly budget CSV ──
+
+#[test]
+fn test_monthly_budget_csv() {
+    let rows = [MonthBudget {
+        month: 1,
+        days: 31,
+        entries: 310,
+        storage_bytes: 620,
+        max_interpolation_error: 1.23456,
+        uncovered_minutes: 7,
+    }];
+    let csv = monthly_budget_csv(&rows, &ReportLocale::EUROPEAN);
+    let lines: Vec<&str> = csv.lines().collect();
+    assert_eq!(
+        lines,
+        vec![
+            "month;days;entries;storage_bytes;max_interpolation_error;uncovered_minutes",
+            "1;31;310;620;1,235;7"
+        ]
+    );
+}
@@ -0,0 +1,149 @@
+use chrono::{TimeZone, Utc};
+
+use solar_tracker::angles::{backtrack, solar_position, tilted_axis_rotation};
+use solar_tracker::row_shading::*;
+use solar_tracker::simulation::simulate_yield;
+use solar_tracker::types::*;
+
+macro_rules! assert_approx {
+    ($left:expr, $right:expr, $tol:expr) => {
+        let (l, r) = ($left as f64, $right as f64);
+        assert!(
+            (l - r).abs() <= $tol,
+            "assert_approx failed: left={}, right={}, diff={}, tol={}",
+            l, r, (l - r).abs(), $tol
+        );
+    };
+}
+
+// Brute-force reference: cast rays toward the sun from points across one
+// row and count those that hit the neighbouring row on the sun side.
+fn ray_cast_fraction(gcr: f64, rotation: f64, projected_zenith: f64) -> f64 {
+    let (pitch, width) = (1.0, gcr);
+    let (sin_r, cos_r) = rotation.to_radians().sin_cos();
+    let (sin_p, cos_p) = projected_zenith.to_radians().sin_cos();
+    let neighbour = pitch * projected_zenith.signum();
+    let n = 2000;
+    let blocked = (0..n)
+        .filter(|i| {
+            let t = (*i as f64 + 0.5) / n as f64 * width - width / 2.0;
+            let (x, z) = (t * cos_r, -t * sin_r);
+            // Solve x + s sin_p = neighbour + u cos_r, z + s cos_p = -u sin_r
+            let det = sin_p * sin_r + cos_p * cos_r;
+            if det.abs() < 1e-12 {
+                return false;
+            }
+            let (dx, dz) = (neighbour - x, -z);
+            let s = (dx * sin_r + dz * cos_r) / det;
+            let u = (sin_p * dz - cos_p * dx) / det;
+            s > 0.0 && u.abs() <= width / 2.0
+        })
+        .count();
+    blocked as f64 / n as f64
+}
+
+// ── Geometry ──
+
+#[test]
+fn test_matches_ray_casting() {
+    for gcr in [0.3, 0.5, 0.7f64] {
+        for projected in [-80.0f64, -65.0, -40.0, 10.0, 50.0, 75.0, 85.0] {
+            for rotation in [-60.0f64, -30.0, 0.0, 30.0, 60.0] {
+                // Only the sunlit face matters
+                if (rotation - projected).abs() >= 90.0 {
+                    continue;
+                }
+                let expected = ray_cast_fraction(gcr, rotation, projected);
+                let got = shaded_fraction(gcr, rotation, projected);
+                assert_approx!(got, expected, 2e-3);
+            }
+        }
+    }
+}
+
+#[test]
+fn test_true_tracking_shading_onset() {
+    // Shading starts once cos ψ drops below the GCR
+    assert_approx!(shaded_fraction(0.5, 60.0, 60.0), 0.0, 1e-9);
+    assert_approx!(shaded_fraction(0.5, 70.0, 70.0), 1.0 - 70f64.to_radians().cos() / 0.5, 1e-9);
+    assert_eq!(shaded_fraction(0.5, 0.0, 0.0), 0.0);
+    assert_eq!(shaded_fraction(0.5, -20.0, -20.0), 0.0);
+}
+
+#[test]
+fn test_backtracking_removes_shade() {
+    for projected in [-85.0, -70.0, 65.0, 80.0] {
+        let rotation = backtrack(projected, 0.5);
+        assert_approx!(shaded_fraction(0.5, rotation, projected), 0.0, 1e-9);
+        assert!(shaded_fraction(0.5, projected, projected) > 0.0);
+    }
+}
+
+#[test]
+fn test_sun_behind_panel_is_unshaded() {
+    assert_eq!(shaded_fraction(0.5, 60.0, -45.0), 0.0);
+    assert_eq!(shaded_fraction(0.0, 60.0, 80.0), 0.0);
+}
+
+// ── Sun position ──
+
+#[test]
+fn test_row_shaded_fraction_early_morning() {
+    let t = Utc.with_ymd_and_hms(2026, 6, 21, 12, 15, 0).unwrap();
+    let pos = solar_position(39.8, -89.6, &t);
+    let rotation = tilted_axis_rotation(&pos, 0.0, 180.0);
+    let shaded = row_shaded_fraction(5.0, 2.0, rotation, 180.0, &pos);
+    assert_approx!(shaded, shaded_fraction(0.4, rotation, rotation), 1e-12);
+    assert!(shaded > 0.0, "{shaded}");
+    // Wider spacing lets the sun through
+    assert_eq!(row_shaded_fraction(20.0, 2.0, rotation, 180.0, &pos), 0.0);
+}
+
+#[test]
+fn test_row_shaded_fraction_sun_down() {
+    let t = Utc.with_ymd_and_hms(2026, 6, 21, 6, 0, 0).unwrap();
+    let pos = solar_position(39.8, -89.6, &t);
+    assert_eq!(row_shaded_fraction(5.0, 2.0, -60.0, 180.0, &pos), 0.0);
+}
+
+// ── Simulation ──
+
+fn tracker(gcr: Option<f64>, row_gcr: Option<f64>) -> f64 {
+    let config = SimulationConfig {
+        step_minutes: 30,
+        strategy: TrackingStrategy::SingleAxis {
+            axis_tilt: 0.0,
+            axis_azimuth: 180.0,
+            max_rotation: None,
+            gcr,
+        },
+        row_gcr,
+        ..Default::default()
+    };
+    simulate_yield(&config).annual_kwh_per_m2
+}
+
+#[test]
+fn test_simulation_charges_shading_without_backtracking() {
+    let unshaded = tracker(None, None);
+    let shaded = tracker(None, Some(0.5));
+    assert!(shaded < unshaded * 0.99, "{shaded} vs {unshaded}");
+    assert!(tracker(None, Some(0.7)) < shaded);
+}
+
+#[test]
+fn test_simulation_backtracking_is_unshaded() {
+    let backtracked = tracker(Some(0.5), None);
+    assert_approx!(tracker(Some(0.5), Some(0.5)), backtracked, 1e-9);
+    assert!(backtracked < tracker(None, None));
+}
+
+#[test]
+fn test_row_gcr_ignored_for_fixed_and_dual() {
+    let fixed = TrackingStrategy::Fixed { tilt: 30.0, azimuth: 180.0 };
+    for strategy in [TrackingStrategy::DualAxis, fixed] {
+        let base = SimulationConfig { step_minutes: 60, strategy, ..Default::default() };
+        let with_rows = SimulationConfig { row_gcr: Some(0.6), ..base };
+        assert_eq!(simulate_yield(&base), simulate_yield(&with_rows));
+    }
+}
@@ -0,0 +1,156 @@
+use std::sync::LazyLock;
+
+use chrono::{TimeZone, Utc};
+
+use solar_tracker::scenario::*;
+use solar_tracker::simulation::{simulate_yield, simulate_yield_with};
+use solar_tracker::stow::plan_stow;
+use solar_tracker::types::*;
+
+static SCENARIO: LazyLock<Scenario> = LazyLock::new(|| {
+    generate_scenario(&ScenarioConfig {
+        seed: 42,
+        ..Default::default()
+    })
+});
+
+// ── Reproducibility ──
+
+#[test]
+fn test_same_seed_same_scenario() {
+    let again = generate_scenario(&ScenarioConfig {
+        seed: 42,
+        ..Default::default()
+    });
+    assert_eq!(again, *SCENARIO);
+    let other = generate_scenario(&ScenarioConfig {
+        seed: 43,
+        ..Default::default()
+    });
+    assert_ne!(other.transmittance, SCENARIO.transmittance);
+    assert_ne!(other.wind, SCENARIO.wind);
+}
+
+#[test]
+fn test_sensor_sequence_is_pinned() {
+    // Bit-exact on every platform; a change here breaks saved studies
+    let mut sensor = NoisySensor::new(7, 1.0, 0.0);
+    let first: Vec<f64> = (0..3).map(|_| sensor.read(0.0)).collect();
+    assert_eq!(
+        first,
+        vec![-1.0009541026316917, 1.3335403660869787, 0.5188620948808316]
+    );
+}
+
+#[test]
+fn test_streams_are_independent() {
+    let calmer = generate_scenario(&ScenarioConfig {
+        seed: 42,
+        mean_wind_speed: 2.0,
+        wind_events_per_year: 3.0,
+        ..Default::default()
+    });
+    assert_eq!(calmer.transmittance, SCENARIO.transmittance);
+    assert_ne!(calmer.wind, SCENARIO.wind);
+}
+
+// ── Clouds ──
+
+#[test]
+fn test_year_of_steps() {
+    assert_eq!(SCENARIO.transmittance.len(), 365 * 96);
+    assert_eq!(SCENARIO.wind.len(), 365 * 96);
+    assert_eq!(
+        SCENARIO.wind[0].time,
+        Utc.with_ymd_and_hms(2026, 1, 1, 0, 0, 0).unwrap()
+    );
+    assert_eq!(
+        SCENARIO.wind[1].time,
+        Utc.with_ymd_and_hms(2026, 1, 1, 0, 15, 0).unwrap()
+    );
+    let leap = generate_scenario(&ScenarioConfig {
+        year: 2028,
+        step_minutes: 60,
+        ..Default::default()
+    });
+    assert_eq!(leap.transmittance.len(), 366 * 24);
+}
+
+#[test]
+fn test_cloud_statistics() {
+    let t = &SCENARIO.transmittance;
+    assert!(t.iter().all(|v| (0.0..=1.0).contains(v)));
+    let cloudy = t.iter().filter(|&&v| v < 1.0).count();
+    let fraction = cloudy as f64 / t.len() as f64;
+    assert!((fraction - 0.4).abs() < 0.05, "{fraction}");
+    // Mean spell of 120 minutes is 8 steps
+    let spells = t.windows(2).filter(|w| w[0] == 1.0 && w[1] < 1.0).count();
+    let mean_steps = cloudy as f64 / spells as f64;
+    assert!((mean_steps - 8.0).abs() < 1.0, "{mean_steps}");
+}
+
+#[test]
+fn test_clear_scenario_matches_clear_sky_simulation() {
+    let clear = generate_scenario(&ScenarioConfig {
+        cloudy_fraction: 0.0,
+        ..Default::default()
+    });
+    assert!(clear.transmittance.iter().all(|&v| v == 1.0));
+    let config = SimulationConfig {
+        step_minutes: 60,
+        ..Default::default()
+    };
+    let expected = simulate_yield(&config);
+    let got = simulate_yield_with(&config, scenario_irradiance(&clear));
+    assert_eq!(got.annual_kwh_per_m2, expected.annual_kwh_per_m2);
+}
+
+#[test]
+fn test_clouds_cut_yield() {
+    let config = SimulationConfig {
+        step_minutes: 60,
+        ..Default::default()
+    };
+    let clear = simulate_yield(&config).annual_kwh_per_m2;
+    let cloudy = simulate_yield_with(&config, scenario_irradiance(&SCENARIO)).annual_kwh_per_m2;
+    assert!(
+        cloudy < clear * 0.85 && cloudy > clear * 0.5,
+        "{cloudy} vs {clear}"
+    );
+}
+
+// ── Wind ──
+
+#[test]
+fn test_wind_events_trigger_stow() {
+    let events = SCENARIO
+        .wind
+        .windows(2)
+        .filter(|w| w[0].wind_gust < 20.0 && w[1].wind_gust >= 20.0)
+        .count();
+    assert!((3..=30).contains(&events), "{events}");
+    let windows = plan_stow(&SCENARIO.wind, &StowPlanConfig::default());
+    assert!(!windows.is_empty());
+    let calm = generate_scenario(&ScenarioConfig {
+        wind_events_per_year: 0.0,
+        ..Default::default()
+    });
+    assert!(calm
+        .wind
+        .iter()
+        .all(|w| w.wind_gust <= 4.0 * 1.5 * 1.4 + 1e-9));
+}
+
+// ── Sensor noise ──
+
+#[test]
+fn test_sensor_noise_statistics() {
+    let mut sensor = NoisySensor::new(1, 0.5, 0.2);
+    let readings: Vec<f64> = (0..20_000).map(|_| sensor.read(30.0) - 30.0).collect();
+    let mean = readings.iter().sum::<f64>() / readings.len() as f64;
+    let var = readings.iter().map(|r| (r - mean).powi(2)).sum::<f64>() / readings.len() as f64;
+    assert!((mean - 0.2).abs() < 0.02, "{mean}");
+    assert!((var.sqrt() - 0.5).abs() < 0.02, "{}", var.sqrt());
+    let mut replay = NoisySensor::new(1, 0.5, 0.2);
+    assert_eq!(replay.read(30.0) - 30.0, readings[0]);
+}
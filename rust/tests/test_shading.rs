@@ -0,0 +1,90 @@
+use std::sync::LazyLock;
+
+use solar_tracker::shading::*;
+use solar_tracker::types::*;
+
+fn minute_of_day(minutes: i32) -> MinuteOfDay {
+    MinuteOfDay::new(minutes).unwrap()
+}
+
+fn config() -> LookupTableConfig {
+    LookupTableConfig {
+        interval_minutes: 10,
+        ..Default::default()
+    }
+}
+
+static SHADE: LazyLock<ShadeTable> =
+    LazyLock::new(|| generate_shade_table(&config(), &ShadeConfig::default()));
+
+// ── Threshold rule ──
+
+#[test]
+fn test_screen_closed_thresholds() {
+    let shade = ShadeConfig::default();
+    assert!(screen_closed(&shade, 30.0, 180.0));
+    assert!(!screen_closed(&shade, 70.0, 180.0));
+    let south_glass = ShadeConfig {
+        azimuth_range: Some((135.0, 225.0)),
+        ..shade
+    };
+    assert!(!screen_closed(&south_glass, 30.0, 100.0));
+    let north_wrap = ShadeConfig {
+        azimuth_range: Some((300.0, 60.0)),
+        ..shade
+    };
+    assert!(screen_closed(&north_wrap, 30.0, 10.0));
+    assert!(!screen_closed(&north_wrap, 30.0, 180.0));
+}
+
+// ── Shade table ──
+
+#[test]
+fn test_summer_day_closes_once_around_noon() {
+    let events = shade_schedule(&SHADE, 172);
+    assert_eq!(events.len(), 2, "{events:?}");
+    assert!(events[0].closed && !events[1].closed);
+    let midpoint = (events[0].minutes + events[1].minutes) / 2;
+    assert!((midpoint - SHADE.days[171].solar_noon_minutes).abs() <= 15);
+}
+
+#[test]
+fn test_winter_closes_less_than_summer() {
+    let closed = |doy: usize| {
+        SHADE.days[doy - 1]
+            .entries
+            .iter()
+            .filter(|e| e.closed)
+            .count()
+    };
+    assert!(closed(355) < closed(172));
+}
+
+#[test]
+fn test_night_entries_open() {
+    let day = &SHADE.days[171];
+    assert!(!day.entries.first().unwrap().closed);
+    assert!(!day.entries.last().unwrap().closed);
+}
+
+#[test]
+fn test_lookup_nearest_entry() {
+    let events = shade_schedule(&SHADE, 172);
+    let close = events[0].minutes;
+    assert!(!lookup_shade(&SHADE, 172, minute_of_day(close - 6)).unwrap().closed);
+    assert!(lookup_shade(&SHADE, 172, minute_of_day(close - 4)).unwrap().closed);
+    assert!(lookup_shade(&SHADE, 172, minute_of_day(close + 3)).unwrap().closed);
+    assert_eq!(
+        lookup_shade(&SHADE, 172, minute_of_day(close - 4)).unwrap().minutes,
+        close - 4
+    );
+    assert!(lookup_shade(&SHADE, 172, minute_of_day(0)).is_none());
+}
+
+#[test]
+fn test_shade_table_storage() {
+    assert_eq!(
+        SHADE.metadata.storage_estimate_kb * 1024.0,
+        SHADE.metadata.total_entries as f64
+    );
+}
@@ -0,0 +1,144 @@
+use std::sync::LazyLock;
+
+use solar_tracker::angles::surface_orientation;
+use solar_tracker::lookup_table::*;
+use solar_tracker::simplify::*;
+use solar_tracker::types::*;
+
+fn minute_of_day(minutes: i32) -> MinuteOfDay {
+    MinuteOfDay::new(minutes).unwrap()
+}
+
+macro_rules! assert_approx {
+    ($left:expr, $right:expr, $tol:expr) => {
+        let (l, r) = ($left as f64, $right as f64);
+        assert!(
+            (l - r).abs() <= $tol,
+            "assert_approx failed: left={}, right={}, diff={}, tol={}",
+            l, r, (l - r).abs(), $tol
+        );
+    };
+}
+
+// London, so the whole day fits the UTC grid
+static TABLE: LazyLock<SingleAxisTable> = LazyLock::new(|| {
+    generate_single_axis_table(&LookupTableConfig {
+        interval_minutes: 5,
+        latitude: 51.5,
+        longitude: -0.1,
+        gcr: Some(0.4),
+        min_rotation: Some(-60.0),
+        max_rotation: Some(60.0),
+        ..Default::default()
+    })
+});
+
+static SIMPLIFIED: LazyLock<(SimplifiedTable, SimplifyReport)> =
+    LazyLock::new(|| simplify_table(&TABLE, 0.5));
+
+fn max_lookup_error(simplified: &SimplifiedTable) -> f64 {
+    let mut worst = 0.0f64;
+    for day in &TABLE.days {
+        for entry in &day.entries {
+            let got = lookup_simplified(simplified, day.day_of_year, minute_of_day(entry.minutes)).unwrap();
+            match (entry.rotation, got.rotation) {
+                (Some(expected), Some(got)) => worst = worst.max((got - expected).abs()),
+                (None, None) => {}
+                _ => panic!("daylight changed at day {} {}", day.day_of_year, entry.minutes),
+            }
+        }
+    }
+    worst
+}
+
+// ── Simplification ──
+
+#[test]
+fn test_error_bound_holds() {
+    let (simplified, report) = &*SIMPLIFIED;
+    assert!(report.max_error <= 0.5);
+    assert!(max_lookup_error(simplified) <= 0.5 + 1e-9);
+}
+
+#[test]
+fn test_report_counts() {
+    let (simplified, report) = &*SIMPLIFIED;
+    assert_eq!(report.original_entries, TABLE.metadata.total_entries);
+    assert_eq!(report.kept_entries, simplified.metadata.total_entries);
+    let month_entries: usize = simplified.metadata.months.iter().map(|m| m.entries).sum();
+    assert_eq!(month_entries, report.kept_entries);
+    assert_eq!(report.original_bytes, report.original_entries * 4);
+    assert_eq!(report.simplified_bytes, report.kept_entries * 6);
+    assert!(report.simplified_bytes * 3 < report.original_bytes);
+    let kb = report.simplified_bytes as f64 / 1024.0;
+    assert_approx!(simplified.metadata.storage_estimate_kb, kb, 1e-9);
+}
+
+#[test]
+fn test_zero_error_keeps_bends() {
+    let (_, exact) = simplify_table(&TABLE, 0.0);
+    let (_, loose) = simplify_table(&TABLE, 2.0);
+    assert!(exact.kept_entries > SIMPLIFIED.1.kept_entries);
+    assert!(loose.kept_entries < SIMPLIFIED.1.kept_entries);
+}
+
+#[test]
+fn test_night_runs_keep_their_ends() {
+    let (simplified, _) = &*SIMPLIFIED;
+    let day = &simplified.days[0];
+    let source = &TABLE.days[0];
+    assert_eq!(day.entries.first(), source.entries.first());
+    assert_eq!(day.entries.last(), source.entries.last());
+    let first_daylight = source.entries.iter().find(|e| e.rotation.is_some()).unwrap();
+    assert!(day.entries.contains(first_daylight));
+}
+
+// ── Budget ──
+
+#[test]
+fn test_fits_budget() {
+    let (simplified, report) = simplify_to_budget(&TABLE, 32 * 1024).unwrap();
+    assert!(report.simplified_bytes <= 32 * 1024);
+    assert!(max_lookup_error(&simplified) <= report.max_error + 1e-9);
+}
+
+#[test]
+fn test_budget_too_small() {
+    assert!(simplify_to_budget(&TABLE, 100).is_none());
+}
+
+// ── Lookup ──
+
+#[test]
+fn test_lookup_interpolates_between_kept_entries() {
+    let expected = lookup_single_axis(&TABLE, 100, minute_of_day(722)).unwrap().rotation.unwrap();
+    let got = lookup_simplified(&SIMPLIFIED.0, 100, minute_of_day(722)).unwrap().rotation.unwrap();
+    assert_approx!(got, expected, 0.5);
+}
+
+#[test]
+fn test_lookup_surface_follows_rotation() {
+    // Kept entries can be an hour or more apart, straddling noon
+    let table = generate_single_axis_table(&LookupTableConfig {
+        include_surface_orientation: true,
+        ..TABLE.config
+    });
+    let (simplified, _) = simplify_table(&table, 2.0);
+    for minutes in (0..1440).step_by(3) {
+        let Some(entry) = lookup_simplified(&simplified, 172, minute_of_day(minutes)) else {
+            continue;
+        };
+        let (Some(rotation), Some(surface)) = (entry.rotation, entry.surface) else {
+            continue;
+        };
+        let expected = surface_orientation(rotation, 0.0, 180.0);
+        assert_approx!(surface.surface_tilt, expected.surface_tilt, 1e-9);
+        assert_approx!(surface.surface_azimuth, expected.surface_azimuth, 1e-9);
+    }
+}
+
+#[test]
+fn test_lookup_out_of_range() {
+    assert_eq!(lookup_simplified(&SIMPLIFIED.0, 0, minute_of_day(720)), None);
+    assert_eq!(lookup_simplified(&SIMPLIFIED.0, 366, minute_of_day(720)), None);
+}
@@ -0,0 +1,304 @@
+use std::sync::LazyLock;
+
+use solar_tracker::angles::{
+    angle_between, solar_position, sun_vector, surface_normal, surface_orientation,
+    tilted_axis_rotation,
+};
+use solar_tracker::daylight::annual_daylight_metrics;
+use solar_tracker::simulation::*;
+use solar_tracker::transposition::poa_irradiance;
+use solar_tracker::types::*;
+
+use chrono::{TimeZone, Utc};
+
+macro_rules! assert_approx {
+    ($left:expr, $right:expr, $tol:expr) => {
+        let (l, r) = ($left as f64, $right as f64);
+        assert!(
+            (l - r).abs() <= $tol,
+            "assert_approx failed: left={}, right={}, diff={}, tol={}",
+            l, r, (l - r).abs(), $tol
+        );
+    };
+}
+
+fn with_strategy(strategy: TrackingStrategy) -> SimulationConfig {
+    SimulationConfig { strategy, ..Default::default() }
+}
+
+const FIXED_SOUTH: TrackingStrategy = TrackingStrategy::Fixed { tilt: 35.0, azimuth: 180.0 };
+
+static FIXED: LazyLock<YieldReport> = LazyLock::new(|| simulate_yield(&with_strategy(FIXED_SOUTH)));
+static SINGLE: LazyLock<YieldReport> = LazyLock::new(|| simulate_yield(&Default::default()));
+static DUAL: LazyLock<YieldReport> =
+    LazyLock::new(|| simulate_yield(&with_strategy(TrackingStrategy::DualAxis)));
+
+// ── Report structure ──
+
+#[test]
+fn test_totals_are_consistent() {
+    for report in [&*FIXED, &*SINGLE, &*DUAL] {
+        assert_eq!(report.daily_kwh_per_m2.len(), 365);
+        let daily: f64 = report.daily_kwh_per_m2.iter().sum();
+        let monthly: f64 = report.monthly_kwh_per_m2.iter().sum();
+        assert_approx!(daily, report.annual_kwh_per_m2, 1e-9);
+        assert_approx!(monthly, report.annual_kwh_per_m2, 1e-9);
+    }
+}
+
+#[test]
+fn test_leap_year_has_366_days() {
+    let config = SimulationConfig { year: 2028, step_minutes: 60, ..Default::default() };
+    assert_eq!(simulate_yield(&config).daily_kwh_per_m2.len(), 366);
+}
+
+#[test]
+fn test_summer_months_dominate_for_trackers() {
+    let june = SINGLE.monthly_kwh_per_m2[5];
+    let december = SINGLE.monthly_kwh_per_m2[11];
+    assert!(june > 2.0 * december, "june {june}, december {december}");
+}
+
+// ── Strategy ranking ──
+
+#[test]
+fn test_dual_beats_single_beats_fixed() {
+    let (fixed, single, dual) =
+        (FIXED.annual_kwh_per_m2, SINGLE.annual_kwh_per_m2, DUAL.annual_kwh_per_m2);
+    assert!(dual > single && single > fixed, "{dual} {single} {fixed}");
+    // Clear-sky gains, roughly in line with published figures
+    assert!(single / fixed > 1.15 && single / fixed < 1.45, "{}", single / fixed);
+    assert!(dual / single < 1.25, "{}", dual / single);
+}
+
+#[test]
+fn test_horizontal_matches_clear_sky_ghi() {
+    let config = SimulationConfig {
+        strategy: TrackingStrategy::Fixed { tilt: 0.0, azimuth: 180.0 },
+        step_minutes: 5,
+        ..Default::default()
+    };
+    let report = simulate_yield(&config);
+    let ghi = annual_daylight_metrics(config.latitude, config.year, 0.0).peak_sun_hours;
+    assert_approx!(report.annual_kwh_per_m2, ghi, 0.01 * ghi);
+}
+
+#[test]
+fn test_southern_hemisphere_prefers_north_facing() {
+    let simulate = |azimuth| {
+        let config = SimulationConfig {
+            latitude: -33.9,
+            longitude: 151.2,
+            strategy: TrackingStrategy::Fixed { tilt: 30.0, azimuth },
+            step_minutes: 30,
+            ..Default::default()
+        };
+        simulate_yield(&config).annual_kwh_per_m2
+    };
+    assert!(simulate(0.0) > 1.3 * simulate(180.0));
+}
+
+#[test]
+fn test_backtracking_and_limits_cost_energy() {
+    let limited = simulate_yield(&with_strategy(TrackingStrategy::SingleAxis {
+        axis_tilt: 0.0,
+        axis_azimuth: 180.0,
+        max_rotation: Some(30.0),
+        gcr: None,
+    }));
+    let backtracked = simulate_yield(&with_strategy(TrackingStrategy::SingleAxis {
+        axis_tilt: 0.0,
+        axis_azimuth: 180.0,
+        max_rotation: Some(60.0),
+        gcr: Some(0.4),
+    }));
+    assert!(limited.annual_kwh_per_m2 < SINGLE.annual_kwh_per_m2);
+    assert!(backtracked.annual_kwh_per_m2 < SINGLE.annual_kwh_per_m2);
+}
+
+// ── Strategy surfaces ──
+
+#[test]
+fn test_dual_axis_surface_faces_sun() {
+    let pos = solar_position(39.8, -89.6, &Utc.with_ymd_and_hms(2026, 5, 1, 15, 0, 0).unwrap());
+    let surface = strategy_surface(&TrackingStrategy::DualAxis, &pos);
+    assert_approx!(angle_between(surface_normal(&surface), sun_vector(&pos)), 0.0, 1e-6);
+}
+
+#[test]
+fn test_single_axis_surface_respects_limit() {
+    let pos = solar_position(39.8, -89.6, &Utc.with_ymd_and_hms(2026, 6, 21, 12, 0, 0).unwrap());
+    let strategy = TrackingStrategy::SingleAxis {
+        axis_tilt: 0.0,
+        axis_azimuth: 180.0,
+        max_rotation: Some(45.0),
+        gcr: None,
+    };
+    // 07:00 CDT, sun well to the east
+    assert!(pos.azimuth < 90.0);
+    assert_approx!(strategy_surface(&strategy, &pos).surface_tilt, 45.0, 1e-9);
+}
+
+// ── Supplied irradiance ──
+
+#[test]
+fn test_supplied_irradiance() {
+    let config = SimulationConfig { step_minutes: 60, ..Default::default() };
+    let mut calls = 0;
+    let dark = simulate_yield_with(&config, |_, _, pos| {
+        calls += 1;
+        assert!(pos.zenith < 90.0);
+        None
+    });
+    assert!(calls > 365 * 8);
+    assert_eq!(dark.annual_kwh_per_m2, 0.0);
+
+    // Pure isotropic diffuse on a horizontal surface integrates to DHI ×
+    // daylight hours
+    let config = SimulationConfig {
+        strategy: TrackingStrategy::Fixed { tilt: 0.0, azimuth: 180.0 },
+        sky_model: SkyDiffuseModel::Isotropic,
+        step_minutes: 60,
+        ..Default::default()
+    };
+    let mut sun_up_hours = 0.0;
+    let overcast = simulate_yield_with(&config, |_, _, _| {
+        sun_up_hours += 1.0;
+        Some(IrradianceComponents { ghi: 100.0, dni: 0.0, dhi: 100.0 })
+    });
+    assert_approx!(overcast.annual_kwh_per_m2, 0.1 * sun_up_hours, 1e-9);
+}
+
+// ── Diffuse-optimal positioning ──
+
+fn morning() -> SolarPosition {
+    solar_position(39.8, -89.6, &Utc.with_ymd_and_hms(2026, 6, 21, 13, 30, 0).unwrap())
+}
+
+const OVERCAST: IrradianceComponents = IrradianceComponents { ghi: 150.0, dni: 0.0, dhi: 150.0 };
+
+fn poa_total(strategy: &TrackingStrategy, rotation: f64, irradiance: &IrradianceComponents) -> f64 {
+    let TrackingStrategy::SingleAxis { axis_tilt, axis_azimuth, .. } = *strategy else {
+        unreachable!()
+    };
+    let surface = surface_orientation(rotation, axis_tilt, axis_azimuth);
+    poa_irradiance(&morning(), &surface, irradiance, 0.2, SkyDiffuseModel::Perez).total
+}
+
+#[test]
+fn test_isotropic_overcast_lies_flat() {
+    let strategy = SimulationConfig::default().strategy;
+    let iso = SkyDiffuseModel::Isotropic;
+    let rotation = diffuse_optimal_rotation(&strategy, &morning(), &OVERCAST, 0.2, iso).unwrap();
+    assert_eq!(rotation, 0.0);
+    let dual_axis = TrackingStrategy::DualAxis;
+    let dual = diffuse_optimal_surface(&dual_axis, &morning(), &OVERCAST, 0.2, iso);
+    assert_eq!(dual.surface_tilt, 0.0);
+}
+
+#[test]
+fn test_perez_overcast_leans_toward_sun() {
+    let strategy = SimulationConfig::default().strategy;
+    let pos = morning();
+    let perez = SkyDiffuseModel::Perez;
+    let sun_pointing = tilted_axis_rotation(&pos, 0.0, 180.0);
+    let rotation = diffuse_optimal_rotation(&strategy, &pos, &OVERCAST, 0.2, perez).unwrap();
+    assert!(sun_pointing < 0.0 && rotation < 0.0, "{rotation}");
+    assert!(rotation > sun_pointing.max(-60.0), "{rotation} vs {sun_pointing}");
+    let best = poa_total(&strategy, rotation, &OVERCAST);
+    assert!(best > poa_total(&strategy, sun_pointing.max(-60.0), &OVERCAST));
+    assert!(best >= poa_total(&strategy, 0.0, &OVERCAST));
+}
+
+#[test]
+fn test_clear_sky_optimum_is_near_sun_pointing() {
+    let strategy = TrackingStrategy::SingleAxis {
+        axis_tilt: 0.0,
+        axis_azimuth: 180.0,
+        max_rotation: None,
+        gcr: None,
+    };
+    let pos = morning();
+    let clear = solar_tracker::irradiance::clear_sky_components(&pos).unwrap();
+    let perez = SkyDiffuseModel::Perez;
+    let rotation = diffuse_optimal_rotation(&strategy, &pos, &clear, 0.2, perez).unwrap();
+    assert_approx!(rotation, tilted_axis_rotation(&pos, 0.0, 180.0), 3.0);
+}
+
+#[test]
+fn test_diffuse_optimal_respects_limits() {
+    let strategy = TrackingStrategy::SingleAxis {
+        axis_tilt: 0.0,
+        axis_azimuth: 180.0,
+        max_rotation: Some(10.0),
+        gcr: None,
+    };
+    let pos = morning();
+    let clear = solar_tracker::irradiance::clear_sky_components(&pos).unwrap();
+    let perez = SkyDiffuseModel::Perez;
+    assert_eq!(diffuse_optimal_rotation(&strategy, &pos, &clear, 0.2, perez), Some(-10.0));
+}
+
+#[test]
+fn test_fixed_array_cannot_move() {
+    let perez = SkyDiffuseModel::Perez;
+    assert_eq!(diffuse_optimal_rotation(&FIXED_SOUTH, &morning(), &OVERCAST, 0.2, perez), None);
+    let surface = diffuse_optimal_surface(&FIXED_SOUTH, &morning(), &OVERCAST, 0.2, perez);
+    assert_eq!((surface.surface_tilt, surface.surface_azimuth), (35.0, 180.0));
+}
+
+// ── Remaining production ──
+
+#[test]
+fn test_remaining_from_midnight_is_whole_day() {
+    // Near Greenwich the UTC day holds the whole solar day
+    let config = SimulationConfig {
+        latitude: 51.5,
+        longitude: -0.1,
+        step_minutes: 5,
+        ..with_strategy(FIXED_SOUTH)
+    };
+    let day = simulate_yield(&config).daily_kwh_per_m2[171];
+    let midnight = Utc.with_ymd_and_hms(2026, 6, 21, 0, 0, 0).unwrap();
+    assert_approx!(remaining_clearsky_energy(&midnight, 51.5, -0.1, &FIXED_SOUTH), day, day * 0.01);
+}
+
+#[test]
+fn test_remaining_decreases_through_the_day() {
+    let at = |hour| {
+        let t = Utc.with_ymd_and_hms(2026, 6, 21, hour, 0, 0).unwrap();
+        remaining_clearsky_energy(&t, 51.5, -0.1, &FIXED_SOUTH)
+    };
+    let (morning, noon, evening) = (at(6), at(12), at(18));
+    assert!(morning > noon && noon > evening && evening > 0.0);
+    assert_approx!(noon / at(0), 0.5, 0.05);
+    assert_eq!(at(22), 0.0);
+}
+
+#[test]
+fn test_remaining_local_time_zone() {
+    // Afternoon in Springfield runs past midnight UTC
+    let cdt = chrono::FixedOffset::west_opt(5 * 3600).unwrap();
+    let afternoon = cdt.with_ymd_and_hms(2026, 6, 21, 15, 0, 0).unwrap();
+    let remaining = remaining_clearsky_energy(&afternoon, 39.8, -89.6, &FIXED_SOUTH);
+    let utc = afternoon.with_timezone(&Utc);
+    assert_eq!(remaining, remaining_clearsky_energy(&utc, 39.8, -89.6, &FIXED_SOUTH));
+    assert!(remaining > 1.0, "{remaining}");
+    let night = cdt.with_ymd_and_hms(2026, 6, 21, 22, 30, 0).unwrap();
+    assert_eq!(remaining_clearsky_energy(&night, 39.8, -89.6, &FIXED_SOUTH), 0.0);
+}
+
+#[test]
+fn test_remaining_tracker_beats_fixed() {
+    let t = Utc.with_ymd_and_hms(2026, 6, 21, 14, 0, 0).unwrap();
+    let fixed = remaining_clearsky_energy(&t, 51.5, -0.1, &FIXED_SOUTH);
+    let dual = remaining_clearsky_energy(&t, 51.5, -0.1, &TrackingStrategy::DualAxis);
+    assert!(dual > fixed);
+}
+
+#[test]
+fn test_remaining_midnight_sun_is_bounded() {
+    let t = Utc.with_ymd_and_hms(2026, 6, 21, 0, 0, 0).unwrap();
+    let day = remaining_clearsky_energy(&t, 78.2, 15.6, &TrackingStrategy::DualAxis);
+    assert!(day > 0.0 && day < 24.0 * 1.4, "{day}");
+}
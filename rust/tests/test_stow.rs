@@ -0,0 +1,249 @@
+use chrono::{DateTime, Duration, TimeZone, Utc};
+
+use solar_tracker::lookup_table::{generate_dual_axis_table, generate_single_axis_table};
+use solar_tracker::stow::*;
+use solar_tracker::types::*;
+
+fn t(hour: u32, minute: u32) -> DateTime<Utc> {
+    Utc.with_ymd_and_hms(2026, 7, 14, hour, minute, 0).unwrap()
+}
+
+fn forecast(gusts: &[(u32, u32, f64)]) -> Vec<WeatherForecast> {
+    gusts
+        .iter()
+        .map(|&(h, m, gust)| WeatherForecast {
+            time: t(h, m),
+            wind_speed: gust * 0.6,
+            wind_gust: gust,
+        })
+        .collect()
+}
+
+// ── Planning ──
+
+#[test]
+fn test_calm_forecast_no_stow() {
+    let fc = forecast(&[(12, 0, 5.0), (12, 15, 7.0), (12, 30, 6.0)]);
+    assert!(plan_stow(&fc, &StowPlanConfig::default()).is_empty());
+}
+
+#[test]
+fn test_gust_front_enters_with_lead_time() {
+    let fc = forecast(&[(12, 0, 5.0), (12, 30, 22.0), (12, 45, 8.0)]);
+    let plan = plan_stow(&fc, &StowPlanConfig::default());
+    assert_eq!(plan.len(), 1);
+    assert_eq!(plan[0].enter, t(12, 15));
+    assert_eq!(plan[0].exit, t(13, 0));
+    assert_eq!(plan[0].peak_gust, 22.0);
+}
+
+#[test]
+fn test_hysteresis_keeps_stow_between_thresholds() {
+    // 15 m/s is below the trigger but above the release threshold
+    let fc = forecast(&[(12, 0, 20.0), (12, 15, 15.0), (12, 30, 15.0), (12, 45, 10.0)]);
+    let plan = plan_stow(&fc, &StowPlanConfig::default());
+    assert_eq!(plan.len(), 1);
+    assert_eq!(plan[0].exit, t(13, 0));
+}
+
+#[test]
+fn test_sub_trigger_wind_alone_does_not_stow() {
+    let fc = forecast(&[(12, 0, 15.0), (12, 15, 16.0), (12, 30, 15.0)]);
+    assert!(plan_stow(&fc, &StowPlanConfig::default()).is_empty());
+}
+
+#[test]
+fn test_close_events_merged_to_avoid_flapping() {
+    let fc = forecast(&[
+        (12, 0, 20.0),
+        (12, 15, 8.0),
+        (12, 45, 8.0),
+        (13, 0, 21.0),
+        (13, 15, 8.0),
+    ]);
+    let plan = plan_stow(&fc, &StowPlanConfig::default());
+    assert_eq!(plan.len(), 1);
+    assert_eq!(plan[0].enter, t(11, 45));
+    assert_eq!(plan[0].exit, t(13, 30));
+    assert_eq!(plan[0].peak_gust, 21.0);
+}
+
+#[test]
+fn test_distant_events_stay_separate() {
+    let fc = forecast(&[(9, 0, 20.0), (9, 15, 5.0), (15, 0, 20.0), (15, 15, 5.0)]);
+    let plan = plan_stow(&fc, &StowPlanConfig::default());
+    assert_eq!(plan.len(), 2);
+}
+
+#[test]
+fn test_unsorted_forecast_handled() {
+    let fc = forecast(&[(12, 45, 8.0), (12, 30, 22.0), (12, 0, 5.0)]);
+    let plan = plan_stow(&fc, &StowPlanConfig::default());
+    assert_eq!(plan.len(), 1);
+    assert_eq!(plan[0].enter, t(12, 15));
+}
+
+#[test]
+fn test_event_running_past_forecast_end() {
+    let fc = forecast(&[(12, 0, 5.0), (12, 30, 25.0)]);
+    let plan = plan_stow(&fc, &StowPlanConfig::default());
+    assert_eq!(plan.len(), 1);
+    assert_eq!(plan[0].exit, t(13, 0));
+}
+
+// ── Provider ──
+
+#[test]
+fn test_plan_from_provider_respects_horizon() {
+    let fc = forecast(&[(12, 0, 5.0), (20, 0, 25.0)]);
+    let config = StowPlanConfig {
+        horizon_minutes: 120,
+        ..Default::default()
+    };
+    assert!(plan_stow_from(&fc, t(11, 0), &config).is_empty());
+    let config = StowPlanConfig::default();
+    assert_eq!(plan_stow_from(&fc, t(15, 0), &config).len(), 1);
+}
+
+#[test]
+fn test_stow_active() {
+    let fc = forecast(&[(12, 0, 5.0), (12, 30, 22.0), (12, 45, 8.0)]);
+    let plan = plan_stow(&fc, &StowPlanConfig::default());
+    assert!(!stow_active(&plan, t(12, 14)));
+    assert!(stow_active(&plan, t(12, 15)));
+    assert!(stow_active(&plan, t(12, 59)));
+    assert!(!stow_active(&plan, t(12, 59) + Duration::minutes(1)));
+}
+
+// ── Stow angles ──
+
+#[test]
+fn test_default_dual_axis_stow_angles() {
+    assert_eq!(stow_angles(StowMode::Wind, TrackerKind::DualAxis), (0.0, 180.0));
+    assert_eq!(stow_angles(StowMode::Snow, TrackerKind::DualAxis), (60.0, 180.0));
+    assert_eq!(stow_angles(StowMode::Night, TrackerKind::DualAxis), (0.0, 180.0));
+}
+
+#[test]
+fn test_default_single_axis_stow_angles() {
+    assert_eq!(stow_angles(StowMode::Wind, TrackerKind::SingleAxis).0, 0.0);
+    assert_eq!(stow_angles(StowMode::Hail, TrackerKind::SingleAxis), (60.0, 270.0));
+}
+
+#[test]
+fn test_custom_single_axis_stow_faces_east() {
+    let config = StowConfig {
+        single_axis_snow: -55.0,
+        ..Default::default()
+    };
+    assert_eq!(
+        stow_angles_with(&config, StowMode::Snow, TrackerKind::SingleAxis),
+        (55.0, 90.0)
+    );
+    assert_eq!(single_axis_stow_rotation(&config, StowMode::Snow), -55.0);
+}
+
+#[test]
+fn test_custom_dual_axis_stow() {
+    let config = StowConfig {
+        dual_axis_hail: StowAngles {
+            tilt: 75.0,
+            azimuth: 0.0,
+        },
+        ..Default::default()
+    };
+    assert_eq!(
+        dual_axis_stow_angles(&config, StowMode::Hail),
+        StowAngles {
+            tilt: 75.0,
+            azimuth: 0.0
+        }
+    );
+}
+
+// ── Controller mapping ──
+
+#[test]
+fn test_stow_reason_to_mode() {
+    assert_eq!(StowMode::from(StowReason::Snow), StowMode::Snow);
+    assert_eq!(StowMode::from(StowReason::Hail), StowMode::Hail);
+    assert_eq!(StowMode::from(StowReason::Lightning), StowMode::Wind);
+    assert_eq!(StowMode::from(StowReason::CommunicationLoss), StowMode::Wind);
+}
+
+#[test]
+fn test_controller_stow_mode() {
+    assert_eq!(
+        controller_stow_mode(ControllerMode::Stowed(StowReason::Snow)),
+        Some(StowMode::Snow)
+    );
+    assert_eq!(controller_stow_mode(ControllerMode::Night), Some(StowMode::Night));
+    assert_eq!(controller_stow_mode(ControllerMode::Tracking), None);
+}
+
+// ── Table substitution ──
+
+#[test]
+fn test_fill_single_axis_stow() {
+    let config = LookupTableConfig {
+        interval_minutes: 30,
+        ..Default::default()
+    };
+    let mut table = generate_single_axis_table(&config);
+    assert!(table.days[0].entries.iter().any(|e| e.rotation.is_none()));
+    fill_single_axis_stow(&mut table, 0.0);
+    for day in &table.days {
+        assert!(day.entries.iter().all(|e| e.rotation.is_some()));
+    }
+    assert_eq!(table.days[0].entries[0].rotation, Some(0.0));
+}
+
+#[test]
+fn test_fill_dual_axis_stow_keeps_daylight_entries() {
+    let config = LookupTableConfig {
+        interval_minutes: 30,
+        ..Default::default()
+    };
+    let original = generate_dual_axis_table(&config);
+    let mut table = original.clone();
+    let angles = StowAngles {
+        tilt: 0.0,
+        azimuth: 180.0,
+    };
+    fill_dual_axis_stow(&mut table, angles);
+    for (filled, orig) in table.days[79].entries.iter().zip(&original.days[79].entries) {
+        match orig.tilt {
+            Some(_) => assert_eq!(filled, orig),
+            None => {
+                assert_eq!(filled.tilt, Some(0.0));
+                assert_eq!(filled.panel_azimuth, Some(180.0));
+            }
+        }
+    }
+}
+
+#[test]
+fn test_fill_dual_axis_stow_encodes_azimuth() {
+    let config = LookupTableConfig {
+        interval_minutes: 30,
+        azimuth_encoding: AzimuthEncoding::NorthOffset,
+        ..Default::default()
+    };
+    let mut table = generate_dual_axis_table(&config);
+    fill_dual_axis_stow(&mut table, StowAngles { tilt: 0.0, azimuth: 180.0 });
+    // A panel bearing due south faces due north
+    assert_eq!(table.days[0].entries[0].panel_azimuth, Some(0.0));
+}
+
+#[test]
+fn test_fill_dual_axis_stow_applies_magnetic_declination() {
+    let config = LookupTableConfig {
+        interval_minutes: 30,
+        magnetic_declination: Some(10.0),
+        ..Default::default()
+    };
+    let mut table = generate_dual_axis_table(&config);
+    fill_dual_axis_stow(&mut table, StowAngles { tilt: 0.0, azimuth: 180.0 });
+    // True south is 170° magnetic with the needle 10° east
+    assert_eq!(table.days[0].entries[0].panel_azimuth, Some(170.0));
+}
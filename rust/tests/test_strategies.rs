@@ -0,0 +1,68 @@
+#![cfg(feature = "proptest")]
+
+use chrono::Datelike;
+use proptest::prelude::*;
+use proptest::strategy::ValueTree;
+
+use solar_tracker::angles::{solar_position, solar_position_checked, VALID_YEARS};
+use solar_tracker::config::validate_config;
+use solar_tracker::lookup_table::{generate_single_axis_table, lookup_single_axis};
+use solar_tracker::strategies::*;
+use solar_tracker::types::MinuteOfDay;
+
+// The strategies stay inside the domain the crate accepts
+
+proptest! {
+    #[test]
+    fn test_positions_in_domain_are_checked_ok(
+        latitude in latitudes(),
+        longitude in longitudes(),
+        at in datetimes(),
+    ) {
+        let pos = solar_position_checked(latitude, longitude, &at).unwrap();
+        prop_assert_eq!(pos, solar_position(latitude, longitude, &at));
+        prop_assert!((0.0..=180.0).contains(&pos.zenith));
+    }
+
+    #[test]
+    fn test_dates_in_valid_years(date in dates()) {
+        prop_assert!(VALID_YEARS.contains(&date.year()));
+    }
+
+    #[test]
+    fn test_intervals_divide_the_day(interval in interval_minutes()) {
+        prop_assert_eq!(1440 % interval, 0);
+    }
+}
+
+proptest! {
+    #![proptest_config(ProptestConfig::with_cases(16))]
+
+    #[test]
+    fn test_configs_validate_and_generate(config in configs()) {
+        prop_assert!(validate_config(&config).is_ok());
+        let table = generate_single_axis_table(&config);
+        let limit = config.max_rotation.unwrap_or(180.0);
+        for day in table.days.iter().step_by(30) {
+            for entry in &day.entries {
+                prop_assert!(entry.rotation.is_none_or(|r| r.abs() <= limit));
+            }
+            if let Some(first) = day.entries.first() {
+                let minutes = MinuteOfDay::new(first.minutes).unwrap();
+                let found = lookup_single_axis(&table, day.day_of_year, minutes);
+                prop_assert!(found.is_some());
+            }
+        }
+    }
+}
+
+#[test]
+fn test_latitudes_reach_the_poles() {
+    let mut runner = proptest::test_runner::TestRunner::deterministic();
+    let strategy = latitudes();
+    let samples: Vec<f64> = (0..500)
+        .map(|_| strategy.new_tree(&mut runner).unwrap().current())
+        .collect();
+    assert!(samples.contains(&90.0) && samples.contains(&-90.0));
+    assert!(samples.iter().all(|l| (-90.0..=90.0).contains(l)));
+}
@@ -0,0 +1,86 @@
+use solar_tracker::angles::day_of_year;
+use solar_tracker::daylight::sunrise_sunset_azimuth;
+use solar_tracker::sun_path::*;
+
+macro_rules! assert_approx {
+    ($left:expr, $right:expr, $tol:expr) => {
+        let (l, r) = ($left as f64, $right as f64);
+        assert!(
+            (l - r).abs() <= $tol,
+            "assert_approx failed: left={}, right={}, diff={}, tol={}",
+            l, r, (l - r).abs(), $tol
+        );
+    };
+}
+
+// ── Day selection ──
+
+#[test]
+fn test_key_days() {
+    assert_eq!(key_sun_path_days(2026), vec![79, 172, 265, 355]);
+    // Leap year shifts every date after February
+    assert_eq!(key_sun_path_days(2028), vec![80, 173, 266, 356]);
+}
+
+#[test]
+fn test_monthly_days() {
+    let days = monthly_sun_path_days(2026);
+    assert_eq!(days.len(), 12);
+    assert_eq!(days[0], 21);
+    assert_eq!(days[5], day_of_year(2026, 6, 21));
+}
+
+// ── Curves ──
+
+#[test]
+fn test_curve_spans_sunrise_to_sunset() {
+    let curve = sun_path(39.8, 172, 30);
+    assert_eq!(curve.day_of_year, 172);
+    let first = curve.points.first().unwrap();
+    let last = curve.points.last().unwrap();
+    // End points sit on the refracted horizon
+    assert_approx!(first.altitude, -0.833, 0.3);
+    assert_approx!(last.altitude, -0.833, 0.3);
+    let (rise_az, set_az) = sunrise_sunset_azimuth(39.8, 172).unwrap();
+    assert_approx!(first.azimuth, rise_az, 0.5);
+    assert_approx!(last.azimuth, set_az, 0.5);
+    // Interior samples fall on the step grid
+    for p in &curve.points[1..curve.points.len() - 1] {
+        assert_eq!(p.minutes % 30, 0);
+        assert!(p.altitude > 0.0);
+    }
+    assert!(curve.points.windows(2).all(|w| w[0].minutes < w[1].minutes));
+}
+
+#[test]
+fn test_curve_noon_altitude() {
+    let curve = sun_path(39.8, 172, 15);
+    let noon = curve.points.iter().find(|p| p.minutes == 720).unwrap();
+    // 90 - lat + decl at the June solstice
+    assert_approx!(noon.altitude, 90.0 - 39.8 + 23.44, 0.5);
+    assert_approx!(noon.azimuth, 180.0, 0.01);
+}
+
+#[test]
+fn test_summer_curve_above_winter_curve() {
+    let curves = sun_path_diagram(39.8, &key_sun_path_days(2026), 60);
+    assert_eq!(curves.len(), 4);
+    let peak = |i: usize| {
+        curves[i]
+            .points
+            .iter()
+            .map(|p| p.altitude)
+            .fold(f64::MIN, f64::max)
+    };
+    assert!(peak(1) > peak(0));
+    assert!(peak(0) > peak(3));
+    assert!(curves[1].points.len() > curves[3].points.len());
+}
+
+#[test]
+fn test_polar_curves() {
+    assert!(sun_path(80.0, 355, 30).points.is_empty());
+    let midnight_sun = sun_path(80.0, 172, 60);
+    assert_eq!(midnight_sun.points.len(), 25);
+    assert!(midnight_sun.points.iter().all(|p| p.altitude > 0.0));
+}
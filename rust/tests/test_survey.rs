@@ -0,0 +1,117 @@
+use solar_tracker::lookup_table::*;
+use solar_tracker::survey::*;
+use solar_tracker::types::*;
+
+const SURVEY: &str = "\
+# Block A as-built, 2026-03
+row_id,axis_azimuth,axis_tilt,gcr_east,gcr_west
+A-01,179.2,1.5,,0.41
+A-02, 180.4 ,-0.8,0.41,0.39
+
+A-03,181.0,2.25,0.39,
+";
+
+// ── Parsing ──
+
+#[test]
+fn test_parse_rows() {
+    let rows = parse_survey_csv(SURVEY).unwrap();
+    assert_eq!(rows.len(), 3);
+    assert_eq!(rows[0].id, "A-01");
+    assert_eq!(rows[0].gcr_east, None);
+    assert_eq!(rows[0].gcr_west, Some(0.41));
+    assert_eq!(rows[1].axis_azimuth, 180.4);
+    assert_eq!(rows[1].axis_tilt, -0.8);
+    assert_eq!(rows[2].gcr_west, None);
+}
+
+#[test]
+fn test_parse_columns_any_order_without_gcr() {
+    let rows = parse_survey_csv("axis_tilt,row_id,axis_azimuth\n0.5,B-7,178\n").unwrap();
+    assert_eq!(rows[0].id, "B-7");
+    assert_eq!(rows[0].axis_azimuth, 178.0);
+    assert_eq!(rows[0].gcr_east, None);
+}
+
+#[test]
+fn test_parse_empty_input() {
+    assert_eq!(parse_survey_csv("").unwrap(), Vec::new());
+}
+
+#[test]
+fn test_parse_errors() {
+    assert_eq!(
+        parse_survey_csv("row_id,axis_tilt\nA,1\n"),
+        Err(SurveyError::MissingColumn("axis_azimuth"))
+    );
+    assert_eq!(
+        parse_survey_csv("row_id,axis_azimuth,axis_tilt\nA,180\n"),
+        Err(SurveyError::MissingField {
+            line: 2,
+            column: "axis_tilt"
+        })
+    );
+    let err = parse_survey_csv("row_id,axis_azimuth,axis_tilt\nA,18O,1\n").unwrap_err();
+    assert_eq!(
+        err,
+        SurveyError::InvalidNumber {
+            line: 2,
+            column: "axis_azimuth"
+        }
+    );
+    assert_eq!(err.to_string(), "line 2: invalid number in axis_azimuth");
+}
+
+// ── Config ──
+
+#[test]
+fn test_row_config_uses_tighter_spacing() {
+    let site = LookupTableConfig {
+        gcr: Some(0.35),
+        ..Default::default()
+    };
+    let rows = parse_survey_csv(SURVEY).unwrap();
+    let config = survey_row_config(&site, &rows[1]);
+    assert_eq!(config.axis_azimuth, Some(180.4));
+    assert_eq!(config.axis_tilt, Some(-0.8));
+    assert_eq!(config.gcr, Some(0.41));
+    assert_eq!(survey_row_config(&site, &rows[2]).gcr, Some(0.39));
+
+    let unsurveyed = SurveyRow {
+        gcr_east: None,
+        gcr_west: None,
+        ..rows[0].clone()
+    };
+    assert_eq!(survey_row_config(&site, &unsurveyed).gcr, Some(0.35));
+}
+
+#[test]
+fn test_row_tables_reflect_axis_tilt() {
+    let site = LookupTableConfig {
+        interval_minutes: 15,
+        include_surface_orientation: true,
+        ..Default::default()
+    };
+    let rows = parse_survey_csv(SURVEY).unwrap();
+    let tables: Vec<SingleAxisTable> = rows
+        .iter()
+        .map(|row| generate_single_axis_table(&survey_row_config(&site, row)))
+        .collect();
+    // At solar noon the panel lies along the axis, so its tilt is the
+    // surveyed axis tilt
+    for (row, table) in rows.iter().zip(&tables) {
+        let noon = table.days[79]
+            .entries
+            .iter()
+            .filter(|e| e.rotation.is_some())
+            .min_by(|a, b| {
+                a.rotation
+                    .unwrap()
+                    .abs()
+                    .total_cmp(&b.rotation.unwrap().abs())
+            })
+            .unwrap();
+        assert!((noon.surface.unwrap().surface_tilt - row.axis_tilt.abs()).abs() < 2.0);
+    }
+    assert_ne!(tables[0].days[79].entries, tables[1].days[79].entries);
+}
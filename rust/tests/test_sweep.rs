@@ -0,0 +1,168 @@
+use std::sync::LazyLock;
+
+use solar_tracker::simulation::simulate_yield;
+use solar_tracker::sweep::*;
+use solar_tracker::types::*;
+
+static BASE: LazyLock<SimulationConfig> = LazyLock::new(|| SimulationConfig {
+    step_minutes: 60,
+    ..Default::default()
+});
+
+fn sweep(strategies: &[SweepStrategy]) -> YieldSweep {
+    YieldSweep {
+        tilts: vec![0.0, 30.0],
+        azimuths: vec![180.0, 200.0],
+        gcrs: vec![0.3, 0.6],
+        strategies: strategies.to_vec(),
+        threads: Some(2),
+    }
+}
+
+// ── Grid ──
+
+#[test]
+fn test_rows_cover_grid_once() {
+    let all = [
+        SweepStrategy::Fixed,
+        SweepStrategy::SingleAxis,
+        SweepStrategy::Backtracking,
+        SweepStrategy::DualAxis,
+    ];
+    let rows = yield_sweep(&BASE, &sweep(&all));
+    let count = |s| rows.iter().filter(|r| r.strategy == s).count();
+    // Fixed ignores GCR; dual-axis ignores everything
+    assert_eq!(count(SweepStrategy::Fixed), 4);
+    assert_eq!(count(SweepStrategy::SingleAxis), 8);
+    assert_eq!(count(SweepStrategy::Backtracking), 8);
+    assert_eq!(count(SweepStrategy::DualAxis), 1);
+    assert!(rows.iter().all(|r| r.annual_kwh_per_m2 > 0.0));
+    let dual = rows
+        .iter()
+        .find(|r| r.strategy == SweepStrategy::DualAxis)
+        .unwrap();
+    assert_eq!((dual.tilt, dual.azimuth, dual.gcr), (None, None, None));
+    assert!(rows
+        .iter()
+        .filter(|r| r.strategy == SweepStrategy::Fixed)
+        .all(|r| r.gcr.is_none()));
+}
+
+#[test]
+fn test_rows_in_grid_order() {
+    let rows = yield_sweep(&BASE, &sweep(&[SweepStrategy::Fixed]));
+    let keys: Vec<(f64, f64)> = rows
+        .iter()
+        .map(|r| (r.tilt.unwrap(), r.azimuth.unwrap()))
+        .collect();
+    assert_eq!(
+        keys,
+        vec![(0.0, 180.0), (0.0, 200.0), (30.0, 180.0), (30.0, 200.0)]
+    );
+}
+
+#[test]
+fn test_without_gcrs_single_axis_is_unshaded() {
+    let grid = YieldSweep {
+        gcrs: Vec::new(),
+        ..sweep(&[SweepStrategy::SingleAxis, SweepStrategy::Backtracking])
+    };
+    let rows = yield_sweep(&BASE, &grid);
+    assert_eq!(rows.len(), 4);
+    assert!(rows
+        .iter()
+        .all(|r| r.strategy == SweepStrategy::SingleAxis && r.gcr.is_none()));
+}
+
+// ── Results ──
+
+#[test]
+fn test_matches_individual_simulations() {
+    let rows = yield_sweep(
+        &BASE,
+        &sweep(&[SweepStrategy::Fixed, SweepStrategy::DualAxis]),
+    );
+    for row in rows {
+        let strategy = match row.strategy {
+            SweepStrategy::Fixed => TrackingStrategy::Fixed {
+                tilt: row.tilt.unwrap(),
+                azimuth: row.azimuth.unwrap(),
+            },
+            _ => TrackingStrategy::DualAxis,
+        };
+        let expected = simulate_yield(&SimulationConfig { strategy, ..*BASE });
+        assert_eq!(row.annual_kwh_per_m2, expected.annual_kwh_per_m2);
+    }
+}
+
+#[test]
+fn test_single_axis_keeps_rotation_limit_and_shading() {
+    let grid = YieldSweep {
+        tilts: vec![0.0],
+        azimuths: vec![180.0],
+        ..sweep(&[SweepStrategy::SingleAxis, SweepStrategy::Backtracking])
+    };
+    let rows = yield_sweep(&BASE, &grid);
+    let expected = simulate_yield(&SimulationConfig {
+        strategy: TrackingStrategy::SingleAxis {
+            axis_tilt: 0.0,
+            axis_azimuth: 180.0,
+            max_rotation: Some(60.0),
+            gcr: Some(0.6),
+        },
+        row_gcr: Some(0.6),
+        ..*BASE
+    });
+    assert_eq!(rows[3].strategy, SweepStrategy::Backtracking);
+    assert_eq!(rows[3].annual_kwh_per_m2, expected.annual_kwh_per_m2);
+    // Denser rows shade more when the tracker does not backtrack
+    assert!(rows[1].annual_kwh_per_m2 < rows[0].annual_kwh_per_m2);
+}
+
+#[test]
+fn test_thread_count_does_not_change_results() {
+    let all = [
+        SweepStrategy::Fixed,
+        SweepStrategy::SingleAxis,
+        SweepStrategy::DualAxis,
+    ];
+    let one = yield_sweep(
+        &BASE,
+        &YieldSweep {
+            threads: Some(1),
+            ..sweep(&all)
+        },
+    );
+    let many = yield_sweep(
+        &BASE,
+        &YieldSweep {
+            threads: Some(8),
+            ..sweep(&all)
+        },
+    );
+    let auto = yield_sweep(
+        &BASE,
+        &YieldSweep {
+            threads: None,
+            ..sweep(&all)
+        },
+    );
+    assert_eq!(one, many);
+    assert_eq!(one, auto);
+}
+
+#[test]
+fn test_custom_irradiance() {
+    let rows = yield_sweep_with(&BASE, &sweep(&[SweepStrategy::DualAxis]), |_, _, _| None);
+    assert_eq!(rows.len(), 1);
+    assert_eq!(rows[0].annual_kwh_per_m2, 0.0);
+}
+
+#[test]
+fn test_empty_grid() {
+    let grid = YieldSweep {
+        strategies: Vec::new(),
+        ..Default::default()
+    };
+    assert!(yield_sweep(&BASE, &grid).is_empty());
+}
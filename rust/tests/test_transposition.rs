@@ -0,0 +1,122 @@
+use solar_tracker::irradiance::relative_air_mass;
+use solar_tracker::transposition::*;
+use solar_tracker::types::*;
+
+macro_rules! assert_approx {
+    ($left:expr, $right:expr, $tol:expr) => {
+        let (l, r) = ($left as f64, $right as f64);
+        assert!(
+            (l - r).abs() <= $tol,
+            "assert_approx failed: left={}, right={}, diff={}, tol={}",
+            l, r, (l - r).abs(), $tol
+        );
+    };
+}
+
+const MODELS: [SkyDiffuseModel; 3] =
+    [SkyDiffuseModel::Isotropic, SkyDiffuseModel::Hdkr, SkyDiffuseModel::Perez];
+
+fn sun(zenith: f64, azimuth: f64) -> SolarPosition {
+    SolarPosition {
+        day_of_year: 172,
+        declination: 23.45,
+        equation_of_time: -1.5,
+        local_solar_time: 12.0,
+        hour_angle: 0.0,
+        zenith,
+        altitude: 90.0 - zenith,
+        azimuth,
+    }
+}
+
+fn clear_sky(zenith: f64) -> IrradianceComponents {
+    let (dni, dhi) = (850.0, 100.0);
+    IrradianceComponents { ghi: dni * zenith.to_radians().cos() + dhi, dni, dhi }
+}
+
+fn surface(tilt: f64, azimuth: f64) -> SurfaceOrientation {
+    SurfaceOrientation { surface_tilt: tilt, surface_azimuth: azimuth }
+}
+
+// ── Air mass ──
+
+#[test]
+fn test_relative_air_mass() {
+    assert_approx!(relative_air_mass(0.0).unwrap(), 1.0, 0.001);
+    assert_approx!(relative_air_mass(60.0).unwrap(), 2.0, 0.01);
+    assert!(relative_air_mass(89.0).unwrap() > 20.0);
+    assert!(relative_air_mass(90.0).is_none());
+}
+
+// ── Horizontal surface ──
+
+#[test]
+fn test_horizontal_surface_recovers_ghi() {
+    let pos = sun(40.0, 180.0);
+    let irr = clear_sky(40.0);
+    for model in MODELS {
+        let poa = poa_irradiance(&pos, &surface(0.0, 180.0), &irr, 0.2, model);
+        assert_approx!(poa.sky_diffuse, irr.dhi, 1e-9);
+        assert_approx!(poa.ground_reflected, 0.0, 1e-9);
+        assert_approx!(poa.total, irr.ghi, 1e-9);
+    }
+}
+
+// ── Tilted surfaces ──
+
+#[test]
+fn test_normal_incidence_gets_full_dni() {
+    let pos = sun(40.0, 180.0);
+    let irr = clear_sky(40.0);
+    let poa = poa_irradiance(&pos, &surface(40.0, 180.0), &irr, 0.2, SkyDiffuseModel::Perez);
+    assert_approx!(poa.beam, irr.dni, 1e-9);
+    assert_approx!(poa.total, poa.beam + poa.sky_diffuse + poa.ground_reflected, 1e-9);
+}
+
+#[test]
+fn test_vertical_ground_reflection() {
+    let pos = sun(40.0, 180.0);
+    let irr = clear_sky(40.0);
+    let poa = poa_irradiance(&pos, &surface(90.0, 180.0), &irr, 0.25, SkyDiffuseModel::Isotropic);
+    assert_approx!(poa.ground_reflected, irr.ghi * 0.25 / 2.0, 1e-9);
+    assert_approx!(poa.sky_diffuse, irr.dhi / 2.0, 1e-9);
+}
+
+#[test]
+fn test_anisotropic_models_favor_sun_facing_surface() {
+    let pos = sun(50.0, 135.0);
+    let irr = clear_sky(50.0);
+    let facing = surface(50.0, 135.0);
+    let away = surface(50.0, 315.0);
+    let iso = poa_irradiance(&pos, &facing, &irr, 0.2, SkyDiffuseModel::Isotropic);
+    for model in [SkyDiffuseModel::Hdkr, SkyDiffuseModel::Perez] {
+        let toward = poa_irradiance(&pos, &facing, &irr, 0.2, model);
+        let behind = poa_irradiance(&pos, &away, &irr, 0.2, model);
+        assert!(toward.sky_diffuse > iso.sky_diffuse, "{model:?}");
+        assert!(toward.sky_diffuse > behind.sky_diffuse, "{model:?}");
+        assert_eq!(behind.beam, 0.0);
+    }
+}
+
+#[test]
+fn test_overcast_perez_close_to_isotropic() {
+    let pos = sun(50.0, 180.0);
+    let irr = IrradianceComponents { ghi: 150.0, dni: 0.0, dhi: 150.0 };
+    let tilted = surface(30.0, 180.0);
+    let iso = poa_irradiance(&pos, &tilted, &irr, 0.2, SkyDiffuseModel::Isotropic);
+    let perez = poa_irradiance(&pos, &tilted, &irr, 0.2, SkyDiffuseModel::Perez);
+    assert_approx!(perez.sky_diffuse, iso.sky_diffuse, 0.1 * iso.sky_diffuse);
+}
+
+// ── Night ──
+
+#[test]
+fn test_sun_below_horizon() {
+    let pos = sun(100.0, 0.0);
+    let irr = IrradianceComponents { ghi: 5.0, dni: 0.0, dhi: 5.0 };
+    for model in MODELS {
+        let poa = poa_irradiance(&pos, &surface(30.0, 180.0), &irr, 0.2, model);
+        assert_eq!(poa.beam, 0.0);
+        assert!(poa.total.is_finite());
+    }
+}
@@ -0,0 +1,201 @@
+#![cfg(feature = "weather")]
+
+use std::fmt::Write;
+
+use chrono::{Datelike, Duration, NaiveDate, TimeZone, Timelike, Utc};
+
+use solar_tracker::angles::solar_position;
+use solar_tracker::irradiance::clear_sky_components;
+use solar_tracker::simulation::{simulate_yield, simulate_yield_with};
+use solar_tracker::types::*;
+use solar_tracker::weather::*;
+
+fn minute_of_day(minutes: i32) -> MinuteOfDay {
+    MinuteOfDay::new(minutes).unwrap()
+}
+
+const TMY3_HEADER: &str = "\
+725315,\"SPRINGFIELD CAPITAL AP\",IL,-6.0,39.833,-89.667,187
+Date (MM/DD/YYYY),Time (HH:MM),ETR (W/m^2),ETRN (W/m^2),GHI (W/m^2),GHI source,\
+GHI uncert (%),DNI (W/m^2),DNI source,DNI uncert (%),DHI (W/m^2),DHI source,\
+DHI uncert (%),Dry-bulb (C),Dry-bulb source,Dry-bulb uncert (%),Wspd (m/s)
+";
+
+fn tmy3_row(date: &str, time: &str, ghi: f64, dni: f64, dhi: f64, temp: f64, wind: f64) -> String {
+    format!("{date},{time},0,0,{ghi},1,8,{dni},1,15,{dhi},1,8,{temp},A,7,{wind}\n")
+}
+
+const EPW_HEADER: &str = "\
+LOCATION,Springfield Capital Ap,IL,USA,TMY3,725315,39.83,-89.67,-6.0,187.0
+DESIGN CONDITIONS,0
+TYPICAL/EXTREME PERIODS,0
+GROUND TEMPERATURES,0
+HOLIDAYS/DAYLIGHT SAVINGS,No,0,0,0
+COMMENTS 1,synthetic
+COMMENTS 2,synthetic
+DATA PERIODS,1,1,Data,Sunday, 1/ 1,12/31
+";
+
+fn epw_row(month: u32, day: u32, hour: u32, ghi: f64, dni: f64, dhi: f64, temp: f64) -> String {
+    format!(
+        "1991,{month},{day},{hour},60,?9?9?9?9E0?9?9?9,{temp},-5.0,80,99000,0,0,300,\
+         {ghi},{dni},{dhi},0,0,0,0,270,3.5,0,0,9999,77777,9,999999999,0,0.1,0,88,0,0,0\n"
+    )
+}
+
+// ── TMY3 ──
+
+#[test]
+fn test_tmy3_site_and_rows() {
+    let mut text = TMY3_HEADER.to_string();
+    text += &tmy3_row("01/01/1988", "01:00", 0.0, 0.0, 0.0, -3.0, 4.1);
+    text += &tmy3_row("06/21/1991", "13:00", 900.0, 800.0, 120.0, 29.5, 2.0);
+    let file = parse_tmy3(&text).unwrap();
+    assert_eq!((file.latitude, file.longitude), (39.833, -89.667));
+    assert_eq!((file.utc_offset_hours, file.elevation), (-6.0, 187.0));
+    assert_eq!(file.records.len(), 2);
+    let june = file.records[1];
+    assert_eq!((june.day_of_year, june.hour), (172, 12));
+    assert_eq!((june.ghi, june.dni, june.dhi), (900.0, 800.0, 120.0));
+    assert_eq!((june.temperature, june.wind_speed), (Some(29.5), Some(2.0)));
+    assert_eq!(file.records[0].hour, 0);
+}
+
+#[test]
+fn test_tmy3_hour_24_and_leap_day() {
+    let mut text = TMY3_HEADER.to_string();
+    text += &tmy3_row("02/29/1996", "12:00", 500.0, 400.0, 100.0, 5.0, 1.0);
+    text += &tmy3_row("12/31/1990", "24:00", 0.0, 0.0, 0.0, -8.0, 1.0);
+    let file = parse_tmy3(&text).unwrap();
+    assert_eq!(file.records.len(), 1);
+    assert_eq!(
+        (file.records[0].day_of_year, file.records[0].hour),
+        (365, 23)
+    );
+}
+
+#[test]
+fn test_tmy3_errors() {
+    assert_eq!(parse_tmy3(""), Err(WeatherFileError::MissingHeader));
+    let no_dni = TMY3_HEADER.replace("DNI (W/m^2)", "Direct");
+    assert_eq!(
+        parse_tmy3(&no_dni),
+        Err(WeatherFileError::MissingColumn("DNI"))
+    );
+    let mut bad = TMY3_HEADER.to_string();
+    bad += &tmy3_row("13/01/1990", "01:00", 0.0, 0.0, 0.0, 0.0, 0.0);
+    assert_eq!(
+        parse_tmy3(&bad),
+        Err(WeatherFileError::InvalidDate { line: 3 })
+    );
+    let bad_number = TMY3_HEADER.to_string() + "01/01/1990,01:00,0,0,x,1,8,0,1,15,0,1,8,0,A,7,0\n";
+    assert_eq!(
+        parse_tmy3(&bad_number),
+        Err(WeatherFileError::InvalidNumber {
+            line: 3,
+            column: "GHI"
+        })
+    );
+}
+
+// ── EPW ──
+
+#[test]
+fn test_epw_site_and_rows() {
+    let mut text = EPW_HEADER.to_string();
+    text += &epw_row(6, 21, 13, 900.0, 800.0, 120.0, 29.5);
+    text += &epw_row(1, 1, 1, 0.0, 0.0, 0.0, -3.0);
+    let file = parse_epw(&text).unwrap();
+    assert_eq!(
+        (file.latitude, file.longitude, file.utc_offset_hours),
+        (39.83, -89.67, -6.0)
+    );
+    assert_eq!(file.elevation, 187.0);
+    // Sorted into day order
+    assert_eq!((file.records[0].day_of_year, file.records[0].hour), (1, 0));
+    let june = file.records[1];
+    assert_eq!((june.day_of_year, june.hour), (172, 12));
+    assert_eq!((june.ghi, june.dni, june.dhi), (900.0, 800.0, 120.0));
+    assert_eq!((june.temperature, june.wind_speed), (Some(29.5), Some(3.5)));
+}
+
+#[test]
+fn test_epw_missing_values() {
+    let mut text = EPW_HEADER.to_string();
+    text += &epw_row(6, 21, 13, 9999.0, 800.0, 120.0, 29.5);
+    text += &epw_row(6, 21, 14, 800.0, 700.0, 110.0, 99.9);
+    let file = parse_epw(&text).unwrap();
+    assert_eq!(file.records.len(), 1);
+    assert_eq!(file.records[0].hour, 13);
+    assert_eq!(file.records[0].temperature, None);
+}
+
+#[test]
+fn test_epw_requires_location() {
+    assert_eq!(
+        parse_epw("DATA PERIODS,1\n"),
+        Err(WeatherFileError::MissingHeader)
+    );
+}
+
+// ── Lookup ──
+
+#[test]
+fn test_record_lookup_shifts_to_standard_time() {
+    let mut text = TMY3_HEADER.to_string();
+    text += &tmy3_row("06/21/1991", "13:00", 900.0, 800.0, 120.0, 29.5, 2.0);
+    text += &tmy3_row("12/31/1991", "24:00", 1.0, 0.0, 1.0, -8.0, 1.0);
+    let file = parse_tmy3(&text).unwrap();
+    // 12:00-13:00 CST is 18:00-19:00 UTC
+    assert_eq!(weather_record(&file, 172, minute_of_day(18 * 60)).unwrap().ghi, 900.0);
+    assert_eq!(weather_record(&file, 172, minute_of_day(18 * 60 + 59)).unwrap().ghi, 900.0);
+    assert!(weather_record(&file, 172, minute_of_day(19 * 60)).is_none());
+    // 23:00 CST on 31 December is 05:00 UTC on 1 January, wrapping the year
+    assert_eq!(weather_record(&file, 1, minute_of_day(5 * 60)).unwrap().ghi, 1.0);
+}
+
+// ── Simulation ──
+
+// A full year of clear-sky hours written as a TMY3 file, values taken at
+// the middle of each local standard hour.
+fn clear_sky_tmy3() -> String {
+    let mut text = TMY3_HEADER.to_string();
+    let start = NaiveDate::from_ymd_opt(2026, 1, 1)
+        .unwrap()
+        .and_hms_opt(0, 0, 0)
+        .unwrap();
+    for hour in 0..8760 {
+        let local = start + Duration::hours(hour);
+        let mid = Utc.from_utc_datetime(&(local + Duration::hours(6) + Duration::minutes(30)));
+        let pos = solar_position(39.8, -89.6, &mid);
+        let c = clear_sky_components(&pos).unwrap_or(IrradianceComponents {
+            ghi: 0.0,
+            dni: 0.0,
+            dhi: 0.0,
+        });
+        let date = format!("{:02}/{:02}/1990", local.month(), local.day());
+        let time = format!("{:02}:00", local.hour() + 1);
+        let _ = write!(
+            text,
+            "{}",
+            tmy3_row(&date, &time, c.ghi, c.dni, c.dhi, 20.0, 3.0)
+        );
+    }
+    text
+}
+
+#[test]
+fn test_simulation_with_weather_file() {
+    let file = parse_tmy3(&clear_sky_tmy3()).unwrap();
+    assert_eq!(file.records.len(), 8760);
+    let config = SimulationConfig {
+        step_minutes: 60,
+        ..Default::default()
+    };
+    let measured = simulate_yield_with(&config, weather_irradiance(&file)).annual_kwh_per_m2;
+    let clear = simulate_yield(&config).annual_kwh_per_m2;
+    assert!(
+        (measured / clear - 1.0).abs() < 0.02,
+        "{measured} vs {clear}"
+    );
+}
@@ -0,0 +1,79 @@
+use std::sync::LazyLock;
+
+use chrono::{TimeZone, Utc};
+
+use solar_tracker::angles::solar_position;
+use solar_tracker::config::ConfigError;
+use solar_tracker::lookup_table::*;
+use solar_tracker::types::*;
+use solar_tracker::worker::*;
+
+fn minute_of_day(minutes: i32) -> MinuteOfDay {
+    MinuteOfDay::new(minutes).unwrap()
+}
+
+static TABLE: LazyLock<SingleAxisTable> = LazyLock::new(|| {
+    generate_single_axis_table_checked(&LookupTableConfig::default(), "2026-01-01T00:00:00Z")
+        .unwrap()
+});
+
+// 2026-03-21 18:00:00 UTC, noon in Springfield
+const EQUINOX_MILLIS: f64 = 1_774_116_000_000.0;
+
+// ── Position ──
+
+#[test]
+fn test_position_matches_solar_position() {
+    let dt = Utc.with_ymd_and_hms(2026, 3, 21, 18, 0, 0).unwrap();
+    assert_eq!(dt.timestamp_millis() as f64, EQUINOX_MILLIS);
+    let pos = position_at_epoch_millis(39.8, -89.6, EQUINOX_MILLIS).unwrap();
+    assert_eq!(pos, solar_position(39.8, -89.6, &dt));
+}
+
+#[test]
+fn test_position_rejects_bad_input() {
+    assert_eq!(position_at_epoch_millis(200.0, -89.6, EQUINOX_MILLIS), None);
+    assert_eq!(position_at_epoch_millis(39.8, -190.0, EQUINOX_MILLIS), None);
+    assert_eq!(position_at_epoch_millis(f64::NAN, -89.6, EQUINOX_MILLIS), None);
+    for millis in [f64::NAN, f64::INFINITY, 1e300, -1e300] {
+        assert_eq!(position_at_epoch_millis(39.8, -89.6, millis), None);
+    }
+}
+
+// ── Lookup ──
+
+#[test]
+fn test_lookup_uses_utc_day_and_minute() {
+    let got = lookup_single_axis_at_epoch_millis(&TABLE, EQUINOX_MILLIS + 30_000.0).unwrap();
+    assert_eq!(got, lookup_single_axis(&TABLE, 80, minute_of_day(1080)).unwrap());
+}
+
+#[test]
+fn test_dual_axis_lookup() {
+    let table =
+        generate_dual_axis_table_checked(&LookupTableConfig::default(), "").unwrap();
+    let got = lookup_dual_axis_at_epoch_millis(&table, EQUINOX_MILLIS).unwrap();
+    assert_eq!(got, lookup_dual_axis(&table, 80, minute_of_day(1080)).unwrap());
+    assert_eq!(lookup_dual_axis_at_epoch_millis(&table, f64::NAN), None);
+}
+
+// ── Generation ──
+
+#[test]
+fn test_generation_stamped_by_caller() {
+    assert_eq!(TABLE.metadata.generated_at, "2026-01-01T00:00:00Z");
+    assert_eq!(TABLE.days, generate_single_axis_table(&LookupTableConfig::default()).days);
+}
+
+#[test]
+fn test_generation_rejects_invalid_config() {
+    let config = LookupTableConfig {
+        interval_minutes: 0,
+        ..Default::default()
+    };
+    assert!(matches!(
+        generate_single_axis_table_checked(&config, ""),
+        Err(ConfigError::OutOfRange { field: "interval_minutes", .. })
+    ));
+    assert!(generate_dual_axis_table_checked(&config, "").is_err());
+}